@@ -9,6 +9,19 @@
 //! The default cache store is [`MokaStore`], which is a wrapper of [`moka`].
 //! You can define your own cache store by implementing [`CacheStore`].
 //!
+//! # Conditional requests
+//!
+//! The cached responses keep their `ETag` header, and one is computed from the body if the response has none.
+//! A `GET` request with a matching `If-None-Match` header gets `304 Not Modified` from the cache without calling
+//! the handler, so the polling clients only download the body when it is changed. A `HEAD` request is answered by
+//! the cached `GET` response without the body, if the route accepts `HEAD` or the cache is a hoop of the `Service`.
+//!
+//! The cache handles the conditional requests by itself, so it does not need the `CachingHeaders` or `ETag`
+//! middleware of `salvo_extra`. If they are used too, add the cache before them: the cache removes
+//! `If-None-Match` before calling the handlers, so they never write a `304` which would be cached for all the
+//! clients, and the cache checks it after the response is stored. If they are added before the cache, they hash
+//! the body again for every cached response.
+//!
 //! Example: [cache-simple](https://github.com/salvo-rs/salvo/tree/main/examples/cache-simple)
 //! Read more: <https://salvo.rs>
 #![doc(html_favicon_url = "https://salvo.rs/favicon-32x32.png")]
@@ -22,8 +35,11 @@ use std::future::Future;
 use std::hash::Hash;
 
 use bytes::Bytes;
+use etag::EntityTag;
 use salvo_core::handler::Skipper;
-use salvo_core::http::{HeaderMap, ResBody, StatusCode};
+use salvo_core::http::header::{ETAG, IF_NONE_MATCH};
+use salvo_core::http::headers::{ETag, HeaderMapExt, IfNoneMatch};
+use salvo_core::http::{HeaderMap, Method, ResBody, StatusCode};
 use salvo_core::{Depot, Error, FlowCtrl, Handler, Request, Response, async_trait};
 
 mod skipper;
@@ -270,7 +286,10 @@ impl<S, I> Cache<S, I> {
     /// Create new `Cache`.
     #[inline]
     pub fn new(store: S, issuer: I) -> Self {
-        let skipper = MethodSkipper::new().skip_all().skip_get(false);
+        let skipper = MethodSkipper::new()
+            .skip_all()
+            .skip_get(false)
+            .skip_head(false);
         Cache {
             store,
             issuer,
@@ -301,21 +320,36 @@ where
         if self.skipper.skipped(req, depot) {
             return;
         }
-        let key = match self.issuer.issue(req, depot).await {
-            Some(key) => key,
-            None => {
-                return;
-            }
+        // `HEAD` requests use the cached `GET` responses.
+        let is_head = req.method() == Method::HEAD;
+        if is_head {
+            *req.method_mut() = Method::GET;
+        }
+        let key = self.issuer.issue(req, depot).await;
+        if is_head {
+            *req.method_mut() = Method::HEAD;
+        }
+        let Some(key) = key else {
+            return;
         };
         let cache = match self.store.load_entry(&key).await {
             Some(cache) => cache,
+            None if is_head => {
+                // The response of `HEAD` has no body, it can not be cached for `GET`.
+                ctrl.call_next(req, depot, res).await;
+                return;
+            }
             None => {
+                let if_none_match = req.headers_mut().remove(IF_NONE_MATCH);
                 ctrl.call_next(req, depot, res).await;
                 if !res.body.is_stream() && !res.body.is_error() {
-                    let headers = res.headers().clone();
                     let body = TryInto::<CachedBody>::try_into(&res.body);
                     match body {
                         Ok(body) => {
+                            if is_success(res.status_code) && !res.headers().contains_key(ETAG) {
+                                res.headers_mut().typed_insert(body_etag(&body));
+                            }
+                            let headers = res.headers().clone();
                             let cached_data = CachedEntry::new(res.status_code, headers, body);
                             if let Err(e) = self.store.save_entry(key, cached_data).await {
                                 tracing::error!(error = ?e, "cache failed");
@@ -324,6 +358,10 @@ where
                         Err(e) => tracing::error!(error = ?e, "cache failed"),
                     }
                 }
+                if let Some(if_none_match) = if_none_match {
+                    req.headers_mut().insert(IF_NONE_MATCH, if_none_match);
+                    check_not_modified(req, res);
+                }
                 return;
             }
         };
@@ -336,11 +374,54 @@ where
             res.status_code(status);
         }
         *res.headers_mut() = headers;
-        *res.body_mut() = body.into();
+        if !is_head {
+            *res.body_mut() = body.into();
+        }
+        check_not_modified(req, res);
         ctrl.skip_rest();
     }
 }
 
+fn is_success(status: Option<StatusCode>) -> bool {
+    status.is_none_or(|status| status.is_success())
+}
+
+// The etag is computed from the body if the response has none.
+fn body_etag(body: &CachedBody) -> ETag {
+    let etag = match body {
+        CachedBody::None => EntityTag::from_data(&[]),
+        CachedBody::Once(bytes) => EntityTag::from_data(bytes),
+        CachedBody::Chunks(chunks) => {
+            let tags = chunks
+                .iter()
+                .map(|item| EntityTag::from_data(item).tag().to_owned())
+                .collect::<Vec<_>>()
+                .concat();
+            EntityTag::from_data(tags.as_bytes())
+        }
+    };
+    etag.to_string()
+        .parse()
+        .expect("entity tag should be a valid etag")
+}
+
+// Write `304 Not Modified` if the etag of the response matches `If-None-Match` of the request.
+fn check_not_modified(req: &Request, res: &mut Response) {
+    if !is_success(res.status_code) {
+        return;
+    }
+    let (Some(if_none_match), Some(etag)) = (
+        req.headers().typed_get::<IfNoneMatch>(),
+        res.headers().typed_get::<ETag>(),
+    ) else {
+        return;
+    };
+    if !if_none_match.precondition_passes(&etag) {
+        res.status_code(StatusCode::NOT_MODIFIED);
+        res.body(ResBody::None);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -390,4 +471,52 @@ mod tests {
 
         assert_ne!(content0, content2);
     }
+
+    #[tokio::test]
+    async fn test_cache_conditional() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        #[handler]
+        async fn counted() -> &'static str {
+            CALLS.fetch_add(1, Ordering::SeqCst);
+            "Hello World"
+        }
+
+        let cache = Cache::new(
+            MokaStore::builder()
+                .time_to_live(std::time::Duration::from_secs(5))
+                .build(),
+            RequestIssuer::default(),
+        );
+        let service = Service::new(Router::new().get(counted)).hoop(cache);
+
+        let res = TestClient::get("http://127.0.0.1:5801")
+            .add_header("if-none-match", "\"stale\"", true)
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::OK);
+        let etag = res.headers().get("etag").unwrap().clone();
+
+        let mut res = TestClient::get("http://127.0.0.1:5801")
+            .add_header("if-none-match", etag.clone(), true)
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::NOT_MODIFIED);
+        assert_eq!(res.headers().get("etag").unwrap(), &etag);
+        assert!(res.take_string().await.unwrap().is_empty());
+
+        let mut res = TestClient::head("http://127.0.0.1:5801")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::OK);
+        assert_eq!(res.headers().get("etag").unwrap(), &etag);
+        assert!(res.take_string().await.unwrap().is_empty());
+
+        let mut res = TestClient::get("http://127.0.0.1:5801")
+            .send(&service)
+            .await;
+        assert_eq!(res.take_string().await.unwrap(), "Hello World");
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+    }
 }
@@ -126,11 +126,15 @@ impl Catcher {
 #[derive(Default)]
 pub struct DefaultGoal {
     footer: Option<Cow<'static, str>>,
+    show_detail: bool,
 }
 impl DefaultGoal {
     /// Create new `DefaultGoal`.
     pub fn new() -> Self {
-        DefaultGoal { footer: None }
+        DefaultGoal {
+            footer: None,
+            show_detail: false,
+        }
     }
     /// Create new `DefaultGoal` with custom footer.
     #[inline]
@@ -146,6 +150,15 @@ impl DefaultGoal {
         self.footer = Some(footer.into());
         self
     }
+
+    /// Set whether the [`StatusError::detail`] is written in the error page, it is `false` by default.
+    ///
+    /// The detail is written for the clients as it is, only enable it if the details of the errors are safe to be
+    /// seen by the clients.
+    pub fn show_detail(mut self, show_detail: bool) -> Self {
+        self.show_detail = show_detail;
+        self
+    }
 }
 #[async_trait]
 impl Handler for DefaultGoal {
@@ -160,7 +173,7 @@ impl Handler for DefaultGoal {
         if (status.is_server_error() || status.is_client_error())
             && (res.body.is_none() || res.body.is_error())
         {
-            write_error_default(req, res, self.footer.as_deref());
+            write_error_default(req, res, self.footer.as_deref(), self.show_detail);
         }
     }
 }
@@ -169,9 +182,13 @@ fn status_error_html(
     code: StatusCode,
     name: &str,
     brief: &str,
+    detail: Option<&str>,
     cause: Option<&str>,
     footer: Option<&str>,
 ) -> String {
+    let detail = detail
+        .map(|detail| format!("<p>{}</p>", escape_html(detail)))
+        .unwrap_or_default();
     format!(
         r#"<!DOCTYPE html>
 <html>
@@ -204,19 +221,42 @@ fn status_error_html(
     </style>
 </head>
 <body>
-    <div><h1>{0}: {1}</h1><h3>{2}</h3><pre>{3}</pre><hr><footer>{4}</footer></div>
+    <div><h1>{0}: {1}</h1><h3>{2}</h3>{5}<pre>{3}</pre><hr><footer>{4}</footer></div>
 </body>
 </html>"#,
         code.as_u16(),
         name,
         brief,
         cause.unwrap_or(EMPTY_CAUSE_MSG),
-        footer.unwrap_or(SALVO_LINK)
+        footer.unwrap_or(SALVO_LINK),
+        detail,
     )
 }
 
+// The detail may contain user input, so it is escaped before being written into html.
+fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#x27;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
 #[inline]
-fn status_error_json(code: StatusCode, name: &str, brief: &str, cause: Option<&str>) -> String {
+fn status_error_json(
+    code: StatusCode,
+    name: &str,
+    brief: &str,
+    detail: Option<&str>,
+    cause: Option<&str>,
+) -> String {
     #[derive(Serialize)]
     struct Data<'a> {
         error: Error<'a>,
@@ -226,6 +266,8 @@ fn status_error_json(code: StatusCode, name: &str, brief: &str, cause: Option<&s
         code: u16,
         name: &'a str,
         brief: &'a str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        detail: Option<&'a str>,
         cause: &'a str,
     }
     let data = Data {
@@ -233,28 +275,47 @@ fn status_error_json(code: StatusCode, name: &str, brief: &str, cause: Option<&s
             code: code.as_u16(),
             name,
             brief,
+            detail,
             cause: cause.unwrap_or(EMPTY_CAUSE_MSG),
         },
     };
     serde_json::to_string(&data).unwrap_or_default()
 }
 
-fn status_error_plain(code: StatusCode, name: &str, brief: &str, cause: Option<&str>) -> String {
+fn status_error_plain(
+    code: StatusCode,
+    name: &str,
+    brief: &str,
+    detail: Option<&str>,
+    cause: Option<&str>,
+) -> String {
+    let detail = detail
+        .map(|detail| format!("\n\ndetail: {detail}"))
+        .unwrap_or_default();
     format!(
-        "code: {}\n\nname: {}\n\nbrief: {}\n\ncause: {}",
+        "code: {}\n\nname: {}\n\nbrief: {}{}\n\ncause: {}",
         code.as_u16(),
         name,
         brief,
+        detail,
         cause.unwrap_or(EMPTY_CAUSE_MSG)
     )
 }
 
-fn status_error_xml(code: StatusCode, name: &str, brief: &str, cause: Option<&str>) -> String {
+fn status_error_xml(
+    code: StatusCode,
+    name: &str,
+    brief: &str,
+    detail: Option<&str>,
+    cause: Option<&str>,
+) -> String {
     #[derive(Serialize)]
     struct Data<'a> {
         code: u16,
         name: &'a str,
         brief: &'a str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        detail: Option<&'a str>,
         cause: &'a str,
     }
 
@@ -262,6 +323,7 @@ fn status_error_xml(code: StatusCode, name: &str, brief: &str, cause: Option<&st
         code: code.as_u16(),
         name,
         brief,
+        detail,
         cause: cause.unwrap_or(EMPTY_CAUSE_MSG),
     };
     serde_xml_rs::to_string(&data).unwrap_or_default()
@@ -274,6 +336,7 @@ pub fn status_error_bytes(
     err: &StatusError,
     prefer_format: &Mime,
     footer: Option<&str>,
+    show_detail: bool,
 ) -> (Mime, Bytes) {
     let format = if !SUPPORTED_FORMATS.contains(&prefer_format.subtype()) {
         mime::TEXT_HTML
@@ -284,26 +347,40 @@ pub fn status_error_bytes(
     let cause = err.cause.as_ref().map(|e| format!("{:#?}", e.as_ref()));
     #[cfg(not(debug_assertions))]
     let cause: Option<String> = None;
+    let detail = err.detail.as_deref().filter(|_| show_detail);
     let content = match format.subtype().as_ref() {
-        "plain" => status_error_plain(err.code, &err.name, &err.brief, cause.as_deref()),
-        "json" => status_error_json(err.code, &err.name, &err.brief, cause.as_deref()),
-        "xml" => status_error_xml(err.code, &err.name, &err.brief, cause.as_deref()),
-        _ => status_error_html(err.code, &err.name, &err.brief, cause.as_deref(), footer),
+        "plain" => status_error_plain(err.code, &err.name, &err.brief, detail, cause.as_deref()),
+        "json" => status_error_json(err.code, &err.name, &err.brief, detail, cause.as_deref()),
+        "xml" => status_error_xml(err.code, &err.name, &err.brief, detail, cause.as_deref()),
+        _ => status_error_html(
+            err.code,
+            &err.name,
+            &err.brief,
+            detail,
+            cause.as_deref(),
+            footer,
+        ),
     };
     (format, Bytes::from(content))
 }
 
 #[doc(hidden)]
-pub fn write_error_default(req: &Request, res: &mut Response, footer: Option<&str>) {
+pub fn write_error_default(
+    req: &Request,
+    res: &mut Response,
+    footer: Option<&str>,
+    show_detail: bool,
+) {
     let format = guess_accept_mime(req, None);
     let (format, data) = if let ResBody::Error(body) = &res.body {
-        status_error_bytes(body, &format, footer)
+        status_error_bytes(body, &format, footer, show_detail)
     } else {
         let status = res.status_code.unwrap_or(StatusCode::NOT_FOUND);
         status_error_bytes(
             &StatusError::from_code(status).unwrap_or_else(StatusError::internal_server_error),
             &format,
             footer,
+            show_detail,
         )
     };
     res.headers_mut().insert(
@@ -384,4 +461,87 @@ mod tests {
 
         assert_eq!(access(&service, "notfound").await, "Custom 404 Error Page");
     }
+
+    #[tokio::test]
+    async fn test_router_catcher() {
+        #[handler]
+        async fn forbidden() -> Result<(), StatusError> {
+            Err(StatusError::forbidden())
+        }
+        #[handler]
+        async fn problem_json(res: &mut Response, ctrl: &mut FlowCtrl) {
+            res.render(Text::Json(r#"{"type":"about:blank"}"#));
+            ctrl.skip_rest();
+        }
+        #[handler]
+        async fn html_page(res: &mut Response, ctrl: &mut FlowCtrl) {
+            res.render(Text::Html("<p>error</p>"));
+            ctrl.skip_rest();
+        }
+        let router = Router::new()
+            .push(
+                Router::with_path("api")
+                    .catch(Catcher::default().hoop(problem_json))
+                    .push(Router::with_path("todos").get(forbidden))
+                    .push(
+                        Router::with_path("legacy")
+                            .catch(Catcher::default())
+                            .get(forbidden),
+                    ),
+            )
+            .push(Router::with_path("web").get(forbidden));
+        let service = Service::new(router).catcher(Catcher::default().hoop(html_page));
+
+        async fn access(service: &Service, path: &str) -> String {
+            let mut res = TestClient::get(format!("http://127.0.0.1:5800/{path}"))
+                .add_header("accept", "text/plain", true)
+                .send(service)
+                .await;
+            res.take_string().await.unwrap()
+        }
+
+        assert_eq!(
+            access(&service, "api/todos").await,
+            r#"{"type":"about:blank"}"#
+        );
+        assert!(access(&service, "api/legacy").await.contains("403"));
+        assert_eq!(access(&service, "web").await, "<p>error</p>");
+        assert_eq!(access(&service, "api/missing").await, "<p>error</p>");
+    }
+
+    #[tokio::test]
+    async fn test_status_error_detail() {
+        #[handler]
+        async fn get_todo() -> Result<(), StatusError> {
+            Err(StatusError::not_found().detail("todo <1> not found"))
+        }
+        let service = Service::new(Router::with_path("todos/1").get(get_todo))
+            .catcher(Catcher::new(DefaultGoal::new().show_detail(true)));
+
+        async fn access(service: &Service, accept: &str) -> String {
+            let mut res = TestClient::get("http://127.0.0.1:5800/todos/1")
+                .add_header("accept", accept, true)
+                .send(service)
+                .await;
+            assert_eq!(res.status_code, Some(StatusCode::NOT_FOUND));
+            res.take_string().await.unwrap()
+        }
+
+        let json = access(&service, "application/json").await;
+        assert!(json.contains(r#""detail":"todo <1> not found""#));
+        let plain = access(&service, "text/plain").await;
+        assert!(plain.contains("detail: todo <1> not found"));
+        let html = access(&service, "text/html").await;
+        assert!(html.contains("<p>todo &lt;1&gt; not found</p>"));
+
+        let service = Service::new(Router::with_path("todos/1").get(get_todo));
+        let json = access(&service, "application/json").await;
+        assert!(!json.contains("todo <1> not found"));
+        let service = service.catcher(Catcher::default());
+        let plain = access(&service, "text/plain").await;
+        assert!(!plain.contains("todo <1> not found"));
+
+        let json = status_error_json(StatusCode::NOT_FOUND, "Not Found", "brief", None, None);
+        assert!(!json.contains(r#""detail""#));
+    }
 }
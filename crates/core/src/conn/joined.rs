@@ -72,6 +72,35 @@ where
 }
 
 /// `JoinedListener` is a listener that can join two listeners.
+///
+/// It is created by [`Listener::join`]. The joined acceptor accepts connections from both listeners, so one
+/// [`Server`](crate::Server) can serve several addresses, like both IPv4 and IPv6, or the public port and an admin
+/// port. Join it again to listen on more addresses. Binding fails if any of the listeners fails to bind.
+///
+/// All the listeners share the accept loop of the server, so [`ServerHandle::stop_graceful`] and
+/// [`ServerHandle::stop_forcible`] stop accepting on all of them at once, and graceful shutdown waits for the
+/// connections from all of them.
+///
+/// On some systems, like Linux, a socket bound to `[::]` also accepts IPv4 connections by default, so binding both
+/// `0.0.0.0:5800` and `[::]:5800` fails with an address in use error. Bind only `[::]:5800` for dual-stack there.
+///
+/// [`ServerHandle::stop_graceful`]: crate::server::ServerHandle::stop_graceful
+/// [`ServerHandle::stop_forcible`]: crate::server::ServerHandle::stop_forcible
+///
+/// # Example
+///
+/// ```no_run
+/// use salvo_core::prelude::*;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let acceptor = TcpListener::new("0.0.0.0:8080")
+///         .join(TcpListener::new("127.0.0.1:9090"))
+///         .bind()
+///         .await;
+///     Server::new(acceptor).serve(Router::new()).await;
+/// }
+/// ```
 #[pin_project]
 pub struct JoinedListener<A, B> {
     #[pin]
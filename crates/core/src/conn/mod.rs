@@ -167,6 +167,11 @@ pub trait Listener {
     type Acceptor: Acceptor;
 
     /// Bind and returns acceptor.
+    ///
+    /// # Panics
+    ///
+    /// Panics if binding fails, for example if the address is already in use. Use [`Listener::try_bind`] to
+    /// handle the error.
     fn bind(self) -> impl Future<Output = Self::Acceptor> + Send
     where
         Self: Sized + Send,
@@ -174,10 +179,38 @@ pub trait Listener {
         async move { self.try_bind().await.expect("bind failed") }
     }
 
-    /// Bind and returns acceptor.
+    /// Bind and returns acceptor, or the error if binding fails.
+    ///
+    /// I/O errors are returned as [`Error::Io`](crate::Error::Io), so the cause can be checked by its kind.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::io::ErrorKind;
+    ///
+    /// use salvo_core::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let acceptor = match TcpListener::new("0.0.0.0:5800").try_bind().await {
+    ///         Ok(acceptor) => acceptor,
+    ///         Err(salvo_core::Error::Io(e)) if e.kind() == ErrorKind::AddrInUse => {
+    ///             eprintln!("port 5800 in use");
+    ///             std::process::exit(1);
+    ///         }
+    ///         Err(e) => {
+    ///             eprintln!("bind failed: {e}");
+    ///             std::process::exit(1);
+    ///         }
+    ///     };
+    ///     Server::new(acceptor).serve(Router::new()).await;
+    /// }
+    /// ```
     fn try_bind(self) -> impl Future<Output = crate::Result<Self::Acceptor>> + Send;
 
-    /// Join current Listener with the other.
+    /// Join current Listener with the other, the joined acceptor accepts connections from both.
+    ///
+    /// View [`JoinedListener`] for more details.
     #[inline]
     fn join<T>(self, other: T) -> JoinedListener<Self, T>
     where
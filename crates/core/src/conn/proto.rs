@@ -5,6 +5,8 @@ use std::io::{Error as IoError, ErrorKind, IoSlice, Result as IoResult};
 use std::marker::PhantomPinned;
 use std::pin::Pin;
 use std::task::{self, Context, Poll, ready};
+#[cfg(any(feature = "http1", feature = "http2"))]
+use std::time::Duration;
 
 use bytes::{Buf, Bytes};
 
@@ -32,8 +34,66 @@ use hyper::server::conn::http2;
 use crate::conn::quinn;
 
 const H2_PREFACE: &[u8] = b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n";
+#[cfg(feature = "http1")]
+const DEFAULT_HEADER_READ_TIMEOUT: Duration = Duration::from_secs(30);
 
-#[doc(hidden)]
+/// Settings of the HTTP protocols used by [`Server`](crate::Server).
+///
+/// Use [`Server::builder`](crate::Server::builder) to create it, and [`HttpBuilder::build`] to create a server
+/// with it. The defaults are the same as [hyper](https://docs.rs/hyper), except that a timer is set, so the
+/// timeouts take effect:
+///
+/// | Setting | Default |
+/// | --- | --- |
+/// | [`http1_keep_alive`](Self::http1_keep_alive) | `true` |
+/// | [`http1_header_read_timeout`](Self::http1_header_read_timeout) | 30 seconds |
+/// | [`http1_max_headers`](Self::http1_max_headers) | 100 |
+/// | [`http1_max_buf_size`](Self::http1_max_buf_size) | ~400KB |
+/// | [`http2_max_concurrent_streams`](Self::http2_max_concurrent_streams) | 200 |
+/// | [`http2_max_header_list_size`](Self::http2_max_header_list_size) | 16KB |
+/// | [`http2_keep_alive_interval`](Self::http2_keep_alive_interval) | disabled |
+/// | [`http2_keep_alive_timeout`](Self::http2_keep_alive_timeout) | 20 seconds |
+///
+/// [`Server::new`](crate::Server::new) uses these defaults too, so a client which sends the headers too slowly is
+/// disconnected after the header read timeout even if nothing is configured.
+///
+/// Other settings of hyper can be changed with [`Server::http1_mut`](crate::Server::http1_mut) and
+/// [`Server::http2_mut`](crate::Server::http2_mut).
+///
+/// # Hardening
+///
+/// These limits are enforced at the connection layer, before the request reaches the router:
+///
+/// - If a HTTP/1 client does not send the entire headers within
+///   [`http1_header_read_timeout`](Self::http1_header_read_timeout), the connection is closed, so slow clients
+///   can not hold connections forever.
+/// - If the headers exceed [`http1_max_header_size`](Self::http1_max_header_size) or
+///   [`http1_max_headers`](Self::http1_max_headers), the server responds `431 Request Header Fields Too Large`
+///   and closes the connection.
+/// - HTTP/2 headers are limited by [`http2_max_header_list_size`](Self::http2_max_header_list_size), and the
+///   streams of a connection by [`http2_max_concurrent_streams`](Self::http2_max_concurrent_streams).
+///
+/// For public-facing deployments, a shorter header read timeout and a header size limit such as 16KB are
+/// recommended.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use salvo_core::prelude::*;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let acceptor = TcpListener::new("127.0.0.1:5800").bind().await;
+///     let server = Server::builder()
+///         .http1_header_read_timeout(Duration::from_secs(10))
+///         .http1_max_header_size(16 * 1024)
+///         .http2_max_concurrent_streams(100)
+///         .build(acceptor);
+/// #   let _ = server;
+/// }
+/// ```
 pub struct HttpBuilder {
     #[cfg(feature = "http1")]
     pub(crate) http1: http1::Builder,
@@ -49,17 +109,123 @@ impl Default for HttpBuilder {
 }
 
 impl HttpBuilder {
+    /// Create a new `HttpBuilder` with default settings.
     pub fn new() -> Self {
         Self {
             #[cfg(feature = "http1")]
-            http1: http1::Builder::new(),
+            http1: {
+                let mut builder = http1::Builder::new();
+                builder
+                    .timer(crate::rt::tokio::TokioTimer::new())
+                    .header_read_timeout(DEFAULT_HEADER_READ_TIMEOUT);
+                builder
+            },
             #[cfg(feature = "http2")]
-            http2: http2::Builder::new(crate::rt::tokio::TokioExecutor::new()),
+            http2: {
+                let mut builder = http2::Builder::new(TokioExecutor::new());
+                builder.timer(crate::rt::tokio::TokioTimer::new());
+                builder
+            },
             #[cfg(feature = "quinn")]
             quinn: crate::conn::quinn::Builder::new(),
         }
     }
 
+    cfg_feature! {
+        #![feature = "http1"]
+        /// Set whether HTTP/1 connections should support keep-alive.
+        ///
+        /// Default is `true`.
+        #[inline]
+        pub fn http1_keep_alive(mut self, enabled: bool) -> Self {
+            self.http1.keep_alive(enabled);
+            self
+        }
+
+        /// Set a timeout for reading the request headers, the connection is closed if the client does not send
+        /// the entire headers within this time. Pass `None` to disable it.
+        ///
+        /// Default is 30 seconds.
+        #[inline]
+        pub fn http1_header_read_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+            self.http1.header_read_timeout(timeout);
+            self
+        }
+
+        /// Set the max number of headers of a request.
+        ///
+        /// Default is 100.
+        #[inline]
+        pub fn http1_max_headers(mut self, max: usize) -> Self {
+            self.http1.max_headers(max);
+            self
+        }
+
+        /// Set the max size of the request headers, including the request line. The server responds
+        /// `431 Request Header Fields Too Large` if it is exceeded.
+        ///
+        /// Default is not set, so it is limited by [`http1_max_buf_size`](Self::http1_max_buf_size).
+        #[inline]
+        pub fn http1_max_header_size(mut self, max: usize) -> Self {
+            self.http1.max_header_size(max);
+            self
+        }
+
+        /// Set the max buffer size of a connection.
+        ///
+        /// Default is ~400KB.
+        ///
+        /// # Panics
+        ///
+        /// The minimum value allowed is 8192.
+        #[inline]
+        pub fn http1_max_buf_size(mut self, max: usize) -> Self {
+            self.http1.max_buf_size(max);
+            self
+        }
+    }
+
+    cfg_feature! {
+        #![feature = "http2"]
+        /// Set the max number of concurrent streams of a connection. Pass `None` to remove the limit.
+        ///
+        /// Default is 200.
+        #[inline]
+        pub fn http2_max_concurrent_streams(mut self, max: impl Into<Option<u32>>) -> Self {
+            self.http2.max_concurrent_streams(max);
+            self
+        }
+
+        /// Set the max size of the received header frames.
+        ///
+        /// Default is 16KB.
+        #[inline]
+        pub fn http2_max_header_list_size(mut self, max: u32) -> Self {
+            self.http2.max_header_list_size(max);
+            self
+        }
+
+        /// Set the interval of the ping frames to keep connections alive. Pass `None` to disable it.
+        ///
+        /// Default is disabled.
+        #[inline]
+        pub fn http2_keep_alive_interval(mut self, interval: impl Into<Option<Duration>>) -> Self {
+            self.http2.keep_alive_interval(interval);
+            self
+        }
+
+        /// Set the timeout for receiving the acknowledgement of the keep-alive ping, the connection is closed if
+        /// it is not received in time. Does nothing if [`http2_keep_alive_interval`](Self::http2_keep_alive_interval)
+        /// is disabled.
+        ///
+        /// Default is 20 seconds.
+        #[inline]
+        pub fn http2_keep_alive_timeout(mut self, timeout: Duration) -> Self {
+            self.http2.keep_alive_timeout(timeout);
+            self
+        }
+    }
+
     /// Serve a connection with the given service.
     #[allow(unused_variables)]
     pub async fn serve_connection<I, S, B>(
@@ -0,0 +1,113 @@
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use tokio_rustls::rustls::pki_types::CertificateDer;
+use x509_parser::extensions::GeneralName;
+use x509_parser::prelude::{FromDer, X509Certificate};
+
+/// A client certificate verified during the TLS handshake, used for mutual TLS.
+///
+/// The client certificates are only sent when client authentication is enabled by
+/// [`RustlsConfig::client_auth_required`](super::RustlsConfig::client_auth_required) or
+/// [`RustlsConfig::client_auth_optional`](super::RustlsConfig::client_auth_optional), and they are verified with the
+/// trust anchor set there, then they are available by [`Request::client_cert`](crate::Request::client_cert) in all
+/// requests of the connection.
+///
+/// # Example
+///
+/// ```
+/// use salvo_core::prelude::*;
+///
+/// #[handler]
+/// async fn internal_api(req: &mut Request, res: &mut Response) {
+///     let allowed = req
+///         .client_cert()
+///         .is_some_and(|cert| cert.subject_alt_names().iter().any(|name| name == "billing.internal"));
+///     if !allowed {
+///         res.render(StatusError::forbidden());
+///         return;
+///     }
+///     res.render("ok");
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ClientCert {
+    der: CertificateDer<'static>,
+    subject: String,
+    common_name: Option<String>,
+    subject_alt_names: Vec<String>,
+}
+
+impl ClientCert {
+    /// Create a new `ClientCert` from the DER encoded certificate.
+    ///
+    /// The certificate is already verified by rustls, if it can not be parsed, the subject is empty.
+    pub fn new(der: CertificateDer<'static>) -> Self {
+        let mut subject = String::new();
+        let mut common_name = None;
+        let mut subject_alt_names = vec![];
+        match X509Certificate::from_der(&der) {
+            Ok((_, cert)) => {
+                subject = cert.subject().to_string();
+                common_name = cert
+                    .subject()
+                    .iter_common_name()
+                    .next()
+                    .and_then(|name| name.as_str().ok())
+                    .map(ToOwned::to_owned);
+                if let Ok(Some(names)) = cert.subject_alternative_name() {
+                    subject_alt_names = names
+                        .value
+                        .general_names
+                        .iter()
+                        .filter_map(general_name_to_string)
+                        .collect();
+                }
+            }
+            Err(e) => tracing::warn!(error = ?e, "failed to parse client certificate"),
+        }
+        Self {
+            der,
+            subject,
+            common_name,
+            subject_alt_names,
+        }
+    }
+
+    /// Get the DER encoded certificate.
+    #[inline]
+    pub fn der(&self) -> &[u8] {
+        &self.der
+    }
+
+    /// Get the subject, like `CN=billing.internal, O=Example`.
+    #[inline]
+    pub fn subject(&self) -> &str {
+        &self.subject
+    }
+
+    /// Get the common name of the subject.
+    #[inline]
+    pub fn common_name(&self) -> Option<&str> {
+        self.common_name.as_deref()
+    }
+
+    /// Get the DNS names, the URIs, the emails and the IP addresses in the subject alternative names.
+    #[inline]
+    pub fn subject_alt_names(&self) -> &[String] {
+        &self.subject_alt_names
+    }
+}
+
+fn general_name_to_string(name: &GeneralName) -> Option<String> {
+    match name {
+        GeneralName::DNSName(name) | GeneralName::URI(name) | GeneralName::RFC822Name(name) => {
+            Some((*name).to_owned())
+        }
+        GeneralName::IPAddress(bytes) => match bytes.len() {
+            4 => <[u8; 4]>::try_from(*bytes).ok().map(|ip| Ipv4Addr::from(ip).to_string()),
+            16 => <[u8; 16]>::try_from(*bytes).ok().map(|ip| Ipv6Addr::from(ip).to_string()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
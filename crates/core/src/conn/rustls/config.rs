@@ -213,6 +213,9 @@ impl RustlsConfig {
     ///
     /// Only authenticated clients will be accepted. If no trust anchor is provided by any of the
     /// `client_auth_` methods, then client authentication is disabled by default.
+    ///
+    /// With the `client-cert` feature, the verified client certificates are available by
+    /// [`Request::client_cert`](crate::Request::client_cert).
     #[inline]
     pub fn client_auth_required(mut self, trust_anchor: impl Into<Vec<u8>>) -> Self {
         self.client_auth = TlsClientAuth::Required(trust_anchor.into());
@@ -163,6 +163,9 @@ where
         } = self.inner.accept(fuse_factory).await?;
         let fusewire = conn.fusewire();
         Ok(Accepted {
+            #[cfg(feature = "client-cert")]
+            conn: HandshakeStream::new(tls_acceptor.accept(conn), fusewire).client_certs(client_certs),
+            #[cfg(not(feature = "client-cert"))]
             conn: HandshakeStream::new(tls_acceptor.accept(conn), fusewire),
             local_addr,
             remote_addr,
@@ -170,3 +173,13 @@ where
         })
     }
 }
+
+#[cfg(feature = "client-cert")]
+fn client_certs<S>(stream: &TlsStream<S>) -> Vec<super::ClientCert> {
+    stream
+        .get_ref()
+        .1
+        .peer_certificates()
+        .map(|certs| certs.iter().map(|cert| super::ClientCert::new(cert.clone().into_owned())).collect())
+        .unwrap_or_default()
+}
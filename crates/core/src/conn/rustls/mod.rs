@@ -6,6 +6,13 @@ use tokio_rustls::rustls::RootCertStore;
 pub(crate) mod config;
 pub use config::{Keycert, RustlsConfig, ServerConfig};
 
+#[cfg(feature = "client-cert")]
+mod client_cert;
+#[cfg(feature = "client-cert")]
+pub use client_cert::ClientCert;
+/// The client certificates of a connection, they are set when the TLS handshake is finished.
+#[cfg(feature = "client-cert")]
+pub(crate) type ClientCertsSlot = std::sync::Arc<std::sync::OnceLock<std::sync::Arc<[ClientCert]>>>;
 mod listener;
 pub use listener::{RustlsAcceptor, RustlsListener};
 
@@ -63,4 +70,75 @@ mod tests {
         let Accepted { mut conn, .. } = acceptor.accept(None).await.unwrap();
         assert_eq!(conn.read_i32().await.unwrap(), 518);
     }
+
+    #[cfg(feature = "client-cert")]
+    #[tokio::test]
+    async fn test_rustls_client_cert() {
+        use rcgen::{BasicConstraints, CertificateParams, DnType, IsCa, KeyPair};
+        use tokio_rustls::rustls::pki_types::PrivateKeyDer;
+
+        use crate::prelude::*;
+
+        #[handler]
+        async fn whoami(req: &mut Request) -> String {
+            let cert = req.client_cert().unwrap();
+            format!(
+                "{} {:?} {}",
+                cert.common_name().unwrap_or_default(),
+                cert.subject_alt_names(),
+                req.client_certs().len()
+            )
+        }
+
+        let ca_key = KeyPair::generate().unwrap();
+        let mut ca_params = CertificateParams::new(Vec::<String>::new()).unwrap();
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let ca = ca_params.self_signed(&ca_key).unwrap();
+        let client_key = KeyPair::generate().unwrap();
+        let mut client_params = CertificateParams::new(vec!["billing.internal".to_owned()]).unwrap();
+        client_params
+            .distinguished_name
+            .push(DnType::CommonName, "billing");
+        let client = client_params
+            .signed_by(&client_key, &ca, &ca_key)
+            .unwrap();
+
+        let acceptor = TcpListener::new("127.0.0.1:0")
+            .rustls(
+                RustlsConfig::new(
+                    Keycert::new()
+                        .key_from_path("certs/key.pem")
+                        .unwrap()
+                        .cert_from_path("certs/cert.pem")
+                        .unwrap(),
+                )
+                .client_auth_required(ca.pem()),
+            )
+            .bind()
+            .await;
+        let addr = acceptor.holdings()[0].local_addr.clone().into_std().unwrap();
+        tokio::spawn(Server::new(acceptor).serve(Router::new().get(whoami)));
+
+        let trust_anchor = include_bytes!("../../../certs/chain.pem");
+        let client_config = ClientConfig::builder()
+            .with_root_certificates(read_trust_anchor(trust_anchor.as_slice()).unwrap())
+            .with_client_auth_cert(
+                vec![client.der().clone()],
+                PrivateKeyDer::Pkcs8(client_key.serialize_der().into()),
+            )
+            .unwrap();
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let mut tls_stream = TlsConnector::from(Arc::new(client_config))
+            .connect(ServerName::try_from("testserver.com").unwrap(), stream)
+            .await
+            .unwrap();
+        tls_stream
+            .write_all(b"GET / HTTP/1.1\r\nhost: testserver.com\r\nconnection: close\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        tls_stream.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"));
+        assert!(response.ends_with(r#"billing ["billing.internal"] 1"#));
+    }
 }
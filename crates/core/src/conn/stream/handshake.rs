@@ -19,10 +19,19 @@ enum State<S> {
     Error,
 }
 
+// Reads the client certificates of the stream into the slot when the handshake is finished.
+#[cfg(feature = "client-cert")]
+type ClientCertsReader<S> = (
+    fn(&S) -> Vec<crate::conn::rustls::ClientCert>,
+    crate::conn::rustls::ClientCertsSlot,
+);
+
 /// Tls stream.
 pub struct HandshakeStream<S> {
     state: State<S>,
     fusewire: Option<ArcFusewire>,
+    #[cfg(feature = "client-cert")]
+    client_certs: Option<ClientCertsReader<S>>,
 }
 
 impl<S> HandshakeStream<S> {
@@ -36,10 +45,27 @@ impl<S> HandshakeStream<S> {
         Self {
             state: State::Handshaking(handshake.boxed()),
             fusewire,
+            #[cfg(feature = "client-cert")]
+            client_certs: None,
         }
     }
 
+    /// Set the function getting the client certificates of the stream, they are read when the handshake is
+    /// finished, which is before the first request of the connection is read.
+    #[cfg(feature = "client-cert")]
+    pub(crate) fn client_certs(mut self, client_certs: fn(&S) -> Vec<crate::conn::rustls::ClientCert>) -> Self {
+        self.client_certs = Some((client_certs, Default::default()));
+        self
+    }
+
     fn set_state_ready(&mut self, stream: S) {
+        #[cfg(feature = "client-cert")]
+        if let Some((client_certs, slot)) = &self.client_certs {
+            let client_certs = client_certs(&stream);
+            if !client_certs.is_empty() {
+                let _ = slot.set(client_certs.into());
+            }
+        }
         self.state = State::Ready(stream);
         if let Some(fusewire) = &self.fusewire {
             fusewire.event(FuseEvent::TlsHandshaked);
@@ -56,6 +82,11 @@ where
         builder: Arc<HttpBuilder>,
         graceful_stop_token: Option<CancellationToken>,
     ) -> IoResult<()> {
+        #[cfg(feature = "client-cert")]
+        let handler = HyperHandler {
+            client_certs: self.client_certs.as_ref().map(|(_, slot)| slot.clone()),
+            ..handler
+        };
         let fusewire = self.fusewire.clone();
         if let Some(fusewire) = &fusewire {
             fusewire.event(FuseEvent::Alive);
@@ -1,12 +1,17 @@
 //! TcpListener and it's implements.
-use std::io::{Error as IoError, Result as IoResult};
+use std::io::{Error as IoError, ErrorKind, Result as IoResult};
 use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
 use std::vec;
 
-use tokio::net::{TcpListener as TokioTcpListener, TcpStream, ToSocketAddrs};
+use async_trait::async_trait;
+use tokio::net::{TcpListener as TokioTcpListener, TcpSocket, TcpStream, ToSocketAddrs};
+use tokio::sync::Notify;
+use tokio_util::sync::CancellationToken;
 
 use crate::conn::{Holding, StraightStream};
-use crate::fuse::{ArcFuseFactory, FuseInfo, TransProto};
+use crate::fuse::{ArcFuseFactory, ArcFusewire, FuseEvent, FuseInfo, Fusewire, TransProto};
 use crate::http::Version;
 use crate::http::uri::Scheme;
 
@@ -28,9 +33,18 @@ use crate::conn::openssl::OpensslListener;
 use crate::conn::acme::AcmeListener;
 
 /// `TcpListener` is used to create a TCP connection listener.
+///
+/// Bind to port `0` to let the operating system assign a free port, and get it by
+/// [`TcpAcceptor::local_addr`] after binding, which is useful for tests and dev servers.
+///
+/// The socket options, like [`TcpListener::nodelay`] and [`TcpListener::reuseaddr`], are applied when binding or
+/// to each accepted connection, the ones not set keep the defaults of the operating system.
 pub struct TcpListener<T> {
     local_addr: T,
     ttl: Option<u32>,
+    nodelay: Option<bool>,
+    reuseaddr: Option<bool>,
+    idle_timeout: Option<Duration>,
     #[cfg(feature = "socket2")]
     backlog: Option<u32>,
 }
@@ -43,6 +57,9 @@ impl<T: ToSocketAddrs + Send> TcpListener<T> {
         TcpListener {
             local_addr,
             ttl: None,
+            nodelay: None,
+            reuseaddr: None,
+            idle_timeout: None,
         }
     }
     /// Bind to socket address.
@@ -52,6 +69,9 @@ impl<T: ToSocketAddrs + Send> TcpListener<T> {
         TcpListener {
             local_addr,
             ttl: None,
+            nodelay: None,
+            reuseaddr: None,
+            idle_timeout: None,
             backlog: None,
         }
     }
@@ -120,9 +140,40 @@ impl<T: ToSocketAddrs + Send> TcpListener<T> {
         self
     }
 
+    /// Sets the value of the `TCP_NODELAY` option on the accepted connections.
+    ///
+    /// If it is `true`, the small packets are sent immediately instead of being buffered by the Nagle's algorithm,
+    /// which lowers the latency of small responses.
+    #[inline]
+    pub fn nodelay(mut self, nodelay: bool) -> Self {
+        self.nodelay = Some(nodelay);
+        self
+    }
+
+    /// Sets the value of the `SO_REUSEADDR` option on the listening socket.
+    ///
+    /// It allows binding the address while the connections of a previous process are in `TIME_WAIT`, so the server
+    /// can be restarted immediately. Most unix systems enable it by default.
+    #[inline]
+    pub fn reuseaddr(mut self, reuseaddr: bool) -> Self {
+        self.reuseaddr = Some(reuseaddr);
+        self
+    }
+
+    /// Sets the timeout for closing the connections which are idle, like the keep-alive connections waiting for
+    /// the next request.
+    ///
+    /// It works together with the fuse factory set by [`Server::fuse_factory`](crate::Server::fuse_factory), the
+    /// connection is closed when either of them is fused.
+    #[inline]
+    pub fn idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = Some(timeout);
+        self
+    }
+
     cfg_feature! {
         #![feature = "socket2"]
-        /// Set backlog capacity.
+        /// Set backlog capacity, default is `1024`.
         #[inline]
         pub fn backlog(mut self, backlog: u32) -> Self {
             self.backlog = Some(backlog);
@@ -137,24 +188,116 @@ where
     type Acceptor = TcpAcceptor;
 
     async fn try_bind(self) -> crate::Result<Self::Acceptor> {
-        let inner = TokioTcpListener::bind(self.local_addr).await?;
-
-        #[cfg(feature = "socket2")]
-        if let Some(backlog) = self.backlog {
-            let socket = socket2::SockRef::from(&inner);
-            socket.listen(backlog as _)?;
-        }
+        let inner = match self.reuseaddr {
+            Some(reuseaddr) => {
+                #[cfg(feature = "socket2")]
+                let backlog = self.backlog.unwrap_or(DEFAULT_BACKLOG);
+                #[cfg(not(feature = "socket2"))]
+                let backlog = DEFAULT_BACKLOG;
+                bind_reuseaddr(self.local_addr, reuseaddr, backlog).await?
+            }
+            None => {
+                let inner = TokioTcpListener::bind(self.local_addr).await?;
+                #[cfg(feature = "socket2")]
+                if let Some(backlog) = self.backlog {
+                    let socket = socket2::SockRef::from(&inner);
+                    socket.listen(backlog as _)?;
+                }
+                inner
+            }
+        };
         if let Some(ttl) = self.ttl {
             inner.set_ttl(ttl)?;
         }
 
-        Ok(inner.try_into()?)
+        let mut acceptor = TcpAcceptor::try_from(inner)?;
+        acceptor.nodelay = self.nodelay;
+        acceptor.idle_timeout = self.idle_timeout;
+        Ok(acceptor)
+    }
+}
+
+// Same as the backlog of `TokioTcpListener::bind`.
+const DEFAULT_BACKLOG: u32 = 1024;
+
+// Bind the first resolved address which can be bound, like `TokioTcpListener::bind` does.
+async fn bind_reuseaddr(
+    local_addr: impl ToSocketAddrs,
+    reuseaddr: bool,
+    backlog: u32,
+) -> IoResult<TokioTcpListener> {
+    let mut last_err = None;
+    for addr in tokio::net::lookup_host(local_addr).await? {
+        let socket = if addr.is_ipv4() {
+            TcpSocket::new_v4()?
+        } else {
+            TcpSocket::new_v6()?
+        };
+        socket.set_reuseaddr(reuseaddr)?;
+        match socket.bind(addr) {
+            Ok(()) => return socket.listen(backlog),
+            Err(e) => last_err = Some(e),
+        }
     }
+    Err(last_err.unwrap_or_else(|| {
+        IoError::new(ErrorKind::InvalidInput, "could not resolve to any address")
+    }))
 }
 /// `TcpAcceptor` is used to accept a TCP connection.
 pub struct TcpAcceptor {
     inner: TokioTcpListener,
     holdings: Vec<Holding>,
+    nodelay: Option<bool>,
+    idle_timeout: Option<Duration>,
+}
+
+// Closes the connection if it is idle, and passes the events to the fusewire of the server.
+struct IdleFusewire {
+    idle_token: CancellationToken,
+    idle_notify: Arc<Notify>,
+    inner: Option<ArcFusewire>,
+}
+impl IdleFusewire {
+    fn new(idle_timeout: Duration, inner: Option<ArcFusewire>) -> Self {
+        let idle_token = CancellationToken::new();
+        let idle_notify = Arc::new(Notify::new());
+        tokio::spawn({
+            let idle_notify = idle_notify.clone();
+            let idle_token = idle_token.clone();
+            async move {
+                while tokio::time::timeout(idle_timeout, idle_notify.notified())
+                    .await
+                    .is_ok()
+                {}
+                idle_token.cancel();
+            }
+        });
+        Self {
+            idle_token,
+            idle_notify,
+            inner,
+        }
+    }
+}
+#[async_trait]
+impl Fusewire for IdleFusewire {
+    fn event(&self, event: FuseEvent) {
+        self.idle_notify.notify_waiters();
+        if let Some(inner) = &self.inner {
+            inner.event(event);
+        }
+    }
+    async fn fused(&self) {
+        match &self.inner {
+            Some(inner) => {
+                tokio::select! {
+                    _ = self.idle_token.cancelled() => {}
+                    _ = inner.fused() => {}
+                }
+            }
+            None => self.idle_token.cancelled().await,
+        }
+    }
 }
 
 impl TcpAcceptor {
@@ -197,7 +340,12 @@ impl TryFrom<TokioTcpListener> for TcpAcceptor {
             http_scheme: Scheme::HTTP,
         }];
 
-        Ok(TcpAcceptor { inner, holdings })
+        Ok(TcpAcceptor {
+            inner,
+            holdings,
+            nodelay: None,
+            idle_timeout: None,
+        })
     }
 }
 
@@ -214,23 +362,29 @@ impl Acceptor for TcpAcceptor {
         &mut self,
         fuse_factory: Option<ArcFuseFactory>,
     ) -> IoResult<Accepted<Self::Conn>> {
-        self.inner.accept().await.map(move |(conn, remote_addr)| {
-            let local_addr = self.holdings[0].local_addr.clone();
-            Accepted {
-                conn: StraightStream::new(
-                    conn,
-                    fuse_factory.map(|f| {
-                        f.create(FuseInfo {
-                            trans_proto: TransProto::Tcp,
-                            remote_addr: remote_addr.into(),
-                            local_addr: local_addr.clone(),
-                        })
-                    }),
-                ),
+        let (conn, remote_addr) = self.inner.accept().await?;
+        if let Some(nodelay) = self.nodelay {
+            conn.set_nodelay(nodelay)?;
+        }
+        let local_addr = self.holdings[0].local_addr.clone();
+        let fusewire = fuse_factory.map(|f| {
+            f.create(FuseInfo {
+                trans_proto: TransProto::Tcp,
                 remote_addr: remote_addr.into(),
-                local_addr,
-                http_scheme: Scheme::HTTP,
+                local_addr: local_addr.clone(),
+            })
+        });
+        let fusewire = match self.idle_timeout {
+            Some(idle_timeout) => {
+                Some(Arc::new(IdleFusewire::new(idle_timeout, fusewire)) as ArcFusewire)
             }
+            None => fusewire,
+        };
+        Ok(Accepted {
+            conn: StraightStream::new(conn, fusewire),
+            remote_addr: remote_addr.into(),
+            local_addr,
+            http_scheme: Scheme::HTTP,
         })
     }
 }
@@ -239,6 +393,8 @@ impl Acceptor for TcpAcceptor {
 mod tests {
     use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
+    use crate::http::HttpConnection;
+
     use super::*;
 
     #[tokio::test]
@@ -258,4 +414,65 @@ mod tests {
         let Accepted { mut conn, .. } = acceptor.accept(None).await.unwrap();
         assert_eq!(conn.read_i32().await.unwrap(), 150);
     }
+
+    #[tokio::test]
+    async fn test_tcp_listener_try_bind() {
+        let acceptor = TcpListener::new("127.0.0.1:0").try_bind().await.unwrap();
+        let addr = acceptor.local_addr().unwrap();
+        assert_ne!(addr.port(), 0);
+        assert_eq!(
+            acceptor.holdings()[0].local_addr.clone().into_std(),
+            Some(addr)
+        );
+
+        match TcpListener::new(addr).try_bind().await {
+            Err(crate::Error::Io(e)) => assert_eq!(e.kind(), std::io::ErrorKind::AddrInUse),
+            other => panic!("expected address in use error, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tcp_listener_options() {
+        let mut acceptor = TcpListener::new("127.0.0.1:0")
+            .nodelay(true)
+            .reuseaddr(true)
+            .idle_timeout(Duration::from_secs(5))
+            .try_bind()
+            .await
+            .unwrap();
+        let addr = acceptor.local_addr().unwrap();
+        assert_ne!(addr.port(), 0);
+        tokio::spawn(async move {
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            stream.write_i32(150).await.unwrap();
+        });
+        let Accepted { mut conn, .. } = acceptor.accept(None).await.unwrap();
+        assert!(conn.fusewire().is_some());
+        assert_eq!(conn.read_i32().await.unwrap(), 150);
+
+        let mut acceptor = TcpListener::new("127.0.0.1:0").try_bind().await.unwrap();
+        let addr = acceptor.local_addr().unwrap();
+        tokio::spawn(async move {
+            TcpStream::connect(addr).await.unwrap();
+        });
+        let Accepted { conn, .. } = acceptor.accept(None).await.unwrap();
+        assert!(conn.fusewire().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_tcp_listener_idle_timeout() {
+        let acceptor = TcpListener::new("127.0.0.1:0")
+            .idle_timeout(Duration::from_millis(100))
+            .bind()
+            .await;
+        let addr = acceptor.local_addr().unwrap();
+        tokio::spawn(crate::Server::new(acceptor).serve(crate::Router::new()));
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let mut buf = [0; 1];
+        let read = tokio::time::timeout(Duration::from_secs(5), stream.read(&mut buf))
+            .await
+            .expect("idle connection should be closed");
+        assert_eq!(read.unwrap_or_default(), 0);
+    }
 }
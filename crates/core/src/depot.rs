@@ -30,17 +30,109 @@ use std::fmt::{self, Debug, Formatter};
 ///     Server::new(acceptor).serve(router).await;
 /// }
 /// ```
-
+///
+/// # Scoping
+///
+/// Each request has its own `Depot`, and only the middlewares and handlers on the matched route
+/// are executed for it. So values set by the middlewares of a router are only visible to the
+/// requests routed to this router or its children, the sibling routers never see them.
+///
+/// There is only one `Depot` for a request, nested routers do not create child depots. If a
+/// middleware of an inner router sets a key which has been set by an outer middleware, the new
+/// value shadows the old one for the rest of the request: the inner middlewares and the handler
+/// see the new value, and so does the code of outer middlewares running after
+/// [`FlowCtrl::call_next`](crate::routing::FlowCtrl::call_next) returns.
+///
+/// ```
+/// use salvo_core::prelude::*;
+/// use salvo_core::test::{ResponseExt, TestClient};
+///
+/// #[handler]
+/// async fn set_outer(depot: &mut Depot) {
+///     depot.insert("scope", "outer");
+/// }
+/// #[handler]
+/// async fn set_inner(depot: &mut Depot) {
+///     depot.insert("scope", "inner");
+/// }
+/// #[handler]
+/// async fn scope(depot: &mut Depot) -> &'static str {
+///     depot.get::<&str>("scope").copied().unwrap_or_default()
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let router = Router::with_path("api")
+///     .hoop(set_outer)
+///     .push(Router::with_path("inner").hoop(set_inner).get(scope))
+///     .push(Router::with_path("sibling").get(scope));
+/// let service = Service::new(router);
+///
+/// let inner = TestClient::get("http://127.0.0.1:5800/api/inner").send(&service).await.take_string().await;
+/// assert_eq!(inner.unwrap(), "inner");
+/// let sibling = TestClient::get("http://127.0.0.1:5800/api/sibling").send(&service).await.take_string().await;
+/// assert_eq!(sibling.unwrap(), "outer");
+/// # }
+/// ```
+///
+/// # Snapshot
+///
+/// A task spawned by a handler can not borrow the `Depot`, since it may outlive the request. The values inserted by
+/// [`Depot::insert_cloneable`] or [`Depot::inject_cloneable`] are cloned by [`Depot::snapshot`] into a new owned
+/// `Depot`, which can be moved into the task. The other values are not cloneable and are not in the snapshot.
+///
+/// The middlewares in salvo insert their cloneable values this way, like the states of `affix_state`, the request id
+/// of `RequestId` and the username of `BasicAuth`, so wrap the values in `Arc` to share them without copying.
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use salvo_core::prelude::*;
+///
+/// struct Mailer;
+/// impl Mailer {
+///     async fn send(&self, user: &str) {}
+/// }
+///
+/// #[handler]
+/// async fn set_user(depot: &mut Depot) {
+///     depot.inject_cloneable(Arc::new(Mailer));
+///     depot.insert_cloneable("user", "jobs".to_owned());
+/// }
+/// #[handler]
+/// async fn create_todo(depot: &mut Depot) -> &'static str {
+///     let snapshot = depot.snapshot();
+///     tokio::spawn(async move {
+///         let mailer = snapshot.obtain::<Arc<Mailer>>().unwrap();
+///         mailer.send(snapshot.get::<String>("user").unwrap()).await;
+///     });
+///     "created"
+/// }
+///
+/// let router = Router::new().hoop(set_user).post(create_todo);
+/// ```
 #[derive(Default)]
 pub struct Depot {
     map: HashMap<String, Box<dyn Any + Send + Sync>>,
+    cloners: HashMap<String, CloneFn>,
 }
 
+type CloneFn = fn(&(dyn Any + Send + Sync)) -> Option<Box<dyn Any + Send + Sync>>;
+
 #[inline]
 fn type_key<T: 'static>() -> String {
     format!("{:?}", TypeId::of::<T>())
 }
 
+// The value may be replaced by a value of other type by `get_mut`, so it returns `None` if the downcast fails.
+fn clone_value<V: Any + Send + Sync + Clone>(
+    value: &(dyn Any + Send + Sync),
+) -> Option<Box<dyn Any + Send + Sync>> {
+    value
+        .downcast_ref::<V>()
+        .map(|value| Box::new(value.clone()) as Box<dyn Any + Send + Sync>)
+}
+
 impl Depot {
     /// Creates an empty `Depot`.
     ///
@@ -49,6 +141,7 @@ impl Depot {
     pub fn new() -> Depot {
         Depot {
             map: HashMap::new(),
+            cloners: HashMap::new(),
         }
     }
 
@@ -65,6 +158,7 @@ impl Depot {
     pub fn with_capacity(capacity: usize) -> Self {
         Depot {
             map: HashMap::with_capacity(capacity),
+            cloners: HashMap::new(),
         }
     }
     /// Returns the number of elements the depot can hold without reallocating.
@@ -76,8 +170,13 @@ impl Depot {
     /// Inject a value into the depot.
     #[inline]
     pub fn inject<V: Any + Send + Sync>(&mut self, value: V) -> &mut Self {
-        self.map.insert(type_key::<V>(), Box::new(value));
-        self
+        self.insert(type_key::<V>(), value)
+    }
+
+    /// Inject a value into the depot, it is cloned into the [`snapshot`](Self::snapshot).
+    #[inline]
+    pub fn inject_cloneable<V: Any + Send + Sync + Clone>(&mut self, value: V) -> &mut Self {
+        self.insert_cloneable(type_key::<V>(), value)
     }
 
     /// Obtain a reference to a value previous inject to the depot.
@@ -107,7 +206,22 @@ impl Depot {
         K: Into<String>,
         V: Any + Send + Sync,
     {
-        self.map.insert(key.into(), Box::new(value));
+        let key = key.into();
+        self.cloners.remove(&key);
+        self.map.insert(key, Box::new(value));
+        self
+    }
+
+    /// Inserts a key-value pair into the depot, the value is cloned into the [`snapshot`](Self::snapshot).
+    #[inline]
+    pub fn insert_cloneable<K, V>(&mut self, key: K, value: V) -> &mut Self
+    where
+        K: Into<String>,
+        V: Any + Send + Sync + Clone,
+    {
+        let key = key.into();
+        self.cloners.insert(key.clone(), clone_value::<V>);
+        self.map.insert(key, Box::new(value));
         self
     }
 
@@ -167,6 +281,7 @@ impl Depot {
         &mut self,
         key: &str,
     ) -> Result<V, Option<Box<dyn Any + Send + Sync>>> {
+        self.cloners.remove(key);
         if let Some(value) = self.map.remove(key) {
             value.downcast::<V>().map(|b| *b).map_err(Some)
         } else {
@@ -177,6 +292,7 @@ impl Depot {
     /// Delete the key from depot, if the key is not present, return `false`.
     #[inline]
     pub fn delete(&mut self, key: &str) -> bool {
+        self.cloners.remove(key);
         self.map.remove(key).is_some()
     }
 
@@ -187,6 +303,22 @@ impl Depot {
     ) -> Result<T, Option<Box<dyn Any + Send + Sync>>> {
         self.remove(&type_key::<T>())
     }
+
+    /// Clone the values inserted by [`insert_cloneable`](Self::insert_cloneable) and
+    /// [`inject_cloneable`](Self::inject_cloneable) into a new `Depot`, which is owned and can be moved into the
+    /// background tasks.
+    ///
+    /// View [`Depot`] for more details.
+    pub fn snapshot(&self) -> Depot {
+        let mut snapshot = Depot::with_capacity(self.cloners.len());
+        for (key, clone) in &self.cloners {
+            if let Some(value) = self.map.get(key).and_then(|value| clone(&**value)) {
+                snapshot.map.insert(key.clone(), value);
+                snapshot.cloners.insert(key.clone(), *clone);
+            }
+        }
+        snapshot
+    }
 }
 
 impl Debug for Depot {
@@ -199,6 +331,8 @@ impl Debug for Depot {
 
 #[cfg(test)]
 mod test {
+    use std::sync::Arc;
+
     use crate::prelude::*;
     use crate::test::{ResponseExt, TestClient};
 
@@ -219,6 +353,34 @@ mod test {
         );
     }
 
+    #[tokio::test]
+    async fn test_depot_snapshot() {
+        let mut depot = Depot::new();
+        depot.inject_cloneable(Arc::new(5u8));
+        depot.insert_cloneable("user", "jobs".to_owned());
+        depot.insert_cloneable("replaced", 1u32);
+        depot.insert("replaced", 2u32);
+        depot.insert_cloneable("removed", 3u32);
+        depot.delete("removed");
+        depot.insert("raw", std::sync::Mutex::new(4u32));
+
+        let snapshot = depot.snapshot();
+        let handle = tokio::spawn(async move {
+            format!(
+                "{} {}",
+                snapshot.obtain::<Arc<u8>>().unwrap(),
+                snapshot.get::<String>("user").unwrap()
+            )
+        });
+        assert_eq!(handle.await.unwrap(), "5 jobs");
+
+        let snapshot = depot.snapshot().snapshot();
+        assert_eq!(snapshot.inner().len(), 2);
+        assert!(!snapshot.contains_key("replaced"));
+        assert!(!snapshot.contains_key("raw"));
+        assert_eq!(depot.get::<u32>("replaced").unwrap(), &2);
+    }
+
     #[tokio::test]
     async fn test_middleware_use_depot() {
         #[handler]
@@ -249,4 +411,45 @@ mod test {
             .unwrap();
         assert_eq!(content, "Hello client");
     }
+
+    #[tokio::test]
+    async fn test_depot_scoping() {
+        #[handler]
+        async fn set_outer(
+            req: &mut Request,
+            depot: &mut Depot,
+            res: &mut Response,
+            ctrl: &mut FlowCtrl,
+        ) {
+            depot.insert("scope", "outer");
+            ctrl.call_next(req, depot, res).await;
+            let value = depot.get::<&str>("scope").copied().unwrap_or_default();
+            res.add_header("x-outer-after", value, true).unwrap();
+        }
+        #[handler]
+        async fn set_inner(depot: &mut Depot) {
+            depot.insert("scope", "inner");
+        }
+        #[handler]
+        async fn scope(depot: &mut Depot) -> &'static str {
+            depot.get::<&str>("scope").copied().unwrap_or_default()
+        }
+        let router = Router::with_path("api")
+            .hoop(set_outer)
+            .push(Router::with_path("inner").hoop(set_inner).get(scope))
+            .push(Router::with_path("sibling").get(scope));
+        let service = Service::new(router);
+
+        let mut res = TestClient::get("http://127.0.0.1:5800/api/inner")
+            .send(&service)
+            .await;
+        assert_eq!(res.headers()["x-outer-after"], "inner");
+        assert_eq!(res.take_string().await.unwrap(), "inner");
+
+        let mut res = TestClient::get("http://127.0.0.1:5800/api/sibling")
+            .send(&service)
+            .await;
+        assert_eq!(res.headers()["x-outer-after"], "outer");
+        assert_eq!(res.take_string().await.unwrap(), "outer");
+    }
 }
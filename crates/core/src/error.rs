@@ -148,12 +148,14 @@ impl From<BoxedError> for Error {
 }
 
 impl Scribe for Error {
+    /// Render [`StatusError`] as it is, [`ParseError`] by the status of the request problem, and the others as
+    /// `500 Internal Server Error`.
     fn render(self, res: &mut Response) {
-        let status_error = match self {
-            Error::HttpStatus(e) => e,
-            _ => StatusError::internal_server_error().cause(self),
-        };
-        res.render(status_error);
+        match self {
+            Error::HttpStatus(e) => res.render(e),
+            Error::HttpParse(e) => res.render(e),
+            _ => res.render(StatusError::internal_server_error().cause(self)),
+        }
     }
 }
 cfg_feature! {
@@ -215,5 +217,17 @@ mod tests {
         let e = Error::Other("detail message".into());
         e.write(&mut req, &mut depot, &mut res).await;
         assert_eq!(res.status_code, Some(StatusCode::INTERNAL_SERVER_ERROR));
+
+        let mut res = Response::default();
+        Error::from(StatusError::not_found())
+            .write(&mut req, &mut depot, &mut res)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::NOT_FOUND));
+
+        let mut res = Response::default();
+        Error::from(ParseError::PayloadTooLarge)
+            .write(&mut req, &mut depot, &mut res)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::PAYLOAD_TOO_LARGE));
     }
 }
@@ -0,0 +1,157 @@
+use std::error::Error as StdError;
+use std::fmt::{self, Display, Formatter};
+use std::slice::Iter;
+use std::vec::IntoIter;
+
+use serde::Serialize;
+
+use crate::Scribe;
+use crate::extract::metadata::SourceFrom;
+use crate::http::{Response, StatusCode};
+use crate::writing::Json;
+
+/// Error information about a field which failed to be extracted from request.
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FieldError {
+    /// The name of the field in request, for example the query key or the json key.
+    pub field: String,
+    /// The source the field is extracted from, it is `None` if the field is not found in any source.
+    pub source: Option<SourceFrom>,
+    /// The error message.
+    pub message: String,
+}
+impl FieldError {
+    /// Create a new `FieldError`.
+    pub fn new(
+        field: impl Into<String>,
+        source: Option<SourceFrom>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            field: field.into(),
+            source,
+            message: message.into(),
+        }
+    }
+}
+impl Display for FieldError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(source) = &self.source {
+            write!(f, "{} (from {}): {}", self.field, source, self.message)
+        } else {
+            write!(f, "{}: {}", self.field, self.message)
+        }
+    }
+}
+
+/// Errors of all the fields which failed to be extracted from request.
+///
+/// It is rendered as `422 Unprocessable Entity` with a json body like this:
+///
+/// ```json
+/// {
+///     "error": {
+///         "code": 422,
+///         "name": "Unprocessable Entity",
+///         "brief": "extract data from request failed.",
+///         "errors": [
+///             { "field": "id", "source": "param", "message": "invalid digit found in string" },
+///             { "field": "title", "source": null, "message": "missing field" }
+///         ]
+///     }
+/// }
+/// ```
+#[derive(Serialize, Clone, Default, Debug, PartialEq, Eq)]
+pub struct FieldErrors(Vec<FieldError>);
+impl FieldErrors {
+    /// Create a new empty `FieldErrors`.
+    #[inline]
+    pub fn new() -> Self {
+        Self(vec![])
+    }
+    /// Add a `FieldError`.
+    #[inline]
+    pub fn push(&mut self, error: FieldError) {
+        self.0.push(error);
+    }
+    /// Check if there is no error.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// Get the count of errors.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    /// Returns an iterator over all errors.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, FieldError> {
+        self.0.iter()
+    }
+}
+impl Extend<FieldError> for FieldErrors {
+    fn extend<T: IntoIterator<Item = FieldError>>(&mut self, iter: T) {
+        self.0.extend(iter);
+    }
+}
+impl FromIterator<FieldError> for FieldErrors {
+    fn from_iter<T: IntoIterator<Item = FieldError>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+impl IntoIterator for FieldErrors {
+    type Item = FieldError;
+    type IntoIter = IntoIter<FieldError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+impl<'a> IntoIterator for &'a FieldErrors {
+    type Item = &'a FieldError;
+    type IntoIter = Iter<'a, FieldError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+impl Display for FieldErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, error) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            Display::fmt(error, f)?;
+        }
+        Ok(())
+    }
+}
+impl StdError for FieldErrors {}
+
+impl Scribe for FieldErrors {
+    fn render(self, res: &mut Response) {
+        #[derive(Serialize)]
+        struct Data<'a> {
+            error: Error<'a>,
+        }
+        #[derive(Serialize)]
+        struct Error<'a> {
+            code: u16,
+            name: &'a str,
+            brief: &'a str,
+            errors: FieldErrors,
+        }
+        let code = StatusCode::UNPROCESSABLE_ENTITY;
+        res.status_code(code);
+        res.render(Json(Data {
+            error: Error {
+                code: code.as_u16(),
+                name: code.canonical_reason().unwrap_or_default(),
+                brief: "extract data from request failed.",
+                errors: self,
+            },
+        }));
+    }
+}
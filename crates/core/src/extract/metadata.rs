@@ -1,13 +1,18 @@
+use std::fmt::{self, Display, Formatter};
 use std::str::FromStr;
 use std::vec;
 
-use crate::extract::RenameRule;
+use serde::Serialize;
+
+use crate::extract::{FieldError, RenameRule};
+use crate::http::Request;
 
 /// Source for a field.
-#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+#[derive(Serialize, Eq, PartialEq, Copy, Clone, Debug)]
+#[serde(rename_all = "lowercase")]
 #[non_exhaustive]
 pub enum SourceFrom {
-    /// The field will extracted from url param.
+    /// The field will extracted from url param, `path` is an alias of `param`.
     Param,
     /// The field will extracted from url query.
     Query,
@@ -20,12 +25,32 @@ pub enum SourceFrom {
     Body,
 }
 
+impl SourceFrom {
+    /// Returns the name of this source.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Param => "param",
+            Self::Query => "query",
+            Self::Header => "header",
+            #[cfg(feature = "cookie")]
+            Self::Cookie => "cookie",
+            Self::Body => "body",
+        }
+    }
+}
+
+impl Display for SourceFrom {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 impl FromStr for SourceFrom {
     type Err = crate::Error;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
         match input {
-            "param" => Ok(Self::Param),
+            "param" | "path" => Ok(Self::Param),
             "query" => Ok(Self::Query),
             "header" => Ok(Self::Header),
             #[cfg(feature = "cookie")]
@@ -53,6 +78,10 @@ pub enum SourceParser {
     Flat,
     /// Smart parser.
     Smart,
+    /// Nested parser, only for query, the keys with bracket or dot notation like `filter[status]=done`,
+    /// `filter.status=done` or `ids[0]=1` are parsed into nested structs, maps and sequences. It is the smart
+    /// parser for the other sources.
+    Nested,
 }
 
 impl FromStr for SourceParser {
@@ -63,6 +92,7 @@ impl FromStr for SourceParser {
             "multimap" => Ok(Self::MultiMap),
             "json" => Ok(Self::Json),
             "smart" => Ok(Self::Smart),
+            "nested" => Ok(Self::Nested),
             _ => Err(crate::Error::Other("invalid source format".into())),
         }
     }
@@ -145,6 +175,11 @@ impl Metadata {
     }
 }
 
+/// Function used to check a field of the request, it returns the error if the field can not be extracted.
+///
+/// This is used to collect the errors of all fields after extracting failed.
+pub type FieldChecker = for<'de> fn(&'de Request, &'de Metadata, &'de Field) -> Option<FieldError>;
+
 /// Information about struct field.
 #[derive(Clone, Debug)]
 #[non_exhaustive]
@@ -163,6 +198,8 @@ pub struct Field {
     pub serde_rename: Option<&'static str>,
     /// Field metadata, this is used for nested extractible types.
     pub metadata: Option<&'static Metadata>,
+    /// Field checker, this is used to collect errors of all fields.
+    pub checker: Option<FieldChecker>,
 }
 impl Field {
     /// Create a new field with the given name and kind.
@@ -180,6 +217,7 @@ impl Field {
             rename: None,
             serde_rename: None,
             metadata: None,
+            checker: None,
         }
     }
 
@@ -195,6 +233,12 @@ impl Field {
         self
     }
 
+    /// Sets the checker to the field.
+    pub fn checker(mut self, checker: FieldChecker) -> Self {
+        self.checker = Some(checker);
+        self
+    }
+
     /// Add a source to sources list.
     pub fn add_source(mut self, source: Source) -> Self {
         self.sources.push(source);
@@ -255,6 +299,7 @@ mod tests {
     fn test_parse_source_from() {
         for (key, value) in [
             ("param", SourceFrom::Param),
+            ("path", SourceFrom::Param),
             ("query", SourceFrom::Query),
             ("header", SourceFrom::Header),
             #[cfg(feature = "cookie")]
@@ -271,6 +316,7 @@ mod tests {
         for (key, value) in [
             ("multimap", SourceParser::MultiMap),
             ("json", SourceParser::Json),
+            ("nested", SourceParser::Nested),
         ] {
             assert_eq!(key.parse::<SourceParser>().unwrap(), value);
         }
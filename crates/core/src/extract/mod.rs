@@ -61,12 +61,64 @@
 //! ```
 //!
 //! View [full source code](https://github.com/salvo-rs/salvo/blob/main/examples/extract-nested/src/main.rs)
+//!
+//! # Sources and precedence
+//!
+//! A single type can be filled from several parts of the request at the same time, for example
+//! `id` from the path, `filter` from the query and the other fields from the body:
+//!
+//! ```
+//! # use salvo_core::prelude::*;
+//! # use serde::{Deserialize, Serialize};
+//! #[derive(Serialize, Deserialize, Extractible, Debug)]
+//! #[salvo(extract(default_source(from = "body")))]
+//! struct UpdateTodo {
+//!     #[salvo(extract(source(from = "path")))]
+//!     id: i64,
+//!     #[salvo(extract(source(from = "query")))]
+//!     filter: Option<String>,
+//!     title: String,
+//!     completed: bool,
+//! }
+//! ```
+//!
+//! Available sources are `param` (or its alias `path`), `query`, `header`, `cookie` and `body`.
+//! The value of each field is resolved by these rules:
+//!
+//! - If the field declares its own `source`s, the `default_source`s of the type are ignored for it.
+//! - Sources are tried in the order they are declared, the first source that contains the field wins.
+//! - In each source, the field name is looked up first, then its aliases in declaration order.
+//! - The field name is `rename`, then `serde(rename)`, then `rename_all`, then `serde(rename_all)`,
+//!   and finally the declared name.
+//!
+//! If no source contains a field, it is treated as missing, so use `Option` or `#[serde(default)]`
+//! for optional fields.
+//!
+//! # Errors
+//!
+//! When some fields are missing or can not be parsed, all of them are checked and collected into
+//! [`FieldErrors`], and extraction fails with [`ParseError::Fields`](crate::http::ParseError::Fields),
+//! which is rendered as `422 Unprocessable Entity`. See [`FieldErrors`] for the json shape.
+//!
+//! If a handler has several extractor arguments, all of them are extracted, and the errors of the
+//! failed arguments are merged into one `422` response as well. An argument whose error has no
+//! field details is reported with the argument name as its `field`.
 
 /// Metadata types.
 pub mod metadata;
 pub use metadata::Metadata;
 mod case;
 pub use case::RenameRule;
+mod field_error;
+pub use field_error::{FieldError, FieldErrors};
+mod obtain;
+pub use obtain::Obtain;
+mod parsed;
+pub use parsed::Parsed;
+mod patch;
+pub use patch::{
+    JSON_PATCH_JSON, JsonPatch, MERGE_PATCH_JSON, MergePatch, PatchError, PatchOperation,
+};
 
 use std::fmt::Debug;
 use std::future::Future;
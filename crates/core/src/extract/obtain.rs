@@ -0,0 +1,132 @@
+use std::any::{Any, type_name};
+use std::fmt::{self, Debug, Formatter};
+use std::ops::{Deref, DerefMut};
+
+use crate::Depot;
+use crate::http::StatusError;
+
+/// Extract a value injected into [`Depot`] by its type, as a handler argument.
+///
+/// It is the argument version of [`Depot::obtain`], the value is cloned out of the depot, so inject the shared
+/// states as `Arc`, like the ones injected by the `affix_state` middleware of `salvo-extra`.
+///
+/// The handler is not called if the value is not injected, and `500 Internal Server Error` is written with the
+/// type name in the brief, like ``The `alloc::sync::Arc<app::AppState>` is not found in depot.``, which usually
+/// means the middleware injecting it is not added to the route.
+///
+/// The handler macros recognize the argument by the name `Obtain`, so do not rename it on import.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use salvo_core::extract::Obtain;
+/// use salvo_core::prelude::*;
+///
+/// struct AppState {
+///     name: String,
+/// }
+///
+/// #[handler]
+/// async fn inject_state(depot: &mut Depot) {
+///     depot.inject(Arc::new(AppState { name: "todos".into() }));
+/// }
+///
+/// #[handler]
+/// async fn hello(state: Obtain<Arc<AppState>>) -> String {
+///     format!("Hello {}", state.name)
+/// }
+///
+/// let router = Router::new().hoop(inject_state).get(hello);
+/// ```
+pub struct Obtain<T>(pub T);
+
+impl<T> Obtain<T>
+where
+    T: Any + Send + Sync + Clone,
+{
+    /// Obtain the value from depot, used by the handler macros.
+    pub fn from_depot(depot: &Depot) -> Result<Self, StatusError> {
+        match depot.obtain::<T>() {
+            Ok(value) => Ok(Self(value.clone())),
+            Err(_) => {
+                let brief = format!("The `{}` is not found in depot.", type_name::<T>());
+                tracing::error!("{brief}");
+                Err(StatusError::internal_server_error().brief(brief))
+            }
+        }
+    }
+}
+
+impl<T> Obtain<T> {
+    /// Consumes self, returning the inner value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Obtain<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Obtain<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: Debug> Debug for Obtain<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("Obtain").field(&self.0).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::prelude::*;
+    use crate::test::{ResponseExt, TestClient};
+
+    #[derive(Debug)]
+    struct AppState {
+        name: &'static str,
+    }
+
+    #[handler]
+    async fn inject_state(depot: &mut Depot) {
+        depot.inject(Arc::new(AppState { name: "todos" }));
+    }
+    #[handler]
+    async fn hello(state: Obtain<Arc<AppState>>, req: &mut Request) -> String {
+        format!("{} {}", state.name, req.uri().path())
+    }
+
+    #[tokio::test]
+    async fn test_obtain() {
+        let router = Router::new()
+            .push(Router::with_path("injected").hoop(inject_state).get(hello))
+            .push(Router::with_path("missing").get(hello));
+        let service = Service::new(router);
+
+        let mut res = TestClient::get("http://127.0.0.1:5800/injected")
+            .send(&service)
+            .await;
+        assert_eq!(res.take_string().await.unwrap(), "todos /injected");
+
+        let mut res = TestClient::get("http://127.0.0.1:5800/missing")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(res.take_string().await.unwrap().contains("AppState"));
+    }
+}
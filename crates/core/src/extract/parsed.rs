@@ -0,0 +1,250 @@
+use std::fmt::{self, Display, Formatter};
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::str::FromStr;
+
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+/// A value parsed from string with its [`FromStr`] implementation.
+///
+/// Types which only implement [`FromStr`] can not be used with [`Request::param`],
+/// [`Request::query`] or as fields of [`Extractible`](super::Extractible) types directly, because
+/// these require [`Deserialize`]. Wrap them in `Parsed` to make them work:
+///
+/// ```
+/// # use std::str::FromStr;
+/// # use salvo_core::prelude::*;
+/// # use salvo_core::http::ParseError;
+/// use salvo_core::extract::Parsed;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum SortOrder {
+///     Asc,
+///     Desc,
+/// }
+/// impl FromStr for SortOrder {
+///     type Err = String;
+///     fn from_str(s: &str) -> Result<Self, Self::Err> {
+///         match s {
+///             "asc" => Ok(Self::Asc),
+///             "desc" => Ok(Self::Desc),
+///             _ => Err(format!("unknown sort order `{s}`")),
+///         }
+///     }
+/// }
+///
+/// #[handler]
+/// async fn list(req: &mut Request) -> Result<String, ParseError> {
+///     // Responds `400 Bad Request` if the value is not a valid `SortOrder`.
+///     let order = req.try_query::<Parsed<SortOrder>>("order")?;
+///     Ok(format!("{:?}", *order))
+/// }
+/// ```
+///
+/// When a `Parsed` value is a field of an [`Extractible`](super::Extractible) type, invalid
+/// values are collected with the other field errors into [`ParseError::Fields`], which responds
+/// `422 Unprocessable Entity` with the failed fields as detail. The request itself is well formed
+/// in that case and only the field values are rejected.
+///
+/// Enums which derive [`Deserialize`] can be used directly, for example
+/// `#[serde(rename_all = "lowercase")]` enums are parsed from the lowercase variant names.
+///
+/// [`ParseError::Fields`]: crate::http::ParseError::Fields
+/// [`Request::param`]: crate::Request::param
+/// [`Request::query`]: crate::Request::query
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Parsed<T>(pub T);
+
+impl<T> Parsed<T> {
+    /// Consumes self and returns the inner value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Parsed<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Parsed<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: Display> Display for Parsed<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: Display> Serialize for Parsed<T> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(&self.0)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Parsed<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ParsedVisitor<T>(PhantomData<T>);
+        impl<T> ParsedVisitor<T>
+        where
+            T: FromStr,
+            T::Err: Display,
+        {
+            fn parse<E: de::Error>(value: &str) -> Result<Parsed<T>, E> {
+                value.parse().map(Parsed).map_err(E::custom)
+            }
+        }
+        impl<T> Visitor<'_> for ParsedVisitor<T>
+        where
+            T: FromStr,
+            T::Err: Display,
+        {
+            type Value = Parsed<T>;
+
+            fn expecting(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a string")
+            }
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                Self::parse(value)
+            }
+            fn visit_bool<E: de::Error>(self, value: bool) -> Result<Self::Value, E> {
+                Self::parse(&value.to_string())
+            }
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+                Self::parse(&value.to_string())
+            }
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+                Self::parse(&value.to_string())
+            }
+            fn visit_f64<E: de::Error>(self, value: f64) -> Result<Self::Value, E> {
+                Self::parse(&value.to_string())
+            }
+        }
+        deserializer.deserialize_str(ParsedVisitor(PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use serde::Deserialize;
+
+    use super::Parsed;
+    use crate::http::{ParseError, StatusCode};
+    use crate::macros::Extractible;
+    use crate::prelude::*;
+    use crate::test::{ResponseExt, TestClient};
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum SortOrder {
+        Asc,
+        Desc,
+    }
+    impl FromStr for SortOrder {
+        type Err = String;
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "asc" => Ok(Self::Asc),
+                "desc" => Ok(Self::Desc),
+                _ => Err(format!("unknown sort order `{s}`")),
+            }
+        }
+    }
+
+    #[derive(Deserialize, Debug, PartialEq, Eq)]
+    #[serde(rename_all = "lowercase")]
+    enum Status {
+        Active,
+        Completed,
+    }
+
+    #[tokio::test]
+    async fn test_parsed_query_and_param() {
+        let mut req =
+            TestClient::get("http://127.0.0.1:5800/todos?order=desc&status=completed").build();
+        req.params.insert("order", "asc".into());
+        assert_eq!(
+            req.query::<Parsed<SortOrder>>("order")
+                .map(Parsed::into_inner),
+            Some(SortOrder::Desc)
+        );
+        assert_eq!(
+            req.param::<Parsed<SortOrder>>("order")
+                .map(Parsed::into_inner),
+            Some(SortOrder::Asc)
+        );
+        assert_eq!(req.query::<Status>("status"), Some(Status::Completed));
+
+        let req =
+            TestClient::get("http://127.0.0.1:5800/todos?order=random&status=unknown").build();
+        assert!(req.try_query::<Parsed<SortOrder>>("order").is_err());
+        assert!(req.try_query::<Status>("status").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_parsed_extract() {
+        #[derive(Deserialize, Extractible, Debug)]
+        #[salvo(extract(default_source(from = "query")))]
+        struct ListTodos {
+            order: Parsed<SortOrder>,
+            status: Option<Status>,
+        }
+        #[handler]
+        async fn list(todos: ListTodos) -> String {
+            format!("{:?} {:?}", *todos.order, todos.status)
+        }
+        let service = Service::new(Router::new().get(list));
+
+        let content = TestClient::get("http://127.0.0.1:5800/?order=asc&status=active")
+            .send(&service)
+            .await
+            .take_string()
+            .await
+            .unwrap();
+        assert_eq!(content, "Asc Some(Active)");
+
+        let res = TestClient::get("http://127.0.0.1:5800/?order=random")
+            .send(&service)
+            .await;
+        // Field errors of extractible types are reported as `422`, see `ParseError::Fields`.
+        assert_eq!(res.status_code, Some(StatusCode::UNPROCESSABLE_ENTITY));
+    }
+
+    #[tokio::test]
+    async fn test_parsed_invalid_variant() {
+        #[handler]
+        async fn list(req: &mut Request) -> Result<String, ParseError> {
+            let order = req.try_query::<Parsed<SortOrder>>("order")?;
+            Ok(format!("{:?}", *order))
+        }
+        let service = Service::new(Router::new().get(list));
+
+        let res = TestClient::get("http://127.0.0.1:5800/?order=random")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::BAD_REQUEST));
+    }
+}
@@ -0,0 +1,502 @@
+use std::mem;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+use super::{Extractible, Metadata};
+use crate::Scribe;
+use crate::http::{ParseError, Request, Response, StatusError};
+
+/// The media type of JSON Merge Patch.
+pub const MERGE_PATCH_JSON: &str = "application/merge-patch+json";
+/// The media type of JSON Patch.
+pub const JSON_PATCH_JSON: &str = "application/json-patch+json";
+
+/// Errors happened when apply a patch.
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum PatchError {
+    /// The path of an operation does not exist or is not a valid JSON Pointer.
+    #[error("invalid path `{0}`")]
+    InvalidPath(String),
+    /// The value at the path of a `test` operation is not equal to the expected value.
+    #[error("test failed at path `{0}`")]
+    TestFailed(String),
+    /// The target can not be serialized, or the patched value can not be deserialized as the target type.
+    #[error("patched value is invalid: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+}
+
+impl From<PatchError> for StatusError {
+    /// Map the error to `409 Conflict` for a failed `test` operation, and `422 Unprocessable Entity` for the others.
+    fn from(e: PatchError) -> Self {
+        let error = if let PatchError::TestFailed(_) = e {
+            StatusError::conflict()
+        } else {
+            StatusError::unprocessable_entity()
+        };
+        error.brief(e.to_string()).cause(e)
+    }
+}
+
+impl Scribe for PatchError {
+    fn render(self, res: &mut Response) {
+        res.render(StatusError::from(self));
+    }
+}
+
+/// A [JSON Merge Patch](https://datatracker.ietf.org/doc/html/rfc7386) document, extracted from the request body.
+///
+/// The content type of the request must be `application/merge-patch+json` or `application/json`, otherwise
+/// [`ParseError::InvalidContentType`] is returned. The fields in the patch replace the fields of the target, a
+/// `null` field removes it, and the fields not in the patch are kept.
+///
+/// # Example
+///
+/// ```
+/// use salvo_core::extract::{MergePatch, PatchError};
+/// use salvo_core::prelude::*;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Todo {
+///     text: String,
+///     completed: bool,
+/// }
+///
+/// #[handler]
+/// async fn update_todo(patch: MergePatch) -> Result<Json<Todo>, PatchError> {
+///     let mut todo = Todo {
+///         text: "write docs".into(),
+///         completed: false,
+///     };
+///     patch.apply(&mut todo)?;
+///     Ok(Json(todo))
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(transparent)]
+pub struct MergePatch(pub Value);
+
+impl MergePatch {
+    /// Consumes self and returns the patch document.
+    #[inline]
+    pub fn into_inner(self) -> Value {
+        self.0
+    }
+
+    /// Apply the patch to the JSON value.
+    pub fn apply_value(&self, target: &mut Value) {
+        merge(target, &self.0);
+    }
+
+    /// Apply the patch to the target, which is converted to JSON and back.
+    ///
+    /// The target is not changed if the patched value can not be deserialized as `T`.
+    pub fn apply<T>(&self, target: &mut T) -> Result<(), PatchError>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        let mut value = serde_json::to_value(&*target)?;
+        self.apply_value(&mut value);
+        *target = serde_json::from_value(value)?;
+        Ok(())
+    }
+}
+
+fn merge(target: &mut Value, patch: &Value) {
+    let Value::Object(patch) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = Value::Object(Default::default());
+    }
+    if let Value::Object(target) = target {
+        for (key, value) in patch {
+            if value.is_null() {
+                target.remove(key);
+            } else {
+                merge(target.entry(key).or_insert(Value::Null), value);
+            }
+        }
+    }
+}
+
+impl<'ex> Extractible<'ex> for MergePatch {
+    fn metadata() -> &'ex Metadata {
+        static METADATA: Metadata = Metadata::new("");
+        &METADATA
+    }
+    #[allow(refining_impl_trait)]
+    async fn extract(req: &'ex mut Request) -> Result<Self, ParseError> {
+        let matched = req.content_type().is_some_and(|ctype| {
+            ctype.essence_str() == MERGE_PATCH_JSON || ctype.essence_str() == "application/json"
+        });
+        if !matched {
+            return Err(ParseError::InvalidContentType {
+                supported: vec![
+                    MERGE_PATCH_JSON
+                        .parse()
+                        .expect("merge patch media type should be valid"),
+                    mime::APPLICATION_JSON,
+                ],
+            });
+        }
+        req.parse_json().await.map(Self)
+    }
+}
+
+/// An operation of [`JsonPatch`].
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(tag = "op", rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum PatchOperation {
+    /// Add the value at the path, `-` as the last token appends to an array.
+    Add {
+        /// The JSON Pointer of the target location.
+        path: String,
+        /// The value to add.
+        value: Value,
+    },
+    /// Remove the value at the path.
+    Remove {
+        /// The JSON Pointer of the target location.
+        path: String,
+    },
+    /// Replace the value at the path.
+    Replace {
+        /// The JSON Pointer of the target location.
+        path: String,
+        /// The new value.
+        value: Value,
+    },
+    /// Move the value from a location to the path.
+    Move {
+        /// The JSON Pointer of the source location.
+        from: String,
+        /// The JSON Pointer of the target location.
+        path: String,
+    },
+    /// Copy the value from a location to the path.
+    Copy {
+        /// The JSON Pointer of the source location.
+        from: String,
+        /// The JSON Pointer of the target location.
+        path: String,
+    },
+    /// Test that the value at the path is equal to the value.
+    Test {
+        /// The JSON Pointer of the target location.
+        path: String,
+        /// The expected value.
+        value: Value,
+    },
+}
+
+/// A [JSON Patch](https://datatracker.ietf.org/doc/html/rfc6902) document, extracted from the request body.
+///
+/// The content type of the request must be `application/json-patch+json`, otherwise
+/// [`ParseError::InvalidContentType`] is returned. The operations are applied in order, and the patch is atomic:
+/// if any operation fails, the target is not changed. A failed `test` operation is written as `409 Conflict`, and
+/// the other [`PatchError`]s are written as `422 Unprocessable Entity`.
+///
+/// # Example
+///
+/// ```
+/// use salvo_core::extract::{JsonPatch, PatchError};
+/// use salvo_core::prelude::*;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Todo {
+///     text: String,
+///     completed: bool,
+/// }
+///
+/// #[handler]
+/// async fn update_todo(patch: JsonPatch) -> Result<Json<Todo>, PatchError> {
+///     let mut todo = Todo {
+///         text: "write docs".into(),
+///         completed: false,
+///     };
+///     patch.apply(&mut todo)?;
+///     Ok(Json(todo))
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(transparent)]
+pub struct JsonPatch(pub Vec<PatchOperation>);
+
+impl JsonPatch {
+    /// Consumes self and returns the operations.
+    #[inline]
+    pub fn into_inner(self) -> Vec<PatchOperation> {
+        self.0
+    }
+
+    /// Apply the operations to the JSON value.
+    pub fn apply_value(&self, target: &mut Value) -> Result<(), PatchError> {
+        let mut patched = target.clone();
+        for operation in &self.0 {
+            apply_operation(&mut patched, operation)?;
+        }
+        *target = patched;
+        Ok(())
+    }
+
+    /// Apply the operations to the target, which is converted to JSON and back.
+    ///
+    /// The target is not changed if any operation fails or the patched value can not be deserialized as `T`.
+    pub fn apply<T>(&self, target: &mut T) -> Result<(), PatchError>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        let mut value = serde_json::to_value(&*target)?;
+        self.apply_value(&mut value)?;
+        *target = serde_json::from_value(value)?;
+        Ok(())
+    }
+}
+
+fn apply_operation(target: &mut Value, operation: &PatchOperation) -> Result<(), PatchError> {
+    match operation {
+        PatchOperation::Add { path, value } => add(target, path, value.clone()),
+        PatchOperation::Remove { path } => remove(target, path).map(|_| ()),
+        PatchOperation::Replace { path, value } => {
+            let current = target
+                .pointer_mut(path)
+                .ok_or_else(|| PatchError::InvalidPath(path.clone()))?;
+            *current = value.clone();
+            Ok(())
+        }
+        PatchOperation::Move { from, path } => {
+            if path.starts_with(&format!("{from}/")) {
+                return Err(PatchError::InvalidPath(path.clone()));
+            }
+            let value = remove(target, from)?;
+            add(target, path, value)
+        }
+        PatchOperation::Copy { from, path } => {
+            let value = target
+                .pointer(from)
+                .cloned()
+                .ok_or_else(|| PatchError::InvalidPath(from.clone()))?;
+            add(target, path, value)
+        }
+        PatchOperation::Test { path, value } => match target.pointer(path) {
+            Some(current) if current == value => Ok(()),
+            Some(_) => Err(PatchError::TestFailed(path.clone())),
+            None => Err(PatchError::InvalidPath(path.clone())),
+        },
+    }
+}
+
+// Split the JSON Pointer into the pointer of the parent and the unescaped last token.
+fn split_pointer(path: &str) -> Result<(&str, String), PatchError> {
+    let (parent, token) = path
+        .rsplit_once('/')
+        .ok_or_else(|| PatchError::InvalidPath(path.to_owned()))?;
+    Ok((parent, token.replace("~1", "/").replace("~0", "~")))
+}
+
+fn add(target: &mut Value, path: &str, value: Value) -> Result<(), PatchError> {
+    if path.is_empty() {
+        *target = value;
+        return Ok(());
+    }
+    let (parent, token) = split_pointer(path)?;
+    let invalid = || PatchError::InvalidPath(path.to_owned());
+    match target.pointer_mut(parent).ok_or_else(invalid)? {
+        Value::Object(map) => {
+            map.insert(token, value);
+        }
+        Value::Array(array) => {
+            let index = if token == "-" {
+                array.len()
+            } else {
+                parse_index(&token)
+                    .filter(|index| *index <= array.len())
+                    .ok_or_else(invalid)?
+            };
+            array.insert(index, value);
+        }
+        _ => return Err(invalid()),
+    }
+    Ok(())
+}
+
+fn remove(target: &mut Value, path: &str) -> Result<Value, PatchError> {
+    if path.is_empty() {
+        return Ok(mem::take(target));
+    }
+    let (parent, token) = split_pointer(path)?;
+    let invalid = || PatchError::InvalidPath(path.to_owned());
+    match target.pointer_mut(parent).ok_or_else(invalid)? {
+        Value::Object(map) => map.remove(&token).ok_or_else(invalid),
+        Value::Array(array) => {
+            let index = parse_index(&token)
+                .filter(|index| *index < array.len())
+                .ok_or_else(invalid)?;
+            Ok(array.remove(index))
+        }
+        _ => Err(invalid()),
+    }
+}
+
+// Array indexes in JSON Pointer must not have leading zeros.
+fn parse_index(token: &str) -> Option<usize> {
+    if token.len() > 1 && token.starts_with('0') {
+        return None;
+    }
+    token.parse().ok()
+}
+
+impl<'ex> Extractible<'ex> for JsonPatch {
+    fn metadata() -> &'ex Metadata {
+        static METADATA: Metadata = Metadata::new("");
+        &METADATA
+    }
+    #[allow(refining_impl_trait)]
+    async fn extract(req: &'ex mut Request) -> Result<Self, ParseError> {
+        let matched = req
+            .content_type()
+            .is_some_and(|ctype| ctype.essence_str() == JSON_PATCH_JSON);
+        if !matched {
+            return Err(ParseError::InvalidContentType {
+                supported: vec![
+                    JSON_PATCH_JSON
+                        .parse()
+                        .expect("json patch media type should be valid"),
+                ],
+            });
+        }
+        req.parse_json().await.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::prelude::*;
+    use crate::test::{ResponseExt, TestClient};
+
+    #[test]
+    fn test_merge_patch() {
+        let mut target = json!({"title": "Goodbye!", "author": {"givenName": "John", "familyName": "Doe"}, "tags": ["example", "sample"]});
+        MergePatch(json!({"title": "Hello!", "phoneNumber": "+01-123-456-7890", "author": {"familyName": null}, "tags": ["example"]}))
+            .apply_value(&mut target);
+        assert_eq!(
+            target,
+            json!({"title": "Hello!", "author": {"givenName": "John"}, "tags": ["example"], "phoneNumber": "+01-123-456-7890"})
+        );
+    }
+
+    #[test]
+    fn test_json_patch() {
+        let patch: JsonPatch = serde_json::from_value(json!([
+            {"op": "test", "path": "/a/b", "value": 1},
+            {"op": "add", "path": "/a/c", "value": [1, 2]},
+            {"op": "add", "path": "/a/c/1", "value": 3},
+            {"op": "add", "path": "/a/c/-", "value": 4},
+            {"op": "remove", "path": "/a/c/0"},
+            {"op": "replace", "path": "/a/b", "value": "x"},
+            {"op": "copy", "from": "/a/b", "path": "/d~1e"},
+            {"op": "move", "from": "/a/c", "path": "/f"},
+        ]))
+        .unwrap();
+        let mut target = json!({"a": {"b": 1}});
+        patch.apply_value(&mut target).unwrap();
+        assert_eq!(target, json!({"a": {"b": "x"}, "d/e": "x", "f": [3, 2, 4]}));
+
+        let patch: JsonPatch = serde_json::from_value(json!([
+            {"op": "replace", "path": "/a", "value": 2},
+            {"op": "test", "path": "/a", "value": 1},
+        ]))
+        .unwrap();
+        let mut target = json!({"a": 1});
+        assert!(matches!(
+            patch.apply_value(&mut target),
+            Err(PatchError::TestFailed(_))
+        ));
+        assert_eq!(target, json!({"a": 1}));
+
+        let patch: JsonPatch =
+            serde_json::from_value(json!([{"op": "remove", "path": "/b"}])).unwrap();
+        assert!(matches!(
+            patch.apply_value(&mut target),
+            Err(PatchError::InvalidPath(_))
+        ));
+        let patch: JsonPatch =
+            serde_json::from_value(json!([{"op": "add", "path": "/a/01", "value": 1}])).unwrap();
+        assert!(matches!(
+            patch.apply_value(&mut json!({"a": [0, 1]})),
+            Err(PatchError::InvalidPath(_))
+        ));
+    }
+
+    #[derive(Serialize, Deserialize, Debug)]
+    struct Todo {
+        text: String,
+        completed: bool,
+    }
+    fn todo() -> Todo {
+        Todo {
+            text: "write docs".into(),
+            completed: false,
+        }
+    }
+
+    #[handler]
+    async fn merge_todo(patch: MergePatch) -> Result<Json<Todo>, PatchError> {
+        let mut todo = todo();
+        patch.apply(&mut todo)?;
+        Ok(Json(todo))
+    }
+    #[handler]
+    async fn patch_todo(patch: JsonPatch) -> Result<Json<Todo>, PatchError> {
+        let mut todo = todo();
+        patch.apply(&mut todo)?;
+        Ok(Json(todo))
+    }
+
+    #[tokio::test]
+    async fn test_extract_patch() {
+        let router = Router::new()
+            .push(Router::with_path("merge").patch(merge_todo))
+            .push(Router::with_path("patch").patch(patch_todo));
+        let service = Service::new(router);
+
+        async fn access(service: &Service, path: &str, ctype: &str, body: &str) -> Response {
+            TestClient::patch(format!("http://127.0.0.1:5800/{path}"))
+                .raw_json(body)
+                .add_header("content-type", ctype, true)
+                .send(service)
+                .await
+        }
+
+        let mut res = access(&service, "merge", MERGE_PATCH_JSON, r#"{"completed":true}"#).await;
+        assert_eq!(
+            res.take_string().await.unwrap(),
+            r#"{"text":"write docs","completed":true}"#
+        );
+        let res = access(&service, "merge", MERGE_PATCH_JSON, r#"{"completed":null}"#).await;
+        assert_eq!(res.status_code, Some(StatusCode::UNPROCESSABLE_ENTITY));
+
+        let body = r#"[{"op":"replace","path":"/text","value":"review docs"}]"#;
+        let mut res = access(&service, "patch", JSON_PATCH_JSON, body).await;
+        assert_eq!(
+            res.take_string().await.unwrap(),
+            r#"{"text":"review docs","completed":false}"#
+        );
+        let body = r#"[{"op":"test","path":"/completed","value":true}]"#;
+        let res = access(&service, "patch", JSON_PATCH_JSON, body).await;
+        assert_eq!(res.status_code, Some(StatusCode::CONFLICT));
+        let res = access(&service, "patch", "application/json", body).await;
+        assert_eq!(res.status_code, Some(StatusCode::UNSUPPORTED_MEDIA_TYPE));
+    }
+}
@@ -17,6 +17,7 @@ use crate::http::header::{
     CONTENT_DISPOSITION, CONTENT_ENCODING, CONTENT_TYPE, IF_NONE_MATCH, RANGE,
 };
 use crate::http::{HttpRange, Mime, Request, Response, StatusCode, StatusError};
+use crate::writing::content_disposition;
 use crate::{Depot, Error, Result, Writer, async_trait};
 
 const CHUNK_SIZE: u64 = 1024 * 1024;
@@ -239,9 +240,7 @@ fn build_content_disposition(
                 .unwrap_or_else(|| "file".into())
                 .into(),
         };
-        format!(r#"attachment; filename="{attached_name}""#)
-            .parse::<HeaderValue>()
-            .map_err(Error::other)?
+        content_disposition(&attached_name)
     } else {
         disposition_type
             .parse::<HeaderValue>()
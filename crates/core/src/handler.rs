@@ -96,6 +96,58 @@
 //! }
 //! ```
 //!
+//! ### Application errors
+//!
+//! A single error type for all the handlers of an application lets them use `?` freely. Convert the errors of the
+//! libraries into it with `From`, and render each kind as a [`StatusError`](crate::http::StatusError), so the errors
+//! are rendered consistently by the [`Catcher`](crate::catcher::Catcher). [`ParseError`](crate::http::ParseError)
+//! and [`PatchError`](crate::extract::PatchError) convert into `StatusError` with the status of the request
+//! problem, like `413 Payload Too Large` for a too large body, and the errors of the database, like `sqlx::Error`,
+//! are usually mapped to `500 Internal Server Error`:
+//!
+//! ```
+//! use salvo_core::http::ParseError;
+//! use salvo_core::prelude::*;
+//! use serde::Deserialize;
+//!
+//! #[derive(Debug, thiserror::Error)]
+//! enum AppError {
+//!     #[error("todo not found")]
+//!     NotFound,
+//!     #[error(transparent)]
+//!     Parse(#[from] ParseError),
+//!     #[error(transparent)]
+//!     Io(#[from] std::io::Error),
+//! }
+//! impl Scribe for AppError {
+//!     fn render(self, res: &mut Response) {
+//!         let error = match self {
+//!             Self::NotFound => StatusError::not_found().brief(self.to_string()),
+//!             Self::Parse(e) => e.into(),
+//!             Self::Io(e) => StatusError::internal_server_error().cause(e),
+//!         };
+//!         res.render(error);
+//!     }
+//! }
+//!
+//! #[derive(Deserialize)]
+//! struct Todo {
+//!     id: u64,
+//! }
+//!
+//! #[handler]
+//! async fn update_todo(req: &mut Request) -> Result<StatusCode, AppError> {
+//!     let todo = req.parse_json::<Todo>().await?;
+//!     if todo.id != 1 {
+//!         return Err(AppError::NotFound);
+//!     }
+//!     Ok(StatusCode::NO_CONTENT)
+//! }
+//! ```
+//!
+//! For the simple cases, [`salvo_core::Error`](crate::Error) can be returned too, it renders `StatusError` as it is,
+//! `ParseError` by the status of the request problem and the others as `500 Internal Server Error`.
+//!
 //! ## Implement Handler trait directly
 //!
 //! Under certain circumstances, We need to implment `Handler` direclty.
@@ -117,10 +169,57 @@
 //!     }
 //! }
 //! ```
+//!
+//! ## Closure handler
+//!
+//! Tiny handlers can be created from closures with [`from_fn`], without the `#[handler]` macro.
+//! The closure receives the [`Request`], [`Depot`] and [`Response`], and returns a boxed future,
+//! the output of the future is written to the response like the return value of `#[handler]`
+//! functions:
+//!
+//! ```
+//! use salvo_core::handler;
+//! use salvo_core::prelude::*;
+//!
+//! let router = Router::with_path("hello").get(handler::from_fn(|req, _depot, _res| {
+//!     Box::pin(async move {
+//!         format!("Hello {}", req.query::<&str>("name").unwrap_or("world"))
+//!     })
+//! }));
+//! ```
+//!
+//! ## Dynamic dispatch
+//!
+//! `Handler` is object safe, so handlers can be stored as `Box<dyn Handler>` and chosen at runtime.
+//! The trait object is `Send + Sync + 'static`, because `Handler` requires these bounds.
+//! `Box<H>` implements `Handler` when `H` does, so it can be used wherever a handler is accepted,
+//! see also [`Router::push_boxed`](crate::Router::push_boxed).
+//!
+//! ```
+//! use salvo_core::prelude::*;
+//!
+//! #[handler]
+//! async fn hello() -> &'static str {
+//!     "hello world!"
+//! }
+//! #[handler]
+//! async fn add_header(res: &mut Response) {
+//!     res.add_header("x-powered-by", "salvo", true).ok();
+//! }
+//!
+//! let handlers: Vec<Box<dyn Handler>> = vec![Box::new(hello), Box::new(hello.hoop(add_header))];
+//! let router = handlers
+//!     .into_iter()
+//!     .enumerate()
+//!     .fold(Router::new(), |router, (i, handler)| router.push(Router::with_path(i.to_string()).goal(handler)));
+//! ```
+use std::marker::PhantomData;
 use std::sync::Arc;
 
+use futures_util::future::BoxFuture;
+
 use crate::http::StatusCode;
-use crate::{Depot, FlowCtrl, Request, Response, async_trait};
+use crate::{Depot, FlowCtrl, Request, Response, Writer, async_trait};
 
 /// `Handler` is used for handle [`Request`].
 ///
@@ -203,6 +302,78 @@ impl Handler for ArcHandler {
     }
 }
 
+#[async_trait]
+impl<H: Handler + ?Sized> Handler for Box<H> {
+    #[inline]
+    fn type_id(&self) -> std::any::TypeId {
+        Handler::type_id(&**self)
+    }
+    #[inline]
+    fn type_name(&self) -> &'static str {
+        Handler::type_name(&**self)
+    }
+    #[inline]
+    async fn handle(
+        &self,
+        req: &mut Request,
+        depot: &mut Depot,
+        res: &mut Response,
+        ctrl: &mut FlowCtrl,
+    ) {
+        (**self).handle(req, depot, res, ctrl).await
+    }
+}
+
+/// A handler created from a closure, see [`from_fn`].
+#[derive(Copy, Clone)]
+#[allow(missing_debug_implementations)]
+pub struct FnHandler<F, O> {
+    func: F,
+    _output: PhantomData<fn() -> O>,
+}
+
+#[async_trait]
+impl<F, O> Handler for FnHandler<F, O>
+where
+    F: for<'a> Fn(&'a mut Request, &'a mut Depot, &'a mut Response) -> BoxFuture<'a, O>
+        + Send
+        + Sync
+        + 'static,
+    O: Writer + Send + 'static,
+{
+    async fn handle(
+        &self,
+        req: &mut Request,
+        depot: &mut Depot,
+        res: &mut Response,
+        _ctrl: &mut FlowCtrl,
+    ) {
+        let output = (self.func)(req, depot, res).await;
+        output.write(req, depot, res).await;
+    }
+}
+
+/// Create a [`Handler`] from a closure.
+///
+/// The closure returns a [`BoxFuture`], which is usually created by `Box::pin(async move { ... })`,
+/// and the output of the future is written to the response with [`Writer`].
+///
+/// View [module level documentation](index.html) for more details.
+#[inline]
+pub fn from_fn<F, O>(func: F) -> FnHandler<F, O>
+where
+    F: for<'a> Fn(&'a mut Request, &'a mut Depot, &'a mut Response) -> BoxFuture<'a, O>
+        + Send
+        + Sync
+        + 'static,
+    O: Writer + Send + 'static,
+{
+    FnHandler {
+        func,
+        _output: PhantomData,
+    }
+}
+
 #[doc(hidden)]
 pub struct EmptyHandler;
 #[async_trait]
@@ -389,3 +560,48 @@ macro_rules! skipper_tuple_impls {
 
 crate::for_each_tuple!(handler_tuple_impls);
 crate::for_each_tuple!(skipper_tuple_impls);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::StatusError;
+    use crate::prelude::*;
+    use crate::test::{ResponseExt, TestClient};
+
+    #[tokio::test]
+    async fn test_from_fn() {
+        #[handler]
+        async fn set_user(depot: &mut Depot) {
+            depot.insert("user", "salvo");
+        }
+        let router = Router::new()
+            .hoop(set_user)
+            .push(Router::with_path("hello").get(from_fn(|req, depot, _res| {
+                Box::pin(async move {
+                    format!(
+                        "{} {}",
+                        req.query::<&str>("greeting").unwrap_or("Hello"),
+                        depot.get::<&str>("user").copied().unwrap_or_default()
+                    )
+                })
+            })))
+            .push(
+                Router::with_path("error").get(from_fn(|_req, _depot, _res| {
+                    Box::pin(async move { Err::<(), _>(StatusError::bad_request()) })
+                })),
+            );
+        let service = Service::new(router);
+
+        let content = TestClient::get("http://127.0.0.1:5800/hello?greeting=Hi")
+            .send(&service)
+            .await
+            .take_string()
+            .await
+            .unwrap();
+        assert_eq!(content, "Hi salvo");
+        let res = TestClient::get("http://127.0.0.1:5800/error")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::BAD_REQUEST));
+    }
+}
@@ -7,6 +7,7 @@ use std::task::{self, Context, Poll, ready};
 
 use futures_channel::{mpsc, oneshot};
 use futures_util::stream::{BoxStream, FusedStream, Stream, TryStreamExt};
+use http::HeaderMap;
 use hyper::body::{Body, Frame, Incoming, SizeHint};
 use sync_wrapper::SyncWrapper;
 
@@ -222,6 +223,69 @@ impl Body for ResBody {
     }
 }
 
+/// Body which sends the trailers after the inner body.
+struct TrailersBody {
+    body: ResBody,
+    trailers: Option<HeaderMap>,
+}
+impl Body for TrailersBody {
+    type Data = Bytes;
+    type Error = BoxedError;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.get_mut();
+        let Some(trailers) = this.trailers.as_mut() else {
+            return Poll::Ready(None);
+        };
+        match ready!(Pin::new(&mut this.body).poll_frame(cx)) {
+            Some(Ok(frame)) => match frame.into_trailers() {
+                // Merge the trailers sent by the inner body, only one trailers frame is allowed.
+                Ok(mut inner) => {
+                    inner.extend(std::mem::take(trailers));
+                    this.trailers = None;
+                    Poll::Ready(Some(Ok(Frame::trailers(inner))))
+                }
+                Err(frame) => Poll::Ready(Some(Ok(frame))),
+            },
+            Some(Err(e)) => Poll::Ready(Some(Err(e.into()))),
+            None => Poll::Ready(
+                this.trailers
+                    .take()
+                    .map(|trailers| Ok(Frame::trailers(trailers))),
+            ),
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.trailers.is_none()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        // Do not forward the exact size, so HTTP/1 uses chunked encoding which is required by trailers.
+        let mut hint = SizeHint::new();
+        hint.set_lower(Body::size_hint(&self.body).lower());
+        hint
+    }
+}
+
+impl ResBody {
+    /// Send the trailers after this body.
+    ///
+    /// If this body sends trailers itself, they are merged.
+    pub(crate) fn with_trailers(self, trailers: HeaderMap) -> Self {
+        if trailers.is_empty() {
+            return self;
+        }
+        Self::Boxed(Box::pin(TrailersBody {
+            body: self,
+            trailers: Some(trailers),
+        }))
+    }
+}
+
 impl Stream for ResBody {
     type Item = IoResult<Frame<Bytes>>;
 
@@ -2,5 +2,6 @@
 
 mod parse_error;
 mod status_error;
+pub(crate) use parse_error::SupportedMediaTypes;
 pub use parse_error::{ParseError, ParseResult};
 pub use status_error::{StatusError, StatusResult};
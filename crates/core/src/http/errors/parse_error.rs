@@ -2,11 +2,13 @@ use std::fmt::Debug;
 use std::io::Error as IoError;
 use std::str::Utf8Error;
 
+use http_body_util::LengthLimitError;
 use serde::de::value::Error as DeError;
 use thiserror::Error;
 
-use crate::http::{Request, Response, StatusError};
-use crate::{BoxedError, Depot, Writer, async_trait};
+use crate::extract::FieldErrors;
+use crate::http::{Mime, Response, StatusError};
+use crate::{BoxedError, Scribe};
 
 /// Result type with `ParseError` has it's error type.
 pub type ParseResult<T> = Result<T, ParseError>;
@@ -16,13 +18,40 @@ pub type ParseResult<T> = Result<T, ParseError>;
 #[non_exhaustive]
 pub enum ParseError {
     /// The Hyper request did not have a valid Content-Type header.
+    ///
+    /// When it is rendered by a [`Service`](crate::Service), the supported media types are listed in the
+    /// `Accept-Post` or `Accept-Patch` header of the response for `POST` and `PATCH` requests.
     #[error("the request did not have a valid Content-Type header")]
-    InvalidContentType,
+    InvalidContentType {
+        /// The media types which can be handled.
+        supported: Vec<Mime>,
+    },
 
     /// The Hyper request's body is empty.
     #[error("the request's body is empty")]
     EmptyBody,
 
+    /// The request's body is larger than the secure max size.
+    #[error("the request's body is too large")]
+    PayloadTooLarge,
+
+    /// The request's body is not received before the body read timeout.
+    #[error("the request's body is not received in time")]
+    BodyReadTimeout,
+
+    /// A file part of `multipart/form-data` is larger than the max file size.
+    #[error("the part `{name}` is too large, the limit is {limit} bytes")]
+    FilePartTooLarge {
+        /// The name of the part.
+        name: String,
+        /// The max file size.
+        limit: u64,
+    },
+
+    /// The count of parts of `multipart/form-data` is larger than the max count.
+    #[error("too many parts, the limit is {0}")]
+    TooManyParts(usize),
+
     /// The Hyper request's body is empty.
     #[error("data is not exist")]
     NotExist,
@@ -57,6 +86,10 @@ pub enum ParseError {
     #[error("deserialize error: {0}")]
     Deserialize(#[from] DeError),
 
+    /// Errors of all the fields which failed to be extracted from request.
+    #[error("extract fields error: {0}")]
+    Fields(#[from] FieldErrors),
+
     /// DuplicateKey.
     #[error("duplicate key")]
     DuplicateKey,
@@ -103,16 +136,58 @@ impl ParseError {
     pub fn other(error: impl Into<BoxedError>) -> Self {
         Self::Other(error.into())
     }
+
+    /// Create error from the error of reading a size limited body.
+    pub(crate) fn from_body_error(error: BoxedError) -> Self {
+        if error.is::<LengthLimitError>() {
+            Self::PayloadTooLarge
+        } else {
+            Self::Other(error)
+        }
+    }
 }
 
-#[async_trait]
-impl Writer for ParseError {
-    async fn write(self, _req: &mut Request, _depot: &mut Depot, res: &mut Response) {
-        res.render(
-            StatusError::bad_request()
-                .brief("parse http data failed.")
-                .cause(self),
-        );
+impl From<ParseError> for StatusError {
+    /// Map the error to the status of the request problem, like `413 Payload Too Large` for a too large body, and
+    /// `400 Bad Request` for the invalid data.
+    ///
+    /// The field errors of [`ParseError::Fields`] are written in the detail as text, while rendering the
+    /// [`ParseError`] itself writes them as a json list, view [`FieldErrors`] for more details.
+    fn from(e: ParseError) -> Self {
+        let error = match &e {
+            ParseError::Fields(errors) => StatusError::unprocessable_entity()
+                .brief("extract data from request failed.")
+                .detail(errors.to_string()),
+            ParseError::FilePartTooLarge { .. } | ParseError::TooManyParts(_) => {
+                StatusError::payload_too_large().brief(e.to_string())
+            }
+            ParseError::PayloadTooLarge => StatusError::payload_too_large(),
+            ParseError::BodyReadTimeout => StatusError::request_timeout(),
+            ParseError::InvalidContentType { .. } => {
+                StatusError::unsupported_media_type().brief(e.to_string())
+            }
+            _ => StatusError::bad_request().brief("parse http data failed."),
+        };
+        error.cause(e)
+    }
+}
+
+// The supported media types of the rendered `ParseError::InvalidContentType`, the service writes them in the
+// `Accept-Post` or `Accept-Patch` header by the request method.
+#[derive(Clone, Debug)]
+pub(crate) struct SupportedMediaTypes(pub(crate) Vec<Mime>);
+
+impl Scribe for ParseError {
+    fn render(self, res: &mut Response) {
+        match self {
+            Self::Fields(errors) => res.render(errors),
+            Self::InvalidContentType { ref supported } => {
+                let supported = SupportedMediaTypes(supported.clone());
+                res.render(StatusError::from(self));
+                res.extensions.insert(supported);
+            }
+            _ => res.render(StatusError::from(self)),
+        }
     }
 }
 
@@ -129,4 +204,55 @@ mod tests {
         let err = ParseError::EmptyBody;
         err.write(&mut req, &mut depot, &mut res).await;
     }
+
+    #[tokio::test]
+    async fn test_write_payload_too_large() {
+        let mut res = Response::default();
+        let mut req = Request::default();
+        let mut depot = Depot::new();
+        ParseError::PayloadTooLarge
+            .write(&mut req, &mut depot, &mut res)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::PAYLOAD_TOO_LARGE));
+    }
+
+    #[test]
+    fn test_fields_to_status_error() {
+        use crate::extract::FieldError;
+        use crate::extract::metadata::SourceFrom;
+
+        let errors = [
+            FieldError::new(
+                "id",
+                Some(SourceFrom::Param),
+                "invalid digit found in string",
+            ),
+            FieldError::new("title", None, "missing field"),
+        ];
+        let error = StatusError::from(ParseError::Fields(errors.into_iter().collect()));
+        assert_eq!(error.code, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            error.detail.as_deref(),
+            Some("id (from param): invalid digit found in string; title: missing field")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_write_invalid_content_type() {
+        let mut res = Response::default();
+        let mut req = Request::default();
+        let mut depot = Depot::new();
+        ParseError::InvalidContentType {
+            supported: vec![mime::APPLICATION_JSON],
+        }
+        .write(&mut req, &mut depot, &mut res)
+        .await;
+        assert_eq!(res.status_code, Some(StatusCode::UNSUPPORTED_MEDIA_TYPE));
+        assert_eq!(
+            res.extensions
+                .get::<SupportedMediaTypes>()
+                .map(|types| &types.0[..]),
+            Some(&[mime::APPLICATION_JSON][..])
+        );
+    }
 }
@@ -32,6 +32,28 @@ macro_rules! default_errors {
 }
 
 /// HTTP status error information.
+///
+/// There is a constructor for each error status code, like [`StatusError::not_found`] and
+/// [`StatusError::unprocessable_entity`]. Return it from a handler, and the [`Catcher`](crate::catcher::Catcher)
+/// writes it in the format accepted by the client, such as HTML, JSON, XML or plain text. The detail set by
+/// [`StatusError::detail`] is written too, so it should not contain anything secret.
+///
+/// # Example
+///
+/// ```
+/// use salvo_core::prelude::*;
+///
+/// #[handler]
+/// async fn delete_todo(req: &mut Request) -> Result<StatusCode, StatusError> {
+///     let id = req
+///         .param::<u64>("id")
+///         .ok_or_else(|| StatusError::bad_request().detail("invalid todo id"))?;
+///     if id != 1 {
+///         return Err(StatusError::not_found().detail("todo not found"));
+///     }
+///     Ok(StatusCode::NO_CONTENT)
+/// }
+/// ```
 #[derive(Debug)]
 #[non_exhaustive]
 pub struct StatusError {
@@ -41,7 +63,8 @@ pub struct StatusError {
     pub name: String,
     /// Brief information about http error.
     pub brief: String,
-    /// Detail information about http error.
+    /// Detail information about http error. It is only written in the error page if the catcher is created with
+    /// [`DefaultGoal::show_detail`](crate::catcher::DefaultGoal::show_detail).
     pub detail: Option<String>,
     /// Cause about http error. This field is only used for internal debugging and only used in debug mode.
     pub cause: Option<Box<dyn StdError + Sync + Send + 'static>>,
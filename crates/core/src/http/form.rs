@@ -6,7 +6,7 @@ use std::path::{Path, PathBuf};
 use base64::engine::Engine;
 use base64::engine::general_purpose::URL_SAFE_NO_PAD;
 use futures_util::StreamExt;
-use http_body_util::BodyExt;
+use http_body_util::{BodyExt, Limited};
 use mime::Mime;
 use multer::{Field, Multipart};
 use multimap::MultiMap;
@@ -16,9 +16,107 @@ use tempfile::Builder;
 use tokio::fs::File;
 use tokio::io::AsyncWriteExt;
 
-use crate::http::ParseError;
 use crate::http::body::ReqBody;
 use crate::http::header::{CONTENT_TYPE, HeaderMap};
+use crate::http::{ParseError, Request, Response};
+use crate::{Depot, FlowCtrl, Handler, async_trait};
+
+/// Limits of the parts of `multipart/form-data` requests.
+///
+/// The plain text fields are kept in memory and limited by the secure max size of [`Request`], while the files are
+/// written to temporary files. These limits protect the server from malicious uploads which fill the disk or send
+/// lots of tiny parts:
+///
+/// - `max_parts`: the maximum count of parts, default is 1000. The request is rejected with
+///   [`ParseError::TooManyParts`].
+/// - `max_file_size`: the maximum size of a single file part, default is unlimited. The request is rejected with
+///   [`ParseError::FilePartTooLarge`], which names the part.
+/// - `max_files_size`: the maximum total size of all file parts, default is unlimited. The request is rejected with
+///   [`ParseError::PayloadTooLarge`].
+///
+/// All of them are responded with `413 Payload Too Large`. Like the `size_limiter` middleware of `salvo-extra`, it can
+/// be used as a hoop to set the limits of the requests of a router, or use [`Request::set_multipart_limits`].
+///
+/// # Example
+///
+/// ```
+/// use salvo_core::http::ParseError;
+/// use salvo_core::http::form::MultipartLimits;
+/// use salvo_core::prelude::*;
+///
+/// #[handler]
+/// async fn upload(req: &mut Request) -> Result<String, ParseError> {
+///     let files = req.form_data().await?.files.len();
+///     Ok(format!("{files} files uploaded"))
+/// }
+///
+/// let router = Router::with_path("upload")
+///     .hoop(MultipartLimits::new().max_parts(16).max_file_size(10 * 1024 * 1024))
+///     .post(upload);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MultipartLimits {
+    /// The maximum count of parts.
+    pub max_parts: usize,
+    /// The maximum size of a single file part.
+    pub max_file_size: Option<u64>,
+    /// The maximum total size of all file parts.
+    pub max_files_size: Option<u64>,
+}
+
+impl MultipartLimits {
+    /// Create new `MultipartLimits` with the default limits.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            max_parts: 1000,
+            max_file_size: None,
+            max_files_size: None,
+        }
+    }
+
+    /// Set the maximum count of parts.
+    #[inline]
+    pub fn max_parts(mut self, max_parts: usize) -> Self {
+        self.max_parts = max_parts;
+        self
+    }
+
+    /// Set the maximum size of a single file part.
+    #[inline]
+    pub fn max_file_size(mut self, max_file_size: u64) -> Self {
+        self.max_file_size = Some(max_file_size);
+        self
+    }
+
+    /// Set the maximum total size of all file parts.
+    #[inline]
+    pub fn max_files_size(mut self, max_files_size: u64) -> Self {
+        self.max_files_size = Some(max_files_size);
+        self
+    }
+}
+
+impl Default for MultipartLimits {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Handler for MultipartLimits {
+    async fn handle(
+        &self,
+        req: &mut Request,
+        _depot: &mut Depot,
+        _res: &mut Response,
+        _ctrl: &mut FlowCtrl,
+    ) {
+        req.set_multipart_limits(*self);
+    }
+}
 
 /// The extracted text fields and uploaded files from a `multipart/form-data` request.
 #[derive(Debug)]
@@ -43,16 +141,26 @@ impl FormData {
     }
 
     /// Parse MIME `multipart/*` information from a stream as a `FormData`.
-    pub(crate) async fn read(headers: &HeaderMap, body: ReqBody) -> Result<FormData, ParseError> {
+    ///
+    /// The size of the data kept in memory, that is the whole body of `application/x-www-form-urlencoded`
+    /// or the plain text fields of `multipart/*`, is limited by `max_size`. Files are written to temporary
+    /// files, they are limited by `limits`.
+    pub(crate) async fn read(
+        headers: &HeaderMap,
+        body: ReqBody,
+        max_size: usize,
+        limits: MultipartLimits,
+    ) -> Result<FormData, ParseError> {
         let ctype: Option<Mime> = headers
             .get(CONTENT_TYPE)
             .and_then(|h| h.to_str().ok())
             .and_then(|v| v.parse().ok());
         match ctype {
             Some(ctype) if ctype.subtype() == mime::WWW_FORM_URLENCODED => {
-                let data = BodyExt::collect(body)
+                let data = Limited::new(body, max_size)
+                    .collect()
                     .await
-                    .map_err(ParseError::other)?
+                    .map_err(ParseError::from_body_error)?
                     .to_bytes();
                 let mut form_data = FormData::new();
                 form_data.fields = form_urlencoded::parse(&data).into_owned().collect();
@@ -67,24 +175,62 @@ impl FormData {
                 {
                     let body = body.map(|f| f.map(|f| f.into_data().unwrap_or_default()));
                     let mut multipart = Multipart::new(body, boundary);
+                    let mut remaining = max_size;
+                    let mut files_remaining = limits.max_files_size;
+                    let mut parts = 0;
                     while let Some(mut field) = multipart.next_field().await? {
+                        parts += 1;
+                        if parts > limits.max_parts {
+                            return Err(ParseError::TooManyParts(limits.max_parts));
+                        }
                         if let Some(name) = field.name().map(|s| s.to_owned()) {
                             if field.headers().get(CONTENT_TYPE).is_some() {
-                                form_data
-                                    .files
-                                    .insert(name, FilePart::create(&mut field).await?);
+                                let file = FilePart::create_limited(
+                                    &mut field,
+                                    limits.max_file_size,
+                                    files_remaining,
+                                )
+                                .await?;
+                                if let Some(files_remaining) = &mut files_remaining {
+                                    *files_remaining -= file.size;
+                                }
+                                form_data.files.insert(name, file);
                             } else {
-                                form_data.fields.insert(name, field.text().await?);
+                                let text = read_text(&mut field, &mut remaining).await?;
+                                form_data.fields.insert(name, text);
                             }
                         }
                     }
                 }
                 Ok(form_data)
             }
-            _ => Err(ParseError::InvalidContentType),
+            _ => Err(ParseError::InvalidContentType {
+                supported: form_media_types(),
+            }),
+        }
+    }
+}
+
+// The media types which can be read as `FormData`.
+pub(crate) fn form_media_types() -> Vec<Mime> {
+    vec![
+        mime::APPLICATION_WWW_FORM_URLENCODED,
+        mime::MULTIPART_FORM_DATA,
+    ]
+}
+
+async fn read_text(field: &mut Field<'_>, remaining: &mut usize) -> Result<String, ParseError> {
+    let mut data = Vec::new();
+    while let Some(chunk) = field.chunk().await? {
+        if chunk.len() > *remaining {
+            return Err(ParseError::PayloadTooLarge);
         }
+        *remaining -= chunk.len();
+        data.extend_from_slice(&chunk);
     }
+    Ok(String::from_utf8_lossy(&data).into_owned())
 }
+
 impl Default for FormData {
     #[inline]
     fn default() -> Self {
@@ -154,6 +300,16 @@ impl FilePart {
     /// Create a new temporary FilePart (when created this way, the file will be
     /// deleted once the FilePart object goes out of scope).
     pub async fn create(field: &mut Field<'_>) -> Result<FilePart, ParseError> {
+        Self::create_limited(field, None, None).await
+    }
+
+    /// Create a temporary `FilePart` whose size is limited by both `max_size` and `remaining`, the temporary file
+    /// is deleted if the limit is exceeded.
+    async fn create_limited(
+        field: &mut Field<'_>,
+        max_size: Option<u64>,
+        remaining: Option<u64>,
+    ) -> Result<FilePart, ParseError> {
         // Setup a file to capture the contents.
         let mut path =
             tokio::task::spawn_blocking(|| Builder::new().prefix("salvo_http_multipart").tempdir())
@@ -169,20 +325,30 @@ impl FilePart {
                 .and_then(|name| { Path::new(name).extension().and_then(OsStr::to_str) })
                 .unwrap_or("unknown")
         ));
-        let mut file = File::create(&path).await?;
-        let mut size = 0;
-        while let Some(chunk) = field.chunk().await? {
-            size += chunk.len() as u64;
-            file.write_all(&chunk).await?;
-        }
-        file.sync_all().await?;
-        Ok(FilePart {
+        // The temporary file is deleted when `part` is dropped, even if an error is returned.
+        let mut part = FilePart {
             name,
             headers: field.headers().to_owned(),
             path,
-            size,
+            size: 0,
             temp_dir,
-        })
+        };
+        let mut file = File::create(&part.path).await?;
+        while let Some(chunk) = field.chunk().await? {
+            part.size += chunk.len() as u64;
+            if max_size.is_some_and(|max_size| part.size > max_size) {
+                return Err(ParseError::FilePartTooLarge {
+                    name: field.name().unwrap_or_default().to_owned(),
+                    limit: max_size.unwrap_or_default(),
+                });
+            }
+            if remaining.is_some_and(|remaining| part.size > remaining) {
+                return Err(ParseError::PayloadTooLarge);
+            }
+            file.write_all(&chunk).await?;
+        }
+        file.sync_all().await?;
+        Ok(part)
     }
 }
 impl Drop for FilePart {
@@ -3,6 +3,7 @@
 pub mod errors;
 pub mod form;
 mod range;
+pub mod redact;
 pub mod request;
 pub mod response;
 cfg_feature! {
@@ -55,6 +56,11 @@ pub trait HttpConnection {
 //     }
 // }
 
+/// Check if the content type is JSON, like `application/json; charset=utf-8` or `application/vnd.todo+json`.
+pub(crate) fn is_json(ctype: &Mime) -> bool {
+    ctype.subtype() == mime::JSON || ctype.suffix() == Some(mime::JSON)
+}
+
 #[doc(hidden)]
 pub fn parse_accept_encoding(header: &str) -> Vec<(String, u8)> {
     let mut vec = header
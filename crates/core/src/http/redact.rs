@@ -0,0 +1,177 @@
+//! Redaction of sensitive data in logs.
+//!
+//! The framework masks the values of sensitive headers and query params with this config anywhere it logs
+//! requests, such as the `Logger` and `BodyLogger` middlewares of `salvo_extra`. The error pages rendered by
+//! [`Catcher`](crate::catcher::Catcher) never contain the headers or the query of the request.
+//!
+//! A header or a param is sensitive if its name contains one of the registered keywords, ignoring case. So the
+//! `x-todo-apikey` header and the `todo_apikey` query param are masked by the keyword `apikey`. The default
+//! keywords are:
+//!
+//! - Headers: `authorization`, `cookie`, `apikey`, `api_key`, `api-key`, `token`, `secret`.
+//! - Params: `password`, `apikey`, `api_key`, `api-key`, `token`, `secret`.
+//!
+//! Register more keywords at startup:
+//!
+//! ```
+//! use salvo_core::http::redact;
+//!
+//! redact::register_header("x-session");
+//! redact::register_param("ssn");
+//! assert!(redact::is_sensitive_header("X-Session-Id"));
+//! assert_eq!(redact::query("ssn=123&page=2"), "ssn=[REDACTED]&page=2");
+//! ```
+use std::sync::LazyLock;
+
+use parking_lot::RwLock;
+use percent_encoding::percent_decode_str;
+
+use crate::http::header::{HeaderMap, HeaderValue};
+use crate::http::uri::Uri;
+
+/// The value used to replace the sensitive values.
+pub const REDACTED: &str = "[REDACTED]";
+
+struct Keywords {
+    headers: Vec<String>,
+    params: Vec<String>,
+}
+
+impl Default for Keywords {
+    fn default() -> Self {
+        let common = ["apikey", "api_key", "api-key", "token", "secret"];
+        Self {
+            headers: ["authorization", "cookie"]
+                .into_iter()
+                .chain(common)
+                .map(String::from)
+                .collect(),
+            params: ["password"]
+                .into_iter()
+                .chain(common)
+                .map(String::from)
+                .collect(),
+        }
+    }
+}
+
+impl Keywords {
+    fn register_header(&mut self, keyword: String) {
+        self.headers.push(keyword.to_ascii_lowercase());
+    }
+    fn register_param(&mut self, keyword: String) {
+        self.params.push(keyword.to_ascii_lowercase());
+    }
+    fn is_sensitive_header(&self, name: &str) -> bool {
+        contains_keyword(&self.headers, name)
+    }
+    // The names of query params and url-encoded fields are decoded, so `pass%77ord` is sensitive as `password`.
+    fn is_sensitive_param(&self, name: &str) -> bool {
+        contains_keyword(&self.params, &percent_decode_str(name).decode_utf8_lossy())
+    }
+}
+
+static KEYWORDS: LazyLock<RwLock<Keywords>> = LazyLock::new(Default::default);
+
+fn contains_keyword(keywords: &[String], name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    keywords
+        .iter()
+        .any(|keyword| name.contains(keyword.as_str()))
+}
+
+/// Register a keyword of sensitive headers.
+pub fn register_header(keyword: impl Into<String>) {
+    KEYWORDS.write().register_header(keyword.into());
+}
+
+/// Register a keyword of sensitive query params and body fields.
+pub fn register_param(keyword: impl Into<String>) {
+    KEYWORDS.write().register_param(keyword.into());
+}
+
+/// Check whether the header is sensitive.
+pub fn is_sensitive_header(name: impl AsRef<str>) -> bool {
+    KEYWORDS.read().is_sensitive_header(name.as_ref())
+}
+
+/// Check whether the query param or body field is sensitive. The name is percent-decoded before it is checked.
+pub fn is_sensitive_param(name: impl AsRef<str>) -> bool {
+    KEYWORDS.read().is_sensitive_param(name.as_ref())
+}
+
+/// Returns a copy of the headers with the values of sensitive headers redacted.
+pub fn headers(headers: &HeaderMap) -> HeaderMap {
+    let mut headers = headers.clone();
+    for (name, value) in headers.iter_mut() {
+        if is_sensitive_header(name) {
+            *value = HeaderValue::from_static(REDACTED);
+        }
+    }
+    headers
+}
+
+/// Returns the query string with the values of sensitive params redacted.
+pub fn query(query: &str) -> String {
+    query
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((name, _)) if is_sensitive_param(name) => format!("{name}={REDACTED}"),
+            _ => pair.to_owned(),
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Returns the uri with the values of sensitive query params redacted.
+pub fn uri(uri: &Uri) -> String {
+    match uri.query() {
+        Some(query) => {
+            let uri = uri.to_string();
+            let (path, _) = uri
+                .split_once('?')
+                .expect("uri with query should contain `?`");
+            format!("{path}?{}", self::query(query))
+        }
+        None => uri.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact() {
+        let mut map = HeaderMap::new();
+        map.insert("authorization", HeaderValue::from_static("Bearer abc"));
+        map.insert("x-todo-apikey", HeaderValue::from_static("abc"));
+        map.insert("content-type", HeaderValue::from_static("text/plain"));
+        let map = headers(&map);
+        assert_eq!(map["authorization"], REDACTED);
+        assert_eq!(map["x-todo-apikey"], REDACTED);
+        assert_eq!(map["content-type"], "text/plain");
+
+        let uri: Uri = "http://localhost/todos?todo_apikey=abc&page=2&Password=1"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            self::uri(&uri),
+            "http://localhost/todos?todo_apikey=[REDACTED]&page=2&Password=[REDACTED]"
+        );
+        assert_eq!(self::uri(&"/todos".parse().unwrap()), "/todos");
+        assert_eq!(query("pass%77ord=1&page=2"), "pass%77ord=[REDACTED]&page=2");
+    }
+
+    #[test]
+    fn test_register_keywords() {
+        // A local value, the global keywords are shared by the other tests running in parallel.
+        let mut keywords = Keywords::default();
+        assert!(!keywords.is_sensitive_header("x-tenant"));
+        keywords.register_header("X-Tenant".to_owned());
+        assert!(keywords.is_sensitive_header("X-Tenant-Id"));
+        assert!(!keywords.is_sensitive_param("ssn"));
+        keywords.register_param("ssn".to_owned());
+        assert!(keywords.is_sensitive_param("user_%73sn"));
+    }
+}
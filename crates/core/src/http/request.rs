@@ -1,15 +1,20 @@
 //! HTTP request.
 use std::error::Error as StdError;
 use std::fmt::{self, Debug, Formatter};
-#[cfg(feature = "quinn")]
+use std::future::Future;
+#[cfg(any(feature = "quinn", feature = "client-cert"))]
 use std::sync::Arc;
 use std::sync::OnceLock;
+use std::time::{Duration, SystemTime};
 
 use bytes::Bytes;
 #[cfg(feature = "cookie")]
 use cookie::{Cookie, CookieJar};
 use http::Extensions;
-use http::header::{AsHeaderName, CONTENT_TYPE, HeaderMap, HeaderValue, IntoHeaderName};
+use http::header::{
+    AsHeaderName, CONTENT_LENGTH, CONTENT_TYPE, EXPECT, HOST, HeaderMap, HeaderValue,
+    IntoHeaderName,
+};
 use http::method::Method;
 use http::uri::{Scheme, Uri};
 
@@ -24,11 +29,13 @@ use crate::conn::SocketAddr;
 use crate::extract::{Extractible, Metadata};
 use crate::fuse::TransProto;
 use crate::http::body::ReqBody;
-use crate::http::form::{FilePart, FormData};
-use crate::http::{Mime, ParseError, ParseResult, Response, Version};
+use crate::http::form::{FilePart, FormData, MultipartLimits, form_media_types};
+use crate::http::headers::{ETag, HeaderMapExt, IfMatch, IfUnmodifiedSince};
+use crate::http::{Mime, ParseError, ParseResult, Response, StatusError, Version};
 use crate::routing::PathParams;
 use crate::serde::{
-    from_request, from_str_map, from_str_multi_map, from_str_multi_val, from_str_val,
+    from_nested_queries, from_request, from_str_map, from_str_multi_map, from_str_multi_val,
+    from_str_val,
 };
 use crate::{Depot, Error, FlowCtrl, Handler, async_trait};
 
@@ -36,9 +43,10 @@ static GLOBAL_SECURE_MAX_SIZE: RwLock<usize> = RwLock::new(64 * 1024);
 
 /// Get global secure maximum size, default value is 64KB.
 ///
-/// **Note**: The security maximum value is only effective when directly obtaining data
-/// from the body. For uploaded files, the files are written to temporary files
-/// and the bytes is not directly obtained, so they will not be affected.
+/// **Note**: The security maximum value is only effective for the data kept in memory, such as
+/// the payload, the parsed body and the plain text fields of form data. For uploaded files,
+/// the files are written to temporary files and the bytes is not directly obtained, so they
+/// will not be affected.
 pub fn global_secure_max_size() -> usize {
     *GLOBAL_SECURE_MAX_SIZE.read()
 }
@@ -48,9 +56,10 @@ pub fn global_secure_max_size() -> usize {
 /// It is recommended to use the [`SecureMaxSize`] middleware to have finer-grained
 /// control over [`Request`].
 ///
-/// **Note**: The security maximum value is only effective when directly obtaining data
-/// from the body. For uploaded files, the files are written to temporary files
-/// and the bytes is not directly obtained, so they will not be affected.
+/// **Note**: The security maximum value is only effective for the data kept in memory, such as
+/// the payload, the parsed body and the plain text fields of form data. For uploaded files,
+/// the files are written to temporary files and the bytes is not directly obtained, so they
+/// will not be affected.
 pub fn set_global_secure_max_size(size: usize) {
     let mut lock = GLOBAL_SECURE_MAX_SIZE.write();
     *lock = size;
@@ -58,9 +67,22 @@ pub fn set_global_secure_max_size(size: usize) {
 
 /// Middleware for set the secure maximum size of request body.
 ///
-/// **Note**: The security maximum value is only effective when directly obtaining data
-/// from the body. For uploaded files, the files are written to temporary files
-/// and the bytes is not directly obtained, so they will not be affected.
+/// The secure maximum size of a request is resolved in this order:
+///
+/// - The size set by this middleware, or by [`Request::set_secure_max_size`]. The one closest
+///   to the handler wins, so it can be added to [`Service`](crate::Service) as the server-level
+///   default and overridden per route.
+/// - The global size set by [`set_global_secure_max_size`], default value is 64KB.
+///
+/// The `size_limiter` middleware in `salvo-extra` works in a different stage, it rejects the
+/// request by its size hint before the handler is called. Requests passing it are still limited
+/// by the secure maximum size when the body is read into memory, so the effective limit is the
+/// smaller one.
+///
+/// **Note**: The security maximum value is only effective for the data kept in memory, such as
+/// the payload, the parsed body and the plain text fields of form data. For uploaded files,
+/// the files are written to temporary files and the bytes is not directly obtained, so they
+/// will not be affected.
 pub struct SecureMaxSize(pub usize);
 impl SecureMaxSize {
     /// Create a new `SecureMaxSize` instance.
@@ -81,6 +103,70 @@ impl Handler for SecureMaxSize {
     }
 }
 
+/// Middleware for set the timeout of reading the request body.
+///
+/// Reading the body by [`Request::payload`], [`Request::form_data`] and the parsing methods based on them, such as
+/// [`Request::parse_json`] and [`Request::parse_body`], fails with [`ParseError::BodyReadTimeout`] if the body is not
+/// received in time, which is rendered as `408 Request Timeout`. This protects the handlers from the slow clients
+/// which stall in the middle of the body. The timeout covers the whole body, including the uploaded files of
+/// `multipart/form-data`.
+///
+/// Like [`SecureMaxSize`], the one closest to the handler wins, so it can be added to
+/// [`Service`](crate::Service) as the server-level default and overridden per route. There is no timeout by
+/// default.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use salvo_core::http::ParseError;
+/// use salvo_core::http::request::BodyReadTimeout;
+/// use salvo_core::prelude::*;
+///
+/// #[handler]
+/// async fn upload(req: &mut Request) -> Result<&'static str, ParseError> {
+///     req.payload().await?;
+///     Ok("uploaded")
+/// }
+///
+/// let router = Router::with_path("upload")
+///     .hoop(BodyReadTimeout::new(Duration::from_secs(30)))
+///     .post(upload);
+/// ```
+pub struct BodyReadTimeout(pub Duration);
+impl BodyReadTimeout {
+    /// Create a new `BodyReadTimeout` instance.
+    pub fn new(timeout: Duration) -> Self {
+        BodyReadTimeout(timeout)
+    }
+}
+#[async_trait]
+impl Handler for BodyReadTimeout {
+    async fn handle(
+        &self,
+        req: &mut Request,
+        _depot: &mut Depot,
+        _res: &mut Response,
+        _ctrl: &mut FlowCtrl,
+    ) {
+        req.body_read_timeout = Some(self.0);
+    }
+}
+
+// Read the body with the timeout, if it is set.
+async fn read_body_with_timeout<T>(
+    timeout: Option<Duration>,
+    read: impl Future<Output = ParseResult<T>>,
+) -> ParseResult<T> {
+    match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, read)
+            .await
+            .map_err(|_| ParseError::BodyReadTimeout)?,
+        None => read.await,
+    }
+}
+
 /// Represents an HTTP request.
 ///
 /// Stores all the properties of the client's request.
@@ -112,8 +198,13 @@ pub struct Request {
     pub(crate) scheme: Scheme,
     pub(crate) local_addr: SocketAddr,
     pub(crate) remote_addr: SocketAddr,
+    pub(crate) base_path: String,
+    #[cfg(feature = "client-cert")]
+    pub(crate) client_certs: Option<Arc<[crate::conn::rustls::ClientCert]>>,
 
     pub(crate) secure_max_size: Option<usize>,
+    pub(crate) body_read_timeout: Option<Duration>,
+    pub(crate) multipart_limits: MultipartLimits,
     #[cfg(feature = "matched-path")]
     pub(crate) matched_path: String,
 }
@@ -161,7 +252,12 @@ impl Request {
             scheme: Scheme::HTTP,
             local_addr: SocketAddr::Unknown,
             remote_addr: SocketAddr::Unknown,
+            base_path: String::new(),
+            #[cfg(feature = "client-cert")]
+            client_certs: None,
             secure_max_size: None,
+            body_read_timeout: None,
+            multipart_limits: MultipartLimits::new(),
             #[cfg(feature = "matched-path")]
             matched_path: Default::default(),
         }
@@ -225,9 +321,14 @@ impl Request {
             // multipart: OnceLock::new(),
             local_addr: SocketAddr::Unknown,
             remote_addr: SocketAddr::Unknown,
+            base_path: String::new(),
+            #[cfg(feature = "client-cert")]
+            client_certs: None,
             version,
             scheme,
             secure_max_size: None,
+            body_read_timeout: None,
+            multipart_limits: MultipartLimits::new(),
             #[cfg(feature = "matched-path")]
             matched_path: Default::default(),
         }
@@ -361,6 +462,9 @@ impl Request {
     }
 
     /// Returns the associated scheme.
+    ///
+    /// It is the scheme of the connection, `https` if it is over TLS. Middlewares can change it, like
+    /// `TrustedProxy` of `salvo_extra`, which sets it from the `X-Forwarded-Proto` header of trusted proxies.
     #[inline]
     pub fn scheme(&self) -> &Scheme {
         &self.scheme
@@ -371,6 +475,85 @@ impl Request {
         &mut self.scheme
     }
 
+    /// Returns the path prefix added by a reverse proxy in front of the server, like `/api` if the proxy serves the
+    /// app under `https://example.com/api`. It is empty by default, and is set by `TrustedProxy` of `salvo_extra`
+    /// from the `X-Forwarded-Prefix` header. It is used by [`Request::full_url`] and [`Request::url_for`], the
+    /// routing is not affected.
+    #[inline]
+    pub fn base_path(&self) -> &str {
+        &self.base_path
+    }
+    /// Returns a mutable reference to the path prefix added by a reverse proxy, it should start with `/` and not
+    /// end with `/`, see [`Request::base_path`].
+    #[inline]
+    pub fn base_path_mut(&mut self) -> &mut String {
+        &mut self.base_path
+    }
+
+    /// Returns whether the scheme of the request is `https`, see [`Request::scheme`].
+    ///
+    /// It is useful to build absolute urls or to decide whether to set the `Secure` flag of cookies.
+    #[inline]
+    pub fn is_secure(&self) -> bool {
+        self.scheme == Scheme::HTTPS
+    }
+
+    /// Returns the host of the request, from the `Host` header or the authority of the uri.
+    fn host(&self) -> Option<&str> {
+        self.headers
+            .get(HOST)
+            .and_then(|host| host.to_str().ok())
+            .or_else(|| self.uri.authority().map(|authority| authority.as_str()))
+            .filter(|host| !host.is_empty())
+    }
+
+    /// Returns the absolute url of the request, built from [`Request::scheme`], the host and the path and query of
+    /// the uri.
+    ///
+    /// The host is read from the `Host` header, or from the authority of the uri if there is no `Host` header, like
+    /// in HTTP/2 requests. `None` is returned if both are missing, which only happens for HTTP/1.0 clients or
+    /// malformed requests, fall back to a relative url or a configured host in this case. Behind a reverse proxy,
+    /// use `TrustedProxy` of `salvo_extra` to apply the forwarded scheme, host and [base path](Request::base_path)
+    /// first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use salvo_core::test::TestClient;
+    /// let req = TestClient::get("https://example.com/todos?page=2").build();
+    /// assert_eq!(req.full_url().as_deref(), Some("https://example.com/todos?page=2"));
+    /// ```
+    pub fn full_url(&self) -> Option<String> {
+        let path = self
+            .uri
+            .path_and_query()
+            .map(|path| path.as_str())
+            .unwrap_or("/");
+        self.url_for(path)
+    }
+
+    /// Returns the absolute url of the path on the host of the request, see [`Request::full_url`] for how the scheme
+    /// and the host are resolved.
+    ///
+    /// The path is relative to the root of the app, it is prefixed by [`Request::base_path`], and a leading `/` is
+    /// added if it is missing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use salvo_core::test::TestClient;
+    /// let req = TestClient::get("https://example.com/todos").build();
+    /// assert_eq!(req.url_for("/todos/1").as_deref(), Some("https://example.com/todos/1"));
+    /// ```
+    pub fn url_for(&self, path: &str) -> Option<String> {
+        let host = self.host()?;
+        let slash = if path.starts_with('/') { "" } else { "/" };
+        Some(format!(
+            "{}://{host}{}{slash}{path}",
+            self.scheme, self.base_path
+        ))
+    }
+
     /// Get request remote address.
     #[inline]
     pub fn remote_addr(&self) -> &SocketAddr {
@@ -382,6 +565,22 @@ impl Request {
         &mut self.remote_addr
     }
 
+    /// Get the verified client certificate of the TLS connection, the first one of [`Request::client_certs`].
+    ///
+    /// View [`ClientCert`](crate::conn::rustls::ClientCert) for more details.
+    #[cfg(feature = "client-cert")]
+    #[inline]
+    pub fn client_cert(&self) -> Option<&crate::conn::rustls::ClientCert> {
+        self.client_certs().first()
+    }
+    /// Get the verified client certificate chain of the TLS connection, the first one is the client's own
+    /// certificate, it is empty if the client does not send a certificate.
+    #[cfg(feature = "client-cert")]
+    #[inline]
+    pub fn client_certs(&self) -> &[crate::conn::rustls::ClientCert] {
+        self.client_certs.as_deref().unwrap_or_default()
+    }
+
     /// Get request local address reference.
     #[inline]
     pub fn local_addr(&self) -> &SocketAddr {
@@ -397,6 +596,13 @@ impl Request {
         #![feature = "matched-path"]
 
         /// Get matched path.
+        ///
+        /// It is the path template of the matched routers rather than the concrete request path, for example,
+        /// request `/api/todos/5` matched by `Router::with_path("api/todos/{id}")` gets `api/todos/{id}`. So it is
+        /// suitable to be used as the label of metrics and logs without high cardinality.
+        ///
+        /// It is set after routing, so it is also available in the hoops of [`Service`](crate::Service). If no
+        /// router is matched, it is empty.
         #[inline]
         pub fn matched_path(&self) -> &str {
             &self.matched_path
@@ -536,6 +742,30 @@ impl Request {
         self.secure_max_size.unwrap_or_else(global_secure_max_size)
     }
 
+    /// Set the timeout of reading the body, see [`BodyReadTimeout`].
+    #[inline]
+    pub fn set_body_read_timeout(&mut self, timeout: Duration) {
+        self.body_read_timeout = Some(timeout);
+    }
+
+    /// Get the timeout of reading the body, there is no timeout by default.
+    #[inline]
+    pub fn body_read_timeout(&self) -> Option<Duration> {
+        self.body_read_timeout
+    }
+
+    /// Set the limits of the parts of `multipart/form-data`, see [`MultipartLimits`] for the default limits.
+    #[inline]
+    pub fn set_multipart_limits(&mut self, limits: MultipartLimits) {
+        self.multipart_limits = limits;
+    }
+
+    /// Get the limits of the parts of `multipart/form-data`.
+    #[inline]
+    pub fn multipart_limits(&self) -> &MultipartLimits {
+        &self.multipart_limits
+    }
+
     cfg_feature! {
         #![feature = "quinn"]
 
@@ -620,7 +850,7 @@ impl Request {
         if let Some(accept) = self.headers.get("accept").and_then(|h| h.to_str().ok()) {
             let parts: Vec<&str> = accept.split(',').collect();
             for part in parts {
-                if let Ok(mt) = part.parse() {
+                if let Ok(mt) = part.trim().parse() {
                     list.push(mt);
                 }
             }
@@ -628,6 +858,116 @@ impl Request {
         list
     }
 
+    /// Check the `If-Match` and `If-Unmodified-Since` headers against the current state of the resource, returns
+    /// `412 Precondition Failed` if the client's version is outdated. It is used for optimistic concurrency, to
+    /// prevent lost updates when several clients modify the same resource.
+    ///
+    /// `If-Match` is checked against `etag` with strong comparison, `If-Match: *` passes if the resource exists,
+    /// that is `etag` is `Some`. `If-Unmodified-Since` is only checked if `If-Match` is not present, and it passes
+    /// if `last_modified` is `None`. The request passes if it has neither of the headers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use salvo_core::http::headers::ETag;
+    /// use salvo_core::prelude::*;
+    ///
+    /// #[handler]
+    /// async fn update_todo(req: &mut Request, res: &mut Response) -> Result<(), StatusError> {
+    ///     let version = 1;
+    ///     let etag: ETag = format!("\"{version}\"").parse().unwrap();
+    ///     req.check_preconditions(Some(&etag), None)?;
+    ///     // Update the todo and respond the new ETag.
+    ///     res.add_header("etag", format!("\"{}\"", version + 1), true).unwrap();
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn check_preconditions(
+        &self,
+        etag: Option<&ETag>,
+        last_modified: Option<SystemTime>,
+    ) -> Result<(), StatusError> {
+        let passed = if let Some(if_match) = self.headers.typed_get::<IfMatch>() {
+            match etag {
+                Some(etag) => if_match == IfMatch::any() || if_match.precondition_passes(etag),
+                None => false,
+            }
+        } else if let (Some(since), Some(last_modified)) =
+            (self.headers.typed_get::<IfUnmodifiedSince>(), last_modified)
+        {
+            since.precondition_passes(last_modified)
+        } else {
+            true
+        };
+        if passed {
+            Ok(())
+        } else {
+            Err(StatusError::precondition_failed())
+        }
+    }
+
+    /// Check whether the media type is acceptable by the `accept` header of the request.
+    ///
+    /// The most specific media range in the header which matches the media type decides, so `*/*` and `text/*`
+    /// wildcards are supported, and `text/*, text/plain;q=0` does not accept `text/plain`. A media type with `q=0`
+    /// is not acceptable. Requests without `accept` header accept any media type. Returns `false` if `mime` is not
+    /// a valid media type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use salvo_core::prelude::*;
+    ///
+    /// #[handler]
+    /// async fn get_todo(req: &mut Request, res: &mut Response) {
+    ///     if req.accepts("text/html") {
+    ///         res.render(Text::Html("<p>write docs</p>"));
+    ///     } else {
+    ///         res.render(Json(serde_json::json!({"text": "write docs"})));
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    pub fn accepts(&self, mime: &str) -> bool {
+        mime.parse().is_ok_and(|mime| self.accepts_mime(&mime))
+    }
+
+    /// Check whether `application/json` is acceptable by the `accept` header of the request.
+    ///
+    /// View [`Request::accepts`] for more details.
+    #[inline]
+    pub fn accepts_json(&self) -> bool {
+        self.accepts_mime(&mime::APPLICATION_JSON)
+    }
+
+    pub(crate) fn accepts_mime(&self, mime: &Mime) -> bool {
+        let accept = self.accept();
+        if accept.is_empty() {
+            return true;
+        }
+        accept
+            .iter()
+            .filter_map(|range| {
+                let specificity = if range.type_() == mime::STAR {
+                    0
+                } else if range.type_() != mime.type_() {
+                    return None;
+                } else if range.subtype() == mime::STAR {
+                    1
+                } else if range.subtype() == mime.subtype() {
+                    2
+                } else {
+                    return None;
+                };
+                let quality = range
+                    .get_param("q")
+                    .map_or(1.0, |q| q.as_str().parse::<f32>().unwrap_or(1.0));
+                Some((specificity, quality))
+            })
+            .max_by_key(|(specificity, _)| *specificity)
+            .is_some_and(|(_, quality)| quality > 0.0)
+    }
+
     /// Get first accept.
     #[inline]
     pub fn first_accept(&self) -> Option<Mime> {
@@ -648,6 +988,38 @@ impl Request {
             .and_then(|v| v.parse().ok())
     }
 
+    /// Get the body size declared by the `Content-Length` header.
+    ///
+    /// It is `None` if the header is missing or invalid, like the chunked bodies.
+    #[inline]
+    pub fn content_length(&self) -> Option<u64> {
+        self.headers
+            .get(CONTENT_LENGTH)
+            .and_then(|h| h.to_str().ok())
+            .and_then(|v| v.parse().ok())
+    }
+
+    /// Check if the client sent `Expect: 100-continue` and waits for `100 Continue` before sending the body.
+    ///
+    /// `100 Continue` is sent automatically when the body is read for the first time, so a handler rejecting the
+    /// request before reading the body, like by the authorization or by the declared size larger than the limits,
+    /// saves the client from sending the body. The expectation is ignored for HTTP/1.0 requests.
+    #[inline]
+    pub fn expects_continue(&self) -> bool {
+        self.version >= Version::HTTP_11
+            && self
+                .headers
+                .get(EXPECT)
+                .is_some_and(|v| v.as_bytes().eq_ignore_ascii_case(b"100-continue"))
+    }
+
+    // The body is not read if the declared size is larger than `max_size`, the bodies without the declared size
+    // are limited while reading.
+    fn declared_too_large(&self, max_size: usize) -> bool {
+        self.content_length()
+            .is_some_and(|len| len > max_size as u64)
+    }
+
     cfg_feature! {
         #![feature = "cookie"]
         /// Get `CookieJar` reference.
@@ -739,6 +1111,25 @@ impl Request {
     }
 
     /// Get field data from form.
+    ///
+    /// The body is parsed as `application/x-www-form-urlencoded` or `multipart/form-data` according to the
+    /// `content-type` the first time a form method is called, and the parsed [`FormData`] is cached, so getting
+    /// several fields only parses the body once. Returns `None` if the field is missing, the body is not a form,
+    /// or the value can not be deserialized to `T`, use [`try_form`](Self::try_form) to get the reason.
+    ///
+    /// ```
+    /// # use salvo_core::prelude::*;
+    /// #[handler]
+    /// async fn sign_up(req: &mut Request) -> String {
+    ///     let name = req.form::<String>("name").await.unwrap_or_default();
+    ///     let age = req.form::<u8>("age").await;
+    ///     format!("{name} {age:?}")
+    /// }
+    /// ```
+    ///
+    /// For multipart forms, all the text fields are kept in memory, their total size is limited by
+    /// [`secure_max_size`](Self::secure_max_size), while the files are written to temporary files on disk and can
+    /// be got with [`file`](Self::file). So the whole body is read even if only one field is needed.
     #[inline]
     pub async fn form<'de, T>(&'de mut self, key: &str) -> Option<T>
     where
@@ -748,6 +1139,8 @@ impl Request {
     }
 
     /// Try to get field data from form.
+    ///
+    /// Returns [`ParseError::NotExist`] if the field is missing, see [`form`](Self::form) for more details.
     #[inline]
     pub async fn try_form<'de, T>(&'de mut self, key: &str) -> ParseResult<T>
     where
@@ -856,6 +1249,9 @@ impl Request {
 
     /// Get request payload with default max size limit(64KB).
     ///
+    /// The raw bytes of the body are limited by [`secure_max_size`](Self::secure_max_size), use
+    /// [`payload_with_max_size`](Self::payload_with_max_size) to set an explicit limit.
+    ///
     /// <https://github.com/hyperium/hyper/issues/3111>
     /// *Notice: This method takes body.
     #[inline]
@@ -865,33 +1261,110 @@ impl Request {
 
     /// Get request payload with max size limit.
     ///
+    /// Returns [`ParseError::PayloadTooLarge`] if the body is larger than `max_size`, which is rendered as
+    /// `413 Payload Too Large`. If the `Content-Length` header declares a larger size, the error is returned
+    /// without reading the body, otherwise the body is counted while reading. The `size_limiter` middleware in
+    /// `salvo-extra` may reject the request by its size hint before, so the effective limit is the smaller one.
+    /// For the clients sending `Expect: 100-continue`, the body is not sent when it is rejected by the declared
+    /// size, see [`expects_continue`](Self::expects_continue).
+    ///
+    /// The payload is cached the first time it is read, the later calls return the cached bytes and ignore
+    /// `max_size`. The body parsing methods, such as [`parse_json`](Self::parse_json) and
+    /// [`form_data`](Self::form_data), reuse the cached payload, so the raw bytes can be checked before parsing,
+    /// for example verifying a signature over the body:
+    ///
+    /// ```
+    /// # use salvo_core::prelude::*;
+    /// # use serde::Deserialize;
+    /// # fn verify(signature: Option<&str>, payload: &[u8]) -> bool { true }
+    /// #[derive(Deserialize)]
+    /// struct Event {
+    ///     kind: String,
+    /// }
+    ///
+    /// #[handler]
+    /// async fn webhook(req: &mut Request) -> Result<String, StatusError> {
+    ///     let signature = req.header::<String>("x-signature");
+    ///     let payload = req
+    ///         .payload_with_max_size(1024 * 1024)
+    ///         .await
+    ///         .map_err(|_| StatusError::payload_too_large())?;
+    ///     if !verify(signature.as_deref(), payload) {
+    ///         return Err(StatusError::unauthorized());
+    ///     }
+    ///     let event = req
+    ///         .parse_json::<Event>()
+    ///         .await
+    ///         .map_err(|_| StatusError::bad_request())?;
+    ///     Ok(event.kind)
+    /// }
+    /// ```
+    ///
     /// <https://github.com/hyperium/hyper/issues/3111>
     /// *Notice: This method takes body.
     #[inline]
     pub async fn payload_with_max_size(&mut self, max_size: usize) -> ParseResult<&Bytes> {
+        if self.payload.get().is_none() && self.declared_too_large(max_size) {
+            return Err(ParseError::PayloadTooLarge);
+        }
         let body = self.take_body();
+        let timeout = self.body_read_timeout;
         self.payload
-            .get_or_try_init(|| async {
-                Ok(Limited::new(body, max_size)
-                    .collect()
-                    .await
-                    .map_err(ParseError::other)?
-                    .to_bytes())
+            .get_or_try_init(|| {
+                read_body_with_timeout(timeout, async {
+                    Ok(Limited::new(body, max_size)
+                        .collect()
+                        .await
+                        .map_err(ParseError::from_body_error)?
+                        .to_bytes())
+                })
             })
             .await
     }
 
     /// Get `FormData` reference from request.
     ///
-    /// *Notice: This method takes body and body's size is not limited.
+    /// The size of the data kept in memory is limited by [`secure_max_size`](Self::secure_max_size),
+    /// the uploaded files are limited by [`multipart_limits`](Self::multipart_limits).
+    ///
+    /// *Notice: This method takes body.
     #[inline]
     pub async fn form_data(&mut self) -> ParseResult<&FormData> {
+        self.form_data_with_max_size(self.secure_max_size()).await
+    }
+
+    /// Get `FormData` reference from request with max size limit of the data kept in memory.
+    ///
+    /// The url encoded forms are rejected without reading the body if the `Content-Length` header declares a size
+    /// larger than `max_size`, the multipart forms are not, since the uploaded files are not counted in `max_size`.
+    ///
+    /// *Notice: This method takes body.
+    #[inline]
+    pub async fn form_data_with_max_size(&mut self, max_size: usize) -> ParseResult<&FormData> {
         if let Some(ctype) = self.content_type() {
             if ctype.subtype() == mime::WWW_FORM_URLENCODED || ctype.type_() == mime::MULTIPART {
-                let body = self.take_body();
+                if ctype.subtype() == mime::WWW_FORM_URLENCODED
+                    && self.payload.get().is_none()
+                    && self.form_data.get().is_none()
+                    && self.declared_too_large(max_size)
+                {
+                    return Err(ParseError::PayloadTooLarge);
+                }
+                // The body is already taken if the payload is read.
+                let body = match self.payload.get() {
+                    Some(payload) => ReqBody::Once(payload.clone()),
+                    None => self.take_body(),
+                };
                 let headers = self.headers();
+                let limits = self.multipart_limits;
+                let timeout = self.body_read_timeout;
                 self.form_data
-                    .get_or_try_init(|| async { FormData::read(headers, body).await })
+                    .get_or_try_init(|| {
+                        read_body_with_timeout(
+                            timeout,
+                            FormData::read(headers, body, max_size, limits),
+                        )
+                    })
                     .await
             } else {
                 Err(ParseError::NotFormData)
@@ -942,6 +1415,22 @@ impl Request {
         from_str_multi_map(queries).map_err(ParseError::Deserialize)
     }
 
+    /// Parse queries with nested keys as type `T` from request.
+    ///
+    /// The keys with bracket or dot notation are parsed into nested structs, maps and sequences, for example
+    /// `filter[status]=done&filter.tags[]=home&ids[0]=1` is parsed as `{"filter": {"status": "done", "tags":
+    /// ["home"]}, "ids": [1]}`. Use `#[salvo(extract(source(from = "query", parse = "nested")))]` to parse a
+    /// field of an extractor this way.
+    ///
+    /// It changes the meaning of the keys containing `[` or `.`, so [`Request::parse_queries`] does not do it.
+    #[inline]
+    pub fn parse_nested_queries<'de, T>(&'de mut self) -> ParseResult<T>
+    where
+        T: Deserialize<'de>,
+    {
+        from_nested_queries(self.queries()).map_err(ParseError::Deserialize)
+    }
+
     /// Parse headers as type `T` from request.
     #[inline]
     pub fn parse_headers<'de, T>(&'de mut self) -> ParseResult<T>
@@ -972,6 +1461,14 @@ impl Request {
     }
 
     /// Parse json body as type `T` from request with default max size limit.
+    ///
+    /// The content type should be `application/json` or a media type with `+json` suffix, like
+    /// `application/vnd.todo+json`, the parameters like `charset` are ignored. Otherwise
+    /// [`ParseError::InvalidContentType`] is returned, which is written as `415 Unsupported Media Type` with
+    /// `application/json` listed in the `Accept-Post` or `Accept-Patch` header.
+    ///
+    /// The body is parsed by `serde_json` directly, so the fields of type `Box<serde_json::value::RawValue>` keep
+    /// the raw json text, which can be forwarded without changing the number precision or the key order.
     #[inline]
     pub async fn parse_json<'de, T>(&'de mut self) -> ParseResult<T>
     where
@@ -987,7 +1484,7 @@ impl Request {
     {
         let ctype = self.content_type();
         if let Some(ctype) = ctype {
-            if ctype.subtype() == mime::JSON {
+            if super::is_json(&ctype) {
                 return self
                     .payload_with_max_size(max_size)
                     .await
@@ -1002,7 +1499,9 @@ impl Request {
                     });
             }
         }
-        Err(ParseError::InvalidContentType)
+        Err(ParseError::InvalidContentType {
+            supported: vec![mime::APPLICATION_JSON],
+        })
     }
 
     /// Parse form body as type `T` from request.
@@ -1017,7 +1516,9 @@ impl Request {
                     .map_err(ParseError::Deserialize);
             }
         }
-        Err(ParseError::InvalidContentType)
+        Err(ParseError::InvalidContentType {
+            supported: form_media_types(),
+        })
     }
 
     /// Parse json body or form body as type `T` from request with default max size.
@@ -1038,13 +1539,15 @@ impl Request {
             if ctype.subtype() == mime::WWW_FORM_URLENCODED || ctype.subtype() == mime::FORM_DATA {
                 return from_str_multi_map(self.form_data().await?.fields.iter_all())
                     .map_err(ParseError::Deserialize);
-            } else if ctype.subtype() == mime::JSON {
+            } else if super::is_json(&ctype) {
                 return self.payload_with_max_size(max_size).await.and_then(|body| {
                     serde_json::from_slice::<T>(body).map_err(ParseError::SerdeJson)
                 });
             }
         }
-        Err(ParseError::InvalidContentType)
+        let mut supported = vec![mime::APPLICATION_JSON];
+        supported.extend(form_media_types());
+        Err(ParseError::InvalidContentType { supported })
     }
 }
 
@@ -1053,7 +1556,8 @@ mod tests {
     use serde::{Deserialize, Serialize};
 
     use super::*;
-    use crate::test::TestClient;
+    use crate::prelude::*;
+    use crate::test::{ResponseExt, TestClient};
 
     #[tokio::test]
     async fn test_parse_queries() {
@@ -1106,6 +1610,88 @@ mod tests {
         );
     }
     #[tokio::test]
+    async fn test_parse_json_raw_value() {
+        #[derive(Deserialize, Debug)]
+        struct Relay {
+            target: String,
+            payload: Box<serde_json::value::RawValue>,
+        }
+        let payload = r#"{ "z": 1, "a": [1.0, 12345678901234567890123] }"#;
+        let build = || {
+            TestClient::post("http://127.0.0.1:5800/relay")
+                .raw_json(format!(r#"{{"target":"todos","payload":{payload}}}"#))
+                .build()
+        };
+        let relay = build().parse_json::<Relay>().await.unwrap();
+        assert_eq!(relay.target, "todos");
+        assert_eq!(relay.payload.get(), payload);
+        let relay = build().parse_body::<Relay>().await.unwrap();
+        assert_eq!(relay.payload.get(), payload);
+    }
+    #[tokio::test]
+    async fn test_parse_json_media_types() {
+        #[derive(Deserialize, Eq, PartialEq, Debug)]
+        struct User {
+            name: String,
+        }
+        for ctype in [
+            "application/json; charset=utf-8",
+            "application/vnd.todo+json",
+            "application/problem+json; charset=UTF-8",
+        ] {
+            let build = || {
+                TestClient::post("http://127.0.0.1:5800/users")
+                    .raw_json(r#"{"name":"jobs"}"#)
+                    .add_header("content-type", ctype, true)
+                    .build()
+            };
+            let user = User {
+                name: "jobs".into(),
+            };
+            assert_eq!(build().parse_json::<User>().await.unwrap(), user);
+            assert_eq!(build().parse_body::<User>().await.unwrap(), user);
+        }
+        let mut req = TestClient::post("http://127.0.0.1:5800/users")
+            .raw_json(r#"{"name":"jobs"}"#)
+            .add_header("content-type", "application/jsonp", true)
+            .build();
+        assert!(matches!(
+            req.parse_json::<User>().await,
+            Err(ParseError::InvalidContentType { .. })
+        ));
+    }
+    #[test]
+    fn test_is_secure() {
+        let req = TestClient::get("https://127.0.0.1:5800/").build();
+        assert_eq!(req.scheme(), &Scheme::HTTPS);
+        assert!(req.is_secure());
+        let req = TestClient::get("http://127.0.0.1:5800/").build();
+        assert!(!req.is_secure());
+    }
+    #[test]
+    fn test_full_url() {
+        let req = TestClient::get("http://127.0.0.1:5800/todos?page=2").build();
+        assert_eq!(
+            req.full_url().as_deref(),
+            Some("http://127.0.0.1:5800/todos?page=2")
+        );
+        assert_eq!(
+            req.url_for("todos/1").as_deref(),
+            Some("http://127.0.0.1:5800/todos/1")
+        );
+
+        let mut req = TestClient::get("http://127.0.0.1:5800/todos")
+            .add_header("host", "example.com", true)
+            .build();
+        *req.scheme_mut() = Scheme::HTTPS;
+        assert_eq!(req.full_url().as_deref(), Some("https://example.com/todos"));
+
+        let mut req = Request::new();
+        *req.uri_mut() = "/todos".parse().unwrap();
+        assert_eq!(req.full_url(), None);
+        assert_eq!(req.url_for("/todos/1"), None);
+    }
+    #[tokio::test]
     async fn test_query() {
         let req = TestClient::get(
             "http://127.0.0.1:5801/hello?name=rust&name=25&name=a&name=2&weapons=98&weapons=gun",
@@ -1130,6 +1716,19 @@ mod tests {
         assert_eq!(req.query_or_form::<String>("q").await.unwrap(), "rust");
         assert_eq!(req.form_or_query::<String>("q").await.unwrap(), "firefox");
 
+        assert_eq!(req.form::<String>("missing").await, None);
+        assert!(matches!(
+            req.try_form::<String>("missing").await,
+            Err(ParseError::NotExist)
+        ));
+        assert_eq!(req.form::<i64>("lover").await, None);
+        assert_eq!(req.form::<&str>("lover").await, Some("dog"));
+
+        let mut req = TestClient::post("http://127.0.0.1:5800/hello")
+            .json(&serde_json::json!({"lover": "dog"}))
+            .build();
+        assert_eq!(req.form::<String>("lover").await, None);
+
         let mut req: Request = TestClient::post("http://127.0.0.1:5800/hello?q=rust")
             .add_header(
                 "content-type",
@@ -1153,4 +1752,335 @@ file content\r\n\
         let files = req.files("file1").await.unwrap();
         assert_eq!(files[0].name().unwrap(), "err.txt");
     }
+
+    #[tokio::test]
+    async fn test_body_read_timeout() {
+        use futures_util::stream::{self, StreamExt};
+        use http_body_util::StreamBody;
+        use hyper::body::Frame;
+
+        use crate::BoxedError;
+
+        #[derive(Deserialize, Debug)]
+        struct User {
+            name: String,
+        }
+
+        // The client sends a part of the body and stalls.
+        fn stalled_body() -> ReqBody {
+            let chunks = stream::iter([Ok::<_, BoxedError>(Frame::data(Bytes::from_static(
+                b"{\"name\":",
+            )))])
+            .chain(stream::pending());
+            ReqBody::Boxed {
+                inner: Box::pin(StreamBody::new(chunks)),
+                fusewire: None,
+            }
+        }
+
+        let mut req = TestClient::post("http://127.0.0.1:5800/hello")
+            .add_header("content-type", "application/json", true)
+            .build();
+        req.replace_body(stalled_body());
+        req.set_body_read_timeout(Duration::from_millis(50));
+        let err = req.parse_json::<User>().await.unwrap_err();
+        assert!(matches!(err, ParseError::BodyReadTimeout));
+        let mut res = Response::new();
+        err.write(&mut req, &mut Depot::new(), &mut res).await;
+        assert_eq!(res.status_code, Some(StatusCode::REQUEST_TIMEOUT));
+
+        let mut req = TestClient::post("http://127.0.0.1:5800/hello")
+            .add_header("content-type", "application/x-www-form-urlencoded", true)
+            .build();
+        req.replace_body(stalled_body());
+        req.set_body_read_timeout(Duration::from_millis(50));
+        assert!(matches!(
+            req.form_data().await,
+            Err(ParseError::BodyReadTimeout)
+        ));
+
+        #[handler]
+        async fn hello(req: &mut Request) -> Result<String, ParseError> {
+            Ok(req.parse_json::<User>().await?.name)
+        }
+        let router = Router::new()
+            .hoop(BodyReadTimeout::new(Duration::from_secs(10)))
+            .push(Router::with_path("hello").post(hello));
+        let mut res = TestClient::post("http://127.0.0.1:5800/hello")
+            .raw_json(r#"{"name":"jobs","age":25}"#)
+            .send(router)
+            .await;
+        assert_eq!(res.take_string().await.unwrap(), "jobs");
+    }
+
+    #[tokio::test]
+    async fn test_secure_max_size() {
+        let mut req = TestClient::post("http://127.0.0.1:5800/hello")
+            .raw_json(r#"{"name":"jobs","age":25}"#)
+            .build();
+        req.set_secure_max_size(8);
+        assert!(matches!(
+            req.payload().await,
+            Err(ParseError::PayloadTooLarge)
+        ));
+
+        let mut req = TestClient::post("http://127.0.0.1:5800/hello")
+            .raw_form("lover=dog&money=sh*t&q=firefox")
+            .build();
+        req.set_secure_max_size(8);
+        assert!(matches!(
+            req.form_data().await,
+            Err(ParseError::PayloadTooLarge)
+        ));
+
+        let body = "------WebKitFormBoundary0mkL0yrNNupCojyz\r\n\
+Content-Disposition: form-data; name=\"money\"\r\n\r\nsh*t\r\n\
+------WebKitFormBoundary0mkL0yrNNupCojyz\r\n\
+Content-Disposition: form-data; name=\"file1\"; filename=\"err.txt\"\r\n\
+Content-Type: text/plain\r\n\r\n\
+file content is not limited\r\n\
+------WebKitFormBoundary0mkL0yrNNupCojyz--\r\n";
+        let multipart = || {
+            TestClient::post("http://127.0.0.1:5800/hello")
+                .add_header(
+                    "content-type",
+                    "multipart/form-data; boundary=----WebKitFormBoundary0mkL0yrNNupCojyz",
+                    true,
+                )
+                .body(body)
+                .build()
+        };
+        let mut req = multipart();
+        req.set_secure_max_size(4);
+        assert_eq!(req.form::<String>("money").await.unwrap(), "sh*t");
+        assert!(req.file("file1").await.is_some());
+
+        let mut req = multipart();
+        req.set_secure_max_size(3);
+        assert!(matches!(
+            req.form_data().await,
+            Err(ParseError::PayloadTooLarge)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_declared_content_length() {
+        let declared = |body: &'static str| {
+            TestClient::post("http://127.0.0.1:5800/hello")
+                .add_header("content-length", "1048576", true)
+                .body(body)
+                .build()
+        };
+        let mut req = declared(r#"{"name":"jobs"}"#);
+        assert_eq!(req.content_length(), Some(1048576));
+        assert!(matches!(
+            req.payload_with_max_size(1024).await,
+            Err(ParseError::PayloadTooLarge)
+        ));
+        assert!(!req.body().is_none());
+
+        let mut req = declared("lover=dog");
+        req.headers_mut().insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/x-www-form-urlencoded"),
+        );
+        req.set_secure_max_size(1024);
+        assert!(matches!(
+            req.form_data().await,
+            Err(ParseError::PayloadTooLarge)
+        ));
+        assert!(!req.body().is_none());
+
+        let mut req = TestClient::post("http://127.0.0.1:5800/hello")
+            .raw_json(r#"{"name":"jobs"}"#)
+            .build();
+        assert_eq!(req.content_length(), None);
+        assert!(matches!(
+            req.payload_with_max_size(8).await,
+            Err(ParseError::PayloadTooLarge)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_payload_then_parse() {
+        let mut req = TestClient::post("http://127.0.0.1:5800/hello")
+            .raw_json(r#"{"name":"jobs","age":25}"#)
+            .build();
+        assert!(matches!(
+            req.payload_with_max_size(8).await,
+            Err(ParseError::PayloadTooLarge)
+        ));
+
+        let mut req = TestClient::post("http://127.0.0.1:5800/hello")
+            .raw_json(r#"{"name":"jobs","age":25}"#)
+            .build();
+        assert_eq!(
+            req.payload_with_max_size(1024).await.unwrap(),
+            r#"{"name":"jobs","age":25}"#
+        );
+        assert_eq!(
+            req.parse_json::<serde_json::Value>().await.unwrap()["name"],
+            "jobs"
+        );
+
+        let mut req = TestClient::post("http://127.0.0.1:5800/hello")
+            .raw_form("lover=dog&money=sh*t")
+            .build();
+        assert_eq!(req.payload().await.unwrap(), "lover=dog&money=sh*t");
+        assert_eq!(req.form::<String>("lover").await.unwrap(), "dog");
+    }
+
+    #[tokio::test]
+    async fn test_multipart_limits() {
+        let body = "------WebKitFormBoundary0mkL0yrNNupCojyz\r\n\
+Content-Disposition: form-data; name=\"money\"\r\n\r\nsh*t\r\n\
+------WebKitFormBoundary0mkL0yrNNupCojyz\r\n\
+Content-Disposition: form-data; name=\"file1\"; filename=\"a.txt\"\r\n\
+Content-Type: text/plain\r\n\r\n\
+0123456789\r\n\
+------WebKitFormBoundary0mkL0yrNNupCojyz\r\n\
+Content-Disposition: form-data; name=\"file2\"; filename=\"b.txt\"\r\n\
+Content-Type: text/plain\r\n\r\n\
+0123456789012345\r\n\
+------WebKitFormBoundary0mkL0yrNNupCojyz--\r\n";
+        let multipart = |limits: MultipartLimits| {
+            let mut req = TestClient::post("http://127.0.0.1:5800/upload")
+                .add_header(
+                    "content-type",
+                    "multipart/form-data; boundary=----WebKitFormBoundary0mkL0yrNNupCojyz",
+                    true,
+                )
+                .body(body)
+                .build();
+            req.set_multipart_limits(limits);
+            req
+        };
+
+        let mut req = multipart(MultipartLimits::new());
+        assert_eq!(req.multipart_limits().max_parts, 1000);
+        assert_eq!(req.all_files().await.len(), 2);
+
+        let mut req = multipart(
+            MultipartLimits::new()
+                .max_parts(3)
+                .max_file_size(16)
+                .max_files_size(26),
+        );
+        assert_eq!(req.file("file2").await.unwrap().size(), 16);
+
+        let mut req = multipart(MultipartLimits::new().max_parts(2));
+        assert!(matches!(
+            req.form_data().await,
+            Err(ParseError::TooManyParts(2))
+        ));
+
+        let mut req = multipart(MultipartLimits::new().max_file_size(15));
+        match req.form_data().await {
+            Err(ParseError::FilePartTooLarge { name, limit }) => {
+                assert_eq!(name, "file2");
+                assert_eq!(limit, 15);
+            }
+            _ => panic!("file part should be too large"),
+        }
+
+        let mut req = multipart(MultipartLimits::new().max_file_size(16).max_files_size(25));
+        assert!(matches!(
+            req.form_data().await,
+            Err(ParseError::PayloadTooLarge)
+        ));
+
+        #[handler]
+        async fn upload(req: &mut Request) -> Result<String, ParseError> {
+            let files = req.form_data().await?.files.len();
+            Ok(format!("{files} files uploaded"))
+        }
+        let router = Router::with_path("upload")
+            .hoop(MultipartLimits::new().max_file_size(15))
+            .post(upload);
+        let mut res = TestClient::post("http://127.0.0.1:5800/upload")
+            .add_header(
+                "content-type",
+                "multipart/form-data; boundary=----WebKitFormBoundary0mkL0yrNNupCojyz",
+                true,
+            )
+            .body(body)
+            .send(router)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::PAYLOAD_TOO_LARGE));
+        assert!(
+            res.take_string()
+                .await
+                .unwrap()
+                .contains("the part `file2` is too large, the limit is 15 bytes")
+        );
+    }
+
+    #[test]
+    fn test_accepts() {
+        let mut req = Request::default();
+        assert!(req.accepts("text/html"));
+        assert!(req.accepts_json());
+
+        req.headers_mut().insert(
+            "accept",
+            HeaderValue::from_static("text/html, application/*;q=0.5, image/png;q=0"),
+        );
+        assert!(req.accepts("text/html"));
+        assert!(!req.accepts("text/plain"));
+        assert!(req.accepts_json());
+        assert!(req.accepts("application/xml"));
+        assert!(!req.accepts("image/png"));
+        assert!(!req.accepts("invalid"));
+
+        req.headers_mut().insert(
+            "accept",
+            HeaderValue::from_static("*/*;q=0.8, text/*, text/plain;q=0"),
+        );
+        assert!(req.accepts("text/html"));
+        assert!(!req.accepts("text/plain"));
+        assert!(req.accepts_json());
+    }
+
+    #[test]
+    fn test_check_preconditions() {
+        use std::time::{Duration, SystemTime};
+
+        let etag: ETag = "\"v2\"".parse().unwrap();
+        let modified = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let check = |name: &'static str, value: &str, etag: Option<&ETag>| {
+            TestClient::patch("http://127.0.0.1:5800/todos/1")
+                .add_header(name, value, true)
+                .build()
+                .check_preconditions(etag, Some(modified))
+                .map_err(|e| e.code)
+        };
+
+        assert!(
+            TestClient::patch("http://127.0.0.1:5800/todos/1")
+                .build()
+                .check_preconditions(Some(&etag), Some(modified))
+                .is_ok()
+        );
+        assert!(check("if-match", "\"v2\"", Some(&etag)).is_ok());
+        assert!(check("if-match", "\"v1\", \"v2\"", Some(&etag)).is_ok());
+        assert!(check("if-match", "*", Some(&etag)).is_ok());
+        assert_eq!(
+            check("if-match", "\"v1\"", Some(&etag)),
+            Err(crate::http::StatusCode::PRECONDITION_FAILED)
+        );
+        assert_eq!(
+            check("if-match", "W/\"v2\"", Some(&etag)),
+            Err(crate::http::StatusCode::PRECONDITION_FAILED)
+        );
+        assert_eq!(
+            check("if-match", "*", None),
+            Err(crate::http::StatusCode::PRECONDITION_FAILED)
+        );
+
+        assert!(check("if-unmodified-since", "Mon, 12 Jan 1970 13:46:40 GMT", None).is_ok());
+        assert_eq!(
+            check("if-unmodified-since", "Sun, 11 Jan 1970 00:00:00 GMT", None),
+            Err(crate::http::StatusCode::PRECONDITION_FAILED)
+        );
+    }
 }
@@ -6,14 +6,14 @@ use std::path::PathBuf;
 #[cfg(feature = "cookie")]
 use cookie::{Cookie, CookieJar};
 use futures_util::stream::Stream;
-use http::header::{HeaderMap, HeaderValue, IntoHeaderName};
+use http::header::{HeaderMap, HeaderValue, IntoHeaderName, TRAILER};
 pub use http::response::Parts;
 use http::{Extensions, version::Version};
 use mime::Mime;
 
 use crate::fs::NamedFile;
 use crate::fuse::TransProto;
-use crate::http::{StatusCode, StatusError};
+use crate::http::StatusCode;
 use crate::{BoxedError, Error, Scribe};
 use bytes::Bytes;
 
@@ -33,6 +33,8 @@ pub struct Response {
     pub cookies: CookieJar,
     /// The HTTP body.
     pub body: ResBody,
+    /// The HTTP trailers, sent after the body.
+    pub trailers: HeaderMap,
     /// Used to store extra data derived from the underlying protocol.
     pub extensions: Extensions,
 }
@@ -81,6 +83,7 @@ where
             headers,
             #[cfg(feature = "cookie")]
             cookies,
+            trailers: HeaderMap::new(),
             extensions: Extensions::new(),
         }
     }
@@ -97,6 +100,7 @@ impl Response {
             headers: HeaderMap::new(),
             #[cfg(feature = "cookie")]
             cookies: CookieJar::default(),
+            trailers: HeaderMap::new(),
             extensions: Extensions::new(),
         }
     }
@@ -111,6 +115,7 @@ impl Response {
             version: Version::default(),
             headers: HeaderMap::new(),
             cookies,
+            trailers: HeaderMap::new(),
             extensions: Extensions::new(),
         }
     }
@@ -156,6 +161,70 @@ impl Response {
         Ok(self)
     }
 
+    /// Set a header for this response and return `&mut Self` for chaining, if the header is
+    /// already present, the value will be replaced.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use salvo_core::http::header::{CACHE_CONTROL, HeaderValue};
+    /// use salvo_core::http::{Response, StatusCode};
+    ///
+    /// let mut res = Response::new();
+    /// res.status(StatusCode::OK)
+    ///     .with_header(CACHE_CONTROL, HeaderValue::from_static("no-cache"))
+    ///     .render("hello world");
+    /// ```
+    #[inline]
+    pub fn with_header<N, V>(&mut self, name: N, value: V) -> &mut Self
+    where
+        N: IntoHeaderName,
+        V: Into<HeaderValue>,
+    {
+        self.headers.insert(name, value.into());
+        self
+    }
+
+    /// Get trailers reference.
+    #[inline]
+    pub fn trailers(&self) -> &HeaderMap {
+        &self.trailers
+    }
+    /// Get mutable trailers reference.
+    #[inline]
+    pub fn trailers_mut(&mut self) -> &mut HeaderMap {
+        &mut self.trailers
+    }
+    /// Set a trailer for this response, it is sent after the body, for example a checksum of a streamed body.
+    /// If the trailer is already present, the value will be replaced.
+    ///
+    /// Trailers are always sent in HTTP/2 and HTTP/3. In HTTP/1, they are only sent with chunked encoding when the
+    /// request has the `te: trailers` header, the names of the trailers are added to the `trailer` header if it is
+    /// not set. Some clients and proxies drop them, so do not use trailers for the data which is required.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use salvo_core::prelude::*;
+    ///
+    /// #[handler]
+    /// async fn download(res: &mut Response) {
+    ///     res.stream(futures_util::stream::iter(vec![Ok::<_, std::io::Error>("hello")]));
+    ///     res.set_trailer("x-checksum", "5d41402abc4b2a76b9719d911017c592").unwrap();
+    /// }
+    /// ```
+    pub fn set_trailer<N, V>(&mut self, name: N, value: V) -> crate::Result<&mut Self>
+    where
+        N: IntoHeaderName,
+        V: TryInto<HeaderValue>,
+    {
+        let value = value
+            .try_into()
+            .map_err(|_| Error::Other("invalid header value".into()))?;
+        self.trailers.insert(name, value);
+        Ok(self)
+    }
+
     /// Get version.
     #[inline]
     pub fn version(&self) -> Version {
@@ -216,13 +285,11 @@ impl Response {
     pub fn into_hyper(self) -> hyper::Response<ResBody> {
         let Self {
             status_code,
-            #[cfg(feature = "cookie")]
             mut headers,
             #[cfg(feature = "cookie")]
             cookies,
-            #[cfg(not(feature = "cookie"))]
-            headers,
             body,
+            trailers,
             extensions,
             ..
         } = self;
@@ -239,7 +306,16 @@ impl Response {
             ResBody::Error(e) => e.code,
             _ => StatusCode::OK,
         });
-        let mut res = hyper::Response::new(body);
+        if !trailers.is_empty() && !headers.contains_key(TRAILER) {
+            let names = trailers
+                .keys()
+                .map(|name| name.as_str())
+                .collect::<Vec<_>>();
+            if let Ok(value) = HeaderValue::from_str(&names.join(", ")) {
+                headers.insert(TRAILER, value);
+            }
+        }
+        let mut res = hyper::Response::new(body.with_trailers(trailers));
         *res.extensions_mut() = extensions;
         *res.headers_mut() = headers;
         *res.status_mut() = status_code;
@@ -251,7 +327,8 @@ impl Response {
     #[doc(hidden)]
     #[inline]
     pub fn strip_to_hyper(&mut self) -> hyper::Response<ResBody> {
-        let mut res = hyper::Response::new(std::mem::take(&mut self.body));
+        let body = std::mem::take(&mut self.body).with_trailers(std::mem::take(&mut self.trailers));
+        let mut res = hyper::Response::new(body);
         *res.extensions_mut() = std::mem::take(&mut self.extensions);
         *res.headers_mut() = std::mem::take(&mut self.headers);
         if let Some(status) = self.status_code {
@@ -374,6 +451,22 @@ impl Response {
         self
     }
 
+    /// Set status code and return `&mut Self` for chaining, it is the same as
+    /// [`status_code`](Self::status_code).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use salvo_core::http::{Response, StatusCode};
+    ///
+    /// let mut res = Response::new();
+    /// res.status(StatusCode::CREATED).render("created");
+    /// ```
+    #[inline]
+    pub fn status(&mut self, code: StatusCode) -> &mut Self {
+        self.status_code(code)
+    }
+
     /// Render content.
     ///
     /// # Example
@@ -401,22 +494,79 @@ impl Response {
         scribe.render(self);
     }
 
+    /// Render content with status code and return `&mut Self` for chaining.
+    ///
+    /// The status code is set before rendering, so a [`Scribe`] which sets status code itself,
+    /// for example [`StatusError`](crate::http::StatusError), takes precedence.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use salvo_core::http::{Response, StatusCode};
+    /// use salvo_core::http::header::{HeaderValue, LOCATION};
+    ///
+    /// let mut res = Response::new();
+    /// res.render_with_status(StatusCode::CREATED, "created")
+    ///     .with_header(LOCATION, HeaderValue::from_static("/todos/1"));
+    /// ```
+    #[inline]
+    pub fn render_with_status<P>(&mut self, code: StatusCode, scribe: P) -> &mut Self
+    where
+        P: Scribe,
+    {
+        self.stuff(code, scribe);
+        self
+    }
+
     /// Attempts to send a file. If file not exists, not found error will occur.
     ///
+    /// The file is streamed with the `content-type` guessed by its extension and the `content-length`.
+    /// The `range` and conditional headers in `req_headers` are respected, so the client can resume
+    /// the download with `206 Partial Content` or get `304 Not Modified`.
+    ///
     /// If you want more settings, you can use `NamedFile::builder` to create a new [`NamedFileBuilder`](crate::fs::NamedFileBuilder).
+    ///
+    /// ```
+    /// # use salvo_core::prelude::*;
+    /// #[handler]
+    /// async fn show_report(req: &mut Request, res: &mut Response) {
+    ///     res.send_file("reports/todos.csv", req.headers()).await;
+    /// }
+    /// ```
     pub async fn send_file<P>(&mut self, path: P, req_headers: &HeaderMap)
     where
         P: Into<PathBuf> + Send,
     {
-        let path = path.into();
-        if !path.exists() {
-            self.render(StatusError::not_found());
-        } else {
-            match NamedFile::builder(path).build().await {
-                Ok(file) => file.send(req_headers, self).await,
-                Err(_) => self.render(StatusError::internal_server_error()),
-            }
-        }
+        NamedFile::builder(path).send(req_headers, self).await;
+    }
+
+    /// Attempts to send a file as an attachment named `attached_name`, so the browser downloads it
+    /// instead of displaying it. If file not exists, not found error will occur.
+    ///
+    /// It is the same as [`send_file`](Self::send_file) except the `content-disposition` is set to
+    /// `attachment; filename="{attached_name}"`.
+    ///
+    /// ```
+    /// # use salvo_core::prelude::*;
+    /// #[handler]
+    /// async fn export_todos(req: &mut Request, res: &mut Response) {
+    ///     res.send_attachment("reports/todos.csv", "todos-2024.csv", req.headers())
+    ///         .await;
+    /// }
+    /// ```
+    pub async fn send_attachment<P, N>(
+        &mut self,
+        path: P,
+        attached_name: N,
+        req_headers: &HeaderMap,
+    ) where
+        P: Into<PathBuf> + Send,
+        N: Into<String> + Send,
+    {
+        NamedFile::builder(path)
+            .attached_name(attached_name)
+            .send(req_headers, self)
+            .await;
     }
 
     /// Write bytes data to body. If body is none, a new `ResBody` will created.
@@ -486,7 +636,18 @@ impl Response {
 
     /// Create a `Body` stream with an associated sender half.
     ///
-    /// Useful when wanting to stream chunks from another thread.
+    /// Useful when wanting to stream chunks from another thread, like the progress or the logs of a task, the
+    /// chunks are streamed to the client until the sender is dropped.
+    ///
+    /// # Backpressure
+    ///
+    /// The channel is bounded, [`BodySender::send_data`] waits until the previous chunk is taken by the connection,
+    /// so a slow client slows down the sender instead of buffering the chunks in memory.
+    ///
+    /// # Client disconnect
+    ///
+    /// If the client disconnects, the body is dropped and [`BodySender::send_data`] returns an error, stop the
+    /// sending task when it happens. [`BodySender::is_closed`] checks it without sending.
     ///
     /// # Example
     ///
@@ -517,6 +678,7 @@ impl Debug for Response {
             .field("headers", &self.headers)
             // omits Extensions because not useful
             .field("body", &self.body)
+            .field("trailers", &self.trailers)
             .finish()
     }
 }
@@ -570,4 +732,144 @@ mod test {
 
         assert_eq!("Hello World", &result)
     }
+
+    #[tokio::test]
+    async fn test_body_channel() {
+        let mut res = Response::new();
+        let mut tx = res.channel();
+        tokio::spawn(async move {
+            for chunk in ["Hello", " ", "World"] {
+                tx.send_data(chunk).await.unwrap();
+            }
+        });
+        let mut result = bytes::BytesMut::new();
+        while let Some(Ok(data)) = res.body.next().await {
+            result.extend_from_slice(&data.into_data().unwrap_or_default())
+        }
+        assert_eq!("Hello World", &result);
+
+        let mut tx = res.channel();
+        res.body = ResBody::None;
+        assert!(tx.is_closed());
+        assert!(tx.send_data("Hello").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_status_and_header_chaining() {
+        use crate::http::header::{CACHE_CONTROL, LOCATION};
+        use crate::test::ResponseExt;
+
+        let mut res = Response::new();
+        res.status(StatusCode::CREATED)
+            .with_header(CACHE_CONTROL, HeaderValue::from_static("no-cache"))
+            .render("created");
+        assert_eq!(res.status_code, Some(StatusCode::CREATED));
+        assert_eq!(res.headers()[CACHE_CONTROL], "no-cache");
+        assert_eq!(res.take_string().await.unwrap(), "created");
+
+        let mut res = Response::new();
+        res.render_with_status(StatusCode::ACCEPTED, "accepted")
+            .with_header(LOCATION, HeaderValue::from_static("/todos/1"));
+        assert_eq!(res.status_code, Some(StatusCode::ACCEPTED));
+        assert_eq!(res.headers()[LOCATION], "/todos/1");
+        assert_eq!(res.take_string().await.unwrap(), "accepted");
+    }
+
+    #[tokio::test]
+    async fn test_send_file() {
+        use crate::http::header::{
+            CONTENT_DISPOSITION, CONTENT_LENGTH, CONTENT_RANGE, CONTENT_TYPE, RANGE,
+        };
+        use crate::prelude::*;
+        use crate::test::{ResponseExt, TestClient};
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("todos.csv");
+        std::fs::write(&path, "id,title\n1,write docs\n").unwrap();
+
+        #[handler]
+        async fn show(req: &mut Request, res: &mut Response) {
+            let path = req.query::<String>("path").unwrap();
+            res.send_file(path, req.headers()).await;
+        }
+        #[handler]
+        async fn export(req: &mut Request, res: &mut Response) {
+            let path = req.query::<String>("path").unwrap();
+            res.send_attachment(path, "report.csv", req.headers()).await;
+        }
+        let router = Router::new()
+            .push(Router::with_path("show").get(show))
+            .push(Router::with_path("export").get(export));
+        let service = Service::new(router);
+        let url = |action: &str, path: &std::path::Path| {
+            format!(
+                "http://127.0.0.1:5800/{action}?path={}",
+                percent_encoding::utf8_percent_encode(
+                    &path.to_string_lossy(),
+                    percent_encoding::NON_ALPHANUMERIC
+                )
+            )
+        };
+
+        let mut res = TestClient::get(url("show", &path)).send(&service).await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+        assert_eq!(res.headers()[CONTENT_TYPE], "text/csv; charset=utf-8");
+        assert_eq!(res.headers()[CONTENT_LENGTH], "22");
+        assert_eq!(res.headers()[CONTENT_DISPOSITION], "inline");
+        assert_eq!(res.take_string().await.unwrap(), "id,title\n1,write docs\n");
+
+        let mut res = TestClient::get(url("export", &path))
+            .add_header(RANGE, "bytes=0-7", true)
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::PARTIAL_CONTENT));
+        assert_eq!(
+            res.headers()[CONTENT_DISPOSITION],
+            r#"attachment; filename="report.csv""#
+        );
+        assert_eq!(res.headers()[CONTENT_RANGE], "bytes 0-7/22");
+        assert_eq!(res.take_string().await.unwrap(), "id,title");
+
+        let res = TestClient::get(url("show", &dir.path().join("missing.csv")))
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::NOT_FOUND));
+    }
+
+    #[tokio::test]
+    async fn test_trailers() {
+        use http_body_util::BodyExt;
+
+        let mut res = Response::new();
+        res.stream(iter(vec![
+            Ok::<_, Box<dyn Error + Send + Sync>>("Hello"),
+            Ok(" World"),
+        ]));
+        res.set_trailer("x-checksum", "b10a8db164e0754105b7a99be72e3fe5")
+            .unwrap();
+        let res = res.into_hyper();
+        assert_eq!(res.headers()[TRAILER], "x-checksum");
+        let collected = BodyExt::collect(res.into_body()).await.unwrap();
+        assert_eq!(
+            collected.trailers().unwrap()["x-checksum"],
+            "b10a8db164e0754105b7a99be72e3fe5"
+        );
+        assert_eq!(collected.to_bytes(), "Hello World");
+
+        let mut res = Response::new();
+        let mut sender = res.channel();
+        res.set_trailer("x-checksum", "value").unwrap();
+        tokio::spawn(async move {
+            sender.send_data("data").await.unwrap();
+            let mut trailers = HeaderMap::new();
+            trailers.insert("x-inner", HeaderValue::from_static("inner"));
+            sender.send_trailers(trailers).await.unwrap();
+        });
+        let collected = BodyExt::collect(res.into_hyper().into_body())
+            .await
+            .unwrap();
+        let trailers = collected.trailers().unwrap();
+        assert_eq!(trailers["x-inner"], "inner");
+        assert_eq!(trailers["x-checksum"], "value");
+    }
 }
@@ -16,6 +16,7 @@
 //! | `test` | Utilities for testing application | ✔️ |
 //! | `acme` | Automatically obtain certificates through ACME | ❌ |
 //! | `rustls` | TLS built on [`rustls`](https://crates.io/crates/rustls) | ❌ |
+//! | `client-cert` | Verified client certificates of the `rustls` connections | ❌ |
 //! | `openssl` | TLS built on [`openssl-tls`](https://crates.io/crates/openssl) | ❌ |
 //! | `native-tls` | TLS built on [`native-tls`](https://crates.io/crates/native-tls) | ❌ |
 //! | `unix` | Listener based on unix socket | ❌ |
@@ -111,6 +111,39 @@ impl<F> fmt::Debug for FnFilter<F> {
     }
 }
 
+/// `WhenFilter` accepts a predicate on `Request`, use this function to filter request by its
+/// attributes beyond path and method, for example headers or queries.
+#[derive(Copy, Clone)]
+#[allow(missing_debug_implementations)]
+pub struct WhenFilter<F>(pub F);
+
+#[async_trait]
+impl<F> Filter for WhenFilter<F>
+where
+    F: Fn(&Request) -> bool + Send + Sync + 'static,
+{
+    #[inline]
+    async fn filter(&self, req: &mut Request, _path: &mut PathState) -> bool {
+        self.0(req)
+    }
+}
+
+impl<F> fmt::Debug for WhenFilter<F> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "when:fn")
+    }
+}
+
+/// Filter request by a predicate on `Request`.
+#[inline]
+pub fn when<F>(predicate: F) -> WhenFilter<F>
+where
+    F: Fn(&Request) -> bool + Send + Sync + 'static,
+{
+    WhenFilter(predicate)
+}
+
 /// Filter request by uri scheme.
 #[inline]
 pub fn scheme(scheme: Scheme) -> SchemeFilter {
@@ -134,6 +167,28 @@ pub fn port(port: u16) -> PortFilter {
 pub fn path(path: impl Into<String>) -> PathFilter {
     PathFilter::new(path)
 }
+/// Filter request by `content-type` header, like `application/json` or `text/*`.
+///
+/// View [`ContentTypeFilter`] for more details.
+///
+/// # Panics
+///
+/// Panics if `mime` is not a valid media type.
+#[inline]
+pub fn content_type(mime: &str) -> ContentTypeFilter {
+    ContentTypeFilter::new(mime.parse().expect("invalid media type"))
+}
+/// Filter request by `accept` header, like `text/csv`.
+///
+/// View [`AcceptFilter`] for more details.
+///
+/// # Panics
+///
+/// Panics if `mime` is not a valid media type.
+#[inline]
+pub fn accept(mime: &str) -> AcceptFilter {
+    AcceptFilter::new(mime.parse().expect("invalid media type"))
+}
 /// Filter request, only allow get method.
 #[inline]
 pub fn get() -> MethodFilter {
@@ -283,4 +338,67 @@ mod tests {
                 .await
         );
     }
+
+    #[tokio::test]
+    async fn test_content_type() {
+        let mut req = Request::default();
+        req.headers_mut().insert(
+            "content-type",
+            "application/json; charset=utf-8".parse().unwrap(),
+        );
+        let mut path_state = PathState::new("");
+        assert!(
+            content_type("application/json")
+                .filter(&mut req, &mut path_state)
+                .await
+        );
+        assert!(
+            content_type("application/*")
+                .filter(&mut req, &mut path_state)
+                .await
+        );
+        assert!(!path_state.once_unsupported_media_type);
+        assert!(
+            !content_type("text/plain")
+                .filter(&mut req, &mut path_state)
+                .await
+        );
+        assert!(path_state.once_unsupported_media_type);
+        assert_eq!(path_state.supported_media_types, vec![mime::TEXT_PLAIN]);
+
+        let mut req = Request::default();
+        let mut path_state = PathState::new("/todos");
+        assert!(
+            !content_type("application/json")
+                .filter(&mut req, &mut path_state)
+                .await
+        );
+        assert!(!path_state.once_unsupported_media_type);
+    }
+
+    #[tokio::test]
+    async fn test_accept() {
+        let mut req = Request::default();
+        req.headers_mut().insert(
+            "accept",
+            "text/html, application/*;q=0.9, text/csv;q=0"
+                .parse()
+                .unwrap(),
+        );
+        let mut path_state = PathState::new("");
+        assert!(accept("text/html").filter(&mut req, &mut path_state).await);
+        assert!(
+            accept("application/json")
+                .filter(&mut req, &mut path_state)
+                .await
+        );
+        assert!(!path_state.once_not_acceptable);
+        assert!(!accept("text/csv").filter(&mut req, &mut path_state).await);
+        assert!(path_state.once_not_acceptable);
+
+        let mut req = Request::default();
+        assert!(accept("text/csv").filter(&mut req, &mut path_state).await);
+        req.headers_mut().insert("accept", "*/*".parse().unwrap());
+        assert!(accept("text/csv").filter(&mut req, &mut path_state).await);
+    }
 }
@@ -2,10 +2,14 @@ use std::fmt::{self, Debug, Formatter};
 
 use crate::async_trait;
 use crate::http::uri::Scheme;
-use crate::http::{Method, Request};
+use crate::http::{Method, Mime, Request};
 use crate::routing::{Filter, PathState};
 
 /// Filter by request method
+///
+/// If the path is ended when the request is rejected by this filter, and no other router matches the request, the
+/// status code of the response will be `405 Method Not Allowed`, and the methods of all the rejecting filters are
+/// listed in the `Allow` header.
 #[derive(Clone, PartialEq, Eq)]
 pub struct MethodFilter(pub Method);
 impl MethodFilter {
@@ -18,8 +22,12 @@ impl MethodFilter {
 #[async_trait]
 impl Filter for MethodFilter {
     #[inline]
-    async fn filter(&self, req: &mut Request, _state: &mut PathState) -> bool {
-        req.method() == self.0
+    async fn filter(&self, req: &mut Request, state: &mut PathState) -> bool {
+        let matched = req.method() == self.0;
+        if !matched && state.is_ended() && !state.allowed_methods.contains(&self.0) {
+            state.allowed_methods.push(self.0.clone());
+        }
+        matched
     }
 }
 impl Debug for MethodFilter {
@@ -183,3 +191,85 @@ impl Debug for PortFilter {
         write!(f, "port:{:?}", self.port)
     }
 }
+
+/// Filter by request `content-type` header.
+///
+/// The parameters of the media type, such as `charset`, are ignored, and `*` can be used as the subtype, like
+/// `text/*`. If the path is ended when the request is rejected by this filter, the status code of the response
+/// will be `415 Unsupported Media Type` if no other router matches the request, and the media types of all the
+/// rejecting filters are listed in the `Accept-Post` or `Accept-Patch` header for `POST` and `PATCH` requests.
+/// The same headers are written for [`ParseError::InvalidContentType`](crate::http::ParseError::InvalidContentType)
+/// returned by [`Request::parse_body`](crate::Request::parse_body) and the extractors.
+#[derive(Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ContentTypeFilter {
+    /// Media type to filter.
+    pub mime: Mime,
+}
+impl ContentTypeFilter {
+    /// Create a new `ContentTypeFilter`.
+    pub fn new(mime: Mime) -> Self {
+        Self { mime }
+    }
+}
+
+#[async_trait]
+impl Filter for ContentTypeFilter {
+    #[inline]
+    async fn filter(&self, req: &mut Request, state: &mut PathState) -> bool {
+        let matched = req.content_type().is_some_and(|ctype| {
+            ctype.type_() == self.mime.type_()
+                && (self.mime.subtype() == mime::STAR || ctype.subtype() == self.mime.subtype())
+        });
+        if !matched && state.is_ended() {
+            state.once_unsupported_media_type = true;
+            if !state.supported_media_types.contains(&self.mime) {
+                state.supported_media_types.push(self.mime.clone());
+            }
+        }
+        matched
+    }
+}
+impl Debug for ContentTypeFilter {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "content_type:{}", self.mime)
+    }
+}
+
+/// Filter by request `accept` header.
+///
+/// The request is accepted if the media type is acceptable by its `accept` header, view [`Request::accepts`] for
+/// how the header is matched. Requests without `accept` header accept any media type. If the path is ended when the
+/// request is rejected by this filter, the status code of the response will be `406 Not Acceptable` if no other
+/// router matches the request.
+#[derive(Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AcceptFilter {
+    /// Media type to filter.
+    pub mime: Mime,
+}
+impl AcceptFilter {
+    /// Create a new `AcceptFilter`.
+    pub fn new(mime: Mime) -> Self {
+        Self { mime }
+    }
+}
+
+#[async_trait]
+impl Filter for AcceptFilter {
+    #[inline]
+    async fn filter(&self, req: &mut Request, state: &mut PathState) -> bool {
+        let matched = req.accepts_mime(&self.mime);
+        if !matched && state.is_ended() {
+            state.once_not_acceptable = true;
+        }
+        matched
+    }
+}
+impl Debug for AcceptFilter {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "accept:{}", self.mime)
+    }
+}
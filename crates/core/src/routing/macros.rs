@@ -0,0 +1,174 @@
+/// Declare a router tree concisely, it expands to the [`Router`](crate::routing::Router) builder calls.
+///
+/// The entries are separated by commas:
+///
+/// - `"path" => { ... }` pushes a child router created by [`Router::with_path`](crate::routing::Router::with_path),
+///   and declares its entries in the braces.
+/// - `GET => handler`, and the same for `POST`, `PUT`, `DELETE`, `PATCH`, `HEAD` and `OPTIONS`, adds a handler
+///   for the method, like [`Router::get`](crate::routing::Router::get).
+/// - `goal => handler` sets the handler for all methods, like [`Router::goal`](crate::routing::Router::goal).
+/// - `hoop => middleware` adds a middleware to the router it is declared in, like
+///   [`Router::hoop`](crate::routing::Router::hoop), so it only runs for the routes of this group.
+/// - `catch => catcher` sets the catcher of the router, like [`Router::catch`](crate::routing::Router::catch).
+///
+/// The outermost entries are declared in a [`Router::new`](crate::routing::Router::new).
+///
+/// ```
+/// use salvo_core::prelude::*;
+/// use salvo_core::routes;
+///
+/// # #[handler] async fn auth() {}
+/// # #[handler] async fn list_todos() {}
+/// # #[handler] async fn create_todo() {}
+/// # #[handler] async fn update_todo() {}
+/// # #[handler] async fn delete_todo() {}
+/// let router = routes! {
+///     "api" => {
+///         "todos" => {
+///             hoop => auth,
+///             GET => list_todos,
+///             POST => create_todo,
+///             "{id}" => {
+///                 PATCH => update_todo,
+///                 DELETE => delete_todo,
+///             },
+///         },
+///     },
+/// };
+/// // Same as:
+/// let router = Router::new().push(
+///     Router::with_path("api").push(
+///         Router::with_path("todos")
+///             .hoop(auth)
+///             .get(list_todos)
+///             .post(create_todo)
+///             .push(Router::with_path("{id}").patch(update_todo).delete(delete_todo)),
+///     ),
+/// );
+/// ```
+///
+/// Every entry is expanded by a recursive call, raise the `recursion_limit` of the crate for very large trees, or
+/// split them into several `routes!` merged by [`Router::push`](crate::routing::Router::push).
+#[macro_export]
+macro_rules! routes {
+    ($($entries:tt)*) => {
+        $crate::__routes!($crate::routing::Router::new(); $($entries)*)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __routes {
+    ($router:expr;) => {
+        $router
+    };
+    ($router:expr; $path:literal => { $($inner:tt)* } $(, $($rest:tt)*)?) => {
+        $crate::__routes!(
+            $router.push($crate::__routes!($crate::routing::Router::with_path($path); $($inner)*));
+            $($($rest)*)?
+        )
+    };
+    ($router:expr; hoop => $hoop:expr $(, $($rest:tt)*)?) => {
+        $crate::__routes!($router.hoop($hoop); $($($rest)*)?)
+    };
+    ($router:expr; goal => $goal:expr $(, $($rest:tt)*)?) => {
+        $crate::__routes!($router.goal($goal); $($($rest)*)?)
+    };
+    ($router:expr; catch => $catcher:expr $(, $($rest:tt)*)?) => {
+        $crate::__routes!($router.catch($catcher); $($($rest)*)?)
+    };
+    ($router:expr; GET => $handler:expr $(, $($rest:tt)*)?) => {
+        $crate::__routes!($router.get($handler); $($($rest)*)?)
+    };
+    ($router:expr; POST => $handler:expr $(, $($rest:tt)*)?) => {
+        $crate::__routes!($router.post($handler); $($($rest)*)?)
+    };
+    ($router:expr; PUT => $handler:expr $(, $($rest:tt)*)?) => {
+        $crate::__routes!($router.put($handler); $($($rest)*)?)
+    };
+    ($router:expr; DELETE => $handler:expr $(, $($rest:tt)*)?) => {
+        $crate::__routes!($router.delete($handler); $($($rest)*)?)
+    };
+    ($router:expr; PATCH => $handler:expr $(, $($rest:tt)*)?) => {
+        $crate::__routes!($router.patch($handler); $($($rest)*)?)
+    };
+    ($router:expr; HEAD => $handler:expr $(, $($rest:tt)*)?) => {
+        $crate::__routes!($router.head($handler); $($($rest)*)?)
+    };
+    ($router:expr; OPTIONS => $handler:expr $(, $($rest:tt)*)?) => {
+        $crate::__routes!($router.options($handler); $($($rest)*)?)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::test::{ResponseExt, TestClient};
+
+    #[handler]
+    async fn list_todos() -> &'static str {
+        "list"
+    }
+    #[handler]
+    async fn create_todo() -> &'static str {
+        "create"
+    }
+    #[handler]
+    async fn delete_todo(req: &mut Request) -> String {
+        format!("delete {}", req.param::<u64>("id").unwrap_or_default())
+    }
+    #[handler]
+    async fn auth(res: &mut Response, ctrl: &mut FlowCtrl) {
+        res.status_code(StatusCode::UNAUTHORIZED);
+        ctrl.skip_rest();
+    }
+
+    #[tokio::test]
+    async fn test_routes() {
+        let router = routes! {
+            "api" => {
+                "todos" => {
+                    GET => list_todos,
+                    POST => create_todo,
+                    "{id}" => { DELETE => delete_todo }
+                },
+                "admin" => {
+                    hoop => auth,
+                    goal => list_todos,
+                },
+            },
+        };
+        let service = Service::new(router);
+
+        async fn access(
+            service: &Service,
+            method: &str,
+            path: &str,
+        ) -> (Option<StatusCode>, String) {
+            let url = format!("http://127.0.0.1:5800/{path}");
+            let mut res = match method {
+                "POST" => TestClient::post(url),
+                "DELETE" => TestClient::delete(url),
+                _ => TestClient::get(url),
+            }
+            .send(service)
+            .await;
+            (res.status_code, res.take_string().await.unwrap())
+        }
+
+        assert_eq!(access(&service, "GET", "api/todos").await.1, "list");
+        assert_eq!(access(&service, "POST", "api/todos").await.1, "create");
+        assert_eq!(
+            access(&service, "DELETE", "api/todos/3").await.1,
+            "delete 3"
+        );
+        assert_eq!(
+            access(&service, "GET", "api/todos/3").await.0,
+            Some(StatusCode::METHOD_NOT_ALLOWED)
+        );
+        assert_eq!(
+            access(&service, "GET", "api/admin").await.0,
+            Some(StatusCode::UNAUTHORIZED)
+        );
+    }
+}
@@ -78,7 +78,8 @@
 //!     );
 //! ```
 //!
-//! This form of definition can make the definition of router clear and simple for complex projects.
+//! This form of definition can make the definition of router clear and simple for complex projects. The same tree
+//! can be declared by the [`routes!`](crate::routes) macro too, which expands to these calls.
 //!
 //! There are many methods in `Router` that will return to `Self` after being called, so as to write code in a chain.
 //! Sometimes, you need to decide how to route according to certain conditions, and the `Router` also provides `then`
@@ -221,6 +222,48 @@
 //! Although there are two routers have the same `path("writers")`, they can still be added to the same parent route
 //! at the same time.
 //!
+//! ## Execution order
+//!
+//! The middlewares of the matched routers are executed from the outer routers to the inner routers, and in the order
+//! they are added in the same router, the middlewares of [`Service`](crate::Service) are executed first. Then the
+//! goal handler is executed. The code after `ctrl.call_next` in the middlewares is executed in the reverse order,
+//! from the inner to the outer:
+//!
+//! ```rust
+//! # use salvo_core::prelude::*;
+//! # #[handler] async fn a() {}
+//! # #[handler] async fn b() {}
+//! # #[handler] async fn c() {}
+//! # #[handler] async fn goal() {}
+//! // Request: a -> b -> c -> goal, response: goal -> c -> b -> a.
+//! Router::new()
+//!     .hoop(a)
+//!     .push(Router::with_path("writers").hoop(b).hoop(c).get(goal));
+//! ```
+//!
+//! [`Router::hoop`] adds a middleware at the end of current router's middlewares, use [`Router::unshift_hoop`] to
+//! insert one at the beginning, for example when the router is built by a library and a middleware needs to run
+//! before the ones it added. It only reorders the middlewares of the same router, [`Service::unshift_hoop`] inserts
+//! one before all the middlewares.
+//!
+//! [`Service::unshift_hoop`]: crate::Service::unshift_hoop
+//!
+//! If a middleware does not call `ctrl.call_next`, the next handler is called after it returns. If a middleware
+//! calls `ctrl.skip_rest`, or sets an error or redirection status code, the rest handlers are skipped, but the code
+//! after `ctrl.call_next` in the outer middlewares is still executed.
+//!
+//! ## Catchers
+//!
+//! When a request gets an error status code without a body, the error is caught by a [`Catcher`](crate::catcher::Catcher) to write the error
+//! page. Use [`Router::catch`] to set a catcher for a subtree, like writing JSON errors for `/api` and HTML error pages
+//! for the others, the catcher of the innermost matched router which has one is used. If none of the matched routers
+//! has a catcher, the catcher of [`Service`](crate::Service) is used, or the default error page if it is not set
+//! either.
+//!
+//! The catchers of the routers only catch the errors of the matched requests, the requests which match no router, like
+//! `404 Not Found` and `405 Method Not Allowed`, are always caught by the catcher of [`Service`](crate::Service),
+//! the same as the middlewares of the routers are not executed for them.
+//!
 //! # Filters
 //!
 //! Many methods in `Router` return to themselves in order to easily implement chain writing. Sometimes, in some cases,
@@ -333,6 +376,72 @@
 //! Router::new().push(show_router).push(update_router).push(delete_router);
 //! ```
 //!
+//! ## Request filter
+//!
+//! Filter requests by other attributes, such as headers or queries, with `Router::push_when`. This is useful for
+//! feature flags, for example routing to a canary handler when the `beta` query is set:
+//!
+//! ```rust
+//! use salvo_core::prelude::*;
+//!
+//! # #[handler] fn canary() {}
+//! # #[handler] fn stable() {}
+//! Router::with_path("articles")
+//!     .push_when(Router::new().get(canary), |req| req.query::<bool>("beta").unwrap_or(false))
+//!     .push(Router::new().get(stable));
+//! ```
+//!
+//! Requests not matched by the predicate fall through to the next routers.
+//!
+//! ## Content type filter
+//!
+//! Filter requests by the `content-type` header with `filters::content_type`, so the same path can be served by
+//! different handlers for different media types:
+//!
+//! ```rust
+//! use salvo_core::prelude::*;
+//! use salvo_core::routing::filters;
+//!
+//! # #[handler] fn create_json() {}
+//! # #[handler] fn create_form() {}
+//! Router::with_path("todos")
+//!     .push(
+//!         Router::with_filter(filters::post())
+//!             .filter(filters::content_type("application/json"))
+//!             .goal(create_json),
+//!     )
+//!     .push(
+//!         Router::with_filter(filters::post())
+//!             .filter(filters::content_type("multipart/form-data"))
+//!             .goal(create_form),
+//!     );
+//! ```
+//!
+//! If the path of a request is matched but none of the routers accepts its content type, the response status is
+//! `415 Unsupported Media Type`. It takes precedence over `405 Method Not Allowed`, so put the method filter
+//! before the content type filter, as above, to respond `405` for other methods. The content type filter should
+//! be added after the path filters, otherwise the response status is `404 Not Found`.
+//!
+//! ## Accept filter
+//!
+//! Similarly, filter requests by the `accept` header with `filters::accept`, so the routers are tried in order
+//! and the first one which the client accepts is used:
+//!
+//! ```rust
+//! use salvo_core::prelude::*;
+//! use salvo_core::routing::filters;
+//!
+//! # #[handler] fn export_json() {}
+//! # #[handler] fn export_csv() {}
+//! Router::with_path("export")
+//!     .push(Router::with_filter(filters::get()).filter(filters::accept("application/json")).goal(export_json))
+//!     .push(Router::with_filter(filters::get()).filter(filters::accept("text/csv")).goal(export_csv));
+//! ```
+//!
+//! If the path of a request is matched but none of the routers accepts it, the response status is
+//! `406 Not Acceptable`. It takes precedence over `405 Method Not Allowed`, but not over
+//! `415 Unsupported Media Type`.
+//!
 //! ## Custom Wisp
 //!
 //! For some frequently-occurring matching expressions, we can name a short name by
@@ -371,6 +480,7 @@
 
 pub mod filters;
 pub use filters::*;
+mod macros;
 mod router;
 pub use router::Router;
 
@@ -384,11 +494,13 @@ pub use flow_ctrl::FlowCtrl;
 use std::sync::Arc;
 
 use crate::Handler;
+use crate::catcher::Catcher;
 
 #[doc(hidden)]
 pub struct DetectMatched {
     pub hoops: Vec<Arc<dyn Handler>>,
     pub goal: Arc<dyn Handler>,
+    pub catcher: Option<Arc<Catcher>>,
 }
 
 pub(crate) fn split_wild_name(name: &str) -> (&str, &str) {
@@ -508,4 +620,58 @@ mod tests {
         access(&service, "/alice3").await;
         access(&service, "/alice1/bob3").await;
     }
+
+    #[tokio::test]
+    async fn test_matched_path_template() {
+        #[handler]
+        async fn show(req: &mut Request) -> String {
+            req.matched_path().to_owned()
+        }
+        #[handler]
+        async fn record(
+            req: &mut Request,
+            depot: &mut Depot,
+            res: &mut Response,
+            ctrl: &mut FlowCtrl,
+        ) {
+            depot.insert("route", req.matched_path().to_owned());
+            ctrl.call_next(req, depot, res).await;
+            let route = depot.get::<String>("route").unwrap().clone();
+            res.headers_mut().insert("x-route", route.parse().unwrap());
+        }
+
+        let router = Router::with_path("api/todos")
+            .get(show)
+            .push(Router::with_path("{id}").get(show))
+            .push(Router::with_path("{id:num}/items/{*rest}").get(show));
+        let service = Service::new(router).hoop(record);
+
+        async fn access(service: &Service, path: &str) -> (String, String) {
+            let mut res = TestClient::get(format!("http://127.0.0.1{path}"))
+                .send(service)
+                .await;
+            let route = res.headers()["x-route"].to_str().unwrap().to_owned();
+            (route, res.take_string().await.unwrap())
+        }
+
+        let template = "api/todos/{id}".to_owned();
+        assert_eq!(
+            access(&service, "/api/todos/5").await,
+            (template.clone(), template.clone())
+        );
+        assert_eq!(
+            access(&service, "/api/todos/6").await,
+            (template.clone(), template)
+        );
+        let template = "api/todos".to_owned();
+        assert_eq!(
+            access(&service, "/api/todos").await,
+            (template.clone(), template)
+        );
+        let template = "api/todos/{id}/items/{*rest}".to_owned();
+        assert_eq!(
+            access(&service, "/api/todos/5/items/a/b").await,
+            (template.clone(), template)
+        );
+    }
 }
@@ -1,5 +1,7 @@
 use std::borrow::Cow;
 
+use crate::http::{Method, Mime};
+
 use super::{PathParams, decode_url_path_safely};
 
 #[doc(hidden)]
@@ -13,6 +15,10 @@ pub struct PathState {
     pub(crate) matched_parts: Vec<String>,
     pub(crate) end_slash: bool, // For rest match, we want include the last slash.
     pub(crate) once_ended: bool, // Once it has ended, used to determine whether the error code returned is 404 or 405.
+    pub(crate) once_unsupported_media_type: bool, // Once it has ended but rejected by content type, the error code is 415.
+    pub(crate) once_not_acceptable: bool, // Once it has ended but rejected by accept, the error code is 406.
+    pub(crate) supported_media_types: Vec<Mime>, // Media types of the content type filters which rejected the request.
+    pub(crate) allowed_methods: Vec<Method>, // Methods of the method filters which rejected the request, for the `Allow` header of 405.
 }
 impl PathState {
     /// Create new `PathState`.
@@ -37,6 +43,10 @@ impl PathState {
             params: PathParams::new(),
             end_slash,
             once_ended: false,
+            once_unsupported_media_type: false,
+            once_not_acceptable: false,
+            supported_media_types: vec![],
+            allowed_methods: vec![],
             #[cfg(feature = "matched-path")]
             matched_parts: vec![],
         }
@@ -1,9 +1,14 @@
+use std::any::TypeId;
 use std::fmt::{self, Debug, Formatter};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
 
-use super::filters::{self, FnFilter, PathFilter};
+use super::filters::{
+    self, AcceptFilter, ContentTypeFilter, FnFilter, HostFilter, MethodFilter, PathFilter,
+    PortFilter, SchemeFilter,
+};
 use super::{DetectMatched, Filter, PathState};
+use crate::catcher::Catcher;
 use crate::handler::{Handler, WhenHoop};
 use crate::http::uri::Scheme;
 use crate::{Depot, Request};
@@ -23,6 +28,9 @@ pub struct Router {
     pub hoops: Vec<Arc<dyn Handler>>,
     /// The final handler to handle request of current router.
     pub goal: Option<Arc<dyn Handler>>,
+    /// The catcher of current router, it catches the errors of the requests handled by current router or it's
+    /// descendants.
+    pub catcher: Option<Arc<Catcher>>,
 }
 
 impl Default for Router {
@@ -43,6 +51,7 @@ impl Router {
             filters: Vec::new(),
             hoops: Vec::new(),
             goal: None,
+            catcher: None,
         }
     }
 
@@ -100,6 +109,7 @@ impl Router {
                         return Some(DetectMatched {
                             hoops: [&self.hoops[..], &dm.hoops[..]].concat(),
                             goal: dm.goal.clone(),
+                            catcher: dm.catcher.or_else(|| self.catcher.clone()),
                         });
                     } else {
                         #[cfg(feature = "matched-path")]
@@ -116,6 +126,7 @@ impl Router {
                     return Some(DetectMatched {
                         hoops: self.hoops.clone(),
                         goal: goal.clone(),
+                        catcher: self.catcher.clone(),
                     });
                 }
             }
@@ -143,12 +154,178 @@ impl Router {
         self.routers.push(router);
         self
     }
+    /// Push a router as child of current router, the child only matches the request when the
+    /// `predicate` returns true, for example to route to a canary handler when a header is set:
+    ///
+    /// ```
+    /// # use salvo_core::prelude::*;
+    /// # #[handler] async fn canary() {}
+    /// # #[handler] async fn stable() {}
+    /// let router = Router::with_path("todos")
+    ///     .push_when(Router::new().get(canary), |req| {
+    ///         req.query::<bool>("beta").unwrap_or(false)
+    ///     })
+    ///     .push(Router::new().get(stable));
+    /// ```
+    ///
+    /// The `predicate` is checked before the other filters of the child, if it returns false, the
+    /// request falls through to the next routers.
+    #[inline]
+    pub fn push_when<F>(mut self, mut router: Router, predicate: F) -> Self
+    where
+        F: Fn(&Request) -> bool + Send + Sync + 'static,
+    {
+        router.filters.insert(0, Box::new(filters::when(predicate)));
+        self.routers.push(router);
+        self
+    }
+    /// Push a child router with `path` and the boxed `handler` as its goal.
+    ///
+    /// It is useful when the handlers are only known at runtime, for example loaded from plugins
+    /// or configuration:
+    ///
+    /// ```
+    /// # use salvo_core::prelude::*;
+    /// # #[handler] async fn list_todos() {}
+    /// # #[handler] async fn list_users() {}
+    /// let plugins: Vec<(&str, Box<dyn Handler>)> = vec![
+    ///     ("todos", Box::new(list_todos)),
+    ///     ("users", Box::new(list_users)),
+    /// ];
+    /// let router = plugins
+    ///     .into_iter()
+    ///     .fold(Router::new(), |router, (path, handler)| router.push_boxed(path, handler));
+    /// ```
+    ///
+    /// A `Box<dyn Handler>` can also be passed to [`Router::goal`], [`Router::get`] and the other
+    /// methods directly, since it implements [`Handler`] too.
+    #[inline]
+    pub fn push_boxed(mut self, path: impl Into<String>, handler: Box<dyn Handler>) -> Self {
+        let mut router = Router::with_path(path);
+        router.goal = Some(Arc::from(handler));
+        self.routers.push(router);
+        self
+    }
+    /// Push a child router with `path`, and build it with `build` inline.
+    ///
+    /// It is a shortcut of `push(build(Router::with_path(path)))`, so the hoops and handlers of
+    /// a subtree are set without another level of nesting:
+    ///
+    /// ```
+    /// # use salvo_core::prelude::*;
+    /// # #[handler] async fn auth() {}
+    /// # #[handler] async fn list_todos() {}
+    /// # #[handler] async fn create_todo() {}
+    /// # #[handler] async fn delete_todo() {}
+    /// let router = Router::new().scope("api/todos", |todos| {
+    ///     todos
+    ///         .hoop(auth)
+    ///         .get(list_todos)
+    ///         .post(create_todo)
+    ///         .scope("{id}", |todo| todo.delete(delete_todo))
+    /// });
+    /// ```
+    ///
+    /// The hoops added in `build` only run for the requests matched by the child router.
+    #[inline]
+    pub fn scope<F>(self, path: impl Into<String>, build: F) -> Self
+    where
+        F: FnOnce(Router) -> Router,
+    {
+        self.push(build(Router::with_path(path)))
+    }
     /// Append all routers in a Vec as children of current router.
     #[inline]
     pub fn append(mut self, others: &mut Vec<Router>) -> Self {
         self.routers.append(others);
         self
     }
+    /// Merge `other` into current router at the same level, unlike [`Router::push`] which mounts
+    /// it as a subtree.
+    ///
+    /// It is useful when the routes of an application are registered by many modules:
+    ///
+    /// ```
+    /// # use salvo_core::prelude::*;
+    /// # #[handler] async fn list_users() {}
+    /// # #[handler] async fn create_user() {}
+    /// # #[handler] async fn list_todos() {}
+    /// let users = Router::with_path("api").push(Router::with_path("users").get(list_users));
+    /// let admin = Router::with_path("api").push(Router::with_path("users").post(create_user));
+    /// let todos = Router::with_path("api").push(Router::with_path("todos").get(list_todos));
+    /// // Routes `GET api/users`, `POST api/users` and `GET api/todos` under one `api` router.
+    /// let router = users.merge(admin).merge(todos);
+    /// ```
+    ///
+    /// The children with the same filters, compared by their [`Debug`] output, are merged
+    /// recursively, and the other children of `other` are appended after the children of current
+    /// router. If the filters of `other` are not the same as current router's, `other` is merged
+    /// as a child of current router.
+    ///
+    /// Only the value filters, like the path, method, scheme, host, port, content type and accept
+    /// filters, are compared. A router with any other filter, like the closures added by
+    /// [`Router::filter_fn`] or [`filters::when`], is never merged with another one.
+    ///
+    /// The hoops and the catcher of a router only apply to its own routes, so a router with hoops
+    /// or a catcher is kept as a whole child without filters instead of being merged, and the
+    /// routes in it are matched after the routes merged before it. Add the hoops shared by all
+    /// routes after merging.
+    ///
+    /// # Panics
+    ///
+    /// If both routers have a goal at the same level, for example both handle `GET api/users`.
+    /// The conflicts inside the routers kept as a whole are not detected, the first matched wins.
+    pub fn merge(mut self, other: Router) -> Self {
+        match (filters_key(&self.filters), filters_key(&other.filters)) {
+            (Some(key), Some(other_key)) if key == other_key => self.merge_contents(other, &key),
+            _ => self.merge_child(other, ""),
+        }
+        self
+    }
+    fn merge_contents(&mut self, mut other: Router, at: &str) {
+        if self.goal.is_some() && other.goal.is_some() {
+            panic!("conflicting goals when merging routers at `{at}`");
+        }
+        if !self.hoops.is_empty() || self.catcher.is_some() {
+            let mut scoped = Router::new();
+            scoped.routers = std::mem::take(&mut self.routers);
+            scoped.hoops = std::mem::take(&mut self.hoops);
+            scoped.goal = self.goal.take();
+            scoped.catcher = self.catcher.take();
+            self.routers.push(scoped);
+        }
+        if !other.hoops.is_empty() || other.catcher.is_some() {
+            other.filters.clear();
+            self.routers.push(other);
+            return;
+        }
+        if other.goal.is_some() {
+            self.goal = other.goal;
+        }
+        for child in other.routers {
+            self.merge_child(child, at);
+        }
+    }
+    fn merge_child(&mut self, child: Router, at: &str) {
+        let Some(key) = filters_key(&child.filters) else {
+            self.routers.push(child);
+            return;
+        };
+        if let Some(existing) = self
+            .routers
+            .iter_mut()
+            .find(|router| filters_key(&router.filters).as_ref() == Some(&key))
+        {
+            let at = if at.is_empty() {
+                key
+            } else {
+                format!("{at} {key}")
+            };
+            existing.merge_contents(child, &at);
+        } else {
+            self.routers.push(child);
+        }
+    }
 
     /// Add a handler as middleware, it will run the handler in current router or it's descendants
     /// handle the request.
@@ -157,6 +334,18 @@ impl Router {
         Router::new().hoop(hoop)
     }
 
+    /// Add several handlers as middlewares at once, they are executed in the order of the iterator.
+    ///
+    /// View [`Router::extend_hoops`] for more details.
+    #[inline]
+    pub fn with_hoops<I>(hoops: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Handler,
+    {
+        Router::new().extend_hoops(hoops)
+    }
+
     /// Add a handler as middleware, it will run the handler in current router or it's descendants
     /// handle the request. This middleware only effective when the filter return true.
     #[inline]
@@ -176,6 +365,58 @@ impl Router {
         self
     }
 
+    /// Insert a handler as middleware at the beginning of current router's middlewares, so it runs before the
+    /// middlewares already added to current router, while [`Router::hoop`] adds it at the end:
+    ///
+    /// ```
+    /// # use salvo_core::prelude::*;
+    /// # #[handler] async fn request_id() {}
+    /// # #[handler] async fn check_authed() {}
+    /// # #[handler] async fn list_writers() {}
+    /// // Runs `request_id` first, then `check_authed`.
+    /// let router = Router::with_path("writers").hoop(check_authed).get(list_writers);
+    /// let router = router.unshift_hoop(request_id);
+    /// ```
+    ///
+    /// It only changes the order in current router, the middlewares of the outer routers and of
+    /// [`Service`](crate::Service) still run before it, use [`Service::unshift_hoop`](crate::Service::unshift_hoop)
+    /// to run a middleware before all of them.
+    ///
+    /// View [module level documentation](super#execution-order) for the execution order of middlewares.
+    #[inline]
+    pub fn unshift_hoop<H: Handler>(mut self, hoop: H) -> Self {
+        self.hoops.insert(0, Arc::new(hoop));
+        self
+    }
+
+    /// Add several handlers as middlewares at once, it is the same as calling [`Router::hoop`] for each of them
+    /// in the order of the iterator. Box the handlers to add middlewares of different types:
+    ///
+    /// ```
+    /// # use salvo_core::prelude::*;
+    /// # #[handler] async fn check_authed() {}
+    /// # #[handler] async fn add_header() {}
+    /// # #[handler] async fn list_writers() {}
+    /// Router::with_path("writers")
+    ///     .extend_hoops([Box::new(check_authed) as Box<dyn Handler>, Box::new(add_header)])
+    ///     .get(list_writers);
+    /// ```
+    ///
+    /// View [module level documentation](super#execution-order) for the execution order of middlewares.
+    #[inline]
+    pub fn extend_hoops<I>(mut self, hoops: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Handler,
+    {
+        self.hoops.extend(
+            hoops
+                .into_iter()
+                .map(|hoop| Arc::new(hoop) as Arc<dyn Handler>),
+        );
+        self
+    }
+
     /// Add a handler as middleware, it will run the handler in current router or it's descendants
     /// handle the request. This middleware only effective when the filter return true.
     #[inline]
@@ -247,6 +488,35 @@ impl Router {
         self
     }
 
+    /// Set the catcher of current router, it catches the errors of the requests handled by current router or it's
+    /// descendants instead of the catcher of [`Service`](crate::Service):
+    ///
+    /// ```
+    /// # use salvo_core::prelude::*;
+    /// # use salvo_core::catcher::Catcher;
+    /// # #[handler] async fn list_todos() {}
+    /// # #[handler] async fn index() {}
+    /// #[handler]
+    /// async fn problem_json(res: &mut Response, ctrl: &mut FlowCtrl) {
+    ///     let status = res.status_code.unwrap_or(StatusCode::NOT_FOUND);
+    ///     res.add_header("content-type", "application/problem+json", true).ok();
+    ///     res.write_body(format!(r#"{{"status":{}}}"#, status.as_u16())).ok();
+    ///     ctrl.skip_rest();
+    /// }
+    ///
+    /// let router = Router::new()
+    ///     .get(index)
+    ///     .push(Router::with_path("api").catch(Catcher::default().hoop(problem_json)).get(list_todos));
+    /// let service = Service::new(router).catcher(Catcher::default());
+    /// ```
+    ///
+    /// View [module level documentation](super#catchers) for the precedence of the catchers.
+    #[inline]
+    pub fn catch(mut self, catcher: impl Into<Arc<Catcher>>) -> Self {
+        self.catcher = Some(catcher.into());
+        self
+    }
+
     /// When you want write router chain, this function will be useful,
     /// You can write your custom logic in FnOnce.
     #[inline]
@@ -358,6 +628,29 @@ const SYMBOL_DOWN: &str = "│";
 const SYMBOL_TEE: &str = "├";
 const SYMBOL_ELL: &str = "└";
 const SYMBOL_RIGHT: &str = "─";
+// The key for merging the routers, it is `None` if any filter can not be compared by its `Debug`
+// output, like the closure based filters, which all print the same.
+fn filters_key(filters: &[Box<dyn Filter>]) -> Option<String> {
+    let comparable = [
+        TypeId::of::<PathFilter>(),
+        TypeId::of::<MethodFilter>(),
+        TypeId::of::<SchemeFilter>(),
+        TypeId::of::<HostFilter>(),
+        TypeId::of::<PortFilter>(),
+        TypeId::of::<ContentTypeFilter>(),
+        TypeId::of::<AcceptFilter>(),
+    ];
+    filters
+        .iter()
+        .map(|filter| {
+            comparable
+                .contains(&Filter::type_id(&**filter))
+                .then(|| format!("{filter:?}"))
+        })
+        .collect::<Option<Vec<_>>>()
+        .map(|keys| keys.join(" "))
+}
+
 impl Debug for Router {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         fn print(f: &mut Formatter, prefix: &str, last: bool, router: &Router) -> fmt::Result {
@@ -416,9 +709,9 @@ impl Debug for Router {
 #[cfg(test)]
 mod tests {
     use super::{PathState, Router};
-    use crate::Response;
     use crate::handler;
-    use crate::test::TestClient;
+    use crate::test::{ResponseExt, TestClient};
+    use crate::{Response, Service};
 
     #[handler]
     async fn fake_handler(_res: &mut Response) {}
@@ -685,4 +978,302 @@ mod tests {
         assert!(matched.is_some());
         assert_eq!(path_state.params["p"], "a/b/c");
     }
+
+    #[tokio::test]
+    async fn test_router_push_when() {
+        #[handler]
+        async fn canary() -> &'static str {
+            "canary"
+        }
+        #[handler]
+        async fn stable() -> &'static str {
+            "stable"
+        }
+        let router = Router::with_path("todos")
+            .push_when(Router::new().get(canary), |req| {
+                req.query::<bool>("beta").unwrap_or(false)
+            })
+            .push(Router::new().get(stable));
+        let service = Service::new(router);
+
+        let content = TestClient::get("http://127.0.0.1:5800/todos?beta=true")
+            .send(&service)
+            .await
+            .take_string()
+            .await
+            .unwrap();
+        assert_eq!(content, "canary");
+        let content = TestClient::get("http://127.0.0.1:5800/todos?beta=false")
+            .send(&service)
+            .await
+            .take_string()
+            .await
+            .unwrap();
+        assert_eq!(content, "stable");
+        let content = TestClient::get("http://127.0.0.1:5800/todos")
+            .send(&service)
+            .await
+            .take_string()
+            .await
+            .unwrap();
+        assert_eq!(content, "stable");
+    }
+
+    #[tokio::test]
+    async fn test_router_push_boxed() {
+        use crate::Handler;
+
+        #[handler]
+        async fn todos() -> &'static str {
+            "todos"
+        }
+        #[handler]
+        async fn users() -> &'static str {
+            "users"
+        }
+        let handlers: Vec<(&str, Box<dyn Handler>)> =
+            vec![("todos", Box::new(todos)), ("users", Box::new(users))];
+        let router = handlers
+            .into_iter()
+            .fold(Router::new(), |router, (path, handler)| {
+                router.push_boxed(path, handler)
+            })
+            .push(Router::with_path("boxed").get(Box::new(todos) as Box<dyn Handler>));
+        let service = Service::new(router);
+
+        for (path, expected) in [("todos", "todos"), ("users", "users"), ("boxed", "todos")] {
+            let content = TestClient::get(format!("http://127.0.0.1:5800/{path}"))
+                .send(&service)
+                .await
+                .take_string()
+                .await
+                .unwrap();
+            assert_eq!(content, expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_router_scope() {
+        use crate::http::{Method, StatusCode};
+        use crate::test::RequestBuilder;
+        use crate::{FlowCtrl, Request};
+
+        #[handler]
+        async fn auth(req: &mut Request, res: &mut Response, ctrl: &mut FlowCtrl) {
+            if req.header::<String>("authorization").is_none() {
+                res.status_code(StatusCode::UNAUTHORIZED);
+                ctrl.skip_rest();
+            }
+        }
+        #[handler]
+        async fn show(req: &mut Request) -> String {
+            format!("{} {:?}", req.method(), req.param::<u32>("id"))
+        }
+        let router = Router::new()
+            .scope("api/todos", |todos| {
+                todos
+                    .hoop(auth)
+                    .get(show)
+                    .post(show)
+                    .scope("{id}", |todo| todo.delete(show))
+            })
+            .scope("public", |public| public.get(show));
+        let service = Service::new(router);
+
+        let access = |method: Method, path: &str, authorized: bool| {
+            let mut client = RequestBuilder::new(format!("http://127.0.0.1:5800/{path}"), method);
+            if authorized {
+                client = client.add_header("authorization", "Bearer token", true);
+            }
+            client.send(&service)
+        };
+        let content = access(Method::POST, "api/todos", true)
+            .await
+            .take_string()
+            .await
+            .unwrap();
+        assert_eq!(content, "POST None");
+        let content = access(Method::DELETE, "api/todos/5", true)
+            .await
+            .take_string()
+            .await
+            .unwrap();
+        assert_eq!(content, "DELETE Some(5)");
+        let res = access(Method::DELETE, "api/todos/5", false).await;
+        assert_eq!(res.status_code, Some(StatusCode::UNAUTHORIZED));
+        let content = access(Method::GET, "public", false)
+            .await
+            .take_string()
+            .await
+            .unwrap();
+        assert_eq!(content, "GET None");
+    }
+
+    #[tokio::test]
+    async fn test_hoops_execution_order() {
+        use crate::{Depot, FlowCtrl, Handler, Request, async_trait};
+
+        struct Trace(&'static str);
+        #[async_trait]
+        impl Handler for Trace {
+            async fn handle(
+                &self,
+                req: &mut Request,
+                depot: &mut Depot,
+                res: &mut Response,
+                ctrl: &mut FlowCtrl,
+            ) {
+                ctrl.call_next(req, depot, res).await;
+                let body = depot.get::<String>("trace").cloned().unwrap_or_default();
+                depot.insert("trace", format!("{body} after:{}", self.0));
+                let body = depot.get::<String>("trace").cloned().unwrap_or_default();
+                res.body(body);
+            }
+        }
+        #[handler]
+        async fn before(depot: &mut Depot) {
+            let body = depot.get::<String>("trace").cloned().unwrap_or_default();
+            depot.insert("trace", format!("{body}|"));
+        }
+        #[handler]
+        async fn goal(depot: &mut Depot) {
+            let body = depot.get::<String>("trace").cloned().unwrap_or_default();
+            depot.insert("trace", format!("{body}goal"));
+        }
+
+        let router = Router::with_hoops([Trace("r1"), Trace("r2")]).push(
+            Router::with_path("inner")
+                .extend_hoops([Box::new(Trace("i1")) as Box<dyn Handler>, Box::new(before)])
+                .hoop(Trace("i2"))
+                .get(goal),
+        );
+        let service = Service::new(router).hoop(Trace("s"));
+        let content = TestClient::get("http://127.0.0.1:5800/inner")
+            .send(&service)
+            .await
+            .take_string()
+            .await
+            .unwrap();
+        assert_eq!(content, "|goal after:i2 after:i1 after:r2 after:r1 after:s");
+    }
+
+    #[tokio::test]
+    async fn test_router_unshift_hoop() {
+        use crate::{Depot, FlowCtrl, Handler, Request, async_trait};
+
+        struct Mark(&'static str);
+        #[async_trait]
+        impl Handler for Mark {
+            async fn handle(
+                &self,
+                _req: &mut Request,
+                depot: &mut Depot,
+                _res: &mut Response,
+                _ctrl: &mut FlowCtrl,
+            ) {
+                let trace = depot.get::<String>("trace").cloned().unwrap_or_default();
+                depot.insert("trace", format!("{trace}{} ", self.0));
+            }
+        }
+        #[handler]
+        async fn goal(depot: &mut Depot) -> String {
+            let trace = depot.get::<String>("trace").cloned().unwrap_or_default();
+            format!("{trace}goal")
+        }
+
+        let router = Router::new().hoop(Mark("r")).push(
+            Router::with_path("inner")
+                .hoop(Mark("i1"))
+                .hoop(Mark("i2"))
+                .get(goal)
+                .unshift_hoop(Mark("i0")),
+        );
+        let service = Service::new(router)
+            .hoop(Mark("s1"))
+            .unshift_hoop(Mark("s0"));
+        let content = TestClient::get("http://127.0.0.1:5800/inner")
+            .send(&service)
+            .await
+            .take_string()
+            .await
+            .unwrap();
+        assert_eq!(content, "s0 s1 r i0 i1 i2 goal");
+    }
+
+    #[tokio::test]
+    async fn test_router_merge() {
+        use crate::FlowCtrl;
+        use crate::http::StatusCode;
+
+        #[handler]
+        async fn list_users() -> &'static str {
+            "list users"
+        }
+        #[handler]
+        async fn create_user() -> &'static str {
+            "create user"
+        }
+        #[handler]
+        async fn list_todos() -> &'static str {
+            "list todos"
+        }
+        #[handler]
+        async fn auth(res: &mut Response, ctrl: &mut FlowCtrl) {
+            res.status_code(StatusCode::UNAUTHORIZED);
+            ctrl.skip_rest();
+        }
+
+        let users = Router::with_path("api").push(Router::with_path("users").get(list_users));
+        let admin = Router::with_path("api")
+            .push(Router::with_path("users").post(create_user))
+            .push(Router::with_path("admin").hoop(auth).get(list_todos));
+        let todos = Router::new()
+            .hoop(auth)
+            .push(Router::with_path("todos").get(list_todos));
+        let router = users.merge(admin).merge(todos);
+        assert_eq!(router.routers().len(), 3);
+        let service = Service::new(router);
+
+        let mut res = TestClient::get("http://127.0.0.1:5800/api/users")
+            .send(&service)
+            .await;
+        assert_eq!(res.take_string().await.unwrap(), "list users");
+        let mut res = TestClient::post("http://127.0.0.1:5800/api/users")
+            .send(&service)
+            .await;
+        assert_eq!(res.take_string().await.unwrap(), "create user");
+        let res = TestClient::get("http://127.0.0.1:5800/api/admin")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::UNAUTHORIZED));
+        let res = TestClient::get("http://127.0.0.1:5800/api/todos")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::UNAUTHORIZED));
+
+        let result = std::panic::catch_unwind(|| {
+            Router::with_path("users")
+                .get(list_users)
+                .merge(Router::with_path("users").get(list_todos))
+        });
+        assert!(result.is_err());
+
+        let beta = Router::with_path("users")
+            .filter_fn(|req, _| req.query::<bool>("beta").unwrap_or(false))
+            .get(list_todos);
+        let stable = Router::with_path("users")
+            .filter_fn(|req, _| !req.query::<bool>("beta").unwrap_or(false))
+            .get(list_users);
+        let router = Router::new().push(beta).merge(Router::new().push(stable));
+        assert_eq!(router.routers().len(), 2);
+        let service = Service::new(router);
+        let mut res = TestClient::get("http://127.0.0.1:5800/users?beta=true")
+            .send(&service)
+            .await;
+        assert_eq!(res.take_string().await.unwrap(), "list todos");
+        let mut res = TestClient::get("http://127.0.0.1:5800/users")
+            .send(&service)
+            .await;
+        assert_eq!(res.take_string().await.unwrap(), "list users");
+    }
 }
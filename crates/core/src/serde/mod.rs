@@ -8,13 +8,15 @@ use serde::de::{
 };
 
 mod request;
-pub use request::from_request;
+pub use request::{check_field, downcast_parse_error, from_request, merge_parse_errors};
 mod cow_value;
 use cow_value::CowValue;
 mod vec_value;
 use vec_value::VecValue;
 mod flat_value;
 use flat_value::FlatValue;
+mod nested_value;
+use nested_value::NestedValue;
 
 #[inline]
 pub fn from_str_map<'de, I, T, K, V>(input: I) -> Result<T, ValError>
@@ -58,6 +60,16 @@ where
     T::deserialize(VecValue(iter))
 }
 
+#[inline]
+pub(crate) fn from_nested_queries<'de, T>(
+    queries: &'de multimap::MultiMap<String, String>,
+) -> Result<T, ValError>
+where
+    T: Deserialize<'de>,
+{
+    T::deserialize(NestedValue::from_queries(queries))
+}
+
 #[inline]
 pub fn from_str_val<'de, I, T>(input: I) -> Result<T, ValError>
 where
@@ -0,0 +1,319 @@
+use indexmap::IndexMap;
+use multimap::MultiMap;
+use serde::de::value::{Error as ValError, MapDeserializer, SeqDeserializer};
+use serde::de::{Deserializer, Error as DeError, IntoDeserializer, Visitor};
+use serde::forward_to_deserialize_any;
+
+use super::{CowValue, FlatValue};
+
+macro_rules! forward_leaf_value {
+    ($($method:ident,)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+                where V: Visitor<'de>
+            {
+                match self {
+                    Self::Leaf(values) => FlatValue(values).$method(visitor),
+                    Self::Map(_) => self.deserialize_any(visitor),
+                }
+            }
+        )*
+    }
+}
+
+/// Value of the url queries with nested keys, like `filter[status]=done`, `filter.status=done` or `ids[0]=1`.
+///
+/// The keys without nested segments are leaves, they are deserialized the same as the flat queries, the keys with
+/// the empty brackets like `tags[]=a&tags[]=b` are appended to the leaf values.
+#[derive(Debug)]
+pub(super) enum NestedValue<'de> {
+    Leaf(Vec<CowValue<'de>>),
+    Map(IndexMap<&'de str, NestedValue<'de>>),
+}
+
+impl<'de> NestedValue<'de> {
+    /// Build the value of all queries, the first segments of the keys are the keys of the returned map.
+    pub(super) fn from_queries(queries: &'de MultiMap<String, String>) -> Self {
+        let mut root = Self::Map(IndexMap::new());
+        for (key, values) in queries.iter_all() {
+            let segments = split_key(key);
+            for value in values {
+                root.insert(&segments, value);
+            }
+        }
+        root
+    }
+
+    /// Build the value of the queries whose first segments of the keys are `name`.
+    pub(super) fn from_field_queries(
+        queries: &'de MultiMap<String, String>,
+        name: &str,
+    ) -> Option<Self> {
+        let mut field = None;
+        for (key, values) in queries.iter_all() {
+            let segments = split_key(key);
+            if segments[0] == name {
+                let field = field.get_or_insert_with(|| Self::Leaf(vec![]));
+                for value in values {
+                    field.insert(&segments[1..], value);
+                }
+            }
+        }
+        field
+    }
+
+    fn insert(&mut self, segments: &[&'de str], value: &'de str) {
+        match segments.split_first() {
+            None | Some((&"", [])) => {
+                if let Self::Leaf(values) = self {
+                    values.push(CowValue(value.into()));
+                }
+            }
+            Some((segment, rest)) => {
+                if matches!(self, Self::Leaf(values) if values.is_empty()) {
+                    *self = Self::Map(IndexMap::new());
+                }
+                // The nested keys of a key which already has a value are ignored, like `a[b]` of `a=1&a[b]=2`.
+                if let Self::Map(map) = self {
+                    map.entry(*segment)
+                        .or_insert_with(|| Self::Leaf(vec![]))
+                        .insert(rest, value);
+                }
+            }
+        }
+    }
+}
+
+// Split `a[b].c[]` into `["a", "b", "c", ""]`.
+fn split_key(key: &str) -> Vec<&str> {
+    let head_end = key.find(['[', '.']).unwrap_or(key.len());
+    let mut segments = vec![&key[..head_end]];
+    let mut rest = &key[head_end..];
+    while !rest.is_empty() {
+        if let Some(inner) = rest.strip_prefix('[') {
+            match inner.find(']') {
+                Some(end) => {
+                    segments.push(&inner[..end]);
+                    rest = &inner[end + 1..];
+                }
+                None => {
+                    segments.push(inner);
+                    break;
+                }
+            }
+        } else {
+            let inner = rest.strip_prefix('.').unwrap_or(rest);
+            let end = inner.find(['[', '.']).unwrap_or(inner.len());
+            segments.push(&inner[..end]);
+            rest = &inner[end..];
+        }
+    }
+    segments
+}
+
+impl<'de> IntoDeserializer<'de> for NestedValue<'de> {
+    type Deserializer = Self;
+
+    #[inline]
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+impl<'de> Deserializer<'de> for NestedValue<'de> {
+    type Error = ValError;
+
+    #[inline]
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Self::Leaf(values) => FlatValue(values).deserialize_any(visitor),
+            Self::Map(map) => visitor.visit_map(MapDeserializer::new(
+                map.into_iter()
+                    .map(|(key, value)| (CowValue(key.into()), value)),
+            )),
+        }
+    }
+
+    #[inline]
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    #[inline]
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Self::Leaf(values) => FlatValue(values).deserialize_seq(visitor),
+            Self::Map(map) => {
+                // The items are sorted by the indices, like `ids[1]=2&ids[0]=1`, the other keys keep their order.
+                let mut items = map.into_iter().collect::<Vec<_>>();
+                items.sort_by_key(|(key, _)| key.parse::<usize>().unwrap_or(usize::MAX));
+                visitor.visit_seq(SeqDeserializer::new(
+                    items.into_iter().map(|(_, value)| value),
+                ))
+            }
+        }
+    }
+
+    #[inline]
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    #[inline]
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    #[inline]
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    #[inline]
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Self::Leaf(values) => FlatValue(values).deserialize_enum(name, variants, visitor),
+            Self::Map(_) => Err(DeError::custom("expected unit variant")),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        char
+        str
+        string
+        unit
+        bytes
+        byte_buf
+        unit_struct
+        struct
+        identifier
+        ignored_any
+        map
+    }
+
+    forward_leaf_value! {
+        deserialize_bool,
+        deserialize_u8,
+        deserialize_u16,
+        deserialize_u32,
+        deserialize_u64,
+        deserialize_i8,
+        deserialize_i16,
+        deserialize_i32,
+        deserialize_i64,
+        deserialize_f32,
+        deserialize_f64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use multimap::MultiMap;
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[test]
+    fn test_split_key() {
+        assert_eq!(split_key("filter"), ["filter"]);
+        assert_eq!(split_key("filter[status]"), ["filter", "status"]);
+        assert_eq!(split_key("filter.owner.name"), ["filter", "owner", "name"]);
+        assert_eq!(split_key("items[0].tags[]"), ["items", "0", "tags", ""]);
+        assert_eq!(split_key("filter[status"), ["filter", "status"]);
+    }
+
+    #[test]
+    fn test_de_nested_value() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Owner {
+            name: String,
+        }
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Filter {
+            status: String,
+            tags: Vec<String>,
+            owner: Owner,
+            ids: Vec<u64>,
+            priority: Option<u8>,
+        }
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Query {
+            filter: Filter,
+            extra: HashMap<String, String>,
+            limit: usize,
+        }
+
+        let mut queries = MultiMap::new();
+        for (key, value) in [
+            ("filter[status]", "done"),
+            ("filter[tags][]", "home"),
+            ("filter[tags][]", "work"),
+            ("filter.owner.name", "jobs"),
+            ("filter[ids][1]", "20"),
+            ("filter[ids][0]", "10"),
+            ("extra[color]", "red"),
+            ("limit", "10"),
+        ] {
+            queries.insert(key.to_owned(), value.to_owned());
+        }
+        let query = Query::deserialize(NestedValue::from_queries(&queries)).unwrap();
+        assert_eq!(
+            query,
+            Query {
+                filter: Filter {
+                    status: "done".into(),
+                    tags: vec!["home".into(), "work".into()],
+                    owner: Owner {
+                        name: "jobs".into()
+                    },
+                    ids: vec![10, 20],
+                    priority: None,
+                },
+                extra: HashMap::from([("color".into(), "red".into())]),
+                limit: 10,
+            }
+        );
+
+        let filter = NestedValue::from_field_queries(&queries, "extra").unwrap();
+        let extra = HashMap::<String, String>::deserialize(filter).unwrap();
+        assert_eq!(extra["color"], "red");
+        assert!(NestedValue::from_field_queries(&queries, "missing").is_none());
+    }
+}
@@ -1,5 +1,7 @@
+use std::any::Any;
 use std::borrow::Cow;
 use std::collections::HashMap;
+use std::marker::PhantomData;
 
 use indexmap::IndexMap;
 use multimap::MultiMap;
@@ -9,13 +11,13 @@ use serde::forward_to_deserialize_any;
 use serde_json::value::RawValue;
 
 use crate::Request;
-use crate::extract::Metadata;
 use crate::extract::metadata::{Field, Source, SourceFrom, SourceParser};
-use crate::http::ParseError;
+use crate::extract::{FieldError, FieldErrors, Metadata};
 use crate::http::form::FormData;
 use crate::http::header::HeaderMap;
+use crate::http::{ParseError, is_json};
 
-use super::{CowValue, FlatValue, VecValue};
+use super::{CowValue, FlatValue, NestedValue, VecValue};
 
 pub async fn from_request<'de, T>(
     req: &'de mut Request,
@@ -32,7 +34,7 @@ where
                     let _ = req.form_data().await;
                 }
             }
-            mime::JSON => {
+            _ if is_json(&ctype) => {
                 if metadata.has_body_required() {
                     let _ = req.payload().await;
                 }
@@ -40,7 +42,129 @@ where
             _ => {}
         }
     }
-    Ok(T::deserialize(RequestDeserializer::new(req, metadata)?)?)
+    match T::deserialize(RequestDeserializer::new(req, metadata)?) {
+        Ok(data) => Ok(data),
+        Err(e) => {
+            let mut errors = FieldErrors::new();
+            collect_field_errors(req, metadata, &mut errors);
+            if errors.is_empty() {
+                Err(e.into())
+            } else {
+                Err(ParseError::Fields(errors))
+            }
+        }
+    }
+}
+
+fn collect_field_errors<'de>(req: &'de Request, metadata: &'de Metadata, errors: &mut FieldErrors) {
+    for field in &metadata.fields {
+        if field.flatten {
+            if let Some(metadata) = field.metadata {
+                collect_field_errors(req, metadata, errors);
+            }
+        } else if let Some(checker) = field.checker {
+            if let Some(error) = checker(req, metadata, field) {
+                errors.push(error);
+            }
+        }
+    }
+}
+
+/// Check if the field can be extracted from request as type `T`.
+///
+/// This function used in macros internal.
+#[doc(hidden)]
+pub fn check_field<'de, T>(
+    req: &'de Request,
+    metadata: &'de Metadata,
+    field: &'de Field,
+    has_default: bool,
+) -> Option<FieldError>
+where
+    T: Deserialize<'de>,
+{
+    let mut deserializer = match RequestDeserializer::new(req, metadata) {
+        Ok(deserializer) => deserializer,
+        Err(e) => return Some(FieldError::new(field.decl_name, None, e.to_string())),
+    };
+    let field_name = deserializer.field_name(field).into_owned();
+    if deserializer.fill_value(field) {
+        let source = deserializer.field_source.map(|s| s.from);
+        deserializer
+            .deserialize_value(PhantomData::<T>)
+            .err()
+            .map(|e| FieldError::new(field_name, source, e.to_string()))
+    } else if has_default {
+        None
+    } else {
+        T::deserialize(MissingFieldDeserializer)
+            .err()
+            .map(|e| FieldError::new(field_name, None, e.to_string()))
+    }
+}
+
+/// Try to downcast the error returned by extractor to [`ParseError`].
+///
+/// This function used in macros internal.
+#[doc(hidden)]
+pub fn downcast_parse_error<E>(error: E) -> Result<ParseError, E>
+where
+    E: Any,
+{
+    let mut error = Some(error);
+    if let Some(error) = (&mut error as &mut dyn Any).downcast_mut::<Option<ParseError>>() {
+        if let Some(error) = error.take() {
+            return Ok(error);
+        }
+    }
+    Err(error.expect("error should exist"))
+}
+
+/// Merge errors of all extractors into one [`ParseError`].
+///
+/// If there is only one error, it is returned directly, otherwise all errors are merged into [`ParseError::Fields`].
+///
+/// This function used in macros internal.
+#[doc(hidden)]
+pub fn merge_parse_errors(mut errors: Vec<(&str, ParseError)>) -> Option<ParseError> {
+    if errors.len() <= 1 {
+        return errors.pop().map(|(_, e)| e);
+    }
+    let mut fields = FieldErrors::new();
+    for (arg, error) in errors {
+        if let ParseError::Fields(errors) = error {
+            fields.extend(errors);
+        } else {
+            fields.push(FieldError::new(arg, None, error.to_string()));
+        }
+    }
+    Some(ParseError::Fields(fields))
+}
+
+/// Deserializer used for the field which is not found in request.
+struct MissingFieldDeserializer;
+impl<'de> de::Deserializer<'de> for MissingFieldDeserializer {
+    type Error = ValError;
+
+    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(ValError::custom("missing field"))
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_none()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -76,6 +200,7 @@ pub(crate) struct RequestDeserializer<'de> {
     field_source: Option<&'de Source>,
     field_str_value: Option<&'de str>,
     field_vec_value: Option<Vec<CowValue<'de>>>,
+    field_nested_value: Option<NestedValue<'de>>,
 }
 
 impl<'de> RequestDeserializer<'de> {
@@ -92,7 +217,7 @@ impl<'de> RequestDeserializer<'de> {
                     mime::WWW_FORM_URLENCODED | mime::FORM_DATA => {
                         payload = request.form_data.get().map(Payload::FormData);
                     }
-                    mime::JSON => {
+                    _ if is_json(&ctype) => {
                         if let Some(data) = request.payload.get() {
                             if !data.is_empty() {
                                 // https://github.com/serde-rs/json/issues/903
@@ -125,11 +250,15 @@ impl<'de> RequestDeserializer<'de> {
             field_source: None,
             field_str_value: None,
             field_vec_value: None,
+            field_nested_value: None,
         })
     }
 
     fn real_parser(&self, source: &Source) -> SourceParser {
         let mut parser = source.parser;
+        if parser == SourceParser::Nested && source.from != SourceFrom::Query {
+            parser = SourceParser::Smart;
+        }
         if parser == SourceParser::Smart {
             if source.from == SourceFrom::Body {
                 if let Some(payload) = &self.payload {
@@ -174,6 +303,7 @@ impl<'de> RequestDeserializer<'de> {
                 field_source: None,
                 field_str_value: None,
                 field_vec_value: None,
+                field_nested_value: None,
             })
         } else {
             let source = self
@@ -191,6 +321,8 @@ impl<'de> RequestDeserializer<'de> {
 
                 seed.deserialize(&mut value)
                     .map_err(|_| ValError::custom("parse value error"))
+            } else if let Some(value) = self.field_nested_value.take() {
+                seed.deserialize(value)
             } else if let Some(value) = self.field_str_value.take() {
                 seed.deserialize(CowValue(value.into()))
             } else if let Some(value) = self.field_vec_value.take() {
@@ -205,6 +337,20 @@ impl<'de> RequestDeserializer<'de> {
         }
     }
 
+    fn field_name(&self, field: &'de Field) -> Cow<'de, str> {
+        if let Some(rename) = field.rename {
+            Cow::from(rename)
+        } else if let Some(serde_rename) = field.serde_rename {
+            Cow::from(serde_rename)
+        } else if let Some(rename_all) = self.metadata.rename_all {
+            Cow::from(rename_all.apply_to_field(field.decl_name))
+        } else if let Some(serde_rename_all) = self.metadata.serde_rename_all {
+            Cow::from(serde_rename_all.apply_to_field(field.decl_name))
+        } else {
+            Cow::from(field.decl_name)
+        }
+    }
+
     #[allow(unreachable_patterns)]
     fn fill_value(&mut self, field: &'de Field) -> bool {
         if field.flatten {
@@ -220,17 +366,7 @@ impl<'de> RequestDeserializer<'de> {
             return false;
         };
 
-        let field_name = if let Some(rename) = field.rename {
-            rename
-        } else if let Some(serde_rename) = field.serde_rename {
-            serde_rename
-        } else if let Some(rename_all) = self.metadata.rename_all {
-            &*rename_all.apply_to_field(field.decl_name)
-        } else if let Some(serde_rename_all) = self.metadata.serde_rename_all {
-            &*serde_rename_all.apply_to_field(field.decl_name)
-        } else {
-            field.decl_name
-        };
+        let field_name = &*self.field_name(field);
 
         for source in sources {
             match source.from {
@@ -250,6 +386,22 @@ impl<'de> RequestDeserializer<'de> {
                         return true;
                     }
                 }
+                SourceFrom::Query if self.real_parser(source) == SourceParser::Nested => {
+                    let mut value = NestedValue::from_field_queries(self.queries, field_name);
+                    if value.is_none() {
+                        for alias in &field.aliases {
+                            value = NestedValue::from_field_queries(self.queries, alias);
+                            if value.is_some() {
+                                break;
+                            }
+                        }
+                    }
+                    if let Some(value) = value {
+                        self.field_nested_value = Some(value);
+                        self.field_source = Some(source);
+                        return true;
+                    }
+                }
                 SourceFrom::Query => {
                     let mut value = self.queries.get_vec(field_name);
                     if value.is_none() {
@@ -484,8 +636,56 @@ impl<'de> de::MapAccess<'de> for RequestDeserializer<'de> {
 mod tests {
     use serde::{Deserialize, Serialize};
 
+    use crate::extract::FieldError;
+    use crate::extract::metadata::SourceFrom;
+    use crate::http::ParseError;
     use crate::macros::Extractible;
-    use crate::test::TestClient;
+    use crate::prelude::*;
+    use crate::test::{ResponseExt, TestClient};
+
+    #[tokio::test]
+    async fn test_de_request_from_nested_query() {
+        #[derive(Deserialize, Eq, PartialEq, Debug)]
+        struct Filter {
+            status: String,
+            tags: Vec<String>,
+        }
+        #[derive(Deserialize, Extractible, Eq, PartialEq, Debug)]
+        #[salvo(extract(default_source(from = "query")))]
+        struct RequestData {
+            #[salvo(extract(source(from = "query", parse = "nested")))]
+            filter: Filter,
+            #[salvo(extract(source(from = "query", parse = "nested")))]
+            ids: Vec<u64>,
+            limit: usize,
+        }
+        let mut req = TestClient::get(
+            "http://127.0.0.1:5800/todos?filter[status]=done&filter.tags[]=home&filter.tags[]=work&ids[1]=2&ids[0]=1&limit=10",
+        )
+        .build();
+        let data: RequestData = req.extract().await.unwrap();
+        assert_eq!(
+            data,
+            RequestData {
+                filter: Filter {
+                    status: "done".into(),
+                    tags: vec!["home".into(), "work".into()],
+                },
+                ids: vec![1, 2],
+                limit: 10,
+            }
+        );
+
+        #[derive(Deserialize, Eq, PartialEq, Debug)]
+        struct Queries {
+            filter: Filter,
+            limit: usize,
+        }
+        let queries: Queries = req.parse_nested_queries().unwrap();
+        assert_eq!(queries.filter.status, "done");
+        assert_eq!(queries.limit, 10);
+        assert!(req.parse_queries::<Queries>().is_err());
+    }
 
     #[tokio::test]
     async fn test_de_request_from_query() {
@@ -610,6 +810,45 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_de_request_with_path_query_and_body() {
+        #[derive(Deserialize, Extractible, Eq, PartialEq, Debug)]
+        #[salvo(extract(default_source(from = "body")))]
+        struct RequestData {
+            #[salvo(extract(source(from = "path")))]
+            id: i64,
+            #[salvo(extract(source(from = "query")))]
+            filter: String,
+            title: String,
+            #[salvo(extract(source(from = "header"), source(from = "query")))]
+            token: String,
+        }
+        #[derive(Serialize)]
+        struct Body<'a> {
+            title: &'a str,
+            filter: &'a str,
+        }
+
+        let mut req = TestClient::post("http://127.0.0.1:5800/todos/12?filter=active&token=t2")
+            .add_header("token", "t1", true)
+            .json(&Body {
+                title: "write docs",
+                filter: "ignored",
+            })
+            .build();
+        req.params.insert("id", "12".into());
+        let data: RequestData = req.extract().await.unwrap();
+        assert_eq!(
+            data,
+            RequestData {
+                id: 12,
+                filter: "active".into(),
+                title: "write docs".into(),
+                token: "t1".into(),
+            }
+        );
+    }
+
     #[tokio::test]
     async fn test_de_request_with_json_vec() {
         #[derive(Deserialize, Extractible, Eq, PartialEq, Debug)]
@@ -657,6 +896,53 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_de_request_with_vendor_json() {
+        #[derive(Deserialize, Extractible, Eq, PartialEq, Debug)]
+        #[salvo(extract(default_source(from = "body")))]
+        struct RequestData {
+            #[salvo(extract(source(from = "query")))]
+            page: u32,
+            title: String,
+        }
+
+        let mut req = TestClient::post("http://127.0.0.1:5800/todos?page=2")
+            .raw_json(r#"{"title":"write docs"}"#)
+            .add_header(
+                "content-type",
+                "application/vnd.todo+json; charset=utf-8",
+                true,
+            )
+            .build();
+        let data: RequestData = req.extract().await.unwrap();
+        assert_eq!(
+            data,
+            RequestData {
+                page: 2,
+                title: "write docs".into(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_de_request_with_raw_value() {
+        #[derive(Deserialize, Extractible, Debug)]
+        #[salvo(extract(default_source(from = "body")))]
+        struct RequestData {
+            #[salvo(extract(source(from = "query")))]
+            target: String,
+            payload: Box<serde_json::value::RawValue>,
+        }
+
+        let payload = r#"{ "z": 1, "a": [1.0, 12345678901234567890123] }"#;
+        let mut req = TestClient::post("http://127.0.0.1:5800/relay?target=todos")
+            .raw_json(format!(r#"{{"payload":{payload}}}"#))
+            .build();
+        let data: RequestData = req.extract().await.unwrap();
+        assert_eq!(data.target, "todos");
+        assert_eq!(data.payload.get(), payload);
+    }
+
     #[tokio::test]
     async fn test_de_request_with_json_bool() {
         #[derive(Deserialize, Extractible, Eq, PartialEq, Debug)]
@@ -852,4 +1138,71 @@ mod tests {
             }
         );
     }
+
+    #[tokio::test]
+    async fn test_de_request_collect_field_errors() {
+        #[derive(Deserialize, Extractible, Debug)]
+        #[salvo(extract(default_source(from = "body")))]
+        #[allow(dead_code)]
+        struct RequestData {
+            #[salvo(extract(source(from = "param")))]
+            id: i64,
+            #[salvo(extract(source(from = "query")))]
+            page: Option<u32>,
+            title: String,
+            #[serde(default)]
+            completed: bool,
+        }
+
+        let mut req = TestClient::post("http://127.0.0.1:5800/todos/abc?page=2")
+            .json(&serde_json::json!({"completed": true}))
+            .build();
+        req.params.insert("id", "abc".into());
+        let Err(ParseError::Fields(errors)) = req.extract::<RequestData>().await else {
+            panic!("extract should failed with field errors");
+        };
+        let errors = errors.into_iter().collect::<Vec<_>>();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].field, "id");
+        assert_eq!(errors[0].source, Some(SourceFrom::Param));
+        assert_eq!(errors[1], FieldError::new("title", None, "missing field"));
+    }
+
+    #[tokio::test]
+    async fn test_handler_merge_field_errors() {
+        #[derive(Deserialize, Extractible, Debug)]
+        #[salvo(extract(default_source(from = "query")))]
+        struct Filter {
+            page: u32,
+        }
+        #[derive(Deserialize, Extractible, Debug)]
+        #[salvo(extract(default_source(from = "body")))]
+        struct Todo {
+            title: String,
+        }
+        #[handler]
+        async fn update(filter: Filter, todo: Todo) -> String {
+            format!("{}:{}", filter.page, todo.title)
+        }
+
+        let service = Service::new(Router::with_path("todos").put(update));
+        let mut res = TestClient::put("http://127.0.0.1:5800/todos?page=2")
+            .json(&serde_json::json!({"title": "docs"}))
+            .send(&service)
+            .await;
+        assert_eq!(res.take_string().await.unwrap(), "2:docs");
+
+        let mut res = TestClient::put("http://127.0.0.1:5800/todos?page=first")
+            .json(&serde_json::json!({"name": "docs"}))
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::UNPROCESSABLE_ENTITY));
+        let data = res.take_json::<serde_json::Value>().await.unwrap();
+        let errors = data["error"]["errors"].as_array().unwrap();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0]["field"], "page");
+        assert_eq!(errors[0]["source"], "query");
+        assert_eq!(errors[1]["field"], "title");
+        assert_eq!(errors[1]["source"], serde_json::Value::Null);
+    }
 }
@@ -25,7 +25,7 @@ use tokio_util::sync::CancellationToken;
 
 #[cfg(feature = "quinn")]
 use crate::conn::quinn;
-use crate::conn::{Accepted, Acceptor, Holding, HttpBuilder};
+use crate::conn::{Accepted, Acceptor, Holding, HttpBuilder, SocketAddr};
 use crate::fuse::{ArcFuseFactory, FuseFactory};
 use crate::http::{HeaderValue, HttpConnection, Version};
 use crate::Service;
@@ -104,6 +104,41 @@ pub struct Server<A> {
     rx_cmd: UnboundedReceiver<ServerCommand>,
 }
 
+impl Server<()> {
+    /// Create a new [`HttpBuilder`] to configure the HTTP protocols, then use [`HttpBuilder::build`] to create
+    /// the `Server`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use salvo_core::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let acceptor = TcpListener::new("127.0.0.1:5800").bind().await;
+    ///     let server = Server::builder()
+    ///         .http1_header_read_timeout(Duration::from_secs(10))
+    ///         .http2_max_concurrent_streams(100)
+    ///         .build(acceptor);
+    ///     server.serve(Router::new()).await;
+    /// }
+    /// ```
+    #[inline]
+    pub fn builder() -> HttpBuilder {
+        HttpBuilder::new()
+    }
+}
+
+impl HttpBuilder {
+    /// Create a new [`Server`] with [`Acceptor`] and this builder.
+    #[inline]
+    pub fn build<A: Acceptor + Send>(self, acceptor: A) -> Server<A> {
+        Server::with_http_builder(acceptor, self)
+    }
+}
+
 impl<A: Acceptor + Send> Server<A> {
     /// Create new `Server` with [`Acceptor`].
     ///
@@ -178,6 +213,15 @@ impl<A: Acceptor + Send> Server<A> {
         self.acceptor.holdings()
     }
 
+    /// Get all local addresses this server is bound to.
+    ///
+    /// It is useful to find the actual port after binding to port `0`. Joined acceptors return the addresses of all
+    /// the inner acceptors, in the order they are joined.
+    #[inline]
+    pub fn local_addrs(&self) -> Vec<SocketAddr> {
+        self.holdings().iter().map(|h| h.local_addr.clone()).collect()
+    }
+
     cfg_feature! {
         #![feature = "http1"]
         /// Use this function to set http1 protocol.
@@ -466,6 +510,137 @@ mod tests {
         assert!(result.contains("<code>404</code>"));
     }
 
+    #[tokio::test]
+    async fn test_server_builder() {
+        use std::time::Duration;
+
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::TcpStream;
+
+        #[handler]
+        async fn hello() -> &'static str {
+            "Hello World"
+        }
+
+        let acceptor = TcpListener::new("127.0.0.1:0").bind().await;
+        let server = Server::builder()
+            .http1_header_read_timeout(Duration::from_millis(100))
+            .http1_max_header_size(8192)
+            .build(acceptor);
+        let addr = *server.holdings()[0].local_addr.as_ipv4().unwrap();
+        tokio::spawn(server.serve(Router::new().get(hello)));
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"GET / HTTP/1.1\r\nhost: localhost\r\nconnection: close\r\n\r\n")
+            .await
+            .unwrap();
+        let mut content = String::new();
+        stream.read_to_string(&mut content).await.unwrap();
+        assert!(content.starts_with("HTTP/1.1 200 OK"));
+        assert!(content.ends_with("Hello World"));
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(b"GET / HTTP/1.1\r\nhost: ").await.unwrap();
+        let mut content = Vec::new();
+        let read = tokio::time::timeout(Duration::from_secs(5), stream.read_to_end(&mut content)).await;
+        assert!(read.is_ok(), "connection should be closed by header read timeout");
+    }
+
+    #[tokio::test]
+    async fn test_server_header_limits() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::TcpStream;
+
+        #[handler]
+        async fn hello() -> &'static str {
+            "Hello World"
+        }
+
+        let acceptor = TcpListener::new("127.0.0.1:0").bind().await;
+        let server = Server::builder()
+            .http1_max_header_size(1024)
+            .http1_max_headers(8)
+            .build(acceptor);
+        let addr = *server.holdings()[0].local_addr.as_ipv4().unwrap();
+        tokio::spawn(server.serve(Router::new().get(hello)));
+
+        async fn send(addr: std::net::SocketAddrV4, headers: &str) -> String {
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            let request = format!("GET / HTTP/1.1\r\nhost: localhost\r\nconnection: close\r\n{headers}\r\n");
+            stream.write_all(request.as_bytes()).await.unwrap();
+            let mut content = String::new();
+            stream.read_to_string(&mut content).await.unwrap();
+            content
+        }
+
+        assert!(send(addr, "").await.starts_with("HTTP/1.1 200 OK"));
+        let large = format!("x-large: {}\r\n", "a".repeat(2048));
+        assert!(send(addr, &large).await.starts_with("HTTP/1.1 431"));
+        let many = (0..16).map(|i| format!("x-header-{i}: {i}\r\n")).collect::<String>();
+        assert!(send(addr, &many).await.starts_with("HTTP/1.1 431"));
+    }
+
+    #[tokio::test]
+    async fn test_server_local_addrs() {
+        let acceptor = TcpListener::new("127.0.0.1:0")
+            .join(TcpListener::new("127.0.0.1:0"))
+            .bind()
+            .await;
+        let server = Server::new(acceptor);
+        let addrs = server.local_addrs();
+        assert_eq!(addrs.len(), 2);
+        let ports = addrs
+            .iter()
+            .map(|addr| addr.as_ipv4().unwrap().port())
+            .collect::<Vec<_>>();
+        assert!(ports.iter().all(|port| *port != 0));
+        assert_ne!(ports[0], ports[1]);
+    }
+
+    #[cfg(feature = "server-handle")]
+    #[tokio::test]
+    async fn test_server_joined_listeners() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::TcpStream;
+
+        #[handler]
+        async fn hello(req: &mut Request) -> String {
+            format!("Hello from {}", req.local_addr())
+        }
+
+        let acceptor = TcpListener::new("127.0.0.1:0")
+            .join(TcpListener::new("127.0.0.1:0"))
+            .bind()
+            .await;
+        let server = Server::new(acceptor);
+        let addrs = server
+            .local_addrs()
+            .into_iter()
+            .map(|addr| *addr.as_ipv4().unwrap())
+            .collect::<Vec<_>>();
+        let handle = server.handle();
+        let serving = tokio::spawn(server.serve(Router::new().get(hello)));
+
+        for addr in &addrs {
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            stream
+                .write_all(b"GET / HTTP/1.1\r\nhost: localhost\r\nconnection: close\r\n\r\n")
+                .await
+                .unwrap();
+            let mut content = String::new();
+            stream.read_to_string(&mut content).await.unwrap();
+            assert!(content.starts_with("HTTP/1.1 200 OK"));
+            assert!(content.ends_with(&format!("Hello from socket://{addr}")));
+        }
+
+        handle.stop_graceful(None);
+        tokio::time::timeout(std::time::Duration::from_secs(5), serving)
+            .await
+            .expect("server should stop on all listeners")
+            .unwrap();
+    }
+
     #[test]
     fn test_regression_209() {
         #[cfg(feature = "acme")]
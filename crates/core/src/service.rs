@@ -3,7 +3,7 @@ use std::pin::Pin;
 use std::sync::Arc;
 
 use headers::HeaderValue;
-use http::header::{ALT_SVC, CONTENT_TYPE};
+use http::header::{ALLOW, ALT_SVC, CONTENT_TYPE, EXPECT, HeaderName};
 use http::uri::Scheme;
 use hyper::service::Service as HyperService;
 use hyper::{Method, Request as HyperRequest, Response as HyperResponse};
@@ -13,11 +13,16 @@ use crate::conn::SocketAddr;
 use crate::fuse::ArcFusewire;
 use crate::handler::{Handler, WhenHoop};
 use crate::http::body::{ReqBody, ResBody};
-use crate::http::{Mime, Request, Response, StatusCode};
+use crate::http::errors::SupportedMediaTypes;
+use crate::http::{Mime, Request, Response, StatusCode, Version};
 use crate::routing::{FlowCtrl, PathState, Router};
 use crate::{Depot, async_trait};
 
 /// Service http request.
+///
+/// The requests with an `Expect` header other than `100-continue` are rejected with `417 Expectation Failed`
+/// before routing. For `Expect: 100-continue`, `100 Continue` is sent when the handler reads the body, see
+/// [`Request::expects_continue`].
 #[non_exhaustive]
 pub struct Service {
     /// The router of this service.
@@ -54,6 +59,9 @@ impl Service {
     /// When the response code is 400-600 and the body is empty, capture and set the error page content.
     /// If catchers is not set, the default error page will be used.
     ///
+    /// The catchers set by [`Router::catch`] take precedence over it for the requests handled by those routers,
+    /// view [module level documentation](crate::routing#catchers) for more details.
+    ///
     /// # Example
     ///
     /// ```
@@ -86,6 +94,15 @@ impl Service {
         self
     }
 
+    /// Insert a handler as middleware at the beginning of the middlewares, so it runs before all the other
+    /// middlewares, including the ones added before it, like a middleware setting the request id which the
+    /// others rely on.
+    #[inline]
+    pub fn unshift_hoop<H: Handler>(mut self, hoop: H) -> Self {
+        self.hoops.insert(0, Arc::new(hoop));
+        self
+    }
+
     /// Add a handler as middleware, it will run the handler when request received.
     ///
     /// This middleware only effective when the filter return true.
@@ -143,10 +160,13 @@ impl Service {
             allowed_media_types: self.allowed_media_types.clone(),
             fusewire,
             alt_svc_h3,
+            #[cfg(feature = "client-cert")]
+            client_certs: None,
         }
     }
-    /// Handle new request, this function only used for test.
-    #[cfg(feature = "test")]
+    /// Handle new request, it runs the hoops and the router of the service like the requests from the server.
+    ///
+    /// It is mostly used in tests, and by the handlers which dispatch requests internally, like batch requests.
     #[inline]
     pub async fn handle(&self, request: impl Into<Request> + Send) -> Response {
         let request = request.into();
@@ -201,14 +221,37 @@ pub struct HyperHandler {
     pub(crate) allowed_media_types: Arc<Vec<Mime>>,
     pub(crate) fusewire: Option<ArcFusewire>,
     pub(crate) alt_svc_h3: Option<HeaderValue>,
+    #[cfg(feature = "client-cert")]
+    pub(crate) client_certs: Option<crate::conn::rustls::ClientCertsSlot>,
+}
+// Write the supported media types in the `Accept-Post` or `Accept-Patch` header of the `415` response.
+fn insert_supported_media_types(method: &Method, supported: &[Mime], res: &mut Response) {
+    let header_name = match *method {
+        Method::POST => HeaderName::from_static("accept-post"),
+        Method::PATCH => HeaderName::from_static("accept-patch"),
+        _ => return,
+    };
+    let supported = supported
+        .iter()
+        .map(|mime| mime.essence_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    if let Ok(value) = HeaderValue::from_str(&supported) {
+        res.headers_mut().insert(header_name, value);
+    }
 }
+
 impl HyperHandler {
     /// Handle [`Request`] and returns [`Response`].
     pub fn handle(&self, mut req: Request) -> impl Future<Output = Response> + 'static {
-        let catcher = self.catcher.clone();
+        let mut catcher = self.catcher.clone();
         let allowed_media_types = self.allowed_media_types.clone();
         req.local_addr = self.local_addr.clone();
         req.remote_addr = self.remote_addr.clone();
+        #[cfg(feature = "client-cert")]
+        if let Some(client_certs) = self.client_certs.as_ref().and_then(|slot| slot.get()) {
+            req.client_certs = Some(client_certs.clone());
+        }
         #[cfg(not(feature = "cookie"))]
         let mut res = Response::new();
         #[cfg(feature = "cookie")]
@@ -224,7 +267,19 @@ impl HyperHandler {
 
         let hoops = self.hoops.clone();
         async move {
-            if let Some(dm) = router.detect(&mut req, &mut path_state).await {
+            // Only `100-continue` is defined, the other expectations can not be met.
+            let expectation_failed = req.version() >= Version::HTTP_11
+                && req.headers().contains_key(EXPECT)
+                && !req.expects_continue();
+            let detected = if expectation_failed {
+                None
+            } else {
+                router.detect(&mut req, &mut path_state).await
+            };
+            if let Some(dm) = detected {
+                if dm.catcher.is_some() {
+                    catcher = dm.catcher;
+                }
                 req.params = path_state.params;
                 #[cfg(feature = "matched-path")]
                 {
@@ -246,25 +301,59 @@ impl HyperHandler {
                 if res.status_code.is_none() {
                     res.status_code = Some(StatusCode::OK);
                 }
-            } else if !hoops.is_empty() {
-                req.params = path_state.params;
-                // Set default status code before service hoops executed.
-                // We hope all hoops in service can get the correct status code.
-                if path_state.once_ended {
-                    res.status_code = Some(StatusCode::METHOD_NOT_ALLOWED);
+            } else {
+                let unmatched_status = if expectation_failed {
+                    Some(StatusCode::EXPECTATION_FAILED)
+                } else if path_state.once_unsupported_media_type {
+                    Some(StatusCode::UNSUPPORTED_MEDIA_TYPE)
+                } else if path_state.once_not_acceptable {
+                    Some(StatusCode::NOT_ACCEPTABLE)
+                } else if path_state.once_ended {
+                    Some(StatusCode::METHOD_NOT_ALLOWED)
                 } else {
-                    res.status_code = Some(StatusCode::NOT_FOUND);
+                    None
+                };
+                if path_state.once_unsupported_media_type {
+                    insert_supported_media_types(
+                        req.method(),
+                        &path_state.supported_media_types,
+                        &mut res,
+                    );
                 }
-                let mut ctrl = FlowCtrl::new(hoops);
-                ctrl.call_next(&mut req, &mut depot, &mut res).await;
-                // Set it to default status code again if any hoop set status code to None.
-                if res.status_code.is_none() && path_state.once_ended {
-                    res.status_code = Some(StatusCode::METHOD_NOT_ALLOWED);
+                if unmatched_status == Some(StatusCode::METHOD_NOT_ALLOWED)
+                    && !path_state.allowed_methods.is_empty()
+                {
+                    let allowed = path_state
+                        .allowed_methods
+                        .iter()
+                        .map(|method| method.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    if let Ok(value) = HeaderValue::from_str(&allowed) {
+                        res.headers_mut().insert(ALLOW, value);
+                    }
+                }
+                if !hoops.is_empty() {
+                    req.params = path_state.params;
+                    // Set default status code before service hoops executed.
+                    // We hope all hoops in service can get the correct status code.
+                    res.status_code = Some(unmatched_status.unwrap_or(StatusCode::NOT_FOUND));
+                    let mut ctrl = FlowCtrl::new(hoops);
+                    ctrl.call_next(&mut req, &mut depot, &mut res).await;
+                    // Set it to default status code again if any hoop set status code to None.
+                    if res.status_code.is_none() {
+                        res.status_code = unmatched_status;
+                    }
+                } else {
+                    res.status_code = unmatched_status;
                 }
-            } else if path_state.once_ended {
-                res.status_code = Some(StatusCode::METHOD_NOT_ALLOWED);
             }
 
+            if let Some(SupportedMediaTypes(supported)) = res.extensions.remove() {
+                if res.status_code == Some(StatusCode::UNSUPPORTED_MEDIA_TYPE) {
+                    insert_supported_media_types(req.method(), &supported, &mut res);
+                }
+            }
             let status_code = if let Some(status_code) = res.status_code {
                 status_code
             } else {
@@ -312,7 +401,7 @@ impl HyperHandler {
                 if let Some(catcher) = catcher {
                     catcher.catch(&mut req, &mut depot, &mut res).await;
                 } else {
-                    write_error_default(&req, &mut res, None);
+                    write_error_default(&req, &mut res, None, false);
                 }
             }
             #[cfg(debug_assertions)]
@@ -399,7 +488,10 @@ where
 
 #[cfg(test)]
 mod tests {
+    use crate::http::ParseError;
+    use crate::http::header::{ALLOW, HeaderValue};
     use crate::prelude::*;
+    use crate::routing::filters;
     use crate::test::{ResponseExt, TestClient};
 
     #[tokio::test]
@@ -533,4 +625,218 @@ mod tests {
             .await;
         assert_eq!(res.status_code.unwrap(), StatusCode::NOT_FOUND);
     }
+
+    #[tokio::test]
+    async fn test_service_options_and_allow() {
+        #[handler]
+        async fn list() -> &'static str {
+            "list"
+        }
+        #[handler]
+        async fn options(res: &mut Response) {
+            res.headers_mut()
+                .insert(ALLOW, HeaderValue::from_static("GET, POST, OPTIONS"));
+            res.status_code(StatusCode::NO_CONTENT);
+        }
+        let router = Router::with_path("todos")
+            .get(list)
+            .post(list)
+            .options(options)
+            .push(Router::with_path("{id}").delete(list));
+        let service = Service::new(router);
+
+        let res = TestClient::options("http://127.0.0.1:5801/todos")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::NO_CONTENT);
+        assert_eq!(res.headers()[ALLOW], "GET, POST, OPTIONS");
+
+        let res = TestClient::put("http://127.0.0.1:5801/todos")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::METHOD_NOT_ALLOWED);
+        assert_eq!(res.headers()[ALLOW], "GET, POST, OPTIONS");
+
+        let res = TestClient::get("http://127.0.0.1:5801/todos/1")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::METHOD_NOT_ALLOWED);
+        assert_eq!(res.headers()[ALLOW], "DELETE");
+
+        let res = TestClient::get("http://127.0.0.1:5801/other")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::NOT_FOUND);
+        assert!(!res.headers().contains_key(ALLOW));
+    }
+
+    #[tokio::test]
+    async fn test_service_expect_continue() {
+        #[handler]
+        async fn upload(req: &mut Request) -> Result<String, StatusError> {
+            assert!(req.expects_continue());
+            let payload = req
+                .payload_with_max_size(1024)
+                .await
+                .map_err(|_| StatusError::payload_too_large())?;
+            Ok(String::from_utf8_lossy(payload).into_owned())
+        }
+        let service = Service::new(Router::with_path("upload").post(upload));
+
+        let mut res = TestClient::post("http://127.0.0.1:5801/upload")
+            .add_header("expect", "100-Continue", true)
+            .body("hello")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::OK);
+        assert_eq!(res.take_string().await.unwrap(), "hello");
+
+        let res = TestClient::post("http://127.0.0.1:5801/upload")
+            .add_header("expect", "100-continue", true)
+            .add_header("content-length", "1048576", true)
+            .body("hello")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        let res = TestClient::post("http://127.0.0.1:5801/upload")
+            .add_header("expect", "200-ok", true)
+            .body("hello")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::EXPECTATION_FAILED);
+    }
+
+    #[tokio::test]
+    async fn test_service_content_type_filter() {
+        #[handler]
+        async fn create_json() -> &'static str {
+            "json"
+        }
+        #[handler]
+        async fn create_form() -> &'static str {
+            "form"
+        }
+        #[handler]
+        async fn list() -> &'static str {
+            "list"
+        }
+        let router = Router::with_path("todos")
+            .get(list)
+            .push(
+                Router::with_filter(filters::post())
+                    .filter(filters::content_type("application/json"))
+                    .goal(create_json),
+            )
+            .push(
+                Router::with_filter(filters::post())
+                    .filter(filters::content_type("application/x-www-form-urlencoded"))
+                    .goal(create_form),
+            );
+        let service = Service::new(router);
+
+        async fn access(service: &Service, method: &str, content_type: Option<&str>) -> Response {
+            let mut req = match method {
+                "GET" => TestClient::get("http://127.0.0.1:5801/todos"),
+                "PUT" => TestClient::put("http://127.0.0.1:5801/todos"),
+                _ => TestClient::post("http://127.0.0.1:5801/todos"),
+            };
+            if let Some(content_type) = content_type {
+                req = req.add_header("content-type", content_type, true);
+            }
+            req.send(service).await
+        }
+
+        let mut res = access(&service, "POST", Some("application/json; charset=utf-8")).await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::OK);
+        assert_eq!(res.take_string().await.unwrap(), "json");
+        let mut res = access(&service, "POST", Some("application/x-www-form-urlencoded")).await;
+        assert_eq!(res.take_string().await.unwrap(), "form");
+        let mut res = access(&service, "GET", None).await;
+        assert_eq!(res.take_string().await.unwrap(), "list");
+
+        let res = access(&service, "POST", Some("text/plain")).await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(
+            res.headers()["accept-post"],
+            "application/json, application/x-www-form-urlencoded"
+        );
+        let res = access(&service, "POST", None).await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        let res = access(&service, "PUT", Some("application/json")).await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::METHOD_NOT_ALLOWED);
+    }
+
+    #[tokio::test]
+    async fn test_service_parse_invalid_content_type() {
+        #[handler]
+        async fn create(req: &mut Request) -> Result<&'static str, ParseError> {
+            req.parse_json::<serde_json::Value>().await?;
+            Ok("created")
+        }
+        let service = Service::new(Router::new().post(create).patch(create).put(create));
+
+        let res = TestClient::post("http://127.0.0.1:5801/")
+            .text("hello")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(res.headers()["accept-post"], "application/json");
+        let res = TestClient::patch("http://127.0.0.1:5801/")
+            .text("hello")
+            .send(&service)
+            .await;
+        assert_eq!(res.headers()["accept-patch"], "application/json");
+        let res = TestClient::put("http://127.0.0.1:5801/")
+            .text("hello")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert!(!res.headers().contains_key("accept-post"));
+    }
+
+    #[tokio::test]
+    async fn test_service_accept_filter() {
+        #[handler]
+        async fn export_json() -> &'static str {
+            "json"
+        }
+        #[handler]
+        async fn export_csv() -> &'static str {
+            "csv"
+        }
+        let router = Router::with_path("export")
+            .push(
+                Router::with_filter(filters::get())
+                    .filter(filters::accept("application/json"))
+                    .goal(export_json),
+            )
+            .push(
+                Router::with_filter(filters::get())
+                    .filter(filters::accept("text/csv"))
+                    .goal(export_csv),
+            );
+        let service = Service::new(router);
+
+        async fn access(service: &Service, accept: &str) -> Response {
+            TestClient::get("http://127.0.0.1:5801/export")
+                .add_header("accept", accept, true)
+                .send(service)
+                .await
+        }
+
+        let mut res = access(&service, "application/json").await;
+        assert_eq!(res.take_string().await.unwrap(), "json");
+        let mut res = access(&service, "text/csv").await;
+        assert_eq!(res.take_string().await.unwrap(), "csv");
+        let mut res = access(&service, "text/html, text/*;q=0.8").await;
+        assert_eq!(res.take_string().await.unwrap(), "csv");
+        let res = access(&service, "text/html").await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::NOT_ACCEPTABLE);
+        let res = TestClient::post("http://127.0.0.1:5801/export")
+            .add_header("accept", "text/csv", true)
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::METHOD_NOT_ALLOWED);
+    }
 }
@@ -1,10 +1,13 @@
 use std::borrow::Borrow;
 use std::convert::TryInto;
 use std::future::Future;
+use std::io::Write;
 use std::str;
 use std::sync::Arc;
 
 use base64::engine::{general_purpose, Engine};
+use flate2::write::{GzEncoder, ZlibEncoder};
+use flate2::Compression;
 use http::header::{self, HeaderMap, HeaderValue, IntoHeaderName};
 use http::uri::Scheme;
 use url::Url;
@@ -190,6 +193,33 @@ impl RequestBuilder {
             .or_insert(HeaderValue::from_static("application/x-www-form-urlencoded"));
         self.body(value.into())
     }
+    /// Set the `Accept-Encoding` header, for example `gzip` or `br, gzip;q=0.5`.
+    ///
+    /// Compressed response can be decompressed by [`ResponseExt::take_string`] and
+    /// [`ResponseExt::take_json`] automatically.
+    ///
+    /// [`ResponseExt::take_string`]: crate::test::ResponseExt::take_string
+    /// [`ResponseExt::take_json`]: crate::test::ResponseExt::take_json
+    pub fn accept_encoding(self, encoding: impl AsRef<str>) -> Self {
+        self.add_header(header::ACCEPT_ENCODING, encoding.as_ref(), true)
+    }
+
+    /// Compress the body which is already set, and set the `Content-Encoding` header.
+    ///
+    /// Supported encodings are `gzip`, `deflate`, `br` and `zstd`.
+    ///
+    /// # Panics
+    /// Panics if the encoding is not supported or the body is a stream.
+    pub fn compressed(mut self, encoding: &str) -> Self {
+        let body = match std::mem::take(&mut self.body) {
+            ReqBody::None => Default::default(),
+            ReqBody::Once(bytes) => bytes,
+            _ => panic!("only bytes body can be compressed"),
+        };
+        self.body = compress(encoding, &body).into();
+        self.add_header(header::CONTENT_ENCODING, encoding, true)
+    }
+
     /// Modify a header for this response.
     ///
     /// When `overwrite` is set to `true`, If the header is already present, the value will be replaced.
@@ -251,6 +281,28 @@ impl RequestBuilder {
     }
 }
 
+fn compress(encoding: &str, body: &[u8]) -> Vec<u8> {
+    match encoding {
+        "gzip" => {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(body).expect("failed to compress body");
+            encoder.finish().expect("failed to compress body")
+        }
+        "deflate" => {
+            let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(body).expect("failed to compress body");
+            encoder.finish().expect("failed to compress body")
+        }
+        "br" => {
+            let mut encoder = brotli::CompressorWriter::new(Vec::new(), 4096, 11, 22);
+            encoder.write_all(body).expect("failed to compress body");
+            encoder.into_inner()
+        }
+        "zstd" => zstd::stream::encode_all(body, 0).expect("failed to compress body"),
+        _ => panic!("unknown compress format: {encoding}"),
+    }
+}
+
 /// Trait for sending request to target, such as [`Router`], [`Service`], [`Handler`] for test usage.
 pub trait SendTarget {
     /// Send request to target, such as [`Router`], [`Service`], [`Handler`].
@@ -299,3 +351,40 @@ where
         SendTarget::call(handler, req).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use http_body_util::BodyExt;
+
+    use crate::http::header::{ACCEPT_ENCODING, CONTENT_ENCODING};
+    use crate::prelude::*;
+    use crate::test::{ResponseExt, TestClient};
+
+    #[handler]
+    async fn echo(req: &mut Request, res: &mut Response) {
+        let encoding = req.header::<String>(CONTENT_ENCODING).unwrap_or_default();
+        let accept = req.header::<String>(ACCEPT_ENCODING).unwrap_or_default();
+        let body = req.take_body().collect().await.unwrap().to_bytes();
+        // Echo the raw body back, so it is decompressed by the test client.
+        res.add_header(CONTENT_ENCODING, encoding, true).unwrap();
+        res.add_header("x-accept-encoding", accept, true).unwrap();
+        res.body(body);
+    }
+
+    #[tokio::test]
+    async fn test_compressed_body() {
+        let service = Service::new(Router::new().post(echo));
+        for encoding in ["gzip", "deflate", "br", "zstd"] {
+            let mut res = TestClient::post("http://127.0.0.1:5800/")
+                .json(&serde_json::json!({"title": "todo"}))
+                .compressed(encoding)
+                .accept_encoding(encoding)
+                .send(&service)
+                .await;
+            assert_eq!(res.headers()["x-accept-encoding"], encoding);
+            assert_eq!(res.headers()[CONTENT_ENCODING], encoding);
+            let value = res.take_json::<serde_json::Value>().await.unwrap();
+            assert_eq!(value, serde_json::json!({"title": "todo"}));
+        }
+    }
+}
@@ -43,6 +43,40 @@ impl io::Write for Writer {
     }
 }
 
+fn decompress(algo: &str, full: Bytes) -> IoResult<Bytes> {
+    let full = match algo {
+        "gzip" => {
+            let mut decoder = GzDecoder::new(Writer::new());
+            decoder.write_all(full.as_ref())?;
+            decoder.flush()?;
+            decoder.get_mut().take()
+        }
+        "deflate" => {
+            let mut decoder = ZlibDecoder::new(Writer::new());
+            decoder.write_all(full.as_ref())?;
+            decoder.flush()?;
+            decoder.get_mut().take()
+        }
+        "br" => {
+            let mut decoder = brotli::DecompressorWriter::new(Writer::new(), 8_096);
+            decoder.write_all(full.as_ref())?;
+            decoder.flush()?;
+            decoder.get_mut().take()
+        }
+        "zstd" => {
+            let mut decoder = ZstdDecoder::new(Writer::new()).expect("failed to create zstd decoder");
+            decoder.write_all(full.as_ref())?;
+            decoder.flush()?;
+            decoder.get_mut().take()
+        }
+        _ => {
+            tracing::error!(algo, "unknown compress format");
+            full
+        }
+    };
+    Ok(full)
+}
+
 /// More utils functions for [`Response`].
 pub trait ResponseExt {
     /// Take body as `String` from response.
@@ -60,6 +94,26 @@ pub trait ResponseExt {
     fn take_bytes(&mut self, content_type: Option<&Mime>) -> impl Future<Output = crate::Result<Bytes>> + Send;
 }
 
+impl Response {
+    /// Take all body bytes and decompress them according to the `Content-Encoding` header.
+    ///
+    /// `gzip`, `deflate`, `br` and `zstd` are supported, the bytes are returned as is if the header
+    /// is not present.
+    pub async fn take_decompressed_bytes(&mut self, content_type: Option<&Mime>) -> crate::Result<Bytes> {
+        let encoding = self
+            .headers()
+            .get(CONTENT_ENCODING)
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.to_owned());
+        let full = self.take_bytes(content_type).await?;
+        if let Some(algo) = encoding {
+            Ok(decompress(&algo, full)?)
+        } else {
+            Ok(full)
+        }
+    }
+}
+
 impl ResponseExt for Response {
     async fn take_string(&mut self) -> crate::Result<String> {
         let content_type = self
@@ -80,7 +134,7 @@ impl ResponseExt for Response {
             .await
     }
     async fn take_json<T: DeserializeOwned>(&mut self) -> crate::Result<T> {
-        let full = self.take_bytes(Some(&mime::APPLICATION_JSON)).await?;
+        let full = self.take_decompressed_bytes(Some(&mime::APPLICATION_JSON)).await?;
         serde_json::from_slice(&full).map_err(Error::SerdeJson)
     }
     async fn take_string_with_charset(
@@ -92,35 +146,7 @@ impl ResponseExt for Response {
         let charset = Encoding::for_label(charset.as_bytes()).unwrap_or(UTF_8);
         let mut full = self.take_bytes(content_type).await?;
         if let Some(algo) = compress {
-            match algo {
-                "gzip" => {
-                    let mut decoder = GzDecoder::new(Writer::new());
-                    decoder.write_all(full.as_ref())?;
-                    decoder.flush()?;
-                    full = decoder.get_mut().take();
-                }
-                "deflate" => {
-                    let mut decoder = ZlibDecoder::new(Writer::new());
-                    decoder.write_all(full.as_ref())?;
-                    decoder.flush()?;
-                    full = decoder.get_mut().take();
-                }
-                "br" => {
-                    let mut decoder = brotli::DecompressorWriter::new(Writer::new(), 8_096);
-                    decoder.write_all(full.as_ref())?;
-                    decoder.flush()?;
-                    full = decoder.get_mut().take();
-                }
-                "zstd" => {
-                    let mut decoder = ZstdDecoder::new(Writer::new()).expect("failed to create zstd decoder");
-                    decoder.write_all(full.as_ref())?;
-                    decoder.flush()?;
-                    full = decoder.get_mut().take();
-                }
-                _ => {
-                    tracing::error!(algo, "unknown compress format");
-                }
-            }
+            full = decompress(algo, full)?;
         }
         let (text, _, _) = charset.decode(&full);
         if let Cow::Owned(s) = text {
@@ -135,9 +161,9 @@ impl ResponseExt for Response {
             ResBody::Once(bytes) => bytes,
             ResBody::Error(e) => {
                 if let Some(content_type) = content_type {
-                    status_error_bytes(&e, content_type, None).1
+                    status_error_bytes(&e, content_type, None, false).1
                 } else {
-                    status_error_bytes(&e, &mime::TEXT_HTML, None).1
+                    status_error_bytes(&e, &mime::TEXT_HTML, None, false).1
                 }
             }
             _ => BodyExt::collect(body).await?.to_bytes(),
@@ -0,0 +1,166 @@
+use std::fmt::{self, Debug, Formatter};
+
+use percent_encoding::{AsciiSet, NON_ALPHANUMERIC, utf8_percent_encode};
+
+use super::Scribe;
+use crate::http::Response;
+use crate::http::header::{CONTENT_DISPOSITION, HeaderValue};
+
+// The `attr-char` of RFC 5987 which are not needed to be encoded.
+const ATTR_CHAR: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'!')
+    .remove(b'#')
+    .remove(b'$')
+    .remove(b'&')
+    .remove(b'+')
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'^')
+    .remove(b'_')
+    .remove(b'`')
+    .remove(b'|')
+    .remove(b'~');
+
+/// Write the inner content as an attachment, so the browser downloads it with the file name instead of
+/// displaying it.
+///
+/// It sets `content-disposition` to `attachment; filename="{name}"`. If the name contains non-ASCII characters
+/// or quotes, it is encoded as [RFC 5987](https://datatracker.ietf.org/doc/html/rfc5987) in the `filename*`
+/// parameter, and the `filename` parameter keeps an ASCII fallback for old clients.
+///
+/// Use it with [`Json::attachment`](super::Json::attachment), [`Text::attachment`](super::Text::attachment) or
+/// [`Attachment::new`] for any other [`Scribe`].
+///
+/// # Example
+///
+/// ```
+/// use salvo_core::prelude::*;
+/// use salvo_core::writing::Attachment;
+///
+/// #[handler]
+/// async fn export_todos() -> Attachment<Text<String>> {
+///     Text::Csv("id,title\n1,write docs\n".to_owned()).attachment("todos.csv")
+/// }
+/// ```
+pub struct Attachment<S> {
+    name: String,
+    scribe: S,
+}
+
+impl<S> Attachment<S> {
+    /// Create a new `Attachment` with the file name and the inner content.
+    #[inline]
+    pub fn new(name: impl Into<String>, scribe: S) -> Self {
+        Self {
+            name: name.into(),
+            scribe,
+        }
+    }
+}
+
+impl<S: Debug> Debug for Attachment<S> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("Attachment")
+            .field("name", &self.name)
+            .field("scribe", &self.scribe)
+            .finish()
+    }
+}
+
+impl<S> Scribe for Attachment<S>
+where
+    S: Scribe,
+{
+    fn render(self, res: &mut Response) {
+        res.headers_mut()
+            .insert(CONTENT_DISPOSITION, content_disposition(&self.name));
+        self.scribe.render(res);
+    }
+}
+
+/// Build `content-disposition` of the attachment with the file name.
+pub(crate) fn content_disposition(name: &str) -> HeaderValue {
+    let fallback: String = name
+        .chars()
+        .map(|c| match c {
+            '"' | '\\' => '_',
+            c if c.is_ascii() && !c.is_ascii_control() => c,
+            _ => '_',
+        })
+        .collect();
+    let value = if fallback == name {
+        format!(r#"attachment; filename="{name}""#)
+    } else {
+        format!(
+            r#"attachment; filename="{fallback}"; filename*=UTF-8''{}"#,
+            utf8_percent_encode(name, ATTR_CHAR)
+        )
+    };
+    HeaderValue::from_str(&value).unwrap_or_else(|_| HeaderValue::from_static("attachment"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use crate::test::{ResponseExt, TestClient};
+
+    #[test]
+    fn test_content_disposition() {
+        assert_eq!(
+            content_disposition("todos.csv"),
+            r#"attachment; filename="todos.csv""#
+        );
+        assert_eq!(
+            content_disposition("待办 事项.csv"),
+            r#"attachment; filename="__ __.csv"; filename*=UTF-8''%E5%BE%85%E5%8A%9E%20%E4%BA%8B%E9%A1%B9.csv"#
+        );
+        assert_eq!(
+            content_disposition(r#"a"b.json"#),
+            r#"attachment; filename="a_b.json"; filename*=UTF-8''a%22b.json"#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_write_attachment() {
+        #[derive(serde::Serialize)]
+        struct Todo {
+            id: u64,
+        }
+        #[handler]
+        async fn csv() -> Attachment<Text<&'static str>> {
+            Text::Csv("id\n1\n").attachment("todos.csv")
+        }
+        #[handler]
+        async fn json() -> Attachment<Json<Vec<Todo>>> {
+            Json(vec![Todo { id: 1 }]).attachment("导出.json")
+        }
+        let router = Router::new()
+            .push(Router::with_path("csv").get(csv))
+            .push(Router::with_path("json").get(json));
+        let service = Service::new(router);
+
+        let mut res = TestClient::get("http://127.0.0.1:5800/csv")
+            .send(&service)
+            .await;
+        assert_eq!(
+            res.headers()[CONTENT_DISPOSITION],
+            r#"attachment; filename="todos.csv""#
+        );
+        assert_eq!(res.headers()["content-type"], "text/csv; charset=utf-8");
+        assert_eq!(res.take_string().await.unwrap(), "id\n1\n");
+
+        let mut res = TestClient::get("http://127.0.0.1:5800/json")
+            .send(&service)
+            .await;
+        assert_eq!(
+            res.headers()[CONTENT_DISPOSITION],
+            r#"attachment; filename="__.json"; filename*=UTF-8''%E5%AF%BC%E5%87%BA.json"#
+        );
+        assert_eq!(
+            res.headers()["content-type"],
+            "application/json; charset=utf-8"
+        );
+        assert_eq!(res.take_string().await.unwrap(), r#"[{"id":1}]"#);
+    }
+}
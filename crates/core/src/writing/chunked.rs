@@ -0,0 +1,162 @@
+use std::fmt::{self, Debug, Formatter};
+
+use bytes::Bytes;
+use futures_util::stream::{Stream, StreamExt};
+
+use super::{Scribe, try_set_header};
+use crate::BoxedError;
+use crate::http::Response;
+use crate::http::body::BytesFrame;
+use crate::http::header::{CONTENT_TYPE, HeaderValue};
+
+/// Write a stream to response as a chunked body.
+///
+/// Items of the stream can be `String`, `&'static str`, `Vec<u8>`, `Bytes`, or `Result` of them. Each item is
+/// sent as a chunk as soon as it is produced, so it is useful for tailing a log. If the stream yields an `Err`,
+/// the response is aborted.
+///
+/// The `content-type` is set to `text/plain; charset=utf-8` if it is not set, use
+/// [`Chunked::content_type`] to set another one. Note that middlewares which buffer the body, such as
+/// compression, and the proxies between server and client may delay the chunks.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use futures_util::stream::{self, Stream, StreamExt};
+/// use salvo_core::prelude::*;
+/// use salvo_core::writing::Chunked;
+///
+/// #[handler]
+/// async fn build_log() -> Chunked<impl Stream<Item = String> + Send + 'static> {
+///     Chunked::new(stream::iter(1..=3).then(|step| async move {
+///         tokio::time::sleep(Duration::from_millis(100)).await;
+///         format!("step {step} done\n")
+///     }))
+/// }
+/// ```
+pub struct Chunked<S> {
+    stream: S,
+    content_type: HeaderValue,
+}
+
+impl<S> Chunked<S> {
+    /// Create a new `Chunked` from a stream.
+    #[inline]
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream,
+            content_type: HeaderValue::from_static("text/plain; charset=utf-8"),
+        }
+    }
+
+    /// Set the `content-type` of the response, it is only used if the `content-type` is not set.
+    #[inline]
+    pub fn content_type(mut self, content_type: HeaderValue) -> Self {
+        self.content_type = content_type;
+        self
+    }
+}
+
+impl<S> Debug for Chunked<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Chunked")
+            .field("content_type", &self.content_type)
+            .finish()
+    }
+}
+
+/// Item of the stream which can be written by [`Chunked`].
+pub trait Chunk: Send + 'static {
+    /// Convert the item to a chunk.
+    fn into_chunk(self) -> Result<BytesFrame, BoxedError>;
+}
+
+macro_rules! chunk_impls {
+    ($($ty:ty),+) => {$(
+        impl Chunk for $ty {
+            #[inline]
+            fn into_chunk(self) -> Result<BytesFrame, BoxedError> {
+                Ok(self.into())
+            }
+        }
+    )+}
+}
+chunk_impls!(String, &'static str, Vec<u8>, Bytes);
+
+impl<T, E> Chunk for Result<T, E>
+where
+    T: Into<BytesFrame> + Send + 'static,
+    E: Into<BoxedError> + Send + 'static,
+{
+    #[inline]
+    fn into_chunk(self) -> Result<BytesFrame, BoxedError> {
+        self.map(Into::into).map_err(Into::into)
+    }
+}
+
+impl<S> Scribe for Chunked<S>
+where
+    S: Stream + Send + 'static,
+    S::Item: Chunk,
+{
+    fn render(self, res: &mut Response) {
+        try_set_header(&mut res.headers, CONTENT_TYPE, self.content_type);
+        res.stream(self.stream.map(Chunk::into_chunk));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Error as IoError;
+
+    use futures_util::stream::{self, Stream};
+
+    use super::*;
+    use crate::prelude::*;
+    use crate::test::{ResponseExt, TestClient};
+
+    #[tokio::test]
+    async fn test_write_chunked() {
+        #[handler]
+        async fn log() -> Chunked<impl Stream<Item = String> + Send + 'static> {
+            Chunked::new(stream::iter(1..=3).map(|step| format!("step {step}\n")))
+        }
+        #[handler]
+        async fn bytes() -> Chunked<impl Stream<Item = Result<Bytes, IoError>> + Send + 'static> {
+            Chunked::new(stream::iter(vec![
+                Ok(Bytes::from_static(b"a")),
+                Ok(Bytes::from_static(b"b")),
+            ]))
+            .content_type(HeaderValue::from_static("application/octet-stream"))
+        }
+        #[handler]
+        async fn error()
+        -> Chunked<impl Stream<Item = Result<&'static str, IoError>> + Send + 'static> {
+            Chunked::new(stream::iter(vec![Ok("ok"), Err(IoError::other("broken"))]))
+        }
+        let router = Router::new()
+            .push(Router::with_path("log").get(log))
+            .push(Router::with_path("bytes").get(bytes))
+            .push(Router::with_path("error").get(error));
+        let service = Service::new(router);
+
+        let mut res = TestClient::get("http://127.0.0.1:5800/log")
+            .send(&service)
+            .await;
+        assert_eq!(res.headers()[CONTENT_TYPE], "text/plain; charset=utf-8");
+        assert_eq!(res.take_string().await.unwrap(), "step 1\nstep 2\nstep 3\n");
+
+        let mut res = TestClient::get("http://127.0.0.1:5800/bytes")
+            .send(&service)
+            .await;
+        assert_eq!(res.headers()[CONTENT_TYPE], "application/octet-stream");
+        assert_eq!(res.take_bytes(None).await.unwrap(), "ab");
+
+        let mut res = TestClient::get("http://127.0.0.1:5800/error")
+            .send(&service)
+            .await;
+        assert!(res.take_bytes(None).await.is_err());
+    }
+}
@@ -0,0 +1,128 @@
+use super::Scribe;
+use crate::http::header::{HeaderValue, LOCATION};
+use crate::http::uri::Uri;
+use crate::http::{Response, StatusCode};
+
+/// Response that reports a created resource, with the `201 Created` status code and the `Location` header of
+/// the resource.
+///
+/// The body is empty by default, use [`Created::body`] to write the created resource too.
+///
+/// # Example
+///
+/// ```
+/// use salvo_core::prelude::*;
+/// use salvo_core::writing::Created;
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Todo {
+///     id: u64,
+///     text: String,
+/// }
+///
+/// #[handler]
+/// async fn create_todo() -> Created<Json<Todo>> {
+///     let todo = Todo {
+///         id: 1,
+///         text: "write docs".into(),
+///     };
+///     Created::at(format!("/api/todos/{}", todo.id)).body(Json(todo))
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Created<T = ()> {
+    location: HeaderValue,
+    body: T,
+}
+
+impl Created {
+    /// Create a new [`Created`] with the location of the created resource and an empty body.
+    ///
+    /// # Panics
+    ///
+    /// If `uri` isn't a valid [`Uri`].
+    pub fn at(uri: impl TryInto<Uri>) -> Self {
+        let Ok(uri) = uri.try_into() else {
+            panic!("invalid uri");
+        };
+        Self {
+            location: HeaderValue::try_from(uri.to_string()).expect("invalid uri"),
+            body: (),
+        }
+    }
+}
+
+impl<T> Created<T> {
+    /// Set the body, it is rendered after the status code and the `Location` header are set.
+    #[inline]
+    pub fn body<B>(self, body: B) -> Created<B> {
+        Created {
+            location: self.location,
+            body,
+        }
+    }
+
+    /// Get the location of the created resource.
+    #[inline]
+    pub fn location(&self) -> &HeaderValue {
+        &self.location
+    }
+}
+
+impl<T> Scribe for Created<T>
+where
+    T: Scribe,
+{
+    #[inline]
+    fn render(self, res: &mut Response) {
+        let Self { location, body } = self;
+        res.status_code(StatusCode::CREATED);
+        res.headers_mut().insert(LOCATION, location);
+        body.render(res);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::prelude::*;
+    use crate::test::{ResponseExt, TestClient};
+
+    #[handler]
+    async fn create_todo() -> Created<Json<Value>> {
+        Created::at("/api/todos/1").body(Json(json!({"id": 1})))
+    }
+    #[handler]
+    async fn create_tag() -> Created {
+        Created::at("/api/tags/rust")
+    }
+
+    #[tokio::test]
+    async fn test_write_created() {
+        let router = Router::new()
+            .push(Router::with_path("todos").post(create_todo))
+            .push(Router::with_path("tags").post(create_tag));
+        let service = Service::new(router);
+
+        let mut res = TestClient::post("http://127.0.0.1:5800/todos")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::CREATED));
+        assert_eq!(res.headers().get(LOCATION).unwrap(), "/api/todos/1");
+        assert_eq!(
+            res.headers().get("content-type").unwrap(),
+            "application/json; charset=utf-8"
+        );
+        assert_eq!(res.take_json::<Value>().await.unwrap(), json!({"id": 1}));
+
+        let mut res = TestClient::post("http://127.0.0.1:5800/tags")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::CREATED));
+        assert_eq!(res.headers().get(LOCATION).unwrap(), "/api/tags/rust");
+        assert!(res.take_string().await.unwrap().is_empty());
+    }
+}
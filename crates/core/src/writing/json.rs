@@ -3,13 +3,13 @@ use std::fmt::{self, Debug, Display, Formatter};
 use async_trait::async_trait;
 use serde::Serialize;
 
-use super::{Scribe, try_set_header};
+use super::{Attachment, Scribe, try_set_header};
 use crate::http::header::{CONTENT_TYPE, HeaderValue};
 use crate::http::{Response, StatusError};
 
 /// Write serializable content to response as json content.
 ///
-/// It will set `content-type` to `application/json; charset=utf-8`.
+/// It will set `content-type` to `application/json; charset=utf-8` if the response has no `content-type` yet.
 ///
 /// # Example
 ///
@@ -28,26 +28,27 @@ use crate::http::{Response, StatusError};
 /// ```
 pub struct Json<T>(pub T);
 
+impl<T> Json<T> {
+    /// Write the json content as an attachment with the file name, see [`Attachment`].
+    #[inline]
+    pub fn attachment(self, name: impl Into<String>) -> Attachment<Self> {
+        Attachment::new(name, self)
+    }
+
+    /// Write the json content pretty-printed, see [`PrettyJson`].
+    #[inline]
+    pub fn pretty(self) -> PrettyJson<T> {
+        PrettyJson(self.0)
+    }
+}
+
 #[async_trait]
 impl<T> Scribe for Json<T>
 where
     T: Serialize + Send,
 {
     fn render(self, res: &mut Response) {
-        match serde_json::to_vec(&self.0) {
-            Ok(bytes) => {
-                try_set_header(
-                    &mut res.headers,
-                    CONTENT_TYPE,
-                    HeaderValue::from_static("application/json; charset=utf-8"),
-                );
-                let _ = res.write_body(bytes);
-            }
-            Err(e) => {
-                tracing::error!(error = ?e, "JsonContent write error");
-                res.render(StatusError::internal_server_error());
-            }
-        }
+        write_json(serde_json::to_vec(&self.0), res);
     }
 }
 impl<T: Debug> Debug for Json<T> {
@@ -61,6 +62,65 @@ impl<T: Display> Display for Json<T> {
     }
 }
 
+/// Write serializable content to response as pretty-printed json content.
+///
+/// It is useful for the responses which are read by humans, like debug endpoints, [`Json`] is more compact and
+/// should be used in other cases. It will set `content-type` to `application/json; charset=utf-8` too.
+///
+/// # Example
+///
+/// ```
+/// use salvo_core::prelude::*;
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct User {
+///    name: String,
+/// }
+/// #[handler]
+/// async fn hello(res: &mut Response) {
+///     res.render(Json(User { name: "jobs".into() }).pretty());
+/// }
+/// ```
+pub struct PrettyJson<T>(pub T);
+
+#[async_trait]
+impl<T> Scribe for PrettyJson<T>
+where
+    T: Serialize + Send,
+{
+    fn render(self, res: &mut Response) {
+        write_json(serde_json::to_vec_pretty(&self.0), res);
+    }
+}
+impl<T: Debug> Debug for PrettyJson<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("PrettyJson").field(&self.0).finish()
+    }
+}
+impl<T: Display> Display for PrettyJson<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+fn write_json(result: serde_json::Result<Vec<u8>>, res: &mut Response) {
+    match result {
+        Ok(bytes) => {
+            try_set_header(
+                &mut res.headers,
+                CONTENT_TYPE,
+                HeaderValue::from_static("application/json; charset=utf-8"),
+            );
+            let _ = res.write_body(bytes);
+        }
+        Err(e) => {
+            tracing::error!(error = ?e, "JsonContent write error");
+            res.render(StatusError::internal_server_error());
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::prelude::*;
@@ -91,4 +151,32 @@ mod tests {
             "application/json; charset=utf-8"
         );
     }
+
+    #[tokio::test]
+    async fn test_write_pretty_json_content() {
+        #[derive(Serialize, Debug)]
+        struct User {
+            name: String,
+        }
+        #[handler]
+        async fn test() -> PrettyJson<User> {
+            Json(User {
+                name: "jobs".into(),
+            })
+            .pretty()
+        }
+
+        let router = Router::new().push(Router::with_path("test").get(test));
+        let mut res = TestClient::get("http://127.0.0.1:5800/test")
+            .send(router)
+            .await;
+        assert_eq!(
+            res.take_string().await.unwrap(),
+            "{\n  \"name\": \"jobs\"\n}"
+        );
+        assert_eq!(
+            res.headers().get("content-type").unwrap(),
+            "application/json; charset=utf-8"
+        );
+    }
 }
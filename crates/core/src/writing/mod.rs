@@ -1,15 +1,28 @@
 //! Writer trait and it's implements.
 
+mod attachment;
+mod chunked;
+mod created;
 mod json;
+mod paginated;
 mod redirect;
+mod rendered;
 mod seek;
+mod sparse;
 mod text;
 
+pub use attachment::Attachment;
+pub(crate) use attachment::content_disposition;
+pub use chunked::{Chunk, Chunked};
+pub use created::Created;
 use http::header::{AsHeaderName, IntoHeaderName};
 use http::{HeaderMap, StatusCode};
-pub use json::Json;
+pub use json::{Json, PrettyJson};
+pub use paginated::{Paginated, Pagination, X_TOTAL_COUNT};
 pub use redirect::Redirect;
+pub use rendered::Rendered;
 pub use seek::ReadSeeker;
+pub use sparse::{FIELDS_PARAM, SparseJson};
 pub use text::Text;
 
 use crate::http::header::{CONTENT_TYPE, HeaderValue};
@@ -31,7 +44,12 @@ pub trait Writer {
 ///
 /// `Scribe` is simpler than [`Writer`] and it implements [`Writer`]. It does not require the use of Depot and Request.
 ///
-/// There are several built-in implementations of the `Scribe` trait.
+/// There are several built-in implementations of the `Scribe` trait. The text ones, like `&str`, `String`, [`Text`]
+/// and [`Json`], set `content-type` with an explicit `charset=utf-8`, but only if the response has no
+/// `content-type` yet, so set it before rendering to override the default.
+///
+/// `render` consumes the scribe, so a `Box<dyn Scribe>` can not be rendered, and a scribe is rendered only once.
+/// Use [`Rendered`] to keep a rendered response, like a cached error body, and render its clones.
 pub trait Scribe {
     /// Render data to [`Response`].
     fn render(self, res: &mut Response);
@@ -206,4 +224,24 @@ mod tests {
             "text/plain; charset=utf-8"
         );
     }
+
+    #[tokio::test]
+    async fn test_write_string_with_content_type() {
+        #[handler]
+        async fn test(res: &mut Response) -> String {
+            res.add_header("content-type", "text/plain; charset=us-ascii", true)
+                .unwrap();
+            "hello".to_owned()
+        }
+
+        let router = Router::new().push(Router::with_path("test").get(test));
+        let mut res = TestClient::get("http://127.0.0.1:5800/test")
+            .send(router)
+            .await;
+        assert_eq!(res.take_string().await.unwrap(), "hello");
+        assert_eq!(
+            res.headers().get("content-type").unwrap(),
+            "text/plain; charset=us-ascii"
+        );
+    }
 }
@@ -0,0 +1,242 @@
+use std::fmt::{self, Debug, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use super::{Json, Scribe};
+use crate::http::header::{HeaderName, HeaderValue, LINK};
+use crate::{Depot, Request, Response, Writer, async_trait};
+
+/// The `X-Total-Count` header name.
+pub const X_TOTAL_COUNT: HeaderName = HeaderName::from_static("x-total-count");
+
+/// Offset and limit of a page, usually parsed from the query of the request.
+///
+/// If `limit` is `None`, the page contains all the items from `offset`.
+#[derive(Serialize, Deserialize, Clone, Copy, Default, Eq, PartialEq, Debug)]
+#[non_exhaustive]
+pub struct Pagination {
+    /// The number of items skipped before the page.
+    pub offset: Option<usize>,
+    /// The maximum number of items in the page.
+    pub limit: Option<usize>,
+}
+
+impl Pagination {
+    /// Create a new `Pagination`.
+    #[inline]
+    pub fn new(offset: Option<usize>, limit: Option<usize>) -> Self {
+        Self { offset, limit }
+    }
+}
+
+/// Write a page of items as json content, with the
+/// [RFC 8288](https://datatracker.ietf.org/doc/html/rfc8288) `Link` header to the `first`, `prev`, `next` and
+/// `last` pages.
+///
+/// The links keep the path and the other query parameters of the request, only `offset` and `limit` are replaced.
+/// `prev` is omitted on the first page and `next` on the last one. No link is written if the limit is not set or
+/// is zero. Use [`Paginated::total_count`] to write the total count in the `X-Total-Count` header too.
+///
+/// # Example
+///
+/// ```
+/// use salvo_core::prelude::*;
+/// use salvo_core::writing::{Paginated, Pagination};
+///
+/// #[handler]
+/// async fn list_todos(req: &mut Request) -> Paginated<Vec<u64>> {
+///     let pagination = req.parse_queries::<Pagination>().unwrap_or_default();
+///     let todos = (0..100).collect::<Vec<u64>>();
+///     let page = todos
+///         .iter()
+///         .copied()
+///         .skip(pagination.offset.unwrap_or(0))
+///         .take(pagination.limit.unwrap_or(usize::MAX))
+///         .collect();
+///     Paginated::new(page, todos.len(), pagination).total_count(true)
+/// }
+/// ```
+pub struct Paginated<T> {
+    items: T,
+    total: usize,
+    pagination: Pagination,
+    total_count: bool,
+}
+
+impl<T> Paginated<T> {
+    /// Create a new `Paginated` with the items of the page, the total count of all the items and the pagination
+    /// of the request.
+    #[inline]
+    pub fn new(items: T, total: usize, pagination: Pagination) -> Self {
+        Self {
+            items,
+            total,
+            pagination,
+            total_count: false,
+        }
+    }
+
+    /// Set whether to write the total count in the `X-Total-Count` header, it is not written by default.
+    #[inline]
+    pub fn total_count(mut self, enabled: bool) -> Self {
+        self.total_count = enabled;
+        self
+    }
+
+    // The relations and the offsets of the linked pages.
+    fn links(&self) -> Vec<(&'static str, usize)> {
+        let limit = match self.pagination.limit {
+            Some(limit) if limit > 0 => limit,
+            _ => return vec![],
+        };
+        let offset = self.pagination.offset.unwrap_or(0);
+        let last = self.total.saturating_sub(1) / limit * limit;
+        let mut links = vec![("first", 0)];
+        if offset > 0 {
+            links.push(("prev", offset.saturating_sub(limit).min(last)));
+        }
+        // The offset and the limit are from the client, so they may overflow.
+        if offset.saturating_add(limit) < self.total {
+            links.push(("next", offset + limit));
+        }
+        links.push(("last", last));
+        links
+    }
+}
+
+impl<T: Debug> Debug for Paginated<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("Paginated")
+            .field("items", &self.items)
+            .field("total", &self.total)
+            .field("pagination", &self.pagination)
+            .field("total_count", &self.total_count)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl<T> Writer for Paginated<T>
+where
+    T: Serialize + Send,
+{
+    async fn write(self, req: &mut Request, _depot: &mut Depot, res: &mut Response) {
+        let limit = self.pagination.limit.unwrap_or_default();
+        for (rel, offset) in self.links() {
+            let url = page_url(req, offset, limit);
+            if let Ok(value) = HeaderValue::from_str(&format!(r#"<{url}>; rel="{rel}""#)) {
+                res.headers_mut().append(LINK, value);
+            }
+        }
+        if self.total_count {
+            res.headers_mut()
+                .insert(X_TOTAL_COUNT, HeaderValue::from(self.total));
+        }
+        Json(self.items).render(res);
+    }
+}
+
+// Build the url of the page, it keeps the path and the other query parameters of the request.
+fn page_url(req: &Request, offset: usize, limit: usize) -> String {
+    let mut serializer = form_urlencoded::Serializer::new(String::new());
+    if let Some(query) = req.uri().query() {
+        for (key, value) in form_urlencoded::parse(query.as_bytes()) {
+            if key != "offset" && key != "limit" {
+                serializer.append_pair(&key, &value);
+            }
+        }
+    }
+    serializer.append_pair("offset", &offset.to_string());
+    serializer.append_pair("limit", &limit.to_string());
+    format!("{}?{}", req.uri().path(), serializer.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use crate::test::{ResponseExt, TestClient};
+
+    #[handler]
+    async fn list_todos(req: &mut Request) -> Paginated<Vec<usize>> {
+        let pagination = req.parse_queries::<Pagination>().unwrap_or_default();
+        let todos = (0..25).collect::<Vec<_>>();
+        let page = todos
+            .iter()
+            .copied()
+            .skip(pagination.offset.unwrap_or(0))
+            .take(pagination.limit.unwrap_or(usize::MAX))
+            .collect();
+        Paginated::new(page, todos.len(), pagination).total_count(true)
+    }
+
+    async fn access(service: &Service, query: &str) -> Response {
+        TestClient::get(format!("http://127.0.0.1:5800/todos?{query}"))
+            .send(service)
+            .await
+    }
+
+    fn links(res: &Response) -> Vec<&str> {
+        res.headers()
+            .get_all(LINK)
+            .iter()
+            .map(|v| v.to_str().unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_write_paginated() {
+        let service = Service::new(Router::with_path("todos").get(list_todos));
+
+        let mut res = access(&service, "done=true&offset=10&limit=10").await;
+        assert_eq!(
+            links(&res),
+            vec![
+                r#"</todos?done=true&offset=0&limit=10>; rel="first""#,
+                r#"</todos?done=true&offset=0&limit=10>; rel="prev""#,
+                r#"</todos?done=true&offset=20&limit=10>; rel="next""#,
+                r#"</todos?done=true&offset=20&limit=10>; rel="last""#,
+            ]
+        );
+        assert_eq!(res.headers()[X_TOTAL_COUNT], "25");
+        assert_eq!(
+            res.take_string().await.unwrap(),
+            "[10,11,12,13,14,15,16,17,18,19]"
+        );
+
+        let res = access(&service, "limit=10").await;
+        assert_eq!(
+            links(&res),
+            vec![
+                r#"</todos?offset=0&limit=10>; rel="first""#,
+                r#"</todos?offset=10&limit=10>; rel="next""#,
+                r#"</todos?offset=20&limit=10>; rel="last""#,
+            ]
+        );
+
+        let res = access(&service, "offset=20&limit=10").await;
+        assert_eq!(
+            links(&res),
+            vec![
+                r#"</todos?offset=0&limit=10>; rel="first""#,
+                r#"</todos?offset=10&limit=10>; rel="prev""#,
+                r#"</todos?offset=20&limit=10>; rel="last""#,
+            ]
+        );
+
+        let res = access(&service, "").await;
+        assert!(links(&res).is_empty());
+        assert_eq!(res.headers()[X_TOTAL_COUNT], "25");
+
+        let res = access(&service, &format!("offset={}&limit=10", usize::MAX)).await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+        assert_eq!(
+            links(&res),
+            vec![
+                r#"</todos?offset=0&limit=10>; rel="first""#,
+                r#"</todos?offset=20&limit=10>; rel="prev""#,
+                r#"</todos?offset=20&limit=10>; rel="last""#,
+            ]
+        );
+    }
+}
@@ -0,0 +1,216 @@
+use std::collections::VecDeque;
+
+use bytes::Bytes;
+
+use super::Scribe;
+use crate::http::header::HeaderMap;
+use crate::http::{ResBody, Response, StatusCode, StatusError};
+
+/// A response rendered ahead of time, it can be cloned and rendered many times, like a cached error body.
+///
+/// [`Scribe`] and [`Writer`](super::Writer) consume themselves when rendering, so they can not be used as trait
+/// objects like `Box<dyn Scribe>` and rendered again. `Rendered` keeps the status code, the headers and the body
+/// written by a scribe instead, rendering it sets the status code, replaces the headers of the same names and
+/// replaces the body.
+///
+/// Use [`Rendered::new`] to render a scribe, or convert a [`Response`] with [`TryFrom`] to keep the status code
+/// too. Only the bodies in memory can be kept, so the streaming bodies are not supported. The cookies are not
+/// kept, set them in the handler.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::LazyLock;
+///
+/// use salvo_core::prelude::*;
+/// use salvo_core::writing::Rendered;
+///
+/// static MAINTENANCE: LazyLock<Rendered> = LazyLock::new(|| {
+///     let mut res = Response::new();
+///     res.status_code(StatusCode::SERVICE_UNAVAILABLE)
+///         .render(Json(serde_json::json!({"error": "under maintenance"})));
+///     Rendered::try_from(res).expect("body should be in memory")
+/// });
+///
+/// #[handler]
+/// async fn maintenance(res: &mut Response) {
+///     res.render(MAINTENANCE.clone());
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Rendered {
+    status_code: Option<StatusCode>,
+    headers: HeaderMap,
+    body: RenderedBody,
+}
+
+#[derive(Clone, Debug)]
+enum RenderedBody {
+    None,
+    Once(Bytes),
+    Chunks(VecDeque<Bytes>),
+    // The cause of the error is not kept, it is only for debugging.
+    Error {
+        code: StatusCode,
+        name: String,
+        brief: String,
+        detail: Option<String>,
+    },
+}
+
+impl Rendered {
+    /// Render `scribe` ahead of time.
+    ///
+    /// # Panics
+    ///
+    /// If `scribe` writes a streaming body.
+    pub fn new(scribe: impl Scribe) -> Self {
+        let mut res = Response::new();
+        scribe.render(&mut res);
+        match Self::try_from(res) {
+            Ok(rendered) => rendered,
+            Err(e) => panic!("{e}"),
+        }
+    }
+
+    /// Get the status code.
+    #[inline]
+    pub fn status_code(&self) -> Option<StatusCode> {
+        self.status_code
+    }
+
+    /// Get the headers.
+    #[inline]
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+}
+
+impl TryFrom<Response> for Rendered {
+    type Error = crate::Error;
+
+    /// Keep the status code, the headers and the body of `res`, it fails if the body is streaming.
+    fn try_from(mut res: Response) -> Result<Self, Self::Error> {
+        let body = match res.take_body() {
+            ResBody::None => RenderedBody::None,
+            ResBody::Once(bytes) => RenderedBody::Once(bytes),
+            ResBody::Chunks(chunks) => RenderedBody::Chunks(chunks),
+            ResBody::Error(e) => RenderedBody::Error {
+                code: e.code,
+                name: e.name,
+                brief: e.brief,
+                detail: e.detail,
+            },
+            _ => {
+                return Err(crate::Error::other(
+                    "streaming body can not be rendered again",
+                ));
+            }
+        };
+        Ok(Self {
+            status_code: res.status_code,
+            headers: res.headers,
+            body,
+        })
+    }
+}
+
+impl Scribe for Rendered {
+    fn render(self, res: &mut Response) {
+        let Self {
+            status_code,
+            headers,
+            body,
+        } = self;
+        if let Some(code) = status_code {
+            res.status_code(code);
+        }
+        res.headers_mut().extend(headers);
+        let body = match body {
+            RenderedBody::None => ResBody::None,
+            RenderedBody::Once(bytes) => ResBody::Once(bytes),
+            RenderedBody::Chunks(chunks) => ResBody::Chunks(chunks),
+            RenderedBody::Error {
+                code,
+                name,
+                brief,
+                detail,
+            } => ResBody::Error(StatusError {
+                code,
+                name,
+                brief,
+                detail,
+                cause: None,
+            }),
+        };
+        res.replace_body(body);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::LazyLock;
+
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::http::header::{CONTENT_TYPE, HeaderValue};
+    use crate::prelude::*;
+    use crate::test::{ResponseExt, TestClient};
+    use crate::writing::Chunked;
+
+    static UNAVAILABLE: LazyLock<Rendered> = LazyLock::new(|| {
+        let mut res = Response::new();
+        res.status_code(StatusCode::SERVICE_UNAVAILABLE)
+            .render(Json(json!({"error": "under maintenance"})));
+        Rendered::try_from(res).unwrap()
+    });
+
+    #[handler]
+    async fn unavailable(res: &mut Response) {
+        res.render(UNAVAILABLE.clone());
+    }
+    #[handler]
+    async fn forbidden(res: &mut Response) {
+        res.render(Rendered::new(StatusError::forbidden().brief("no access")));
+    }
+
+    #[tokio::test]
+    async fn test_write_rendered() {
+        let router = Router::new()
+            .push(Router::with_path("json").get(unavailable))
+            .push(Router::with_path("error").get(forbidden));
+        let service = Service::new(router);
+
+        for _ in 0..2 {
+            let mut res = TestClient::get("http://127.0.0.1:5800/json")
+                .send(&service)
+                .await;
+            assert_eq!(res.status_code, Some(StatusCode::SERVICE_UNAVAILABLE));
+            assert_eq!(
+                res.headers().get(CONTENT_TYPE).unwrap(),
+                "application/json; charset=utf-8"
+            );
+            assert_eq!(
+                res.take_json::<Value>().await.unwrap(),
+                json!({"error": "under maintenance"})
+            );
+        }
+        assert_eq!(
+            UNAVAILABLE.status_code(),
+            Some(StatusCode::SERVICE_UNAVAILABLE)
+        );
+
+        let mut res = TestClient::get("http://127.0.0.1:5800/error")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::FORBIDDEN));
+        assert!(res.take_string().await.unwrap().contains("no access"));
+
+        let mut res = Response::new();
+        res.headers_mut()
+            .insert(CONTENT_TYPE, HeaderValue::from_static("text/plain"));
+        Chunked::new(futures_util::stream::iter([Ok::<_, std::io::Error>("a")])).render(&mut res);
+        assert!(Rendered::try_from(res).is_err());
+    }
+}
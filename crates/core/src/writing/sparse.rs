@@ -0,0 +1,193 @@
+use std::fmt::{self, Debug, Formatter};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::{Json, Scribe};
+use crate::http::StatusError;
+use crate::{Depot, Request, Response, Writer, async_trait};
+
+/// The query parameter of the field list of [`SparseJson`].
+pub const FIELDS_PARAM: &str = "fields";
+
+/// Write serializable content to response as json content, with only the fields requested by the client.
+///
+/// The fields are read from the `fields` query parameter as a comma separated list, like `?fields=id,text`, or set
+/// by [`SparseJson::fields`]. If the value is an object, only the requested fields of it are written, if it is an
+/// array, only the requested fields of each object in it are written. The unknown fields are ignored, and the whole
+/// value is written if no field is requested.
+///
+/// Only the top-level fields are supported, nested fields like `owner.name` are not, they are matched as the
+/// literal field names, so a nested object is either written as a whole or omitted.
+///
+/// # Example
+///
+/// ```
+/// use salvo_core::prelude::*;
+/// use salvo_core::writing::SparseJson;
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Todo {
+///     id: u64,
+///     text: String,
+///     completed: bool,
+/// }
+///
+/// #[handler]
+/// async fn list_todos() -> SparseJson<Vec<Todo>> {
+///     SparseJson::new(vec![Todo {
+///         id: 1,
+///         text: "write docs".into(),
+///         completed: false,
+///     }])
+/// }
+/// ```
+pub struct SparseJson<T> {
+    value: T,
+    fields: Option<Vec<String>>,
+}
+
+impl<T> SparseJson<T> {
+    /// Create a new `SparseJson`, the fields are read from the `fields` query parameter of the request.
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            fields: None,
+        }
+    }
+
+    /// Set the requested fields, the `fields` query parameter is not read if they are set.
+    #[inline]
+    pub fn fields<I, F>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = F>,
+        F: Into<String>,
+    {
+        self.fields = Some(fields.into_iter().map(Into::into).collect());
+        self
+    }
+}
+
+impl<T: Debug> Debug for SparseJson<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("SparseJson")
+            .field("value", &self.value)
+            .field("fields", &self.fields)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl<T> Writer for SparseJson<T>
+where
+    T: Serialize + Send,
+{
+    async fn write(self, req: &mut Request, _depot: &mut Depot, res: &mut Response) {
+        let fields = self.fields.unwrap_or_else(|| {
+            req.query::<String>(FIELDS_PARAM)
+                .map(|fields| {
+                    fields
+                        .split(',')
+                        .map(|field| field.trim().to_owned())
+                        .filter(|field| !field.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default()
+        });
+        if fields.is_empty() {
+            Json(self.value).render(res);
+            return;
+        }
+        match serde_json::to_value(&self.value) {
+            Ok(mut value) => {
+                select_fields(&mut value, &fields);
+                Json(value).render(res);
+            }
+            Err(e) => {
+                tracing::error!(error = ?e, "SparseJson write error");
+                res.render(StatusError::internal_server_error());
+            }
+        }
+    }
+}
+
+// Keep only the fields of the object, or of each object in the array.
+fn select_fields(value: &mut Value, fields: &[String]) {
+    match value {
+        Value::Object(map) => map.retain(|key, _| fields.iter().any(|field| field == key)),
+        Value::Array(items) => {
+            for item in items {
+                if let Value::Object(map) = item {
+                    map.retain(|key, _| fields.iter().any(|field| field == key));
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::prelude::*;
+    use crate::test::{ResponseExt, TestClient};
+
+    #[handler]
+    async fn list_todos() -> SparseJson<Value> {
+        SparseJson::new(json!([
+            {"id": 1, "text": "write docs", "owner": {"name": "jobs"}},
+            {"id": 2, "text": "review", "owner": {"name": "ive"}},
+        ]))
+    }
+    #[handler]
+    async fn show_todo() -> SparseJson<Value> {
+        SparseJson::new(json!({"id": 1, "text": "write docs", "completed": false}))
+            .fields(["id", "completed"])
+    }
+
+    #[tokio::test]
+    async fn test_write_sparse_json() {
+        let router = Router::new()
+            .push(Router::with_path("todos").get(list_todos))
+            .push(Router::with_path("todo").get(show_todo));
+        let service = Service::new(router);
+
+        let mut res =
+            TestClient::get("http://127.0.0.1:5800/todos?fields=id,%20owner.name,unknown")
+                .send(&service)
+                .await;
+        assert_eq!(
+            res.headers().get("content-type").unwrap(),
+            "application/json; charset=utf-8"
+        );
+        assert_eq!(res.take_string().await.unwrap(), r#"[{"id":1},{"id":2}]"#);
+
+        let mut res = TestClient::get("http://127.0.0.1:5800/todos?fields=text,owner")
+            .send(&service)
+            .await;
+        assert_eq!(
+            res.take_json::<Value>().await.unwrap(),
+            json!([
+                {"text": "write docs", "owner": {"name": "jobs"}},
+                {"text": "review", "owner": {"name": "ive"}},
+            ])
+        );
+
+        let mut res = TestClient::get("http://127.0.0.1:5800/todos")
+            .send(&service)
+            .await;
+        assert_eq!(res.take_json::<Value>().await.unwrap()[1]["id"], 2);
+
+        let mut res = TestClient::get("http://127.0.0.1:5800/todo?fields=text")
+            .send(&service)
+            .await;
+        assert_eq!(
+            res.take_json::<Value>().await.unwrap(),
+            json!({"id": 1, "completed": false})
+        );
+    }
+}
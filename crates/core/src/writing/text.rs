@@ -1,11 +1,14 @@
 use std::fmt::{self, Debug, Display, Formatter};
 
-use super::{Scribe, try_set_header};
+use super::{Attachment, Scribe, try_set_header};
 use crate::http::Response;
 use crate::http::header::{CONTENT_TYPE, HeaderValue};
 
 /// Write text content to response as text content.
 ///
+/// The `content-type` of each variant is only set if the response has no `content-type` yet, set it before
+/// rendering to use another one.
+///
 /// # Example
 ///
 /// ```
@@ -40,6 +43,14 @@ pub enum Text<C> {
     Rdf(C),
 }
 
+impl<C> Text<C> {
+    /// Write the text content as an attachment with the file name, see [`Attachment`].
+    #[inline]
+    pub fn attachment(self, name: impl Into<String>) -> Attachment<Self> {
+        Attachment::new(name, self)
+    }
+}
+
 impl<C> Text<C>
 where
     C: AsRef<str>,
@@ -240,4 +251,23 @@ mod tests {
             "text/html; charset=utf-8"
         );
     }
+
+    #[tokio::test]
+    async fn test_write_html_text_with_content_type() {
+        #[handler]
+        async fn test(res: &mut Response) {
+            res.add_header("content-type", "text/html; charset=utf-16", true)
+                .unwrap();
+            res.render(Text::Html("<html><body>hello</body></html>"));
+        }
+
+        let router = Router::new().push(Router::with_path("test").get(test));
+        let res = TestClient::get("http://127.0.0.1:5800/test")
+            .send(router)
+            .await;
+        assert_eq!(
+            res.headers().get("content-type").unwrap(),
+            "text/html; charset=utf-16"
+        );
+    }
 }
@@ -1,6 +1,5 @@
 use cookie::time::Duration;
 use cookie::{Cookie, Expiration, SameSite};
-use salvo_core::http::uri::Scheme;
 use salvo_core::{Depot, Error, Request, Response};
 
 use crate::CsrfCipher;
@@ -82,7 +81,7 @@ impl CsrfStore for CookieStore {
         token: &str,
         proof: &str,
     ) -> Result<(), Self::Error> {
-        let secure = req.uri().scheme() == Some(&Scheme::HTTPS);
+        let secure = req.is_secure();
         let expires = cookie::time::OffsetDateTime::now_utc() + self.ttl;
         let cookie_builder = Cookie::build((self.name.clone(), format!("{token}.{proof}")))
             .http_only(true)
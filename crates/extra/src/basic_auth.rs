@@ -152,7 +152,7 @@ where
     async fn handle(&self, req: &mut Request, depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl) {
         if let Ok((username, password)) = self.parse_credentials(req) {
             if self.validator.validate(&username, &password, depot).await {
-                depot.insert(USERNAME_KEY, username);
+                depot.insert_cloneable(USERNAME_KEY, username);
                 ctrl.call_next(req, depot, res).await;
                 return;
             }
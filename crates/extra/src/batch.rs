@@ -0,0 +1,331 @@
+//! Handler for batch requests.
+//!
+//! [`Batch`] accepts a JSON array of sub-requests, dispatches each of them through a [`Service`] and responds with
+//! a JSON array of sub-responses in the same order, so the clients can send several requests in one round-trip:
+//!
+//! ```json
+//! [
+//!     {"method": "POST", "path": "/todos", "body": {"text": "write docs"}},
+//!     {"method": "GET", "path": "/todos?limit=10"}
+//! ]
+//! ```
+//!
+//! ```json
+//! [
+//!     {"status": 201, "headers": {"content-type": "application/json; charset=utf-8"}, "body": {"id": 1}},
+//!     {"status": 200, "headers": {"content-type": "application/json; charset=utf-8"}, "body": [{"id": 1}]}
+//! ]
+//! ```
+//!
+//! The sub-requests are dispatched one by one, so the later ones see the changes of the earlier ones. Each of them
+//! is isolated: an invalid or failed sub-request, even one which panics, only makes its own sub-response an error,
+//! and the batch continues. The batch itself responds `200 OK` as long as the array is valid.
+//!
+//! The sub-requests inherit the headers of the batch request except the `content-*` ones, like `authorization` and
+//! `cookie`, and the headers of the sub-request replace them. The `body` is sent as JSON, and the JSON bodies of
+//! the sub-responses are embedded as they are, the other bodies are embedded as strings.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use salvo_core::prelude::*;
+//! use salvo_extra::batch::Batch;
+//!
+//! #[handler]
+//! async fn list_todos() -> Json<Vec<u64>> {
+//!     Json(vec![1, 2, 3])
+//! }
+//!
+//! fn api() -> Router {
+//!     Router::with_path("todos").get(list_todos)
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let router = Router::new()
+//!         .push(Router::with_path("batch").post(Batch::new(Service::new(api()))))
+//!         .push(api());
+//!
+//!     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
+//!     Server::new(acceptor).serve(router).await;
+//! }
+//! ```
+use std::collections::{BTreeMap, HashMap};
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
+
+use futures_util::FutureExt;
+use http_body_util::BodyExt;
+use salvo_core::http::header::{self, HeaderMap, HeaderName, HeaderValue};
+use salvo_core::http::{mime, Method, Mime, Request, Response, StatusCode, StatusError};
+use salvo_core::routing::FlowCtrl;
+use salvo_core::writing::Json;
+use salvo_core::{async_trait, Depot, Handler, Service, Writer};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The default maximum number of sub-requests in a batch.
+pub const DEFAULT_MAX_REQUESTS: usize = 20;
+
+/// A sub-request of a batch.
+#[derive(Deserialize, Clone, Debug)]
+#[non_exhaustive]
+pub struct BatchRequest {
+    /// The method of the sub-request, `GET` by default.
+    #[serde(default = "default_method")]
+    pub method: String,
+    /// The path and query of the sub-request, it must start with `/`.
+    pub path: String,
+    /// The headers of the sub-request.
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// The JSON body of the sub-request.
+    #[serde(default)]
+    pub body: Option<Value>,
+}
+
+fn default_method() -> String {
+    "GET".into()
+}
+
+/// A sub-response of a batch.
+#[derive(Serialize, Clone, Debug)]
+#[non_exhaustive]
+pub struct BatchResponse {
+    /// The status code of the sub-response.
+    pub status: u16,
+    /// The headers of the sub-response, the values of a repeated header are joined by `, `.
+    pub headers: BTreeMap<String, String>,
+    /// The body of the sub-response, `null` if it is empty.
+    pub body: Value,
+}
+
+impl BatchResponse {
+    fn error(error: StatusError) -> Self {
+        Self {
+            status: error.code.as_u16(),
+            headers: BTreeMap::new(),
+            body: Value::String(error.brief),
+        }
+    }
+}
+
+/// Handler for dispatching batch requests through a [`Service`].
+///
+/// View [module level documentation](index.html) for more details.
+pub struct Batch {
+    service: Arc<Service>,
+    max_requests: usize,
+}
+
+impl Batch {
+    /// Create a new `Batch` which dispatches the sub-requests through the service.
+    #[inline]
+    pub fn new(service: impl Into<Arc<Service>>) -> Self {
+        Self {
+            service: service.into(),
+            max_requests: DEFAULT_MAX_REQUESTS,
+        }
+    }
+
+    /// Set the maximum number of sub-requests in a batch, `413 Payload Too Large` is returned for larger batches.
+    #[inline]
+    pub fn max_requests(mut self, max_requests: usize) -> Self {
+        self.max_requests = max_requests;
+        self
+    }
+
+    // Build the request of the sub-request, it inherits the headers of the batch request.
+    fn build_request(&self, req: &Request, sub: BatchRequest) -> Result<Request, StatusError> {
+        let method = Method::from_bytes(sub.method.as_bytes())
+            .map_err(|_| StatusError::bad_request().brief(format!("Invalid method `{}`.", sub.method)))?;
+        if !sub.path.starts_with('/') {
+            return Err(StatusError::bad_request().brief(format!("Invalid path `{}`.", sub.path)));
+        }
+        let mut builder = hyper::Request::builder().method(method).uri(&sub.path);
+        let headers = builder.headers_mut().expect("request builder should be valid");
+        for (name, value) in req.headers() {
+            if !name.as_str().starts_with("content-") {
+                headers.append(name, value.clone());
+            }
+        }
+        for (name, value) in &sub.headers {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|_| StatusError::bad_request().brief(format!("Invalid header name `{name}`.")))?;
+            let value = HeaderValue::from_str(value)
+                .map_err(|_| StatusError::bad_request().brief(format!("Invalid value of header `{name}`.")))?;
+            headers.insert(name, value);
+        }
+        let body = match &sub.body {
+            Some(body) => {
+                if !headers.contains_key(header::CONTENT_TYPE) {
+                    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("application/json"));
+                }
+                serde_json::to_vec(body).expect("json value should be serialized")
+            }
+            None => vec![],
+        };
+        let sub_req = builder
+            .body(body)
+            .map_err(|_| StatusError::bad_request().brief(format!("Invalid path `{}`.", sub.path)))?;
+        let mut sub_req = Request::from_hyper(sub_req, req.scheme().clone());
+        *sub_req.local_addr_mut() = req.local_addr().clone();
+        *sub_req.remote_addr_mut() = req.remote_addr().clone();
+        Ok(sub_req)
+    }
+
+    async fn dispatch(&self, req: &Request, sub: BatchRequest) -> BatchResponse {
+        let sub_req = match self.build_request(req, sub) {
+            Ok(sub_req) => sub_req,
+            Err(e) => return BatchResponse::error(e),
+        };
+        let res = match AssertUnwindSafe(self.service.handle(sub_req)).catch_unwind().await {
+            Ok(res) => res,
+            Err(e) => {
+                tracing::error!(error = ?e, "panic occurred in batch sub-request");
+                return BatchResponse::error(
+                    StatusError::internal_server_error().brief("Panic occurred on server."),
+                );
+            }
+        };
+        let (parts, body) = res.into_hyper().into_parts();
+        let bytes = match body.collect().await {
+            Ok(body) => body.to_bytes(),
+            Err(e) => {
+                tracing::error!(error = ?e, "read batch sub-response body failed");
+                return BatchResponse::error(
+                    StatusError::internal_server_error().brief("Read sub-response body failed."),
+                );
+            }
+        };
+        BatchResponse {
+            status: parts.status.as_u16(),
+            body: response_body(&parts.headers, &bytes),
+            headers: response_headers(&parts.headers),
+        }
+    }
+}
+
+fn response_headers(headers: &HeaderMap) -> BTreeMap<String, String> {
+    let mut map = BTreeMap::<String, String>::new();
+    for (name, value) in headers {
+        let value = String::from_utf8_lossy(value.as_bytes());
+        map.entry(name.as_str().to_owned())
+            .and_modify(|values| {
+                values.push_str(", ");
+                values.push_str(&value);
+            })
+            .or_insert_with(|| value.into_owned());
+    }
+    map
+}
+
+fn response_body(headers: &HeaderMap, bytes: &[u8]) -> Value {
+    if bytes.is_empty() {
+        return Value::Null;
+    }
+    let is_json = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|ctype| ctype.to_str().ok())
+        .and_then(|ctype| ctype.parse::<Mime>().ok())
+        .is_some_and(|ctype| ctype.subtype() == mime::JSON || ctype.suffix() == Some(mime::JSON));
+    if is_json {
+        if let Ok(value) = serde_json::from_slice(bytes) {
+            return value;
+        }
+    }
+    Value::String(String::from_utf8_lossy(bytes).into_owned())
+}
+
+#[async_trait]
+impl Handler for Batch {
+    async fn handle(&self, req: &mut Request, depot: &mut Depot, res: &mut Response, _ctrl: &mut FlowCtrl) {
+        let requests = match req.parse_json::<Vec<BatchRequest>>().await {
+            Ok(requests) => requests,
+            Err(e) => {
+                e.write(req, depot, res).await;
+                return;
+            }
+        };
+        if requests.len() > self.max_requests {
+            res.render(
+                StatusError::payload_too_large()
+                    .brief(format!("A batch can contain at most {} requests.", self.max_requests)),
+            );
+            return;
+        }
+        let mut responses = Vec::with_capacity(requests.len());
+        for sub in requests {
+            responses.push(self.dispatch(req, sub).await);
+        }
+        res.status_code(StatusCode::OK);
+        res.render(Json(responses));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use salvo_core::prelude::*;
+    use salvo_core::test::{ResponseExt, TestClient};
+    use serde_json::json;
+
+    use super::*;
+
+    #[handler]
+    async fn create_todo(req: &mut Request, res: &mut Response) {
+        let todo = req.parse_json::<Value>().await.unwrap();
+        res.status_code(StatusCode::CREATED);
+        res.render(Json(json!({"text": todo["text"], "user": req.header::<String>("x-user")})));
+    }
+    #[handler]
+    async fn list_todos() -> &'static str {
+        "todos"
+    }
+    #[handler]
+    async fn panic_todo() {
+        panic!("panic in handler");
+    }
+
+    #[tokio::test]
+    async fn test_batch() {
+        let api = Router::with_path("todos")
+            .get(list_todos)
+            .post(create_todo)
+            .push(Router::with_path("panic").get(panic_todo));
+        let router = Router::with_path("batch").post(Batch::new(Service::new(api)).max_requests(5));
+        let service = Service::new(router);
+
+        let mut res = TestClient::post("http://127.0.0.1:5801/batch")
+            .add_header("x-user", "jobs", true)
+            .json(&json!([
+                {"method": "POST", "path": "/todos", "body": {"text": "write docs"}},
+                {"path": "/todos/panic"},
+                {"method": "BAD METHOD", "path": "/todos"},
+                {"path": "/missing"},
+                {"path": "/todos", "headers": {"accept": "text/plain"}},
+            ]))
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+        let responses: Value = res.take_json().await.unwrap();
+        assert_eq!(responses[0]["status"], 201);
+        assert_eq!(responses[0]["body"], json!({"text": "write docs", "user": "jobs"}));
+        assert_eq!(responses[1]["status"], 500);
+        assert_eq!(responses[2]["status"], 400);
+        assert_eq!(responses[3]["status"], 404);
+        assert_eq!(responses[4]["status"], 200);
+        assert_eq!(responses[4]["headers"]["content-type"], "text/plain; charset=utf-8");
+        assert_eq!(responses[4]["body"], "todos");
+
+        let res = TestClient::post("http://127.0.0.1:5801/batch")
+            .json(&vec![json!({"path": "/todos"}); 6])
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::PAYLOAD_TOO_LARGE));
+        let res = TestClient::post("http://127.0.0.1:5801/batch")
+            .json(&json!({"path": "/todos"}))
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::BAD_REQUEST));
+    }
+}
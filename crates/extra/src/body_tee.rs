@@ -0,0 +1,248 @@
+//! Middleware for teeing response bodies to another writer, like an audit sink.
+//!
+//! [`BodyTee`] runs after the handlers and copies the response body to an [`AsyncWrite`] opened for each response,
+//! while the body is still sent to the client. It only applies to the routes it is added to, so the responses of a
+//! subtree can be audited without touching the other routes:
+//!
+//! ```no_run
+//! use salvo_core::prelude::*;
+//! use salvo_extra::body_tee::BodyTee;
+//!
+//! #[handler]
+//! async fn statement() -> &'static str {
+//!     "statement"
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let tee = BodyTee::new(|_req: &Request, depot: &Depot, _res: &Response| {
+//!         let id = depot.get::<String>("request_id").ok()?;
+//!         let file = std::fs::File::create(format!("audit/{id}.body")).ok()?;
+//!         Some(tokio::fs::File::from_std(file))
+//!     });
+//!     let router = Router::new()
+//!         .push(Router::with_path("statements").hoop(tee).get(statement))
+//!         .push(Router::with_path("health").get(statement));
+//!
+//!     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
+//!     Server::new(acceptor).serve(router).await;
+//! }
+//! ```
+//!
+//! The writer is written and shut down in a spawned task, so it does not delay the response.
+//!
+//! - Buffered bodies, like the ones written by [`Text`](salvo_core::writing::Text) and
+//!   [`Json`](salvo_core::writing::Json), are already in memory, they are shared with the writer without copying.
+//! - Streamed bodies, such as files and proxied responses, are not buffered. Each chunk is passed to the writer when
+//!   it is sent to the client, and at most [`BodyTee::buffer`] chunks wait for the writer, so a slow writer slows
+//!   down the response instead of using more memory. The writer only gets the chunks sent before the client
+//!   disconnects.
+//!
+//! If the writer fails, the error is logged and the rest of the body is only sent to the client. The body is teed as
+//! written by the inner handlers, so add compression outside of this middleware to get the uncompressed body. Empty
+//! bodies and error bodies rendered by the catcher are not teed.
+use std::fmt::{self, Debug, Formatter};
+
+use futures_util::StreamExt;
+use salvo_core::handler::Skipper;
+use salvo_core::http::body::{BytesFrame, ResBody};
+use salvo_core::http::{Request, Response};
+use salvo_core::hyper::body::Bytes;
+use salvo_core::{async_trait, Depot, FlowCtrl, Handler};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::sync::mpsc;
+
+/// The default count of the chunks waiting for the writer.
+pub const DEFAULT_BUFFER: usize = 16;
+
+/// Middleware for teeing response bodies to another writer.
+///
+/// View [module level documentation](index.html) for more details.
+#[non_exhaustive]
+pub struct BodyTee<F> {
+    open: F,
+    /// The count of the chunks of a streamed body waiting for the writer.
+    pub buffer: usize,
+    /// Skipper to not tee the response of some requests.
+    pub skipper: Option<Box<dyn Skipper>>,
+}
+
+impl<F> Debug for BodyTee<F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BodyTee").field("buffer", &self.buffer).finish()
+    }
+}
+
+impl<F, W> BodyTee<F>
+where
+    F: Fn(&Request, &Depot, &Response) -> Option<W> + Send + Sync + 'static,
+    W: AsyncWrite + Send + Unpin + 'static,
+{
+    /// Create a new `BodyTee` with the function which opens the writer of a response, the response is not teed if it
+    /// returns `None`.
+    #[inline]
+    pub fn new(open: F) -> Self {
+        Self {
+            open,
+            buffer: DEFAULT_BUFFER,
+            skipper: None,
+        }
+    }
+
+    /// Set the count of the chunks of a streamed body waiting for the writer.
+    ///
+    /// # Panics
+    ///
+    /// If `buffer` is zero.
+    #[inline]
+    pub fn buffer(mut self, buffer: usize) -> Self {
+        assert!(buffer > 0, "buffer must be greater than zero");
+        self.buffer = buffer;
+        self
+    }
+
+    /// Set the skipper, the response is not teed if it returns `true`.
+    #[inline]
+    pub fn skipper(mut self, skipper: impl Skipper) -> Self {
+        self.skipper = Some(Box::new(skipper));
+        self
+    }
+}
+
+fn spawn_writer<W>(mut writer: W, mut rx: mpsc::Receiver<Bytes>)
+where
+    W: AsyncWrite + Send + Unpin + 'static,
+{
+    tokio::spawn(async move {
+        while let Some(bytes) = rx.recv().await {
+            if let Err(e) = writer.write_all(&bytes).await {
+                tracing::warn!(error = ?e, "failed to write teed response body");
+                return;
+            }
+        }
+        if let Err(e) = writer.shutdown().await {
+            tracing::warn!(error = ?e, "failed to shut down teed response body writer");
+        }
+    });
+}
+
+#[async_trait]
+impl<F, W> Handler for BodyTee<F>
+where
+    F: Fn(&Request, &Depot, &Response) -> Option<W> + Send + Sync + 'static,
+    W: AsyncWrite + Send + Unpin + 'static,
+{
+    async fn handle(&self, req: &mut Request, depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl) {
+        if self.skipper.as_ref().is_some_and(|skipper| skipper.skipped(req, depot)) {
+            return;
+        }
+        ctrl.call_next(req, depot, res).await;
+
+        let chunks = match &res.body {
+            ResBody::None | ResBody::Error(_) => return,
+            ResBody::Once(bytes) => Some(vec![bytes.clone()]),
+            ResBody::Chunks(chunks) => Some(chunks.iter().cloned().collect()),
+            _ => None,
+        };
+        let Some(writer) = (self.open)(req, depot, res) else {
+            return;
+        };
+        if let Some(chunks) = chunks {
+            let (tx, rx) = mpsc::channel(chunks.len().max(1));
+            for chunk in chunks {
+                let _ = tx.try_send(chunk);
+            }
+            spawn_writer(writer, rx);
+            return;
+        }
+
+        let (tx, rx) = mpsc::channel(self.buffer);
+        spawn_writer(writer, rx);
+        let body = res.take_body().then(move |frame| {
+            let tx = tx.clone();
+            async move {
+                if let Some(data) = frame.as_ref().ok().and_then(|frame| frame.data_ref()) {
+                    // The writer failed if the channel is closed, keep sending the body to the client.
+                    let _ = tx.send(data.clone()).await;
+                }
+                frame.map(BytesFrame)
+            }
+        });
+        res.body(ResBody::stream(body));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::pin::Pin;
+    use std::sync::{Arc, Mutex};
+    use std::task::{Context, Poll};
+
+    use salvo_core::prelude::*;
+    use salvo_core::test::{ResponseExt, TestClient};
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct Sink(Arc<Mutex<Vec<u8>>>);
+    impl AsyncWrite for Sink {
+        fn poll_write(self: Pin<&mut Self>, _cx: &mut Context<'_>, buf: &[u8]) -> Poll<std::io::Result<usize>> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Poll::Ready(Ok(buf.len()))
+        }
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+        fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[handler]
+    async fn chunks(res: &mut Response) {
+        res.write_body("[1,").unwrap();
+        res.write_body("2]").unwrap();
+    }
+    #[handler]
+    async fn stream(res: &mut Response) {
+        let items = (0..10).map(|i| Ok::<_, std::io::Error>(i.to_string()));
+        res.stream(tokio_stream::iter(items));
+    }
+
+    #[tokio::test]
+    async fn test_body_tee() {
+        let sink = Sink::default();
+        let tee = BodyTee::new({
+            let sink = sink.clone();
+            move |_req: &Request, _depot: &Depot, _res: &Response| Some(sink.clone())
+        })
+        .buffer(2)
+        .skipper(|req: &mut Request, _depot: &Depot| req.uri().path() == "/skipped");
+        let router = Router::new()
+            .hoop(tee)
+            .push(Router::with_path("chunks").get(chunks))
+            .push(Router::with_path("stream").get(stream))
+            .push(Router::with_path("skipped").get(chunks));
+        let service = Service::new(router);
+
+        async fn access(service: &Service, path: &str) -> String {
+            TestClient::get(format!("http://127.0.0.1:5801/{path}"))
+                .send(service)
+                .await
+                .take_string()
+                .await
+                .unwrap()
+        }
+        async fn teed(sink: &Sink) -> String {
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            String::from_utf8(std::mem::take(&mut *sink.0.lock().unwrap())).unwrap()
+        }
+
+        assert_eq!(access(&service, "chunks").await, "[1,2]");
+        assert_eq!(teed(&sink).await, "[1,2]");
+        assert_eq!(access(&service, "stream").await, "0123456789");
+        assert_eq!(teed(&sink).await, "0123456789");
+        assert_eq!(access(&service, "skipped").await, "[1,2]");
+        assert_eq!(teed(&sink).await, "");
+    }
+}
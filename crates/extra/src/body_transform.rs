@@ -0,0 +1,196 @@
+//! Middleware for transforming response bodies.
+//!
+//! [`BodyTransform`] runs after the handlers and passes the buffered response body to a function, which returns the
+//! new body. It is useful for cross-cutting transforms, such as injecting a CSP nonce into HTML or wrapping JSON in
+//! an envelope, without touching every handler. The function can also change the headers of the response, and the
+//! `Content-Length` header is removed after transforming, since it may not match the new body.
+//!
+//! Only buffered bodies are transformed, like the ones written by [`Text`](salvo_core::writing::Text) and
+//! [`Json`](salvo_core::writing::Json). Streamed bodies, such as files and server-sent events, are passed through
+//! untouched, so they are not buffered in memory. Bodies larger than [`BodyTransform::max_size`], empty bodies and
+//! bodies with `Content-Encoding` are passed through too. Use [`BodyTransform::skipper`] to skip some requests.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use salvo_core::hyper::body::Bytes;
+//! use salvo_core::prelude::*;
+//! use salvo_extra::body_transform::BodyTransform;
+//!
+//! #[handler]
+//! async fn hello() -> Text<&'static str> {
+//!     Text::Html(r#"<script nonce="{nonce}">console.log("hello")</script>"#)
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let transform = BodyTransform::new(|body: Bytes, _req: &Request, depot: &Depot, res: &mut Response| {
+//!         let is_html = res.content_type().is_some_and(|mime| mime.subtype() == "html");
+//!         match (is_html, depot.get::<String>("csp_nonce")) {
+//!             (true, Ok(nonce)) => String::from_utf8_lossy(&body).replace("{nonce}", nonce).into(),
+//!             _ => body,
+//!         }
+//!     });
+//!     let router = Router::new().hoop(transform).get(hello);
+//!
+//!     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
+//!     Server::new(acceptor).serve(router).await;
+//! }
+//! ```
+use std::fmt::{self, Debug, Formatter};
+
+use salvo_core::handler::Skipper;
+use salvo_core::http::body::ResBody;
+use salvo_core::http::header::{CONTENT_ENCODING, CONTENT_LENGTH};
+use salvo_core::http::{Request, Response};
+use salvo_core::hyper::body::Bytes;
+use salvo_core::{async_trait, Depot, FlowCtrl, Handler};
+
+/// The default maximum size of the body to transform, 1 MiB.
+pub const DEFAULT_MAX_SIZE: usize = 1024 * 1024;
+
+/// Middleware for transforming response bodies.
+///
+/// View [module level documentation](index.html) for more details.
+#[non_exhaustive]
+pub struct BodyTransform<F> {
+    transform: F,
+    /// The maximum size of the body to transform, larger bodies are passed through untouched.
+    pub max_size: usize,
+    /// Skipper to pass through the response of some requests.
+    pub skipper: Option<Box<dyn Skipper>>,
+}
+
+impl<F> Debug for BodyTransform<F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BodyTransform")
+            .field("max_size", &self.max_size)
+            .finish()
+    }
+}
+
+impl<F> BodyTransform<F>
+where
+    F: Fn(Bytes, &Request, &Depot, &mut Response) -> Bytes + Send + Sync + 'static,
+{
+    /// Create a new `BodyTransform` with the function which returns the new body.
+    #[inline]
+    pub fn new(transform: F) -> Self {
+        Self {
+            transform,
+            max_size: DEFAULT_MAX_SIZE,
+            skipper: None,
+        }
+    }
+
+    /// Set the maximum size of the body to transform.
+    #[inline]
+    pub fn max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Set the skipper, the response is passed through untouched if it returns `true`.
+    #[inline]
+    pub fn skipper(mut self, skipper: impl Skipper) -> Self {
+        self.skipper = Some(Box::new(skipper));
+        self
+    }
+}
+
+#[async_trait]
+impl<F> Handler for BodyTransform<F>
+where
+    F: Fn(Bytes, &Request, &Depot, &mut Response) -> Bytes + Send + Sync + 'static,
+{
+    async fn handle(&self, req: &mut Request, depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl) {
+        if self.skipper.as_ref().is_some_and(|skipper| skipper.skipped(req, depot)) {
+            return;
+        }
+        ctrl.call_next(req, depot, res).await;
+
+        if res.headers().contains_key(CONTENT_ENCODING) {
+            return;
+        }
+        let size = match &res.body {
+            ResBody::Once(bytes) => bytes.len(),
+            ResBody::Chunks(chunks) => chunks.iter().map(|chunk| chunk.len()).sum(),
+            _ => return,
+        };
+        if size == 0 || size > self.max_size {
+            return;
+        }
+        let body = match res.take_body() {
+            ResBody::Once(bytes) => bytes,
+            ResBody::Chunks(chunks) => {
+                let mut bytes = Vec::with_capacity(size);
+                for chunk in chunks {
+                    bytes.extend_from_slice(&chunk);
+                }
+                Bytes::from(bytes)
+            }
+            _ => unreachable!("only buffered body is transformed"),
+        };
+        let body = (self.transform)(body, req, depot, res);
+        res.headers_mut().remove(CONTENT_LENGTH);
+        res.body(ResBody::Once(body));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use salvo_core::prelude::*;
+    use salvo_core::test::{ResponseExt, TestClient};
+
+    use super::*;
+
+    #[handler]
+    async fn todos() -> Json<Vec<u64>> {
+        Json(vec![1, 2])
+    }
+    #[handler]
+    async fn chunks(res: &mut Response) {
+        res.write_body("[3,").unwrap();
+        res.write_body("4]").unwrap();
+    }
+    #[handler]
+    async fn large() -> String {
+        "a".repeat(64)
+    }
+    #[handler]
+    async fn stream(res: &mut Response) {
+        res.stream(tokio_stream::iter(vec![Ok::<_, std::io::Error>("[5]")]));
+    }
+
+    #[tokio::test]
+    async fn test_body_transform() {
+        let transform = BodyTransform::new(|body: Bytes, _req: &Request, _depot: &Depot, _res: &mut Response| {
+            format!(r#"{{"data":{}}}"#, String::from_utf8_lossy(&body)).into()
+        })
+        .max_size(32)
+        .skipper(|req: &mut Request, _depot: &Depot| req.uri().path() == "/skipped");
+        let router = Router::new()
+            .hoop(transform)
+            .push(Router::with_path("todos").get(todos))
+            .push(Router::with_path("chunks").get(chunks))
+            .push(Router::with_path("large").get(large))
+            .push(Router::with_path("stream").get(stream))
+            .push(Router::with_path("skipped").get(todos));
+        let service = Service::new(router);
+
+        async fn access(service: &Service, path: &str) -> String {
+            TestClient::get(format!("http://127.0.0.1:5801/{path}"))
+                .send(service)
+                .await
+                .take_string()
+                .await
+                .unwrap()
+        }
+
+        assert_eq!(access(&service, "todos").await, r#"{"data":[1,2]}"#);
+        assert_eq!(access(&service, "chunks").await, r#"{"data":[3,4]}"#);
+        assert_eq!(access(&service, "large").await, "a".repeat(64));
+        assert_eq!(access(&service, "stream").await, "[5]");
+        assert_eq!(access(&service, "skipped").await, "[1,2]");
+    }
+}
@@ -0,0 +1,244 @@
+//! Opaque cursors for keyset pagination.
+//!
+//! Keyset pagination continues a list after the last item of the previous page, like `WHERE id > ?`, which stays
+//! fast on large tables where `OFFSET` has to skip all the previous rows. [`Cursor`] encodes the position, usually
+//! the sort keys of the last item, into an opaque string which is sent to the client and sent back in the
+//! `cursor` query parameter, and [`CursorParam`] decodes it in the handler.
+//!
+//! # Encoding
+//!
+//! The position is serialized as JSON and encoded by URL-safe base64 without padding, so it can be put into the
+//! query without escaping. A cursor is opaque to the clients but it is **not** encrypted, anyone can decode it.
+//!
+//! # Signing
+//!
+//! An unsigned cursor can be changed by the clients, use [`Cursor::signed`] to append an HMAC-SHA256 signature to
+//! it, as `<payload>.<signature>`. A changed or unsigned cursor fails to decode, and [`CursorParam`] responds with
+//! `400 Bad Request`. Put the `Cursor` on the router as a middleware, so [`CursorParam`] decodes the cursors with
+//! its secret. Unsigned cursors are used if there is no `Cursor` middleware.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use salvo_core::prelude::*;
+//! use salvo_extra::cursor::{Cursor, CursorParam};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct After {
+//!     id: u64,
+//! }
+//!
+//! #[handler]
+//! async fn list_todos(after: CursorParam<After>, req: &mut Request, res: &mut Response) {
+//!     let start = after.into_inner().map(|after| after.id + 1).unwrap_or(0);
+//!     let todos: Vec<u64> = (start..start + 10).collect();
+//!     if let Some(last) = todos.last() {
+//!         let next = Cursor::from_request(req).encode(&After { id: *last });
+//!         res.add_header("x-next-cursor", next, true).ok();
+//!     }
+//!     res.render(Json(todos));
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let router = Router::with_path("todos").hoop(Cursor::signed("secret")).get(list_todos);
+//!
+//!     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
+//!     Server::new(acceptor).serve(router).await;
+//! }
+//! ```
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+use base64::engine::{general_purpose::URL_SAFE_NO_PAD, Engine};
+use hmac::{Hmac, Mac};
+use salvo_core::extract::{Extractible, Metadata};
+use salvo_core::http::{Request, Response, StatusError};
+use salvo_core::{async_trait, Depot, FlowCtrl, Handler};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sha2::Sha256;
+
+/// The query parameter of the cursor.
+pub const CURSOR_PARAM: &str = "cursor";
+
+/// Encode and decode opaque cursors.
+///
+/// It is also a middleware which makes [`CursorParam`] decode the cursors with it.
+///
+/// View [module level documentation](index.html) for more details.
+#[derive(Clone, Default)]
+pub struct Cursor {
+    secret: Option<Arc<Vec<u8>>>,
+}
+
+impl Debug for Cursor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cursor")
+            .field("signed", &self.secret.is_some())
+            .finish()
+    }
+}
+
+impl Cursor {
+    /// Create a new `Cursor` without signature.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new `Cursor` which signs the cursors with the secret.
+    #[inline]
+    pub fn signed(secret: impl Into<Vec<u8>>) -> Self {
+        Self {
+            secret: Some(Arc::new(secret.into())),
+        }
+    }
+
+    /// Get the `Cursor` set by the middleware on the request, or an unsigned one.
+    pub fn from_request(req: &Request) -> Self {
+        req.extensions().get::<Self>().cloned().unwrap_or_default()
+    }
+
+    /// Encode the value into a cursor.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value can not be serialized as JSON.
+    pub fn encode<T: Serialize>(&self, value: &T) -> String {
+        let payload = serde_json::to_vec(value).expect("cursor value should be serialized as json");
+        let mut cursor = URL_SAFE_NO_PAD.encode(&payload);
+        if let Some(mac) = self.mac() {
+            cursor.push('.');
+            cursor.push_str(&URL_SAFE_NO_PAD.encode(mac.chain_update(&payload).finalize().into_bytes()));
+        }
+        cursor
+    }
+
+    /// Decode the cursor, returns `None` if it is malformed or the signature does not match.
+    pub fn decode<T: DeserializeOwned>(&self, cursor: &str) -> Option<T> {
+        let (payload, signature) = match cursor.split_once('.') {
+            Some((payload, signature)) => (payload, Some(signature)),
+            None => (cursor, None),
+        };
+        let payload = URL_SAFE_NO_PAD.decode(payload).ok()?;
+        match (self.mac(), signature) {
+            (Some(mac), Some(signature)) => {
+                let signature = URL_SAFE_NO_PAD.decode(signature).ok()?;
+                mac.chain_update(&payload).verify_slice(&signature).ok()?;
+            }
+            (None, None) => {}
+            _ => return None,
+        }
+        serde_json::from_slice(&payload).ok()
+    }
+
+    fn mac(&self) -> Option<Hmac<Sha256>> {
+        self.secret
+            .as_ref()
+            .map(|secret| Hmac::<Sha256>::new_from_slice(secret).expect("HMAC can take key of any size"))
+    }
+}
+
+#[async_trait]
+impl Handler for Cursor {
+    async fn handle(&self, req: &mut Request, _depot: &mut Depot, _res: &mut Response, _ctrl: &mut FlowCtrl) {
+        req.extensions_mut().insert(self.clone());
+    }
+}
+
+/// Extract the decoded cursor from the `cursor` query parameter.
+///
+/// It is `None` if there is no cursor, which is usually the first page. `400 Bad Request` is returned if the cursor
+/// is malformed or its signature does not match.
+///
+/// View [module level documentation](index.html) for more details.
+#[derive(Clone, Debug)]
+pub struct CursorParam<T>(pub Option<T>);
+
+impl<T> CursorParam<T> {
+    /// Consume self and return the decoded cursor.
+    #[inline]
+    pub fn into_inner(self) -> Option<T> {
+        self.0
+    }
+}
+
+impl<'ex, T> Extractible<'ex> for CursorParam<T>
+where
+    T: DeserializeOwned + Send,
+{
+    fn metadata() -> &'ex Metadata {
+        static METADATA: Metadata = Metadata::new("");
+        &METADATA
+    }
+    #[allow(refining_impl_trait)]
+    async fn extract(req: &'ex mut Request) -> Result<Self, StatusError> {
+        let Some(cursor) = req.query::<String>(CURSOR_PARAM) else {
+            return Ok(Self(None));
+        };
+        Cursor::from_request(req)
+            .decode(&cursor)
+            .map(|value| Self(Some(value)))
+            .ok_or_else(|| StatusError::bad_request().brief("Invalid cursor."))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use salvo_core::prelude::*;
+    use salvo_core::test::{ResponseExt, TestClient};
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct After {
+        id: u64,
+    }
+
+    #[test]
+    fn test_encode_decode() {
+        let cursor = Cursor::new();
+        let encoded = cursor.encode(&After { id: 7 });
+        assert_eq!(encoded, "eyJpZCI6N30");
+        assert_eq!(cursor.decode::<After>(&encoded), Some(After { id: 7 }));
+
+        let signed = Cursor::signed("secret");
+        let encoded = signed.encode(&After { id: 7 });
+        assert!(encoded.starts_with("eyJpZCI6N30."));
+        assert_eq!(signed.decode::<After>(&encoded), Some(After { id: 7 }));
+        assert_eq!(signed.decode::<After>("eyJpZCI6N30"), None);
+        assert_eq!(Cursor::signed("other").decode::<After>(&encoded), None);
+        let tampered = encoded.replacen("eyJpZCI6N30", &cursor.encode(&After { id: 8 }), 1);
+        assert_eq!(signed.decode::<After>(&tampered), None);
+        assert_eq!(cursor.decode::<After>(&encoded), None);
+    }
+
+    #[handler]
+    async fn list_todos(cursor: CursorParam<After>) -> String {
+        match cursor.into_inner() {
+            Some(after) => format!("after {}", after.id),
+            None => "first".into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cursor_param() {
+        let cursor = Cursor::signed("secret");
+        let router = Router::with_path("todos").hoop(cursor.clone()).get(list_todos);
+        let service = Service::new(router);
+
+        let mut res = TestClient::get("http://127.0.0.1:5801/todos").send(&service).await;
+        assert_eq!(res.take_string().await.unwrap(), "first");
+
+        let url = format!("http://127.0.0.1:5801/todos?cursor={}", cursor.encode(&After { id: 7 }));
+        let mut res = TestClient::get(url).send(&service).await;
+        assert_eq!(res.take_string().await.unwrap(), "after 7");
+
+        let url = format!("http://127.0.0.1:5801/todos?cursor={}", Cursor::new().encode(&After { id: 7 }));
+        let res = TestClient::get(url).send(&service).await;
+        assert_eq!(res.status_code, Some(StatusCode::BAD_REQUEST));
+    }
+}
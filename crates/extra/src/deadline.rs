@@ -0,0 +1,196 @@
+//! Middleware for setting a deadline of requests.
+//!
+//! The deadline is derived from the `X-Request-Timeout` header sent by the client, or a default budget, and it is
+//! stored in the [`Depot`]. Handlers can read it with [`DeadlineDepotExt::deadline`] and cancel the work which can
+//! not be done in time, for example when fanning out to several upstreams.
+//!
+//! Unlike [`Timeout`](crate::timeout::Timeout), this middleware does not abort the request, it only exposes the
+//! budget to the handlers. They can be used together.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use salvo_core::prelude::*;
+//! use salvo_extra::deadline::{Deadline, DeadlineDepotExt};
+//!
+//! #[handler]
+//! async fn fan_out(depot: &mut Depot) -> Result<&'static str, StatusError> {
+//!     let deadline = depot.deadline().ok_or_else(StatusError::internal_server_error)?;
+//!     let upstream = tokio::time::sleep(Duration::from_millis(100));
+//!     tokio::time::timeout_at(deadline.into(), upstream)
+//!         .await
+//!         .map_err(|_| StatusError::gateway_timeout())?;
+//!     Ok("done")
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let router = Router::new()
+//!         .hoop(Deadline::new(Duration::from_secs(5)).max(Duration::from_secs(30)))
+//!         .get(fan_out);
+//!
+//!     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
+//!     Server::new(acceptor).serve(router).await;
+//! }
+//! ```
+use std::time::{Duration, Instant};
+
+use salvo_core::http::header::HeaderName;
+use salvo_core::http::{Request, Response};
+use salvo_core::{async_trait, Depot, FlowCtrl, Handler};
+
+/// Key for the deadline in depot.
+pub const DEADLINE_KEY: &str = "::salvo::deadline";
+
+/// Extesion for Depot.
+pub trait DeadlineDepotExt {
+    /// Get the deadline of current request.
+    fn deadline(&self) -> Option<Instant>;
+
+    /// Get the remaining time before the deadline, it is zero if the deadline has passed.
+    fn remaining(&self) -> Option<Duration> {
+        self.deadline().map(|deadline| deadline.saturating_duration_since(Instant::now()))
+    }
+}
+
+impl DeadlineDepotExt for Depot {
+    #[inline]
+    fn deadline(&self) -> Option<Instant> {
+        self.get::<Instant>(DEADLINE_KEY).ok().copied()
+    }
+}
+
+/// Middleware for setting a deadline of requests.
+///
+/// View [module level documentation](index.html) for more details.
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+pub struct Deadline {
+    /// The header name of the timeout sent by client. Default is `x-request-timeout`.
+    pub header_name: HeaderName,
+    /// The budget used when the request does not have the timeout header.
+    pub default: Duration,
+    /// The max budget the client can ask for. Default is the same as `default`, so the client can only
+    /// shorten the budget.
+    pub max: Duration,
+}
+
+impl Deadline {
+    /// Create a new `Deadline` with the default budget.
+    #[inline]
+    pub fn new(default: Duration) -> Self {
+        Self {
+            header_name: HeaderName::from_static("x-request-timeout"),
+            default,
+            max: default,
+        }
+    }
+
+    /// Set the header name of the timeout sent by client.
+    #[inline]
+    pub fn header_name(mut self, name: HeaderName) -> Self {
+        self.header_name = name;
+        self
+    }
+
+    /// Set the max budget the client can ask for.
+    #[inline]
+    pub fn max(mut self, max: Duration) -> Self {
+        self.max = max;
+        self
+    }
+
+    /// Get the budget of the request.
+    ///
+    /// The header value is in milliseconds, like `1500`, the `ms` and `s` suffixes are also accepted, like `1500ms`
+    /// and `2s`. Invalid value is ignored and the default budget is used.
+    fn budget(&self, req: &Request) -> Duration {
+        req.headers()
+            .get(&self.header_name)
+            .and_then(|value| value.to_str().ok())
+            .and_then(parse_timeout)
+            .map(|budget| budget.min(self.max))
+            .unwrap_or(self.default)
+    }
+}
+
+fn parse_timeout(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Some(millis) = value.strip_suffix("ms") {
+        millis.trim().parse().ok().map(Duration::from_millis)
+    } else if let Some(secs) = value.strip_suffix('s') {
+        secs.trim().parse().ok().and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+    } else {
+        value.parse().ok().map(Duration::from_millis)
+    }
+}
+
+#[async_trait]
+impl Handler for Deadline {
+    async fn handle(&self, req: &mut Request, depot: &mut Depot, _res: &mut Response, _ctrl: &mut FlowCtrl) {
+        let deadline = Instant::now() + self.budget(req);
+        // The deadline set by outer middleware can only be shortened.
+        if depot.deadline().is_none_or(|outer| outer > deadline) {
+            depot.insert(DEADLINE_KEY, deadline);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use salvo_core::prelude::*;
+    use salvo_core::test::{ResponseExt, TestClient};
+
+    use super::*;
+
+    #[handler]
+    async fn remaining(depot: &mut Depot) -> String {
+        depot.remaining().map(|d| d.as_secs().to_string()).unwrap_or_default()
+    }
+
+    #[test]
+    fn test_parse_timeout() {
+        assert_eq!(parse_timeout("1500"), Some(Duration::from_millis(1500)));
+        assert_eq!(parse_timeout("1500ms"), Some(Duration::from_millis(1500)));
+        assert_eq!(parse_timeout(" 2s "), Some(Duration::from_secs(2)));
+        assert_eq!(parse_timeout("0.5s"), Some(Duration::from_millis(500)));
+        assert_eq!(parse_timeout("-1s"), None);
+        assert_eq!(parse_timeout("soon"), None);
+    }
+
+    #[tokio::test]
+    async fn test_deadline() {
+        let router = Router::new()
+            .hoop(Deadline::new(Duration::from_secs(10)).max(Duration::from_secs(60)))
+            .get(remaining)
+            .push(
+                Router::with_path("short")
+                    .hoop(Deadline::new(Duration::from_secs(5)))
+                    .get(remaining),
+            )
+            .push(
+                Router::with_path("long")
+                    .hoop(Deadline::new(Duration::from_secs(120)))
+                    .get(remaining),
+            );
+        let service = Service::new(router);
+
+        async fn access(service: &Service, path: &str, timeout: Option<&str>) -> String {
+            let mut req = TestClient::get(format!("http://127.0.0.1:5801/{path}"));
+            if let Some(timeout) = timeout {
+                req = req.add_header("x-request-timeout", timeout, true);
+            }
+            req.send(service).await.take_string().await.unwrap()
+        }
+
+        assert_eq!(access(&service, "", None).await, "9");
+        assert_eq!(access(&service, "", Some("30s")).await, "29");
+        assert_eq!(access(&service, "", Some("3000")).await, "2");
+        assert_eq!(access(&service, "", Some("120s")).await, "59");
+        assert_eq!(access(&service, "", Some("invalid")).await, "9");
+        assert_eq!(access(&service, "short", None).await, "4");
+        assert_eq!(access(&service, "long", None).await, "9");
+    }
+}
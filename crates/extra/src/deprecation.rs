@@ -0,0 +1,172 @@
+//! Middleware for advertising the deprecation of endpoints.
+//!
+//! [`Deprecation`] adds the `Deprecation` header of [RFC 9745](https://datatracker.ietf.org/doc/html/rfc9745) to the
+//! responses, which is the time when the endpoint was deprecated, like `Deprecation: @1735689600`, and optionally
+//! the `Sunset` header of [RFC 8594](https://datatracker.ietf.org/doc/html/rfc8594), which tells when the endpoint
+//! will stop working, and a `Link` header to the migration guide. Clients can detect them and warn their users
+//! before the endpoint is removed.
+//!
+//! It only changes the live responses. To mark the operation as deprecated in the OpenAPI spec as well, use
+//! `#[endpoint(deprecated)]` of `salvo-oapi`.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::time::{Duration, SystemTime};
+//!
+//! use salvo_core::prelude::*;
+//! use salvo_extra::deprecation::Deprecation;
+//!
+//! #[handler]
+//! async fn update_todo() -> &'static str {
+//!     "updated"
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let deprecation = Deprecation::new(SystemTime::UNIX_EPOCH + Duration::from_secs(1_735_689_600))
+//!         .sunset(SystemTime::UNIX_EPOCH + Duration::from_secs(1_767_225_600))
+//!         .link("https://example.com/docs/migrate-todos");
+//!     let router = Router::with_path("todos/{id}").hoop(deprecation).put(update_todo);
+//!
+//!     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
+//!     Server::new(acceptor).serve(router).await;
+//! }
+//! ```
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use salvo_core::http::header::{HeaderName, HeaderValue, LINK};
+use salvo_core::http::headers::{Expires, Header};
+use salvo_core::http::{Request, Response};
+use salvo_core::{async_trait, Depot, FlowCtrl, Handler};
+
+/// The `Deprecation` header name.
+pub const DEPRECATION: HeaderName = HeaderName::from_static("deprecation");
+/// The `Sunset` header name.
+pub const SUNSET: HeaderName = HeaderName::from_static("sunset");
+
+/// Middleware for advertising the deprecation of endpoints.
+///
+/// View [module level documentation](index.html) for more details.
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+pub struct Deprecation {
+    /// When the endpoint was deprecated, it is sent as `Deprecation: @<unix timestamp>`.
+    pub since: SystemTime,
+    /// When the endpoint will stop working.
+    pub sunset: Option<SystemTime>,
+    /// The link to the documentation about the deprecation.
+    pub link: Option<String>,
+}
+
+impl Deprecation {
+    /// Create a new `Deprecation` with the time when the endpoint was deprecated.
+    ///
+    /// RFC 9745 only allows a timestamp in the header, use [`SystemTime::now`] if the endpoint is deprecated from
+    /// the deployment of this middleware.
+    #[inline]
+    pub fn new(since: SystemTime) -> Self {
+        Self {
+            since,
+            sunset: None,
+            link: None,
+        }
+    }
+
+    /// Set when the endpoint was deprecated, it is sent as `Deprecation: @<unix timestamp>`.
+    #[inline]
+    pub fn since(mut self, since: SystemTime) -> Self {
+        self.since = since;
+        self
+    }
+
+    /// Set when the endpoint will stop working, it is sent as `Sunset: <http date>`.
+    #[inline]
+    pub fn sunset(mut self, sunset: SystemTime) -> Self {
+        self.sunset = Some(sunset);
+        self
+    }
+
+    /// Set the link to the documentation about the deprecation, it is sent as `Link: <url>; rel="deprecation"`.
+    #[inline]
+    pub fn link(mut self, link: impl Into<String>) -> Self {
+        self.link = Some(link.into());
+        self
+    }
+}
+
+#[async_trait]
+impl Handler for Deprecation {
+    async fn handle(&self, _req: &mut Request, _depot: &mut Depot, res: &mut Response, _ctrl: &mut FlowCtrl) {
+        let secs = self.since.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default();
+        let deprecation =
+            HeaderValue::from_str(&format!("@{secs}")).expect("timestamp should be a valid header value");
+        let headers = res.headers_mut();
+        headers.insert(DEPRECATION, deprecation);
+        if let Some(sunset) = self.sunset {
+            let mut values = Vec::with_capacity(1);
+            Expires::from(sunset).encode(&mut values);
+            if let Some(value) = values.pop() {
+                headers.insert(SUNSET, value);
+            }
+        }
+        if let Some(link) = &self.link {
+            match HeaderValue::from_str(&format!(r#"<{link}>; rel="deprecation""#)) {
+                Ok(value) => {
+                    headers.append(LINK, value);
+                }
+                Err(e) => {
+                    tracing::error!(error = ?e, "invalid deprecation link");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use salvo_core::prelude::*;
+    use salvo_core::test::{ResponseExt, TestClient};
+
+    use super::*;
+
+    #[handler]
+    async fn update_todo() -> &'static str {
+        "updated"
+    }
+
+    #[tokio::test]
+    async fn test_deprecation() {
+        let deprecation = Deprecation::new(UNIX_EPOCH + Duration::from_secs(1_735_689_600))
+            .sunset(UNIX_EPOCH + Duration::from_secs(1_767_225_600))
+            .link("https://example.com/docs/migrate-todos");
+        let router = Router::new()
+            .push(Router::with_path("v1").hoop(deprecation).put(update_todo))
+            .push(
+                Router::with_path("v2")
+                    .hoop(Deprecation::new(UNIX_EPOCH + Duration::from_secs(1_704_067_200)))
+                    .put(update_todo),
+            )
+            .push(Router::with_path("v3").put(update_todo));
+        let service = Service::new(router);
+
+        let mut res = TestClient::put("http://127.0.0.1:5801/v1").send(&service).await;
+        assert_eq!(res.headers()[DEPRECATION], "@1735689600");
+        assert_eq!(res.headers()[SUNSET], "Thu, 01 Jan 2026 00:00:00 GMT");
+        assert_eq!(
+            res.headers()[LINK],
+            r#"<https://example.com/docs/migrate-todos>; rel="deprecation""#
+        );
+        assert_eq!(res.take_string().await.unwrap(), "updated");
+
+        let res = TestClient::put("http://127.0.0.1:5801/v2").send(&service).await;
+        assert_eq!(res.headers()[DEPRECATION], "@1704067200");
+        assert!(res.headers().get(SUNSET).is_none());
+        assert!(res.headers().get(LINK).is_none());
+
+        let res = TestClient::put("http://127.0.0.1:5801/v3").send(&service).await;
+        assert!(res.headers().get(DEPRECATION).is_none());
+    }
+}
@@ -12,6 +12,10 @@
 //! Regardless of whether the request is successfully matched by the route,
 //! the middleware added to the Service will always be executed.
 //!
+//! The request is considered as https by [`Request::is_secure`]. Behind a reverse proxy which terminates TLS,
+//! place [`TrustedProxy`](crate::trusted_proxy::TrustedProxy) before this middleware, otherwise all the requests
+//! are redirected.
+//!
 //! Example:
 //!
 //! ```no_run
@@ -83,7 +87,7 @@ impl ForceHttps {
 #[async_trait]
 impl Handler for ForceHttps {
     async fn handle(&self, req: &mut Request, depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl) {
-        if req.uri().scheme() == Some(&Scheme::HTTPS)
+        if req.is_secure()
             || self
                 .skipper
                 .as_ref()
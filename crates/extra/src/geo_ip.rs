@@ -0,0 +1,207 @@
+//! Middleware for attaching geo and network metadata of the client IP to the depot.
+//!
+//! [`GeoIp`] passes the IP of [`Request::remote_addr`] to a [`GeoResolver`] provided by the application, like a
+//! lookup in a MaxMind database or a call to an internal service, and inserts the returned [`GeoInfo`] into the
+//! depot, so the handlers and the other middlewares can make decisions per country or per autonomous system. No
+//! database is included.
+//!
+//! Behind reverse proxies, place [`TrustedProxy`](crate::trusted_proxy::TrustedProxy) before this middleware, so the
+//! IP of the client is resolved instead of the IP of the proxy. Nothing is inserted if the address is not an IP
+//! address, like a Unix socket, or if the resolver returns `None`.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::net::IpAddr;
+//!
+//! use salvo_core::prelude::*;
+//! use salvo_extra::geo_ip::{GeoInfo, GeoIp, GeoIpDepotExt};
+//!
+//! #[handler]
+//! async fn hello(depot: &mut Depot, res: &mut Response) {
+//!     if depot.geo_info().and_then(|info| info.country.as_deref()) == Some("XX") {
+//!         res.render(StatusError::forbidden());
+//!         return;
+//!     }
+//!     res.render("hello");
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let geo_ip = GeoIp::new(|ip: IpAddr| async move {
+//!         // Look up the IP in a geo database.
+//!         ip.is_loopback().then(|| GeoInfo::new().country("XX").asn(64512))
+//!     });
+//!     let router = Router::new().hoop(geo_ip).get(hello);
+//!
+//!     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
+//!     Server::new(acceptor).serve(router).await;
+//! }
+//! ```
+//!
+//! A rate limiter can key on the metadata too, like an issuer of `salvo-rate-limiter` limiting the requests of each
+//! autonomous system:
+//!
+//! ```
+//! # use salvo_core::prelude::*;
+//! # use salvo_extra::geo_ip::GeoIpDepotExt;
+//! let issuer = |_req: &mut Request, depot: &Depot| depot.geo_info().and_then(|info| info.asn);
+//! ```
+use std::future::Future;
+use std::net::IpAddr;
+
+use salvo_core::http::{Request, Response};
+use salvo_core::{async_trait, Depot, FlowCtrl, Handler};
+
+/// Key for the geo info in depot.
+pub const GEO_INFO_KEY: &str = "::salvo::geo_ip::info";
+
+/// Extension for Depot.
+pub trait GeoIpDepotExt {
+    /// Get the geo info of the client IP from depot.
+    fn geo_info(&self) -> Option<&GeoInfo>;
+}
+
+impl GeoIpDepotExt for Depot {
+    #[inline]
+    fn geo_info(&self) -> Option<&GeoInfo> {
+        self.get::<GeoInfo>(GEO_INFO_KEY).ok()
+    }
+}
+
+/// Geo and network metadata of an IP address.
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct GeoInfo {
+    /// The ISO 3166-1 alpha-2 code of the country, like `US`.
+    pub country: Option<String>,
+    /// The region of the country, like a state or a province.
+    pub region: Option<String>,
+    /// The city.
+    pub city: Option<String>,
+    /// The number of the autonomous system.
+    pub asn: Option<u32>,
+    /// The organization owning the autonomous system.
+    pub as_org: Option<String>,
+}
+
+impl GeoInfo {
+    /// Create a new empty `GeoInfo`.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the ISO 3166-1 alpha-2 code of the country.
+    #[inline]
+    pub fn country(mut self, country: impl Into<String>) -> Self {
+        self.country = Some(country.into());
+        self
+    }
+
+    /// Set the region of the country.
+    #[inline]
+    pub fn region(mut self, region: impl Into<String>) -> Self {
+        self.region = Some(region.into());
+        self
+    }
+
+    /// Set the city.
+    #[inline]
+    pub fn city(mut self, city: impl Into<String>) -> Self {
+        self.city = Some(city.into());
+        self
+    }
+
+    /// Set the number of the autonomous system.
+    #[inline]
+    pub fn asn(mut self, asn: u32) -> Self {
+        self.asn = Some(asn);
+        self
+    }
+
+    /// Set the organization owning the autonomous system.
+    #[inline]
+    pub fn as_org(mut self, as_org: impl Into<String>) -> Self {
+        self.as_org = Some(as_org.into());
+        self
+    }
+}
+
+/// Resolver of the geo info of an IP address.
+///
+/// It is implemented for the async functions taking an [`IpAddr`]. The resolver is called for every request, cache
+/// the results in it if the lookups are slow.
+pub trait GeoResolver: Send + Sync + 'static {
+    /// Resolve the geo info of `ip`, returns `None` if it is unknown.
+    fn resolve(&self, ip: IpAddr) -> impl Future<Output = Option<GeoInfo>> + Send;
+}
+impl<F, Fut> GeoResolver for F
+where
+    F: Fn(IpAddr) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Option<GeoInfo>> + Send,
+{
+    fn resolve(&self, ip: IpAddr) -> impl Future<Output = Option<GeoInfo>> + Send {
+        (self)(ip)
+    }
+}
+
+/// Middleware for attaching the geo info of the client IP to the depot.
+///
+/// View [module level documentation](index.html) for more details.
+#[derive(Debug)]
+pub struct GeoIp<R> {
+    resolver: R,
+}
+
+impl<R: GeoResolver> GeoIp<R> {
+    /// Create a new `GeoIp` with the resolver.
+    #[inline]
+    pub fn new(resolver: R) -> Self {
+        Self { resolver }
+    }
+}
+
+#[async_trait]
+impl<R: GeoResolver> Handler for GeoIp<R> {
+    async fn handle(&self, req: &mut Request, depot: &mut Depot, _res: &mut Response, _ctrl: &mut FlowCtrl) {
+        let Some(addr) = req.remote_addr().clone().into_std() else {
+            return;
+        };
+        if let Some(info) = self.resolver.resolve(addr.ip()).await {
+            depot.insert_cloneable(GEO_INFO_KEY, info);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{Ipv4Addr, SocketAddr};
+
+    use salvo_core::prelude::*;
+    use salvo_core::test::{ResponseExt, TestClient};
+
+    use super::*;
+
+    #[handler]
+    async fn country(depot: &mut Depot) -> String {
+        let info = depot.geo_info();
+        format!("{:?} {:?}", info.and_then(|info| info.country.as_deref()), info.and_then(|info| info.asn))
+    }
+
+    #[tokio::test]
+    async fn test_geo_ip() {
+        let geo_ip = GeoIp::new(|ip: IpAddr| async move {
+            (ip == IpAddr::V4(Ipv4Addr::new(203, 0, 113, 7))).then(|| GeoInfo::new().country("NZ").asn(64512))
+        });
+        let service = Service::new(Router::new().hoop(geo_ip).get(country));
+
+        async fn access(service: &Service, ip: [u8; 4]) -> String {
+            let mut req = TestClient::get("http://127.0.0.1:5801/").build();
+            *req.remote_addr_mut() = SocketAddr::from((ip, 8080)).into();
+            service.handle(req).await.take_string().await.unwrap()
+        }
+        assert_eq!(access(&service, [203, 0, 113, 7]).await, r#"Some("NZ") Some(64512)"#);
+        assert_eq!(access(&service, [198, 51, 100, 1]).await, "None None");
+    }
+}
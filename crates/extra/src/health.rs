@@ -0,0 +1,141 @@
+//! Handlers for health check endpoints.
+//!
+//! [`liveness`] always responds `200 OK`, it tells the orchestrator that the process is alive. [`readiness`] runs
+//! a user provided async check, such as pinging the database, and responds `200 OK` if the check passes, otherwise
+//! `503 Service Unavailable`, so the instance is taken out of the load balancer until it is ready again.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use salvo_core::prelude::*;
+//! use salvo_extra::health;
+//!
+//! async fn ping_database() -> bool {
+//!     true
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let router = Router::new()
+//!         .push(Router::with_path("healthz").get(health::liveness()))
+//!         .push(Router::with_path("readyz").get(health::readiness(ping_database)));
+//!
+//!     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
+//!     Server::new(acceptor).serve(router).await;
+//! }
+//! ```
+use std::fmt::{self, Debug, Formatter};
+use std::future::Future;
+
+use salvo_core::http::header::{HeaderValue, CACHE_CONTROL};
+use salvo_core::http::{Request, Response, StatusCode};
+use salvo_core::writing::Text;
+use salvo_core::{async_trait, Depot, FlowCtrl, Handler};
+
+/// Handler for the liveness check, it always responds `200 OK`.
+///
+/// View [module level documentation](index.html) for more details.
+#[non_exhaustive]
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Liveness;
+
+#[async_trait]
+impl Handler for Liveness {
+    async fn handle(&self, _req: &mut Request, _depot: &mut Depot, res: &mut Response, _ctrl: &mut FlowCtrl) {
+        write_status(res, true);
+    }
+}
+
+/// Handler for the readiness check, it responds `200 OK` if the check passes, otherwise `503 Service Unavailable`.
+///
+/// View [module level documentation](index.html) for more details.
+#[non_exhaustive]
+pub struct Readiness<F> {
+    check: F,
+}
+
+impl<F> Debug for Readiness<F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Readiness").finish()
+    }
+}
+
+#[async_trait]
+impl<F, Fut> Handler for Readiness<F>
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = bool> + Send + 'static,
+{
+    async fn handle(&self, _req: &mut Request, _depot: &mut Depot, res: &mut Response, _ctrl: &mut FlowCtrl) {
+        write_status(res, (self.check)().await);
+    }
+}
+
+fn write_status(res: &mut Response, healthy: bool) {
+    // Health status should always be fresh.
+    res.headers_mut()
+        .insert(CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    if healthy {
+        res.status_code(StatusCode::OK);
+        res.render(Text::Plain("ok"));
+    } else {
+        res.status_code(StatusCode::SERVICE_UNAVAILABLE);
+        res.render(Text::Plain("unavailable"));
+    }
+}
+
+/// Create a handler for the liveness check.
+#[inline]
+pub fn liveness() -> Liveness {
+    Liveness
+}
+
+/// Create a handler for the readiness check, `check` returns `true` if the service is ready to handle requests.
+#[inline]
+pub fn readiness<F, Fut>(check: F) -> Readiness<F>
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = bool> + Send + 'static,
+{
+    Readiness { check }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    use salvo_core::prelude::*;
+    use salvo_core::test::{ResponseExt, TestClient};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_health() {
+        let ready = Arc::new(AtomicBool::new(false));
+        let router = Router::new()
+            .push(Router::with_path("healthz").get(liveness()))
+            .push(Router::with_path("readyz").get(readiness({
+                let ready = ready.clone();
+                move || {
+                    let ready = ready.clone();
+                    async move { ready.load(Ordering::Relaxed) }
+                }
+            })));
+        let service = Service::new(router);
+
+        async fn access(service: &Service, path: &str) -> (StatusCode, String) {
+            let mut res = TestClient::get(format!("http://127.0.0.1:5801/{path}")).send(service).await;
+            assert_eq!(res.headers()[CACHE_CONTROL], "no-store");
+            (res.status_code.unwrap(), res.take_string().await.unwrap())
+        }
+
+        assert_eq!(access(&service, "healthz").await, (StatusCode::OK, "ok".into()));
+        assert_eq!(
+            access(&service, "readyz").await,
+            (StatusCode::SERVICE_UNAVAILABLE, "unavailable".into())
+        );
+        ready.store(true, Ordering::Relaxed);
+        assert_eq!(access(&service, "readyz").await, (StatusCode::OK, "ok".into()));
+    }
+}
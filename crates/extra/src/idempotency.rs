@@ -0,0 +1,463 @@
+//! Middleware for making the unsafe requests safely retryable with idempotency keys.
+//!
+//! A client sends a unique `Idempotency-Key` header with a `POST` request, and sends the same key again when it
+//! retries the request, like after a timeout. [`Idempotency`] executes the handlers only for the first request of the
+//! key, stores the response in the [`IdempotencyStore`], and replays the stored response for the later requests of
+//! the same key instead of executing the handlers again, so a retried request never creates a resource twice.
+//!
+//! The replay semantics:
+//!
+//! * Only the requests of the unsafe methods are handled, `POST` and `PATCH` by default, see
+//!   [`Idempotency::methods`]. The requests without the key are passed through, or rejected with `400 Bad Request`
+//!   if [`Idempotency::required`] is set.
+//! * A key is scoped by the method and the path, the same key sent to other routes is a different key. The bodies
+//!   of the requests are not compared.
+//! * The keys are shared by all the clients unless [`Idempotency::scope`] is set, it should return the
+//!   authenticated principal, like the user id, so a client can not replay the responses of another one by sending
+//!   the same key.
+//! * While the first request of a key is being handled, the requests of the same key are rejected with
+//!   `409 Conflict` at once instead of waiting, the client should retry them later. If the first request never
+//!   completes, like the client disconnects, the key can be claimed again after [`Idempotency::lock_timeout`].
+//! * The successful, redirection and client error responses are stored for [`Idempotency::ttl`], and they are
+//!   replayed with the same status code, headers and body, and an `idempotent-replayed: true` header. The cookies
+//!   are not replayed.
+//! * The server error responses and the streaming responses are not stored, the key is released so the retried
+//!   request executes the handlers again.
+//!
+//! [`MemoryStore`] keeps the responses in the memory of current process, implement [`IdempotencyStore`] on a shared
+//! database, like Redis, if the server has several instances.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use salvo_core::prelude::*;
+//! use salvo_extra::idempotency::idempotency;
+//!
+//! #[handler]
+//! async fn create_todo() -> StatusCode {
+//!     StatusCode::CREATED
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let router = Router::with_path("todos").hoop(idempotency()).post(create_todo);
+//!     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
+//!     Server::new(acceptor).serve(router).await;
+//! }
+//! ```
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::error::Error as StdError;
+use std::fmt::{self, Debug, Formatter};
+use std::future::Future;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use salvo_core::http::header::{HeaderName, HeaderValue};
+use salvo_core::http::{Method, Request, Response, StatusError};
+use salvo_core::writing::Rendered;
+use salvo_core::{async_trait, Depot, FlowCtrl, Handler};
+
+/// The header of the replayed responses.
+pub const IDEMPOTENT_REPLAYED: HeaderName = HeaderName::from_static("idempotent-replayed");
+
+/// The result of [`IdempotencyStore::claim`].
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum Claim {
+    /// The key is claimed by current request, the handlers should be executed.
+    Acquired,
+    /// The key is claimed by another request which is still being handled.
+    InFlight,
+    /// The request of the key is completed, the stored response should be replayed.
+    Completed(Rendered),
+}
+
+/// Stores the states of the idempotency keys for [`Idempotency`].
+pub trait IdempotencyStore: Send + Sync + 'static {
+    /// The error type of the store.
+    type Error: StdError + Send + Sync + 'static;
+
+    /// Claim the `key` for current request, it must be atomic, so only one of the concurrent requests of the same key
+    /// gets [`Claim::Acquired`].
+    ///
+    /// The claim expires after `lock_timeout` if it is not completed or released, then the key can be claimed again.
+    fn claim(&self, key: &str, lock_timeout: Duration) -> impl Future<Output = Result<Claim, Self::Error>> + Send;
+    /// Store the response of the claimed `key` for `ttl`.
+    fn complete(
+        &self,
+        key: &str,
+        response: Rendered,
+        ttl: Duration,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+    /// Release the claimed `key` without storing a response, so it can be claimed again.
+    fn release(&self, key: &str) -> impl Future<Output = Result<(), Self::Error>> + Send;
+}
+
+enum Entry {
+    InFlight { expires_at: Instant },
+    Completed { response: Rendered, expires_at: Instant },
+}
+impl Entry {
+    fn expires_at(&self) -> Instant {
+        match self {
+            Self::InFlight { expires_at } | Self::Completed { expires_at, .. } => *expires_at,
+        }
+    }
+}
+
+// The interval of removing all the expired keys of `MemoryStore`.
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Default)]
+struct Entries {
+    entries: HashMap<String, Entry>,
+    swept_at: Option<Instant>,
+}
+
+/// An [`IdempotencyStore`] which keeps the responses in memory.
+///
+/// An expired key is removed when it is claimed again, and all the expired keys are removed at most once a minute
+/// when a key is claimed.
+#[derive(Default)]
+pub struct MemoryStore {
+    entries: Mutex<Entries>,
+}
+impl MemoryStore {
+    /// Create a new `MemoryStore`.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+impl Debug for MemoryStore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemoryStore").finish()
+    }
+}
+impl IdempotencyStore for MemoryStore {
+    type Error = Infallible;
+
+    async fn claim(&self, key: &str, lock_timeout: Duration) -> Result<Claim, Self::Error> {
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        let Entries { entries, swept_at } = &mut *entries;
+        let now = Instant::now();
+        if swept_at.is_none_or(|swept_at| now.duration_since(swept_at) >= SWEEP_INTERVAL) {
+            entries.retain(|_, entry| entry.expires_at() > now);
+            *swept_at = Some(now);
+        }
+        match entries.get(key).filter(|entry| entry.expires_at() > now) {
+            Some(Entry::InFlight { .. }) => Ok(Claim::InFlight),
+            Some(Entry::Completed { response, .. }) => Ok(Claim::Completed(response.clone())),
+            None => {
+                let expires_at = now + lock_timeout;
+                entries.insert(key.to_owned(), Entry::InFlight { expires_at });
+                Ok(Claim::Acquired)
+            }
+        }
+    }
+    async fn complete(&self, key: &str, response: Rendered, ttl: Duration) -> Result<(), Self::Error> {
+        let expires_at = Instant::now() + ttl;
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        entries
+            .entries
+            .insert(key.to_owned(), Entry::Completed { response, expires_at });
+        Ok(())
+    }
+    async fn release(&self, key: &str) -> Result<(), Self::Error> {
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(Entry::InFlight { .. }) = entries.entries.get(key) {
+            entries.entries.remove(key);
+        }
+        Ok(())
+    }
+}
+
+/// Middleware for making the unsafe requests safely retryable with idempotency keys.
+///
+/// View [module level documentation](index.html) for more details.
+#[derive(Debug)]
+pub struct Idempotency<S> {
+    store: S,
+    header_name: HeaderName,
+    methods: Vec<Method>,
+    ttl: Duration,
+    lock_timeout: Duration,
+    required: bool,
+    scope: Option<fn(&Request, &Depot) -> Option<String>>,
+}
+
+/// Create a new `Idempotency` with [`MemoryStore`].
+#[inline]
+pub fn idempotency() -> Idempotency<MemoryStore> {
+    Idempotency::new(MemoryStore::new())
+}
+
+impl<S: IdempotencyStore> Idempotency<S> {
+    /// Create a new `Idempotency` with the store.
+    ///
+    /// The header is `idempotency-key`, the methods are `POST` and `PATCH`, the responses are stored for 24 hours,
+    /// and the lock timeout is 1 minute.
+    #[inline]
+    pub fn new(store: S) -> Self {
+        Self {
+            store,
+            header_name: HeaderName::from_static("idempotency-key"),
+            methods: vec![Method::POST, Method::PATCH],
+            ttl: Duration::from_secs(24 * 60 * 60),
+            lock_timeout: Duration::from_secs(60),
+            required: false,
+            scope: None,
+        }
+    }
+
+    /// Get the store.
+    #[inline]
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Set the header name of the idempotency key.
+    #[inline]
+    pub fn header_name(mut self, name: HeaderName) -> Self {
+        self.header_name = name;
+        self
+    }
+
+    /// Set the methods of the requests which are handled, the requests of the other methods are passed through.
+    #[inline]
+    pub fn methods(mut self, methods: impl IntoIterator<Item = Method>) -> Self {
+        self.methods = methods.into_iter().collect();
+        self
+    }
+
+    /// Set how long the responses are stored and replayed.
+    #[inline]
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Set how long a key is locked by a request which is not completed, it should be longer than the time to handle
+    /// a request.
+    #[inline]
+    pub fn lock_timeout(mut self, timeout: Duration) -> Self {
+        self.lock_timeout = timeout;
+        self
+    }
+
+    /// Set whether the requests without the idempotency key are rejected with `400 Bad Request`, default is `false`.
+    #[inline]
+    pub fn required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+
+    /// Set the function getting the scope of the keys, like the id of the authenticated user, the same key sent in
+    /// different scopes is a different key. The keys are not scoped if it returns `None`.
+    ///
+    /// The authentication middleware should be added before `Idempotency`, so the principal is in the depot.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use salvo_core::prelude::*;
+    /// use salvo_extra::idempotency::idempotency;
+    ///
+    /// let idempotency = idempotency().scope(|_req, depot| depot.get::<String>("user_id").ok().cloned());
+    /// ```
+    #[inline]
+    pub fn scope(mut self, scope: fn(&Request, &Depot) -> Option<String>) -> Self {
+        self.scope = Some(scope);
+        self
+    }
+}
+
+#[async_trait]
+impl<S: IdempotencyStore> Handler for Idempotency<S> {
+    async fn handle(&self, req: &mut Request, depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl) {
+        if !self.methods.contains(req.method()) {
+            return;
+        }
+        let key = match req.headers().get(&self.header_name).map(|value| value.to_str()) {
+            Some(Ok(key)) if !key.is_empty() => {
+                let key = format!("{} {} {key}", req.method(), req.uri().path());
+                match self.scope.and_then(|scope| scope(req, depot)) {
+                    // The length keeps the scopes containing spaces from colliding with the other keys.
+                    Some(scope) => format!("{}:{scope} {key}", scope.len()),
+                    None => key,
+                }
+            }
+            Some(_) => {
+                res.render(StatusError::bad_request().brief("The idempotency key is invalid."));
+                ctrl.skip_rest();
+                return;
+            }
+            None if self.required => {
+                res.render(StatusError::bad_request().brief("The idempotency key is missing."));
+                ctrl.skip_rest();
+                return;
+            }
+            None => return,
+        };
+        match self.store.claim(&key, self.lock_timeout).await {
+            Ok(Claim::Acquired) => {}
+            Ok(Claim::InFlight) => {
+                res.render(StatusError::conflict().brief("A request with the same idempotency key is being handled."));
+                ctrl.skip_rest();
+                return;
+            }
+            Ok(Claim::Completed(response)) => {
+                res.render(response);
+                res.headers_mut().insert(IDEMPOTENT_REPLAYED, HeaderValue::from_static("true"));
+                ctrl.skip_rest();
+                return;
+            }
+            Err(e) => {
+                tracing::error!(error = ?e, "claim idempotency key failed");
+                res.render(StatusError::internal_server_error().brief("Claim idempotency key failed.").cause(e));
+                ctrl.skip_rest();
+                return;
+            }
+        }
+
+        ctrl.call_next(req, depot, res).await;
+
+        let is_server_error = res.status_code.is_some_and(|code| code.is_server_error());
+        let is_stored = !is_server_error
+            && (res.body.is_none() || res.body.is_once() || res.body.is_chunks() || res.body.is_error());
+        if !is_stored {
+            if let Err(e) = self.store.release(&key).await {
+                tracing::error!(error = ?e, "release idempotency key failed");
+            }
+            return;
+        }
+        let mut stored = Response::new();
+        stored.status_code = res.status_code;
+        stored.headers = res.headers.clone();
+        stored.body = res.take_body();
+        let response = match Rendered::try_from(stored) {
+            Ok(response) => response,
+            Err(e) => {
+                tracing::error!(error = ?e, "idempotent response can not be stored");
+                if let Err(e) = self.store.release(&key).await {
+                    tracing::error!(error = ?e, "release idempotency key failed");
+                }
+                return;
+            }
+        };
+        res.render(response.clone());
+        if let Err(e) = self.store.complete(&key, response, self.ttl).await {
+            tracing::error!(error = ?e, "store idempotent response failed");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use salvo_core::prelude::*;
+    use salvo_core::test::{ResponseExt, TestClient};
+
+    use super::*;
+
+    static CREATED: AtomicUsize = AtomicUsize::new(0);
+
+    #[handler]
+    async fn create_todo(req: &mut Request, res: &mut Response) {
+        if req.query::<bool>("fail").unwrap_or(false) {
+            res.status_code(StatusCode::SERVICE_UNAVAILABLE);
+            return;
+        }
+        let id = CREATED.fetch_add(1, Ordering::SeqCst) + 1;
+        res.status_code(StatusCode::CREATED);
+        res.render(format!("todo {id}"));
+    }
+
+    async fn create(service: &Service, path: &str, key: Option<&str>) -> Response {
+        let mut client = TestClient::post(format!("http://127.0.0.1:5800/{path}"));
+        if let Some(key) = key {
+            client = client.add_header("idempotency-key", key, true);
+        }
+        client.send(service).await
+    }
+
+    #[tokio::test]
+    async fn test_idempotency() {
+        let router = Router::with_hoop(idempotency())
+            .push(Router::with_path("todos").post(create_todo).get(create_todo))
+            .push(Router::with_path("others").post(create_todo));
+        let service = Service::new(router);
+
+        let mut res = create(&service, "todos", Some("a")).await;
+        assert_eq!(res.status_code, Some(StatusCode::CREATED));
+        assert!(res.headers().get(IDEMPOTENT_REPLAYED).is_none());
+        let first = res.take_string().await.unwrap();
+
+        let mut res = create(&service, "todos", Some("a")).await;
+        assert_eq!(res.status_code, Some(StatusCode::CREATED));
+        assert_eq!(res.headers()[IDEMPOTENT_REPLAYED], "true");
+        assert_eq!(res.take_string().await.unwrap(), first);
+
+        let mut res = create(&service, "others", Some("a")).await;
+        assert_ne!(res.take_string().await.unwrap(), first);
+        let mut res = create(&service, "todos", None).await;
+        assert_ne!(res.take_string().await.unwrap(), first);
+        let mut res = TestClient::get("http://127.0.0.1:5800/todos")
+            .add_header("idempotency-key", "a", true)
+            .send(&service)
+            .await;
+        assert_ne!(res.take_string().await.unwrap(), first);
+
+        let res = create(&service, "todos?fail=true", Some("b")).await;
+        assert_eq!(res.status_code, Some(StatusCode::SERVICE_UNAVAILABLE));
+        let res = create(&service, "todos", Some("b")).await;
+        assert_eq!(res.status_code, Some(StatusCode::CREATED));
+        assert!(res.headers().get(IDEMPOTENT_REPLAYED).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_scope() {
+        let router = Router::with_path("todos")
+            .hoop(idempotency().scope(|req, _depot| req.header::<String>("x-user")))
+            .post(create_todo);
+        let service = Service::new(router);
+        let create = |user: &'static str| {
+            TestClient::post("http://127.0.0.1:5800/todos")
+                .add_header("idempotency-key", "a", true)
+                .add_header("x-user", user, true)
+                .send(&service)
+        };
+
+        let first = create("alice").await.take_string().await.unwrap();
+        let mut res = create("alice").await;
+        assert_eq!(res.headers()[IDEMPOTENT_REPLAYED], "true");
+        assert_eq!(res.take_string().await.unwrap(), first);
+        let mut res = create("bob").await;
+        assert!(res.headers().get(IDEMPOTENT_REPLAYED).is_none());
+        assert_ne!(res.take_string().await.unwrap(), first);
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_in_flight() {
+        let store = MemoryStore::new();
+        let lock_timeout = Duration::from_millis(50);
+        assert!(matches!(store.claim("k", lock_timeout).await, Ok(Claim::Acquired)));
+        assert!(matches!(store.claim("k", lock_timeout).await, Ok(Claim::InFlight)));
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert!(matches!(store.claim("k", lock_timeout).await, Ok(Claim::Acquired)));
+        store.release("k").await.unwrap();
+        assert!(matches!(store.claim("k", lock_timeout).await, Ok(Claim::Acquired)));
+
+        assert!(matches!(store.claim("POST /todos c", lock_timeout).await, Ok(Claim::Acquired)));
+        let router = Router::with_path("todos")
+            .hoop(Idempotency::new(store).required(true))
+            .post(create_todo);
+        let service = Service::new(router);
+        let res = create(&service, "todos", Some("c")).await;
+        assert_eq!(res.status_code, Some(StatusCode::CONFLICT));
+        let res = create(&service, "todos", None).await;
+        assert_eq!(res.status_code, Some(StatusCode::BAD_REQUEST));
+    }
+}
@@ -11,17 +11,34 @@
 //! | --- | --- |
 //! | [`affix-state`](affix_state) | Middleware for adding prefix and suffix to the request path |
 //! | [`basic-auth`](basic_auth) | Middleware for basic authentication |
+//! | [`batch`] | Handler for batch requests |
+//! | [`body-tee`](body_tee) | Middleware for teeing response bodies to another writer |
+//! | [`body-transform`](body_transform) | Middleware for transforming response bodies |
 //! | [`caching-headers`](caching_headers) | Middleware for setting caching headers |
 //! | [`catch-panic`](catch_panic) | Middleware for catching panics |
 //! | [`concurrency-limiter`](concurrency_limiter) | Middleware for limiting concurrency |
+//! | [`cursor`] | Opaque cursors for keyset pagination |
+//! | [`deadline`] | Middleware for setting a request deadline |
+//! | [`deprecation`] | Middleware for advertising the deprecation of endpoints |
 //! | [`force-https`](force_https) | Middleware for forcing HTTPS |
+//! | [`geo-ip`](geo_ip) | Middleware for attaching geo metadata of the client IP |
+//! | [`health`] | Handlers for health check endpoints |
+//! | [`idempotency`] | Middleware for replaying the responses of retried requests by idempotency keys |
+//! | [`locale`] | Middleware for detecting the preferred locale |
 //! | [`logging`] | Middleware for logging requests and responses |
 //! | [`request-id`](request_id) | Middleware for setting a request ID |
+//! | [`rewrite`] | Middleware for rewriting request paths |
+//! | [`server-stats`](server_stats) | Middleware and handler for a summary of server statistics |
+//! | [`server-timing`](server_timing) | Middleware for writing the `Server-Timing` header |
 //! | [`size-limiter`](size_limiter) | Middleware for limiting request size |
 //! | [`sse`] | Server-Sent Events (SSE) middleware |
 //! | [`timeout`] | Middleware for setting a timeout |
 //! | [`trailing-slash`](trailing_slash) | Middleware for handling trailing slashes |
 //! | [`tower-compat`](tower_compat) | Adapters for `tower::Layer` and `tower::Service` |
+//! | [`transaction`] | Middleware for running requests in database transactions |
+//! | [`trusted-proxy`](trusted_proxy) | Middleware for requests forwarded by trusted proxies |
+//! | [`vcr`] | Middleware for recording and replaying responses |
+//! | [`webhook`] | Middleware for verifying webhook signatures |
 //! | [`websocket`] | WebSocket implementation |
 #![doc(html_favicon_url = "https://salvo.rs/favicon-32x32.png")]
 #![doc(html_logo_url = "https://salvo.rs/images/logo.svg")]
@@ -34,12 +51,34 @@ cfg_feature! {
     #![feature = "basic-auth"]
     pub mod basic_auth;
 }
+cfg_feature! {
+    #![feature = "batch"]
+    pub mod batch;
+}
 
 cfg_feature! {
     #![feature = "affix-state"]
     pub mod affix_state;
 }
 
+cfg_feature! {
+    #![feature = "body-tee"]
+    pub mod body_tee;
+}
+
+cfg_feature! {
+    #![feature = "body-transform"]
+    pub mod body_transform;
+}
+
+cfg_feature! {
+    #![feature = "cursor"]
+    pub mod cursor;
+}
+cfg_feature! {
+    #![feature = "deprecation"]
+    pub mod deprecation;
+}
 cfg_feature! {
     #![feature = "force-https"]
     pub mod force_https;
@@ -49,6 +88,23 @@ cfg_feature! {
     pub mod catch_panic;
 }
 
+cfg_feature! {
+    #![feature = "geo-ip"]
+    pub mod geo_ip;
+}
+
+cfg_feature! {
+    #![feature = "health"]
+    pub mod health;
+}
+cfg_feature! {
+    #![feature = "idempotency"]
+    pub mod idempotency;
+}
+cfg_feature! {
+    #![feature = "locale"]
+    pub mod locale;
+}
 cfg_feature! {
     #![feature = "logging"]
     pub mod logging;
@@ -57,6 +113,10 @@ cfg_feature! {
     #![feature = "sse"]
     pub mod sse;
 }
+cfg_feature! {
+    #![feature = "webhook"]
+    pub mod webhook;
+}
 cfg_feature! {
     #![feature = "websocket"]
     pub mod websocket;
@@ -77,6 +137,22 @@ cfg_feature! {
     #![feature = "timeout"]
     pub mod timeout;
 }
+cfg_feature! {
+    #![feature = "deadline"]
+    pub mod deadline;
+}
+cfg_feature! {
+    #![feature = "transaction"]
+    pub mod transaction;
+}
+cfg_feature! {
+    #![feature = "trusted-proxy"]
+    pub mod trusted_proxy;
+}
+cfg_feature! {
+    #![feature = "vcr"]
+    pub mod vcr;
+}
 cfg_feature! {
     #![feature = "caching-headers"]
     pub mod caching_headers;
@@ -85,6 +161,18 @@ cfg_feature! {
     #![feature = "request-id"]
     pub mod request_id;
 }
+cfg_feature! {
+    #![feature = "rewrite"]
+    pub mod rewrite;
+}
+cfg_feature! {
+    #![feature = "server-stats"]
+    pub mod server_stats;
+}
+cfg_feature! {
+    #![feature = "server-timing"]
+    pub mod server_timing;
+}
 cfg_feature! {
     #![feature ="tower-compat"]
     pub mod tower_compat;
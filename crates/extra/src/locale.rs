@@ -0,0 +1,212 @@
+//! Middleware for detecting the preferred locale of the client.
+//!
+//! [`Locale`] negotiates the best locale from a supported set and stores it in the [`Depot`], so handlers and
+//! catchers can render localized responses with [`LocaleDepotExt::locale`]. It does not translate anything.
+//!
+//! The locale is resolved in this order:
+//!
+//! - The cookie set by [`Locale::cookie_name`], if its value is one of the supported locales. It is useful to let
+//!   users choose their language.
+//! - The `Accept-Language` header. Language ranges are tried by their quality values and `q=0` ones are ignored.
+//!   A range matches a supported locale exactly, otherwise their primary language subtags are compared, so `zh-TW`
+//!   matches `zh` and `en` matches `en-US`. Matching is case-insensitive.
+//! - The default locale, which is the first supported locale.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use salvo_core::prelude::*;
+//! use salvo_extra::locale::{Locale, LocaleDepotExt};
+//!
+//! #[handler]
+//! async fn hello(depot: &mut Depot) -> &'static str {
+//!     match depot.locale() {
+//!         Some("zh-CN") => "你好",
+//!         _ => "Hello",
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let router = Router::new()
+//!         .hoop(Locale::new(["en-US", "zh-CN"]).cookie_name("lang"))
+//!         .get(hello);
+//!
+//!     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
+//!     Server::new(acceptor).serve(router).await;
+//! }
+//! ```
+use salvo_core::http::header::ACCEPT_LANGUAGE;
+use salvo_core::http::{Request, Response};
+use salvo_core::{async_trait, Depot, FlowCtrl, Handler};
+
+/// Key for store the negotiated locale in depot.
+pub const LOCALE_KEY: &str = "::salvo::locale";
+
+/// Extension for Depot.
+pub trait LocaleDepotExt {
+    /// Get the negotiated locale from depot.
+    fn locale(&self) -> Option<&str>;
+}
+
+impl LocaleDepotExt for Depot {
+    #[inline]
+    fn locale(&self) -> Option<&str> {
+        self.get::<String>(LOCALE_KEY).map(|v| &**v).ok()
+    }
+}
+
+/// Middleware for detecting the preferred locale of the client.
+///
+/// View [module level documentation](index.html) for more details.
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+pub struct Locale {
+    /// The supported locales, the first one is the default locale.
+    pub supported: Vec<String>,
+    /// The name of the cookie which stores the user chosen locale.
+    pub cookie_name: Option<String>,
+}
+
+impl Locale {
+    /// Create new `Locale` middleware with the supported locales, the first one is the default locale.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `supported` is empty.
+    pub fn new<I, S>(supported: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let supported: Vec<String> = supported.into_iter().map(Into::into).collect();
+        assert!(!supported.is_empty(), "supported locales should not be empty");
+        Self {
+            supported,
+            cookie_name: None,
+        }
+    }
+
+    /// Read the user chosen locale from the cookie with this name, it takes precedence over `Accept-Language`.
+    #[inline]
+    pub fn cookie_name(mut self, cookie_name: impl Into<String>) -> Self {
+        self.cookie_name = Some(cookie_name.into());
+        self
+    }
+
+    /// Negotiate the best supported locale for the request.
+    pub fn negotiate(&self, req: &Request) -> &str {
+        if let Some(cookie) = self.cookie_name.as_deref().and_then(|name| req.cookie(name)) {
+            if let Some(locale) = self.supported.iter().find(|s| s.eq_ignore_ascii_case(cookie.value())) {
+                return locale;
+            }
+        }
+        let ranges = req
+            .headers()
+            .get_all(ACCEPT_LANGUAGE)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .filter_map(parse_language_range);
+        let mut ranges = ranges.collect::<Vec<_>>();
+        // Stable sort keeps the order of the ranges with the same quality.
+        ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+        ranges
+            .into_iter()
+            .find_map(|(range, _)| self.lookup(range))
+            .unwrap_or(&self.supported[0])
+    }
+
+    fn lookup(&self, range: &str) -> Option<&str> {
+        if range == "*" {
+            return Some(&self.supported[0]);
+        }
+        if let Some(locale) = self.supported.iter().find(|s| s.eq_ignore_ascii_case(range)) {
+            return Some(locale);
+        }
+        let primary = primary_subtag(range);
+        self.supported
+            .iter()
+            .find(|s| primary_subtag(s).eq_ignore_ascii_case(primary))
+            .map(|s| &**s)
+    }
+}
+
+fn primary_subtag(tag: &str) -> &str {
+    tag.split(['-', '_']).next().unwrap_or(tag)
+}
+
+/// Parse a language range like `en-US;q=0.8`, returns `None` if it is empty or its quality is `0`.
+fn parse_language_range(value: &str) -> Option<(&str, f32)> {
+    let mut parts = value.split(';');
+    let range = parts.next()?.trim();
+    if range.is_empty() {
+        return None;
+    }
+    let mut quality = 1.0;
+    for param in parts {
+        if let Some(q) = param.trim().strip_prefix("q=") {
+            quality = q.trim().parse().ok()?;
+        }
+    }
+    (quality > 0.0).then_some((range, quality))
+}
+
+#[async_trait]
+impl Handler for Locale {
+    async fn handle(&self, req: &mut Request, depot: &mut Depot, _res: &mut Response, _ctrl: &mut FlowCtrl) {
+        let locale = self.negotiate(req).to_owned();
+        depot.insert(LOCALE_KEY, locale);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use salvo_core::prelude::*;
+    use salvo_core::test::{ResponseExt, TestClient};
+
+    use super::*;
+
+    #[handler]
+    async fn show(depot: &mut Depot) -> String {
+        depot.locale().unwrap_or_default().to_owned()
+    }
+
+    #[tokio::test]
+    async fn test_locale() {
+        let router = Router::new()
+            .hoop(Locale::new(["en-US", "zh-CN", "fr"]).cookie_name("lang"))
+            .get(show);
+        let service = Service::new(router);
+
+        async fn access(service: &Service, name: &'static str, value: &str) -> String {
+            TestClient::get("http://127.0.0.1:5801")
+                .add_header(name, value, true)
+                .send(service)
+                .await
+                .take_string()
+                .await
+                .unwrap()
+        }
+
+        assert_eq!(access(&service, "accept-language", "zh-CN,zh;q=0.9,en;q=0.8").await, "zh-CN");
+        assert_eq!(access(&service, "accept-language", "de;q=0.9, fr;q=0.5, zh;q=0.7").await, "zh-CN");
+        assert_eq!(access(&service, "accept-language", "fr-CA").await, "fr");
+        assert_eq!(access(&service, "accept-language", "EN").await, "en-US");
+        assert_eq!(access(&service, "accept-language", "fr;q=0, de").await, "en-US");
+        assert_eq!(access(&service, "accept-language", "ja, *;q=0.1").await, "en-US");
+        assert_eq!(access(&service, "x-other", "none").await, "en-US");
+
+        assert_eq!(access(&service, "cookie", "lang=fr").await, "fr");
+        assert_eq!(access(&service, "cookie", "lang=ja").await, "en-US");
+    }
+
+    #[test]
+    fn test_parse_language_range() {
+        assert_eq!(parse_language_range(" en-US "), Some(("en-US", 1.0)));
+        assert_eq!(parse_language_range("zh;q=0.5"), Some(("zh", 0.5)));
+        assert_eq!(parse_language_range("zh;q=0"), None);
+        assert_eq!(parse_language_range("zh;q=abc"), None);
+        assert_eq!(parse_language_range(""), None);
+    }
+}
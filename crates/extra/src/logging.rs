@@ -21,11 +21,73 @@
 //!     Server::new(acceptor).serve(service).await;
 //! }
 //! ```
+//!
+//! # Span fields
+//!
+//! [`Logger`] handles the request in a `Request` span with these fields, so all the events logged by the later
+//! middlewares and the handlers, like `tracing::debug!`, are logged with them:
+//!
+//! * `request_id`: the id set by [`RequestId`](crate::request_id::RequestId), it is filled no matter whether
+//!   `RequestId` is placed before or after `Logger`, and is empty if there is no `RequestId`.
+//! * `method`, `path` and `version` of the request, the sensitive query params in `path` are redacted.
+//! * `remote_addr` and `client_ip`, the ip of `remote_addr`. It is the address of the proxy if the server is
+//!   behind one.
+//!
+//! The fields of a span can not be added after it is created, to add your own fields, such as the id of the
+//! todo, enter a new span in the handler. It is a child of the `Request` span, and the formatters of
+//! `tracing-subscriber` log the fields of both spans, the outer fields first, so a field with the same name does
+//! not replace the one of the `Request` span:
+//!
+//! ```
+//! use salvo_core::prelude::*;
+//! use tracing::Instrument;
+//!
+//! #[handler]
+//! async fn update_todo(req: &mut Request) -> StatusCode {
+//!     let id = req.param::<u64>("id").unwrap_or_default();
+//!     async move {
+//!         // Logged with `request_id`, `method`, `path` and the other fields, and `todo_id`.
+//!         tracing::debug!("update todo");
+//!         StatusCode::OK
+//!     }
+//!     .instrument(tracing::debug_span!("update_todo", todo_id = id))
+//!     .await
+//! }
+//! ```
+//!
+//! # Logging bodies
+//!
+//! For development, [`BodyLogger`] logs the request and response bodies at `DEBUG` level, put it after
+//! [`Logger`] so the bodies are logged in the span of the request:
+//!
+//! ```no_run
+//! use salvo_core::prelude::*;
+//! use salvo_extra::logging::{debug_log_bodies, Logger};
+//!
+//! #[handler]
+//! async fn create_todo(req: &mut Request) -> String {
+//!     req.payload().await.map(|body| body.len().to_string()).unwrap_or_default()
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let router = Router::new()
+//!         .hoop(Logger::new())
+//!         .hoop(debug_log_bodies().redact_field("secret"))
+//!         .post(create_todo);
+//!
+//!     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
+//!     Server::new(acceptor).serve(router).await;
+//! }
+//! ```
 use std::time::Instant;
 
 use tracing::{Instrument, Level};
 
-use salvo_core::http::{Request, ResBody, Response, StatusCode};
+use salvo_core::http::body::{Body, ReqBody};
+use salvo_core::http::header::{HeaderMap, HeaderName, HeaderValue};
+use salvo_core::http::redact::{self, REDACTED};
+use salvo_core::http::{mime, Mime, Request, ResBody, Response, StatusCode};
 use salvo_core::{async_trait, Depot, FlowCtrl, Handler};
 
 /// A simple logger middleware.
@@ -42,13 +104,20 @@ impl Logger {
 #[async_trait]
 impl Handler for Logger {
     async fn handle(&self, req: &mut Request, depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl) {
+        #[cfg(feature = "request-id")]
+        let request_id = depot.get::<String>(crate::request_id::REQUST_ID_KEY).ok().cloned();
+        #[cfg(not(feature = "request-id"))]
+        let request_id: Option<String> = None;
+        let client_ip = req.remote_addr().clone().into_std().map(|addr| addr.ip());
         let span = tracing::span!(
             Level::INFO,
             "Request",
+            request_id = request_id.as_deref().map(tracing::field::display),
             remote_addr = %req.remote_addr().to_string(),
+            client_ip = client_ip.map(tracing::field::display),
             version = ?req.version(),
             method = %req.method(),
-            path = %req.uri(),
+            path = %redact::uri(req.uri()),
         );
 
         async move {
@@ -72,6 +141,204 @@ impl Handler for Logger {
     }
 }
 
+/// Create a [`BodyLogger`] middleware.
+#[inline]
+pub fn debug_log_bodies() -> BodyLogger {
+    BodyLogger::new()
+}
+
+/// A middleware logs the request and response bodies at `DEBUG` level, for development.
+///
+/// The request body is read into memory and put back into the request, so the handlers can still read it. Only
+/// the request bodies whose size is known and not larger than [`max_buffer_size`](Self::max_buffer_size) are
+/// read, and only the response bodies which are already in memory are logged, streamed bodies are not.
+///
+/// The sensitive headers and the fields of json and url-encoded bodies are redacted, as configured by
+/// [`salvo_core::http::redact`] and the extra names added to this middleware. The logged bodies are truncated
+/// to [`max_len`](Self::max_len) characters.
+///
+/// It is only enabled in debug builds by default, use [`enabled`](Self::enabled) to change it.
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+pub struct BodyLogger {
+    /// Whether to log the bodies. Default is `true` in debug builds.
+    pub enabled: bool,
+    /// The max number of characters of a logged body. Default is 1024.
+    pub max_len: usize,
+    /// The max size of the request body read into memory. Default is 64KB.
+    pub max_buffer_size: usize,
+    /// The headers whose values are redacted, in addition to the sensitive headers of [`redact`].
+    pub redacted_headers: Vec<HeaderName>,
+    /// The fields of json and url-encoded bodies whose values are redacted, in addition to the sensitive params
+    /// of [`redact`].
+    pub redacted_fields: Vec<String>,
+}
+impl Default for BodyLogger {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl BodyLogger {
+    /// Create new `BodyLogger` middleware.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            enabled: cfg!(debug_assertions),
+            max_len: 1024,
+            max_buffer_size: 64 * 1024,
+            redacted_headers: vec![],
+            redacted_fields: vec![],
+        }
+    }
+
+    /// Set whether to log the bodies.
+    #[inline]
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Set the max number of characters of a logged body.
+    #[inline]
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    /// Set the max size of the request body read into memory.
+    #[inline]
+    pub fn max_buffer_size(mut self, max_buffer_size: usize) -> Self {
+        self.max_buffer_size = max_buffer_size;
+        self
+    }
+
+    /// Add a header whose value is redacted.
+    #[inline]
+    pub fn redact_header(mut self, name: HeaderName) -> Self {
+        self.redacted_headers.push(name);
+        self
+    }
+
+    /// Add a field of json and url-encoded bodies whose value is redacted.
+    #[inline]
+    pub fn redact_field(mut self, name: impl Into<String>) -> Self {
+        self.redacted_fields.push(name.into());
+        self
+    }
+
+    fn headers(&self, headers: &HeaderMap) -> HeaderMap {
+        let mut headers = redact::headers(headers);
+        // Every value of a repeated header is redacted, not only the first one.
+        for (name, value) in headers.iter_mut() {
+            if self.redacted_headers.contains(name) {
+                *value = HeaderValue::from_static(REDACTED);
+            }
+        }
+        headers
+    }
+
+    fn is_redacted_field(&self, name: &str) -> bool {
+        self.redacted_fields.iter().any(|field| field == name) || redact::is_sensitive_param(name)
+    }
+
+    fn body(&self, bytes: &[u8], content_type: Option<Mime>) -> String {
+        let Ok(text) = std::str::from_utf8(bytes) else {
+            return format!("<{} bytes binary>", bytes.len());
+        };
+        let subtype = content_type.as_ref().map(|c| c.subtype());
+        let suffix = content_type.as_ref().and_then(|c| c.suffix());
+        let text = if subtype == Some(mime::JSON) || suffix == Some(mime::JSON) {
+            match serde_json::from_str::<serde_json::Value>(text) {
+                Ok(mut value) => {
+                    self.redact_json(&mut value);
+                    value.to_string()
+                }
+                Err(_) => text.to_owned(),
+            }
+        } else if subtype == Some(mime::WWW_FORM_URLENCODED) {
+            text.split('&')
+                .map(|pair| match pair.split_once('=') {
+                    Some((name, _)) if self.is_redacted_field(name) => format!("{name}={REDACTED}"),
+                    _ => pair.to_owned(),
+                })
+                .collect::<Vec<_>>()
+                .join("&")
+        } else {
+            text.to_owned()
+        };
+        match text.char_indices().nth(self.max_len) {
+            Some((index, _)) => format!("{}...({} bytes)", &text[..index], bytes.len()),
+            None => text,
+        }
+    }
+
+    fn redact_json(&self, value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::Object(map) => {
+                for (name, value) in map.iter_mut() {
+                    if self.is_redacted_field(name) {
+                        *value = REDACTED.into();
+                    } else {
+                        self.redact_json(value);
+                    }
+                }
+            }
+            serde_json::Value::Array(values) => {
+                for value in values {
+                    self.redact_json(value);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    async fn request_body(&self, req: &mut Request) -> String {
+        match req.body().size_hint().upper() {
+            Some(0) => String::new(),
+            Some(size) if size as usize <= self.max_buffer_size => {
+                match req.payload_with_max_size(self.max_buffer_size).await {
+                    Ok(bytes) => {
+                        let bytes = bytes.clone();
+                        // Put the body back, so the handlers can read it again.
+                        req.replace_body(ReqBody::Once(bytes.clone()));
+                        self.body(&bytes, req.content_type())
+                    }
+                    Err(e) => format!("<failed to read body: {e}>"),
+                }
+            }
+            Some(size) => format!("<{size} bytes omitted>"),
+            None => "<stream omitted>".into(),
+        }
+    }
+
+    fn response_body(&self, res: &Response) -> String {
+        let content_type = res.content_type();
+        match &res.body {
+            ResBody::None => String::new(),
+            ResBody::Once(bytes) => self.body(bytes, content_type),
+            ResBody::Chunks(chunks) => self.body(&chunks.iter().flatten().copied().collect::<Vec<_>>(), content_type),
+            ResBody::Error(e) => e.to_string(),
+            _ => "<stream omitted>".into(),
+        }
+    }
+}
+
+#[async_trait]
+impl Handler for BodyLogger {
+    async fn handle(&self, req: &mut Request, depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl) {
+        if !self.enabled {
+            ctrl.call_next(req, depot, res).await;
+            return;
+        }
+        let body = self.request_body(req).await;
+        tracing::debug!(headers = ?self.headers(req.headers()), %body, "Request body");
+        ctrl.call_next(req, depot, res).await;
+        let body = self.response_body(res);
+        tracing::debug!(headers = ?self.headers(res.headers()), %body, "Response body");
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use salvo_core::prelude::*;
@@ -92,12 +359,123 @@ mod tests {
             .hoop(Logger::new())
             .push(Router::with_path("hello").get(hello));
 
-        TestClient::get("http://127.0.0.1:5801/hello")
+        TestClient::get("http://127.0.0.1:5801/hello?todo_apikey=secret-key&page=1")
             .send(router)
             .await
             .take_string()
             .await
             .unwrap();
         assert!(logs_contain("duration"));
+        assert!(logs_contain("todo_apikey=[REDACTED]&page=1"));
+        assert!(!logs_contain("secret-key"));
+    }
+
+    #[cfg(feature = "request-id")]
+    #[tokio::test]
+    #[traced_test]
+    async fn test_log_span_fields() {
+        use crate::request_id::RequestId;
+
+        #[handler]
+        async fn create_todo() -> &'static str {
+            tracing::debug!("create todo");
+            "created"
+        }
+
+        for router in [
+            Router::new()
+                .hoop(Logger::new())
+                .hoop(RequestId::new().generator(|| "id-after".to_owned()))
+                .post(create_todo),
+            Router::new()
+                .hoop(RequestId::new().generator(|| "id-before".to_owned()))
+                .hoop(Logger::new())
+                .post(create_todo),
+        ] {
+            TestClient::post("http://127.0.0.1:5801/").send(router).await;
+        }
+        logs_assert(|lines: &[&str]| {
+            for id in ["id-after", "id-before"] {
+                let found = lines.iter().any(|line| {
+                    line.contains("create todo")
+                        && line.contains(&format!("request_id={id}"))
+                        && line.contains("method=POST")
+                });
+                if !found {
+                    return Err(format!("no handler log with request id {id}"));
+                }
+            }
+            Ok(())
+        });
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn test_log_bodies() {
+        #[handler]
+        async fn login(req: &mut Request) -> Json<serde_json::Value> {
+            let body = req.parse_json::<serde_json::Value>().await.unwrap();
+            Json(serde_json::json!({"token": "xyz", "length": body["password"].as_str().unwrap().len()}))
+        }
+        #[handler]
+        async fn form(req: &mut Request) -> String {
+            req.form::<String>("password").await.unwrap_or_default()
+        }
+
+        let router = Router::new()
+            .hoop(debug_log_bodies().enabled(true).redact_field("user").max_len(64))
+            .push(Router::with_path("login").post(login))
+            .push(Router::with_path("form").post(form));
+        let service = Service::new(router);
+
+        let content = TestClient::post("http://127.0.0.1:5801/login")
+            .add_header("authorization", "Bearer secret-token", true)
+            .json(&serde_json::json!({"user": "chris", "password": "hunter2"}))
+            .send(&service)
+            .await
+            .take_string()
+            .await
+            .unwrap();
+        assert_eq!(content, r#"{"length":7,"token":"xyz"}"#);
+        assert!(logs_contain(r#"{"password":"[REDACTED]","user":"[REDACTED]"}"#));
+        assert!(logs_contain(r#"{"length":7,"token":"[REDACTED]"}"#));
+        assert!(!logs_contain("hunter2"));
+        assert!(!logs_contain("secret-token"));
+
+        let content = TestClient::post("http://127.0.0.1:5801/form")
+            .form(&[("user", "chris"), ("password", "swordfish")])
+            .send(&service)
+            .await
+            .take_string()
+            .await
+            .unwrap();
+        assert_eq!(content, "swordfish");
+        assert!(logs_contain("user=[REDACTED]&password=[REDACTED]"));
+    }
+
+    #[test]
+    fn test_redact_repeated_headers() {
+        let logger = BodyLogger::new().redact_header(HeaderName::from_static("x-session"));
+        let mut headers = HeaderMap::new();
+        for value in ["a=secret-1", "b=secret-2"] {
+            headers.append("set-cookie", HeaderValue::from_static(value));
+        }
+        for value in ["session-1", "session-2"] {
+            headers.append("x-session", HeaderValue::from_static(value));
+        }
+        headers.append("accept", HeaderValue::from_static("text/plain"));
+        let headers = logger.headers(&headers);
+        for name in ["set-cookie", "x-session"] {
+            let values = headers.get_all(name).iter().collect::<Vec<_>>();
+            assert_eq!(values, [REDACTED, REDACTED]);
+        }
+        assert_eq!(headers["accept"], "text/plain");
+    }
+
+    #[test]
+    fn test_truncate_body() {
+        let logger = BodyLogger::new().max_len(4);
+        assert_eq!(logger.body("hello world".as_bytes(), None), "hell...(11 bytes)");
+        assert_eq!(logger.body(&[0xff, 0xfe], None), "<2 bytes binary>");
     }
 }
@@ -118,11 +118,16 @@ impl IdGenerator for UlidGenerator {
 #[async_trait]
 impl Handler for RequestId {
     async fn handle(&self, req: &mut Request, depot: &mut Depot, _res: &mut Response, _ctrl: &mut FlowCtrl) {
-        if !self.overwrite && req.headers().contains_key(&self.header_name) {
-            return;
-        }
-        let id = self.generator.generate(req, depot);
-        let _ = req.add_header(self.header_name.clone(), &id, true);
-        depot.insert(REQUST_ID_KEY, id);
+        let id = match req.header::<String>(&self.header_name) {
+            Some(id) if !self.overwrite => id,
+            _ => {
+                let id = self.generator.generate(req, depot);
+                let _ = req.add_header(self.header_name.clone(), &id, true);
+                id
+            }
+        };
+        // Fill the `request_id` field of the span of `Logger` if it is placed before this middleware.
+        tracing::Span::current().record("request_id", tracing::field::display(&id));
+        depot.insert_cloneable(REQUST_ID_KEY, id);
     }
 }
@@ -0,0 +1,234 @@
+//! Middleware for rewriting request paths.
+//!
+//! It is useful to keep old URLs working after migrating routes, without touching the route definitions. Each rule
+//! matches the request path with a regex, and the replacement can reference the captured groups, like `$1`. The query
+//! of the request is kept unless the replacement has its own query. Rules are tried in the order they are added,
+//! and the first matched one wins.
+//!
+//! [`Rewrite`] works in two ways:
+//!
+//! - Internal rewrites, added by [`Rewrite::rule`], change the path before routing, so the request is routed as if
+//!   it was sent to the new path, while the client still sees the old URL. Add `Rewrite` as a filter of the root
+//!   router with [`Router::filter`](salvo_core::Router::filter), the filter always passes.
+//! - Redirects, added by [`Rewrite::redirect`], respond with `301 Moved Permanently` to tell the client the new URL.
+//!   Add `Rewrite` as a hoop of [`Service`](salvo_core::Service), which runs even if no route matches the old path.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use salvo_core::prelude::*;
+//! use salvo_extra::rewrite::Rewrite;
+//!
+//! #[handler]
+//! async fn list_todos() -> &'static str {
+//!     "todos"
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let rewrite = Rewrite::new()
+//!         .rule("^/old/todos(/.*)?$", "/api/todos$1")
+//!         .redirect("^/legacy/(.*)$", "/api/$1");
+//!     let router = Router::new()
+//!         .filter(rewrite.clone())
+//!         .push(Router::with_path("api/todos").get(list_todos));
+//!     let service = Service::new(router).hoop(rewrite);
+//!
+//!     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
+//!     Server::new(acceptor).serve(service).await;
+//! }
+//! ```
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+use regex::Regex;
+use salvo_core::http::uri::{PathAndQuery, Uri};
+use salvo_core::http::{Request, Response, StatusCode};
+use salvo_core::routing::{Filter, PathState};
+use salvo_core::writing::Redirect;
+use salvo_core::{async_trait, Depot, FlowCtrl, Handler};
+
+/// A rewrite rule.
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+pub struct RewriteRule {
+    /// The regex matched against the request path.
+    pub from: Regex,
+    /// The replacement of the path, it can reference the captured groups of `from`, like `$1`.
+    pub to: String,
+    /// The redirect status code, the path is rewritten internally if it is `None`.
+    pub redirect: Option<StatusCode>,
+}
+
+impl RewriteRule {
+    fn rewrite(&self, uri: &Uri) -> Option<Uri> {
+        let path = uri.path();
+        if !self.from.is_match(path) {
+            return None;
+        }
+        let new_path = self.from.replace(path, self.to.as_str());
+        let path_and_query = match uri.query() {
+            Some(query) if !new_path.contains('?') => format!("{new_path}?{query}"),
+            _ => new_path.into_owned(),
+        };
+        let mut parts = uri.clone().into_parts();
+        parts.path_and_query = Some(PathAndQuery::try_from(path_and_query).ok()?);
+        Uri::from_parts(parts).ok()
+    }
+}
+
+/// Middleware for rewriting request paths.
+///
+/// View [module level documentation](index.html) for more details.
+#[derive(Clone, Default)]
+pub struct Rewrite {
+    rules: Arc<Vec<RewriteRule>>,
+}
+
+impl Debug for Rewrite {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.rules.iter()).finish()
+    }
+}
+
+impl Rewrite {
+    /// Create a new `Rewrite` without rules.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a rule to rewrite the path internally before routing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from` is not a valid regex.
+    #[inline]
+    pub fn rule(self, from: &str, to: impl Into<String>) -> Self {
+        self.add_rule(from, to.into(), None)
+    }
+
+    /// Add a rule to redirect the client to the new path with `301 Moved Permanently`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from` is not a valid regex.
+    #[inline]
+    pub fn redirect(self, from: &str, to: impl Into<String>) -> Self {
+        self.add_rule(from, to.into(), Some(StatusCode::MOVED_PERMANENTLY))
+    }
+
+    /// Add a rule to redirect the client to the new path with the given redirect status code.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from` is not a valid regex, or `status_code` is not a redirection status code.
+    #[inline]
+    pub fn redirect_with_status_code(self, from: &str, to: impl Into<String>, status_code: StatusCode) -> Self {
+        assert!(status_code.is_redirection(), "status code should be a redirection");
+        self.add_rule(from, to.into(), Some(status_code))
+    }
+
+    fn add_rule(mut self, from: &str, to: String, redirect: Option<StatusCode>) -> Self {
+        let from = Regex::new(from).unwrap_or_else(|e| panic!("invalid rewrite rule `{from}`: {e}"));
+        Arc::make_mut(&mut self.rules).push(RewriteRule { from, to, redirect });
+        self
+    }
+
+    /// Get the rules.
+    #[inline]
+    pub fn rules(&self) -> &[RewriteRule] {
+        &self.rules
+    }
+
+    fn rewrite(&self, uri: &Uri, redirect: bool) -> Option<(Uri, Option<StatusCode>)> {
+        self.rules
+            .iter()
+            .filter(|rule| rule.redirect.is_some() == redirect)
+            .find_map(|rule| rule.rewrite(uri).map(|uri| (uri, rule.redirect)))
+    }
+}
+
+#[async_trait]
+impl Filter for Rewrite {
+    async fn filter(&self, req: &mut Request, state: &mut PathState) -> bool {
+        if let Some((uri, _)) = self.rewrite(req.uri(), false) {
+            *state = PathState::new(uri.path());
+            req.set_uri(uri);
+        }
+        true
+    }
+}
+
+#[async_trait]
+impl Handler for Rewrite {
+    async fn handle(&self, req: &mut Request, _depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl) {
+        if let Some((uri, Some(status_code))) = self.rewrite(req.uri(), true) {
+            let location = uri.path_and_query().map(PathAndQuery::as_str).unwrap_or("/");
+            match Redirect::with_status_code(status_code, location) {
+                Ok(redirect) => {
+                    res.render(redirect);
+                    ctrl.skip_rest();
+                }
+                Err(e) => {
+                    tracing::error!(error = ?e, "redirect failed");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use salvo_core::http::header::LOCATION;
+    use salvo_core::prelude::*;
+    use salvo_core::test::{ResponseExt, TestClient};
+
+    use super::*;
+
+    #[handler]
+    async fn show(req: &mut Request) -> String {
+        format!("{} {:?}", req.uri().path(), req.query::<String>("page"))
+    }
+
+    #[tokio::test]
+    async fn test_rewrite() {
+        let rewrite = Rewrite::new()
+            .rule("^/old/todos(/.*)?$", "/api/todos$1")
+            .redirect("^/legacy/(.*)$", "/api/$1")
+            .redirect_with_status_code("^/moved$", "/api/todos?page=1", StatusCode::TEMPORARY_REDIRECT);
+        let router = Router::new().filter(rewrite.clone()).push(
+            Router::with_path("api/todos")
+                .get(show)
+                .push(Router::with_path("{id}").get(show)),
+        );
+        let service = Service::new(router).hoop(rewrite);
+
+        let content = TestClient::get("http://127.0.0.1:5801/old/todos?page=2")
+            .send(&service)
+            .await
+            .take_string()
+            .await
+            .unwrap();
+        assert_eq!(content, r#"/api/todos Some("2")"#);
+        let content = TestClient::get("http://127.0.0.1:5801/old/todos/3")
+            .send(&service)
+            .await
+            .take_string()
+            .await
+            .unwrap();
+        assert_eq!(content, "/api/todos/3 None");
+
+        let res = TestClient::get("http://127.0.0.1:5801/legacy/todos?page=2")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::MOVED_PERMANENTLY));
+        assert_eq!(res.headers()[LOCATION], "/api/todos?page=2");
+        let res = TestClient::get("http://127.0.0.1:5801/moved?page=2").send(&service).await;
+        assert_eq!(res.status_code, Some(StatusCode::TEMPORARY_REDIRECT));
+        assert_eq!(res.headers()[LOCATION], "/api/todos?page=1");
+
+        let res = TestClient::get("http://127.0.0.1:5801/old/users").send(&service).await;
+        assert_eq!(res.status_code, Some(StatusCode::NOT_FOUND));
+    }
+}
@@ -0,0 +1,320 @@
+//! Middleware and handler for a summary of server statistics.
+//!
+//! [`ServerStats`] is a middleware which counts the requests passing through it with atomics, and
+//! [`ServerStats::handler`] returns a handler which writes the summary as JSON:
+//!
+//! ```json
+//! {"version":"1.0.0","uptime":"1d 2h 3m 4s","uptime_secs":93784,"total_requests":1024,"active_requests":2,
+//!  "active_connections":1}
+//! ```
+//!
+//! `active_requests` counts the requests which are being handled, including the stats request itself.
+//! `active_connections` counts the open connections, it is only available when the `ServerStats` is also set as
+//! the [`FuseFactory`] of the server with `Server::fuse_factory`, because the middleware can not see the
+//! connections. Use [`ServerStats::fuse`] to keep another fuse factory, such as [`FlexFactory`], working.
+//!
+//! It is a lightweight summary for small deployments without a metrics system, use `salvo-otel` for full metrics.
+//!
+//! [`FlexFactory`]: salvo_core::fuse::FlexFactory
+//!
+//! # Example
+//!
+//! ```no_run
+//! use salvo_core::prelude::*;
+//! use salvo_extra::server_stats::ServerStats;
+//!
+//! #[handler]
+//! async fn hello() -> &'static str {
+//!     "Hello World"
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let stats = ServerStats::new().version(env!("CARGO_PKG_VERSION"));
+//!     let router = Router::new()
+//!         .hoop(stats.clone())
+//!         .get(hello)
+//!         .push(Router::with_path("stats").get(stats.handler()));
+//!
+//!     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
+//!     Server::new(acceptor).fuse_factory(stats).serve(router).await;
+//! }
+//! ```
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use salvo_core::fuse::{FuseEvent, FuseFactory, FuseInfo, Fusewire};
+use salvo_core::http::{Request, Response};
+use salvo_core::writing::Json;
+use salvo_core::{async_trait, Depot, FlowCtrl, Handler};
+use serde::Serialize;
+
+#[derive(Debug)]
+struct Counters {
+    started: Instant,
+    total_requests: AtomicU64,
+    active_requests: AtomicU64,
+    active_connections: AtomicU64,
+}
+
+type ArcFuseFactory = Arc<dyn FuseFactory + Send + Sync + 'static>;
+type ArcFusewire = Arc<dyn Fusewire + Send + Sync + 'static>;
+
+/// Middleware for counting requests, use [`ServerStats::handler`] to write the summary.
+///
+/// It is cheap to clone, the clones share the same counters.
+///
+/// View [module level documentation](index.html) for more details.
+#[derive(Clone)]
+pub struct ServerStats {
+    counters: Arc<Counters>,
+    version: Option<String>,
+    fuse: Option<ArcFuseFactory>,
+}
+
+impl Default for ServerStats {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ServerStats {
+    /// Create a new `ServerStats`, the uptime starts from now.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            counters: Arc::new(Counters {
+                started: Instant::now(),
+                total_requests: AtomicU64::new(0),
+                active_requests: AtomicU64::new(0),
+                active_connections: AtomicU64::new(0),
+            }),
+            version: None,
+            fuse: None,
+        }
+    }
+
+    /// Set the version of the application in the summary.
+    #[inline]
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Set the inner fuse factory, its fusewires are wrapped when the `ServerStats` is used as the fuse factory
+    /// of the server.
+    #[inline]
+    pub fn fuse<F>(mut self, factory: F) -> Self
+    where
+        F: FuseFactory + Send + Sync + 'static,
+    {
+        self.fuse = Some(Arc::new(factory));
+        self
+    }
+
+    /// Get the time since the `ServerStats` is created.
+    #[inline]
+    pub fn uptime(&self) -> Duration {
+        self.counters.started.elapsed()
+    }
+
+    /// Get the number of requests which have passed through the middleware.
+    #[inline]
+    pub fn total_requests(&self) -> u64 {
+        self.counters.total_requests.load(Ordering::Relaxed)
+    }
+
+    /// Get the number of requests which are being handled.
+    #[inline]
+    pub fn active_requests(&self) -> u64 {
+        self.counters.active_requests.load(Ordering::Relaxed)
+    }
+
+    /// Get the number of open connections, it is always `0` if the `ServerStats` is not the fuse factory of the
+    /// server.
+    #[inline]
+    pub fn active_connections(&self) -> u64 {
+        self.counters.active_connections.load(Ordering::Relaxed)
+    }
+
+    /// Create a handler which writes the summary as JSON.
+    #[inline]
+    pub fn handler(&self) -> ServerStatsHandler {
+        ServerStatsHandler(self.clone())
+    }
+}
+
+impl fmt::Debug for ServerStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ServerStats")
+            .field("counters", &self.counters)
+            .field("version", &self.version)
+            .finish()
+    }
+}
+
+// Decrease the active requests even if the handlers panic.
+struct ActiveGuard<'a>(&'a AtomicU64);
+impl Drop for ActiveGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl FuseFactory for ServerStats {
+    fn create(&self, info: FuseInfo) -> ArcFusewire {
+        self.counters.active_connections.fetch_add(1, Ordering::Relaxed);
+        Arc::new(ConnectionFusewire {
+            counters: self.counters.clone(),
+            inner: self.fuse.as_ref().map(|fuse| fuse.create(info)),
+        })
+    }
+}
+
+// Created for every connection and dropped with it, so it decreases the active connections when the connection
+// is closed.
+struct ConnectionFusewire {
+    counters: Arc<Counters>,
+    inner: Option<ArcFusewire>,
+}
+impl Drop for ConnectionFusewire {
+    fn drop(&mut self) {
+        self.counters.active_connections.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+#[async_trait]
+impl Fusewire for ConnectionFusewire {
+    fn event(&self, event: FuseEvent) {
+        if let Some(inner) = &self.inner {
+            inner.event(event);
+        }
+    }
+    async fn fused(&self) {
+        match &self.inner {
+            Some(inner) => inner.fused().await,
+            None => std::future::pending().await,
+        }
+    }
+}
+
+#[async_trait]
+impl Handler for ServerStats {
+    async fn handle(&self, req: &mut Request, depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl) {
+        self.counters.total_requests.fetch_add(1, Ordering::Relaxed);
+        self.counters.active_requests.fetch_add(1, Ordering::Relaxed);
+        let _guard = ActiveGuard(&self.counters.active_requests);
+        ctrl.call_next(req, depot, res).await;
+    }
+}
+
+/// Handler which writes the summary of [`ServerStats`] as JSON.
+#[derive(Clone, Debug)]
+pub struct ServerStatsHandler(ServerStats);
+
+#[derive(Serialize, Debug)]
+struct Summary<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<&'a str>,
+    uptime: String,
+    uptime_secs: u64,
+    total_requests: u64,
+    active_requests: u64,
+    active_connections: u64,
+}
+
+#[async_trait]
+impl Handler for ServerStatsHandler {
+    async fn handle(&self, _req: &mut Request, _depot: &mut Depot, res: &mut Response, _ctrl: &mut FlowCtrl) {
+        let stats = &self.0;
+        let uptime = stats.uptime();
+        res.render(Json(Summary {
+            version: stats.version.as_deref(),
+            uptime: format_duration(uptime),
+            uptime_secs: uptime.as_secs(),
+            total_requests: stats.total_requests(),
+            active_requests: stats.active_requests(),
+            active_connections: stats.active_connections(),
+        }));
+    }
+}
+
+// Format the duration like `1d 2h 3m 4s`, the leading zero units are omitted.
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (days, hours, minutes, secs) = (secs / 86400, secs / 3600 % 24, secs / 60 % 60, secs % 60);
+    if days > 0 {
+        format!("{days}d {hours}h {minutes}m {secs}s")
+    } else if hours > 0 {
+        format!("{hours}h {minutes}m {secs}s")
+    } else if minutes > 0 {
+        format!("{minutes}m {secs}s")
+    } else {
+        format!("{secs}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use salvo_core::prelude::*;
+    use salvo_core::test::{ResponseExt, TestClient};
+
+    use super::*;
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_secs(4)), "4s");
+        assert_eq!(format_duration(Duration::from_secs(184)), "3m 4s");
+        assert_eq!(format_duration(Duration::from_secs(7384)), "2h 3m 4s");
+        assert_eq!(format_duration(Duration::from_secs(93784)), "1d 2h 3m 4s");
+    }
+
+    #[handler]
+    async fn hello() -> &'static str {
+        "Hello World"
+    }
+
+    #[tokio::test]
+    async fn test_server_stats() {
+        let stats = ServerStats::new().version("1.0.0");
+        let router = Router::new()
+            .hoop(stats.clone())
+            .get(hello)
+            .push(Router::with_path("stats").get(stats.handler()));
+        let service = Service::new(router);
+
+        for _ in 0..3 {
+            TestClient::get("http://127.0.0.1:5801/").send(&service).await;
+        }
+        assert_eq!(stats.total_requests(), 3);
+        assert_eq!(stats.active_requests(), 0);
+
+        let mut res = TestClient::get("http://127.0.0.1:5801/stats").send(&service).await;
+        let summary: serde_json::Value = res.take_json().await.unwrap();
+        assert_eq!(summary["version"], "1.0.0");
+        assert_eq!(summary["uptime"], "0s");
+        assert_eq!(summary["total_requests"], 4);
+        assert_eq!(summary["active_requests"], 1);
+        assert_eq!(summary["active_connections"], 0);
+        assert_eq!(stats.active_requests(), 0);
+    }
+
+    #[test]
+    fn test_server_stats_connections() {
+        let stats = ServerStats::new();
+        let info = FuseInfo {
+            trans_proto: salvo_core::fuse::TransProto::Tcp,
+            remote_addr: std::net::SocketAddr::from(([127, 0, 0, 1], 5801)).into(),
+            local_addr: std::net::SocketAddr::from(([127, 0, 0, 1], 5800)).into(),
+        };
+        let first = stats.create(info.clone());
+        let second = stats.create(info);
+        assert_eq!(stats.active_connections(), 2);
+        drop(first);
+        assert_eq!(stats.active_connections(), 1);
+        drop(second);
+        assert_eq!(stats.active_connections(), 0);
+    }
+}
@@ -0,0 +1,267 @@
+//! Middleware for writing the `Server-Timing` header.
+//!
+//! [`ServerTiming`] measures the duration of the handlers after it and writes it as the `total` metric of the
+//! [`Server-Timing`](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Server-Timing) response header, so
+//! the browser devtools show how long the server spent on the request. The handlers can record the durations of
+//! their own phases with [`ServerTimingDepotExt::timing`], they are written before `total`:
+//!
+//! ```text
+//! server-timing: db;dur=12.500, render;dur=0.800, total;dur=14.100
+//! ```
+//!
+//! The durations are in milliseconds. The metric names must be tokens, like `db` or `cache-read`, the metrics
+//! whose names are not tokens are skipped. The descriptions recorded with [`ServerTimingDepotExt::timing_desc`]
+//! are written as quoted strings, so they can contain spaces and separators.
+//!
+//! The timings tell the clients about the internals of the server, so consider adding this middleware only in
+//! development, or behind a condition.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::time::Instant;
+//!
+//! use salvo_core::prelude::*;
+//! use salvo_extra::server_timing::{ServerTiming, ServerTimingDepotExt};
+//!
+//! #[handler]
+//! async fn list_todos(depot: &mut Depot) -> &'static str {
+//!     let started = Instant::now();
+//!     // Query the database.
+//!     depot.timing("db", started.elapsed());
+//!     "todos"
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let router = Router::new().hoop(ServerTiming::new()).get(list_todos);
+//!
+//!     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
+//!     Server::new(acceptor).serve(router).await;
+//! }
+//! ```
+use std::time::{Duration, Instant};
+
+use salvo_core::http::header::{HeaderName, HeaderValue};
+use salvo_core::http::{Request, Response};
+use salvo_core::{async_trait, Depot, FlowCtrl, Handler};
+
+/// The `Server-Timing` header name.
+pub const SERVER_TIMING: HeaderName = HeaderName::from_static("server-timing");
+
+/// Key for the recorded timings in depot.
+pub const SERVER_TIMING_KEY: &str = "::salvo::server_timing";
+
+/// A timing recorded by [`ServerTimingDepotExt`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Timing {
+    /// Name of the metric, it must be a token.
+    pub name: String,
+    /// Description of the metric.
+    pub desc: Option<String>,
+    /// Duration of the phase.
+    pub duration: Duration,
+}
+
+/// Extension for Depot.
+pub trait ServerTimingDepotExt {
+    /// Record the duration of a phase of current request, it is written in the `Server-Timing` header.
+    fn timing(&mut self, name: impl Into<String>, duration: Duration) -> &mut Self;
+
+    /// Record the duration of a phase of current request with a description.
+    fn timing_desc(&mut self, name: impl Into<String>, desc: impl Into<String>, duration: Duration) -> &mut Self;
+
+    /// Get the recorded timings of current request.
+    fn timings(&self) -> &[Timing];
+}
+
+fn push_timing(depot: &mut Depot, timing: Timing) {
+    match depot.get_mut::<Vec<Timing>>(SERVER_TIMING_KEY) {
+        Ok(timings) => timings.push(timing),
+        Err(_) => {
+            depot.insert(SERVER_TIMING_KEY, vec![timing]);
+        }
+    }
+}
+
+impl ServerTimingDepotExt for Depot {
+    #[inline]
+    fn timing(&mut self, name: impl Into<String>, duration: Duration) -> &mut Self {
+        push_timing(
+            self,
+            Timing {
+                name: name.into(),
+                desc: None,
+                duration,
+            },
+        );
+        self
+    }
+
+    #[inline]
+    fn timing_desc(&mut self, name: impl Into<String>, desc: impl Into<String>, duration: Duration) -> &mut Self {
+        push_timing(
+            self,
+            Timing {
+                name: name.into(),
+                desc: Some(desc.into()),
+                duration,
+            },
+        );
+        self
+    }
+
+    #[inline]
+    fn timings(&self) -> &[Timing] {
+        self.get::<Vec<Timing>>(SERVER_TIMING_KEY)
+            .map(|timings| &**timings)
+            .unwrap_or_default()
+    }
+}
+
+/// Middleware for writing the `Server-Timing` header.
+///
+/// View [module level documentation](index.html) for more details.
+#[derive(Clone, Debug)]
+pub struct ServerTiming {
+    total_name: String,
+}
+
+impl Default for ServerTiming {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ServerTiming {
+    /// Create a new `ServerTiming`.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            total_name: "total".into(),
+        }
+    }
+
+    /// Set the name of the metric of the handlers duration. Default is `total`.
+    #[inline]
+    pub fn total_name(mut self, name: impl Into<String>) -> Self {
+        self.total_name = name.into();
+        self
+    }
+}
+
+// Token as defined in RFC 7230 section 3.2.6.
+fn is_token(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+}
+
+// Returns `None` if the name is not a token.
+fn format_metric(name: &str, desc: Option<&str>, duration: Duration) -> Option<String> {
+    if !is_token(name) {
+        return None;
+    }
+    let mut metric = name.to_owned();
+    if let Some(desc) = desc {
+        metric.push_str(";desc=\"");
+        for c in desc.chars() {
+            if c == '"' || c == '\\' {
+                metric.push('\\');
+            }
+            metric.push(c);
+        }
+        metric.push('"');
+    }
+    metric.push_str(&format!(";dur={:.3}", duration.as_secs_f64() * 1000.0));
+    Some(metric)
+}
+
+#[async_trait]
+impl Handler for ServerTiming {
+    async fn handle(&self, req: &mut Request, depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl) {
+        let started = Instant::now();
+        ctrl.call_next(req, depot, res).await;
+        let total = started.elapsed();
+
+        let metrics = depot
+            .timings()
+            .iter()
+            .map(|timing| (&*timing.name, timing.desc.as_deref(), timing.duration))
+            .chain([(&*self.total_name, None, total)]);
+        for (name, desc, duration) in metrics {
+            match format_metric(name, desc, duration).and_then(|metric| HeaderValue::from_str(&metric).ok()) {
+                Some(value) => {
+                    res.headers_mut().append(SERVER_TIMING, value);
+                }
+                None => tracing::warn!(name, "invalid server timing metric"),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use salvo_core::prelude::*;
+    use salvo_core::test::TestClient;
+
+    use super::*;
+
+    #[handler]
+    async fn list_todos(depot: &mut Depot) -> &'static str {
+        depot
+            .timing("db", Duration::from_micros(12500))
+            .timing("bad\nname", Duration::from_millis(1))
+            .timing("db;dur=0", Duration::from_millis(1))
+            .timing_desc("render", "page, \"todos\"", Duration::from_millis(2));
+        "todos"
+    }
+
+    #[test]
+    fn test_format_metric() {
+        assert_eq!(
+            format_metric("db", None, Duration::from_micros(12500)).as_deref(),
+            Some("db;dur=12.500")
+        );
+        assert_eq!(
+            format_metric("total", None, Duration::from_secs(1)).as_deref(),
+            Some("total;dur=1000.000")
+        );
+        assert_eq!(
+            format_metric("cache-read", Some(r#"a "b" \c"#), Duration::ZERO).as_deref(),
+            Some(r#"cache-read;desc="a \"b\" \\c";dur=0.000"#)
+        );
+        assert_eq!(format_metric("db,total", None, Duration::ZERO), None);
+        assert_eq!(format_metric("db desc", None, Duration::ZERO), None);
+        assert_eq!(format_metric("", None, Duration::ZERO), None);
+    }
+
+    #[tokio::test]
+    async fn test_server_timing() {
+        let router = Router::new()
+            .hoop(ServerTiming::new().total_name("app"))
+            .push(Router::with_path("todos").get(list_todos));
+        let service = Service::new(router);
+
+        let res = TestClient::get("http://127.0.0.1:5801/todos").send(&service).await;
+        let metrics = res
+            .headers()
+            .get_all(SERVER_TIMING)
+            .iter()
+            .map(|value| value.to_str().unwrap().to_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(metrics.len(), 3);
+        assert_eq!(metrics[0], "db;dur=12.500");
+        assert_eq!(metrics[1], r#"render;desc="page, \"todos\"";dur=2.000"#);
+        assert!(metrics[2].starts_with("app;dur="));
+
+        let service = Service::new(Router::new()).hoop(ServerTiming::new());
+        let res = TestClient::get("http://127.0.0.1:5801/missing").send(&service).await;
+        assert_eq!(res.status_code, Some(StatusCode::NOT_FOUND));
+        let metrics = res.headers().get_all(SERVER_TIMING).iter().collect::<Vec<_>>();
+        assert_eq!(metrics.len(), 1);
+        assert!(metrics[0].to_str().unwrap().starts_with("total;dur="));
+    }
+}
@@ -1,5 +1,13 @@
 //! Middleware for limiting request size.
 //!
+//! [`MaxSize`] rejects the request with `413 Payload Too Large` by its size hint before the
+//! handler is called. It is independent from the secure maximum size of `Request`, which limits
+//! the data read into memory by `payload`, `parse_body` and `form_data`, see
+//! [`SecureMaxSize`](salvo_core::http::request::SecureMaxSize).
+//! If both are set, the smaller one takes effect. The uploaded files of multipart forms are limited
+//! by [`MultipartLimits`](salvo_core::http::form::MultipartLimits) instead, which also limits the
+//! count of parts and the size of a single file part.
+//!
 //! # Example
 //!
 //! ```no_run
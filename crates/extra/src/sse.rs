@@ -44,6 +44,34 @@
 //! Each field already is event which can be sent to client.
 //! The events with multiple fields can be created by combining fields using tuples.
 //!
+//! # Resume
+//!
+//! When the connection is lost, the browser reconnects and sends the `id` of the last event it
+//! received in the `Last-Event-ID` header, so events should be sent with [`SseEvent::id`].
+//! Use [`resume`] to pass the received id to the closure producing the stream, so the server can
+//! continue from where the client left off:
+//!
+//! ```no_run
+//! use std::convert::Infallible;
+//! use futures_util::stream::iter;
+//!
+//! use salvo_core::prelude::*;
+//! use salvo_extra::sse::{self, SseEvent};
+//!
+//! #[handler]
+//! async fn handle(req: &mut Request, res: &mut Response) {
+//!     sse::resume(req, res, |last_event_id| {
+//!         let start = last_event_id.and_then(|id| id.parse::<u64>().ok()).map_or(0, |id| id + 1);
+//!         iter((start..start + 10).map(|id| {
+//!             Ok::<_, Infallible>(SseEvent::default().id(id.to_string()).text(format!("event {id}")))
+//!         }))
+//!     });
+//! }
+//! ```
+//!
+//! The comments sent by [`SseKeepAlive`] have no `id`, so they do not change the last event id
+//! remembered by the browser, and wrapping the resumed stream in [`SseKeepAlive`] is safe.
+//!
 //! See also the [EventSource](https://developer.mozilla.org/en-US/docs/Web/API/EventSource) API,
 //! which specifies the expected behavior of Server Sent Events.
 
@@ -59,10 +87,13 @@ use std::time::Duration;
 use futures_util::future;
 use futures_util::stream::{Stream, TryStream, TryStreamExt};
 use pin_project::pin_project;
-use salvo_core::http::header::{HeaderValue, CACHE_CONTROL, CONTENT_TYPE};
+use salvo_core::http::header::{HeaderName, HeaderValue, CACHE_CONTROL, CONTENT_TYPE};
 use tokio::time::{self, Sleep};
 
-use salvo_core::http::Response;
+use salvo_core::http::{Request, Response};
+
+/// The header sent by the browser with the `id` of the last received event when it reconnects.
+pub const LAST_EVENT_ID: HeaderName = HeaderName::from_static("last-event-id");
 
 /// Server-sent event data type
 #[derive(Clone, Debug)]
@@ -129,6 +160,9 @@ impl SseEvent {
     }
 
     /// Sets Server-sent event id.
+    ///
+    /// The browser remembers the id of the last received event, and sends it in the
+    /// `Last-Event-ID` header when it reconnects, see [`resume`].
     #[inline]
     pub fn id<T: Into<String>>(mut self, id: T) -> SseEvent {
         self.id = Some(id.into());
@@ -285,6 +319,29 @@ where
     res.stream(body_stream)
 }
 
+/// Get the `Last-Event-ID` header sent by the client when it reconnects.
+#[inline]
+pub fn last_event_id(req: &Request) -> Option<&str> {
+    req.headers()
+        .get(LAST_EVENT_ID)
+        .and_then(|v| v.to_str().ok())
+}
+
+/// Send event stream resumed from the `Last-Event-ID` header.
+///
+/// The `producer` is called with the value of the header, it is `None` if the client connects
+/// for the first time.
+#[inline]
+pub fn resume<F, S>(req: &Request, res: &mut Response, producer: F)
+where
+    F: FnOnce(Option<String>) -> S,
+    S: TryStream<Ok = SseEvent> + Send + 'static,
+    S::Error: StdError + Send + Sync + 'static,
+{
+    let event_stream = producer(last_event_id(req).map(ToOwned::to_owned));
+    stream(res, event_stream)
+}
+
 impl<S> Stream for SseKeepAlive<S>
 where
     S: TryStream<Ok = SseEvent> + Send + 'static,
@@ -324,7 +381,7 @@ mod tests {
     use std::time::Duration;
 
     use salvo_core::prelude::*;
-    use salvo_core::test::ResponseExt;
+    use salvo_core::test::{ResponseExt, TestClient};
     use tokio_stream;
 
     use super::*;
@@ -406,6 +463,39 @@ mod tests {
         assert!(text.contains("retry:1001"));
     }
 
+    #[tokio::test]
+    async fn test_sse_resume() {
+        #[handler]
+        async fn handle(req: &mut Request, res: &mut Response) {
+            super::resume(req, res, |last_event_id| {
+                let start = last_event_id
+                    .and_then(|id| id.parse::<u64>().ok())
+                    .map_or(0, |id| id + 1);
+                tokio_stream::iter((start..3).map(|id| {
+                    Ok::<_, Infallible>(SseEvent::default().id(id.to_string()).text("data"))
+                }))
+            });
+        }
+        let service = Service::new(Router::new().get(handle));
+
+        let text = TestClient::get("http://127.0.0.1:5800/")
+            .send(&service)
+            .await
+            .take_string()
+            .await
+            .unwrap();
+        assert!(text.contains("id:0") && text.contains("id:2"));
+
+        let text = TestClient::get("http://127.0.0.1:5800/")
+            .add_header(LAST_EVENT_ID, "1", true)
+            .send(&service)
+            .await
+            .take_string()
+            .await
+            .unwrap();
+        assert!(!text.contains("id:0") && !text.contains("id:1") && text.contains("id:2"));
+    }
+
     #[tokio::test]
     async fn test_sse_id() {
         let event_stream = tokio_stream::iter(vec![Ok::<_, Infallible>(SseEvent::default().id("jobs"))]);
@@ -0,0 +1,210 @@
+//! Middleware for running requests in database transactions.
+//!
+//! [`Transaction`] begins a transaction with the [`Transactor`] before the handlers are called and injects it into
+//! the [`Depot`], so the handlers can get it by [`Depot::obtain_mut`] and run all their writes in it. After the
+//! handlers, the transaction is committed if the status code of the response is successful, otherwise it is rolled
+//! back. If committing fails, the response is replaced by `500 Internal Server Error`, so the client never sees a
+//! success for writes which are not saved.
+//!
+//! Place this middleware after [`CatchPanic`](crate::catch_panic::CatchPanic): if a handler panics, the transaction
+//! is dropped without being committed, which rolls it back for database drivers like `sqlx`. If `CatchPanic` is
+//! placed after this middleware instead, the panic is written as `500` and the transaction is rolled back explicitly.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use salvo_core::prelude::*;
+//! use salvo_extra::transaction::{Transaction, Transactor};
+//! use sqlx::{PgPool, Postgres};
+//!
+//! struct PgTransactor(PgPool);
+//! impl Transactor for PgTransactor {
+//!     type Transaction = sqlx::Transaction<'static, Postgres>;
+//!     type Error = sqlx::Error;
+//!
+//!     async fn begin(&self) -> Result<Self::Transaction, Self::Error> {
+//!         self.0.begin().await
+//!     }
+//!     async fn commit(&self, tx: Self::Transaction) -> Result<(), Self::Error> {
+//!         tx.commit().await
+//!     }
+//!     async fn rollback(&self, tx: Self::Transaction) -> Result<(), Self::Error> {
+//!         tx.rollback().await
+//!     }
+//! }
+//!
+//! #[handler]
+//! async fn create_todo(depot: &mut Depot) -> Result<StatusCode, StatusError> {
+//!     let tx = depot
+//!         .obtain_mut::<sqlx::Transaction<'static, Postgres>>()
+//!         .map_err(|_| StatusError::internal_server_error())?;
+//!     sqlx::query("INSERT INTO todos (text) VALUES ('write docs')")
+//!         .execute(&mut **tx)
+//!         .await
+//!         .map_err(|_| StatusError::internal_server_error())?;
+//!     Ok(StatusCode::CREATED)
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let pool = PgPool::connect("postgres://localhost/todos").await.unwrap();
+//!     let router = Router::with_path("todos").hoop(Transaction::new(PgTransactor(pool))).post(create_todo);
+//!
+//!     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
+//!     Server::new(acceptor).serve(router).await;
+//! }
+//! ```
+use std::error::Error as StdError;
+use std::future::Future;
+
+use salvo_core::http::{Request, Response, StatusCode, StatusError};
+use salvo_core::{async_trait, Depot, FlowCtrl, Handler};
+
+/// Begins, commits and rolls back transactions for [`Transaction`].
+pub trait Transactor: Send + Sync + 'static {
+    /// The transaction type, it is injected into the [`Depot`].
+    type Transaction: Send + Sync + 'static;
+    /// The error type of beginning, committing and rolling back.
+    type Error: StdError + Send + Sync + 'static;
+
+    /// Begin a new transaction.
+    fn begin(&self) -> impl Future<Output = Result<Self::Transaction, Self::Error>> + Send;
+    /// Commit the transaction.
+    fn commit(&self, tx: Self::Transaction) -> impl Future<Output = Result<(), Self::Error>> + Send;
+    /// Roll back the transaction.
+    fn rollback(&self, tx: Self::Transaction) -> impl Future<Output = Result<(), Self::Error>> + Send;
+}
+
+/// Middleware for running requests in database transactions.
+///
+/// View [module level documentation](index.html) for more details.
+#[derive(Debug)]
+pub struct Transaction<T> {
+    transactor: T,
+}
+
+impl<T: Transactor> Transaction<T> {
+    /// Create a new `Transaction` with the transactor.
+    #[inline]
+    pub fn new(transactor: T) -> Self {
+        Self { transactor }
+    }
+
+    /// Get the transactor.
+    #[inline]
+    pub fn transactor(&self) -> &T {
+        &self.transactor
+    }
+}
+
+#[async_trait]
+impl<T: Transactor> Handler for Transaction<T> {
+    async fn handle(&self, req: &mut Request, depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl) {
+        let tx = match self.transactor.begin().await {
+            Ok(tx) => tx,
+            Err(e) => {
+                tracing::error!(error = ?e, "begin transaction failed");
+                res.render(StatusError::internal_server_error().brief("Begin transaction failed.").cause(e));
+                ctrl.skip_rest();
+                return;
+            }
+        };
+        depot.inject(tx);
+        ctrl.call_next(req, depot, res).await;
+
+        let Ok(tx) = depot.scrape::<T::Transaction>() else {
+            tracing::warn!("transaction is removed from depot by handlers");
+            return;
+        };
+        if res.status_code.unwrap_or(StatusCode::OK).is_success() {
+            if let Err(e) = self.transactor.commit(tx).await {
+                tracing::error!(error = ?e, "commit transaction failed");
+                res.render(StatusError::internal_server_error().brief("Commit transaction failed.").cause(e));
+            }
+        } else if let Err(e) = self.transactor.rollback(tx).await {
+            tracing::error!(error = ?e, "rollback transaction failed");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use salvo_core::prelude::*;
+    use salvo_core::test::TestClient;
+
+    use super::*;
+
+    #[derive(Default, Debug)]
+    struct Log(Mutex<Vec<String>>);
+
+    struct MockTransactor {
+        log: Arc<Log>,
+        fail_commit: bool,
+    }
+    impl Transactor for MockTransactor {
+        type Transaction = Vec<&'static str>;
+        type Error = std::io::Error;
+
+        async fn begin(&self) -> Result<Self::Transaction, Self::Error> {
+            Ok(vec![])
+        }
+        async fn commit(&self, tx: Self::Transaction) -> Result<(), Self::Error> {
+            if self.fail_commit {
+                return Err(std::io::Error::other("commit failed"));
+            }
+            self.log.0.lock().unwrap().push(format!("commit {}", tx.join(",")));
+            Ok(())
+        }
+        async fn rollback(&self, tx: Self::Transaction) -> Result<(), Self::Error> {
+            self.log.0.lock().unwrap().push(format!("rollback {}", tx.join(",")));
+            Ok(())
+        }
+    }
+
+    #[handler]
+    async fn create_todo(depot: &mut Depot) -> StatusCode {
+        depot.obtain_mut::<Vec<&'static str>>().unwrap().push("todo");
+        StatusCode::CREATED
+    }
+    #[handler]
+    async fn conflict(depot: &mut Depot) -> StatusError {
+        depot.obtain_mut::<Vec<&'static str>>().unwrap().push("todo");
+        StatusError::conflict()
+    }
+
+    #[tokio::test]
+    async fn test_transaction() {
+        let log = Arc::new(Log::default());
+        let transaction = Transaction::new(MockTransactor {
+            log: log.clone(),
+            fail_commit: false,
+        });
+        let router = Router::new()
+            .hoop(transaction)
+            .push(Router::with_path("create").post(create_todo))
+            .push(Router::with_path("conflict").post(conflict));
+        let service = Service::new(router);
+
+        let res = TestClient::post("http://127.0.0.1:5801/create").send(&service).await;
+        assert_eq!(res.status_code, Some(StatusCode::CREATED));
+        let res = TestClient::post("http://127.0.0.1:5801/conflict").send(&service).await;
+        assert_eq!(res.status_code, Some(StatusCode::CONFLICT));
+        assert_eq!(*log.0.lock().unwrap(), vec!["commit todo", "rollback todo"]);
+    }
+
+    #[tokio::test]
+    async fn test_transaction_commit_failed() {
+        let log = Arc::new(Log::default());
+        let transaction = Transaction::new(MockTransactor {
+            log: log.clone(),
+            fail_commit: true,
+        });
+        let service = Service::new(Router::new().hoop(transaction).post(create_todo));
+
+        let res = TestClient::post("http://127.0.0.1:5801/").send(&service).await;
+        assert_eq!(res.status_code, Some(StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(log.0.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,267 @@
+//! Middleware for requests forwarded by trusted proxies.
+//!
+//! Behind a reverse proxy, the connection of the server comes from the proxy, so [`Request::remote_addr`] is the
+//! address of the proxy and [`Request::scheme`] is the scheme between the proxy and the server, usually `http`
+//! even if the client uses `https`. The proxies send the original ones in the `X-Forwarded-For` and
+//! `X-Forwarded-Proto` headers, and [`TrustedProxy`] applies them to the request, so [`Request::is_secure`] and
+//! the cookie `Secure` flag decisions of the other middlewares consider the scheme of the client.
+//!
+//! The headers can be sent by anyone, so they are only used if the connection comes from a trusted proxy, the
+//! request is not changed otherwise:
+//!
+//! * The scheme is set from the first value of `X-Forwarded-Proto`, if it is `http` or `https`.
+//! * The `Host` header is set from the first value of `X-Forwarded-Host`, so [`Request::full_url`] builds the urls
+//!   with the host of the client.
+//! * The [base path](Request::base_path) is set from the first value of `X-Forwarded-Prefix`, like `/api` if the
+//!   proxy serves the app under a path prefix, so [`Request::full_url`] includes it. The value must be a path, it is
+//!   ignored if it contains a query, a fragment or `//`.
+//! * The remote address is set from `X-Forwarded-For`, which is walked from right to left, skipping the trusted
+//!   proxies, and the first untrusted address is used. The port of the client is unknown, so it is `0`.
+//!
+//! Place this middleware before the others which use the address or the scheme, like `Logger` and `ForceHttps`.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::net::{IpAddr, Ipv4Addr};
+//!
+//! use salvo_core::prelude::*;
+//! use salvo_extra::trusted_proxy::TrustedProxy;
+//!
+//! #[handler]
+//! async fn hello(req: &mut Request) -> String {
+//!     format!("secure: {}, client: {}", req.is_secure(), req.remote_addr())
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let proxy = TrustedProxy::new([IpAddr::V4(Ipv4Addr::LOCALHOST)]);
+//!     let service = Service::new(Router::new().get(hello)).hoop(proxy);
+//!
+//!     let acceptor = TcpListener::new("127.0.0.1:5800").bind().await;
+//!     Server::new(acceptor).serve(service).await;
+//! }
+//! ```
+use std::net::{IpAddr, SocketAddr};
+
+use salvo_core::http::uri::Scheme;
+use salvo_core::http::header::HOST;
+use salvo_core::http::{HeaderName, HeaderValue, Request, Response};
+use salvo_core::{async_trait, Depot, FlowCtrl, Handler};
+
+/// The `X-Forwarded-For` header name.
+pub const X_FORWARDED_FOR: HeaderName = HeaderName::from_static("x-forwarded-for");
+/// The `X-Forwarded-Proto` header name.
+pub const X_FORWARDED_PROTO: HeaderName = HeaderName::from_static("x-forwarded-proto");
+/// The `X-Forwarded-Host` header name.
+pub const X_FORWARDED_HOST: HeaderName = HeaderName::from_static("x-forwarded-host");
+/// The `X-Forwarded-Prefix` header name.
+pub const X_FORWARDED_PREFIX: HeaderName = HeaderName::from_static("x-forwarded-prefix");
+
+/// Middleware for applying the forwarded address and scheme of trusted proxies to the request.
+///
+/// View [module level documentation](index.html) for more details.
+#[derive(Clone, Default, Debug)]
+pub struct TrustedProxy {
+    proxies: Vec<IpAddr>,
+    trust_all: bool,
+}
+
+impl TrustedProxy {
+    /// Create a new `TrustedProxy` which trusts the proxies with the addresses.
+    #[inline]
+    pub fn new(proxies: impl IntoIterator<Item = IpAddr>) -> Self {
+        Self {
+            proxies: proxies.into_iter().collect(),
+            trust_all: false,
+        }
+    }
+
+    /// Create a new `TrustedProxy` which trusts all the connections.
+    ///
+    /// Only use it if the server can not be reached without the proxies, or the clients can fake their address
+    /// and scheme.
+    #[inline]
+    pub fn all() -> Self {
+        Self {
+            proxies: vec![],
+            trust_all: true,
+        }
+    }
+
+    /// Add a trusted proxy.
+    #[inline]
+    pub fn add_proxy(mut self, proxy: IpAddr) -> Self {
+        self.proxies.push(proxy);
+        self
+    }
+
+    /// Returns whether the address is a trusted proxy.
+    #[inline]
+    pub fn is_trusted(&self, addr: &IpAddr) -> bool {
+        self.trust_all || self.proxies.contains(&addr.to_canonical())
+    }
+
+    // Walk `X-Forwarded-For` from right to left, the first untrusted address is the client.
+    fn forwarded_for(&self, req: &Request) -> Option<IpAddr> {
+        let mut client = None;
+        for value in req.headers().get_all(X_FORWARDED_FOR).iter().rev() {
+            let value = value.to_str().ok()?;
+            for addr in value.rsplit(',') {
+                let addr = addr.trim().parse::<IpAddr>().ok()?;
+                client = Some(addr);
+                if !self.is_trusted(&addr) {
+                    return client;
+                }
+            }
+        }
+        client
+    }
+
+    fn apply(&self, req: &mut Request) {
+        let proto = req
+            .headers()
+            .get(X_FORWARDED_PROTO)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split(',').next())
+            .map(|value| value.trim().to_ascii_lowercase());
+        match proto.as_deref() {
+            Some("https") => *req.scheme_mut() = Scheme::HTTPS,
+            Some("http") => *req.scheme_mut() = Scheme::HTTP,
+            _ => {}
+        }
+        let host = req
+            .headers()
+            .get(X_FORWARDED_HOST)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split(',').next())
+            .and_then(|value| HeaderValue::from_str(value.trim()).ok());
+        if let Some(host) = host {
+            req.headers_mut().insert(HOST, host);
+        }
+        let prefix = req
+            .headers()
+            .get(X_FORWARDED_PREFIX)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split(',').next())
+            .map(|value| value.trim().trim_end_matches('/'))
+            .filter(|value| !value.contains(['?', '#']) && !value.contains("//"))
+            .map(|value| {
+                if value.is_empty() || value.starts_with('/') {
+                    value.to_owned()
+                } else {
+                    format!("/{value}")
+                }
+            });
+        if let Some(prefix) = prefix {
+            *req.base_path_mut() = prefix;
+        }
+        if let Some(client) = self.forwarded_for(req) {
+            *req.remote_addr_mut() = SocketAddr::new(client, 0).into();
+        }
+    }
+}
+
+#[async_trait]
+impl Handler for TrustedProxy {
+    async fn handle(&self, req: &mut Request, _depot: &mut Depot, _res: &mut Response, _ctrl: &mut FlowCtrl) {
+        let trusted = match req.remote_addr().clone().into_std() {
+            Some(addr) => self.is_trusted(&addr.ip()),
+            None => self.trust_all,
+        };
+        if trusted {
+            self.apply(req);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use salvo_core::prelude::*;
+    use salvo_core::test::{ResponseExt, TestClient};
+
+    use super::*;
+
+    #[handler]
+    async fn connect_from_proxy(req: &mut Request) {
+        *req.remote_addr_mut() = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)), 5801).into();
+    }
+    #[handler]
+    async fn hello(req: &mut Request) -> String {
+        format!("{} {}", req.scheme(), req.remote_addr())
+    }
+
+    async fn access(service: &Service, forwarded_for: &str, proto: &str) -> String {
+        TestClient::get("http://127.0.0.1:5801/")
+            .add_header(X_FORWARDED_FOR, forwarded_for, true)
+            .add_header(X_FORWARDED_PROTO, proto, true)
+            .send(service)
+            .await
+            .take_string()
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_trusted_proxy() {
+        let proxy = TrustedProxy::new([IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1))])
+            .add_proxy(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2)));
+        let service = Service::new(Router::new().hoop(connect_from_proxy).hoop(proxy).get(hello));
+
+        assert_eq!(access(&service, "203.0.113.7", "https").await, "https socket://203.0.113.7:0");
+        let res = access(&service, "198.51.100.1, 203.0.113.7, 10.0.0.2", "HTTPS, http").await;
+        assert_eq!(res, "https socket://203.0.113.7:0");
+        assert_eq!(access(&service, "not an ip", "ftp").await, "http socket://10.0.0.1:5801");
+
+        let proxy = TrustedProxy::new([IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2))]);
+        let service = Service::new(Router::new().hoop(connect_from_proxy).hoop(proxy).get(hello));
+        assert_eq!(access(&service, "203.0.113.7", "https").await, "http socket://10.0.0.1:5801");
+    }
+
+    #[tokio::test]
+    async fn test_forwarded_host() {
+        #[handler]
+        async fn full_url(req: &mut Request) -> String {
+            req.full_url().unwrap_or_default()
+        }
+        let service = Service::new(Router::with_path("todos").hoop(TrustedProxy::all()).get(full_url));
+        let res = TestClient::get("http://127.0.0.1:5801/todos?page=2")
+            .add_header(X_FORWARDED_PROTO, "https", true)
+            .add_header(X_FORWARDED_HOST, "example.com", true)
+            .send(&service)
+            .await
+            .take_string()
+            .await
+            .unwrap();
+        assert_eq!(res, "https://example.com/todos?page=2");
+
+        let res = TestClient::get("http://127.0.0.1:5801/todos")
+            .add_header(X_FORWARDED_HOST, "example.com", true)
+            .add_header(X_FORWARDED_PREFIX, "/api/", true)
+            .send(&service)
+            .await
+            .take_string()
+            .await
+            .unwrap();
+        assert_eq!(res, "http://example.com/api/todos");
+
+        let res = TestClient::get("http://127.0.0.1:5801/todos")
+            .add_header(X_FORWARDED_HOST, "example.com", true)
+            .add_header(X_FORWARDED_PREFIX, "//evil.com", true)
+            .send(&service)
+            .await
+            .take_string()
+            .await
+            .unwrap();
+        assert_eq!(res, "http://example.com/todos");
+    }
+
+    #[tokio::test]
+    async fn test_trust_all() {
+        let service = Service::new(Router::new().hoop(TrustedProxy::all()).get(hello));
+        let res = access(&service, "203.0.113.7", "https").await;
+        assert_eq!(res, "https socket://203.0.113.7:0");
+    }
+}
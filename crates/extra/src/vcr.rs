@@ -0,0 +1,369 @@
+//! Middleware for recording and replaying responses.
+//!
+//! The hoop returned by [`record`] passes the requests through and records each request and response pair to a
+//! cassette file, and the one returned by [`replay`] serves the recorded responses without calling the handlers
+//! after it. It is useful for deterministic tests of the routes which call third-party APIs, like the `proxy`
+//! middleware: record the cassette against the real upstream once, then replay it in the tests.
+//!
+//! A request matches a recorded one if all of them are equal:
+//!
+//! * The method.
+//! * The path and the query, like `/todos?limit=10`, the host is ignored.
+//! * The body, compared byte by byte.
+//!
+//! The headers are not matched. If several recorded requests match, they are served in the recorded order, and the
+//! last one is served again once all of them are used. `404 Not Found` is written if none matches.
+//!
+//! The cassette is a JSON array of the interactions, which can be reviewed and edited by hand. The bodies are
+//! stored as text, or as base64 with `"base64": true` if they are not valid UTF-8:
+//!
+//! ```json
+//! [
+//!     {
+//!         "request": {"method": "GET", "path": "/todos?limit=10", "body": ""},
+//!         "response": {"status": 200, "headers": [["content-type", "application/json"]], "body": "[]"}
+//!     }
+//! ]
+//! ```
+//!
+//! The response bodies are buffered to be recorded, so do not record the endless streams, like server-sent events.
+//! The `connection`, `content-length` and `transfer-encoding` headers are not recorded, they are set again when
+//! the response is sent.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use salvo_core::prelude::*;
+//! use salvo_extra::vcr;
+//!
+//! #[handler]
+//! async fn upstream() -> &'static str {
+//!     "response from a third-party API"
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let vcr = if std::env::var("VCR_RECORD").is_ok() {
+//!         vcr::record("tests/cassettes/upstream.json")
+//!     } else {
+//!         vcr::replay("tests/cassettes/upstream.json")
+//!     };
+//!     let router = Router::new().hoop(vcr).get(upstream);
+//!
+//!     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
+//!     Server::new(acceptor).serve(router).await;
+//! }
+//! ```
+use std::path::{Path, PathBuf};
+
+use base64::engine::{general_purpose, Engine};
+use http_body_util::BodyExt;
+use salvo_core::http::body::{ReqBody, ResBody};
+use salvo_core::http::header::{HeaderName, HeaderValue, CONNECTION, CONTENT_LENGTH, TRANSFER_ENCODING};
+use salvo_core::http::{Request, Response, StatusCode, StatusError};
+use salvo_core::hyper::body::Bytes;
+use salvo_core::{async_trait, Depot, FlowCtrl, Handler, Writer};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct Interaction {
+    request: RecordedRequest,
+    response: RecordedResponse,
+}
+
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug)]
+struct RecordedRequest {
+    method: String,
+    path: String,
+    #[serde(flatten)]
+    body: RecordedBody,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct RecordedResponse {
+    status: u16,
+    #[serde(default)]
+    headers: Vec<(String, String)>,
+    #[serde(flatten)]
+    body: RecordedBody,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default, Eq, PartialEq, Debug)]
+struct RecordedBody {
+    #[serde(default)]
+    body: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    base64: bool,
+}
+
+impl RecordedBody {
+    fn new(bytes: &[u8]) -> Self {
+        match std::str::from_utf8(bytes) {
+            Ok(body) => Self {
+                body: body.to_owned(),
+                base64: false,
+            },
+            Err(_) => Self {
+                body: general_purpose::STANDARD.encode(bytes),
+                base64: true,
+            },
+        }
+    }
+
+    fn to_bytes(&self) -> Bytes {
+        if self.base64 {
+            match general_purpose::STANDARD.decode(&self.body) {
+                Ok(bytes) => bytes.into(),
+                Err(e) => {
+                    tracing::error!(error = ?e, "invalid base64 body in cassette");
+                    Bytes::new()
+                }
+            }
+        } else {
+            Bytes::from(self.body.clone())
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Mode {
+    Record,
+    Replay,
+}
+
+#[derive(Default, Debug)]
+struct Cassette {
+    interactions: Vec<Interaction>,
+    // Whether the recorded responses have been served in replay mode.
+    used: Vec<bool>,
+}
+
+/// Middleware for recording and replaying responses, created by [`record`] and [`replay`].
+///
+/// View [module level documentation](index.html) for more details.
+#[derive(Debug)]
+pub struct Vcr {
+    path: PathBuf,
+    mode: Mode,
+    cassette: Mutex<Cassette>,
+}
+
+/// Create a hoop which records the request and response pairs to the cassette file.
+///
+/// The cassette is started empty, and the file is rewritten after each request, so an existing file is replaced by
+/// the first request.
+pub fn record(path: impl Into<PathBuf>) -> Vcr {
+    Vcr {
+        path: path.into(),
+        mode: Mode::Record,
+        cassette: Mutex::new(Cassette::default()),
+    }
+}
+
+/// Create a hoop which serves the responses recorded in the cassette file.
+///
+/// # Panics
+///
+/// If the cassette file can not be read or is not a valid cassette.
+pub fn replay(path: impl AsRef<Path>) -> Vcr {
+    let path = path.as_ref();
+    let content = std::fs::read(path)
+        .unwrap_or_else(|e| panic!("failed to read cassette `{}`: {e}", path.display()));
+    let interactions: Vec<Interaction> = serde_json::from_slice(&content)
+        .unwrap_or_else(|e| panic!("invalid cassette `{}`: {e}", path.display()));
+    Vcr {
+        path: path.to_owned(),
+        mode: Mode::Replay,
+        cassette: Mutex::new(Cassette {
+            used: vec![false; interactions.len()],
+            interactions,
+        }),
+    }
+}
+
+impl Vcr {
+    /// Get the path of the cassette file.
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    async fn record(
+        &self,
+        request: RecordedRequest,
+        req: &mut Request,
+        depot: &mut Depot,
+        res: &mut Response,
+        ctrl: &mut FlowCtrl,
+    ) {
+        ctrl.call_next(req, depot, res).await;
+
+        let bytes = match &res.body {
+            ResBody::None | ResBody::Error(_) => Bytes::new(),
+            _ => match res.take_body().collect().await {
+                Ok(body) => body.to_bytes(),
+                Err(e) => {
+                    tracing::error!(error = ?e, "read response body for recording failed");
+                    res.render(StatusError::internal_server_error());
+                    return;
+                }
+            },
+        };
+        let headers = res
+            .headers()
+            .iter()
+            .filter(|(name, _)| ![CONNECTION, CONTENT_LENGTH, TRANSFER_ENCODING].contains(name))
+            .map(|(name, value)| {
+                (name.as_str().to_owned(), String::from_utf8_lossy(value.as_bytes()).into_owned())
+            })
+            .collect();
+        let response = RecordedResponse {
+            status: res.status_code.unwrap_or(StatusCode::OK).as_u16(),
+            headers,
+            body: RecordedBody::new(&bytes),
+        };
+        if !bytes.is_empty() {
+            res.body(ResBody::Once(bytes));
+        }
+
+        let mut cassette = self.cassette.lock().await;
+        cassette.interactions.push(Interaction { request, response });
+        let content = serde_json::to_vec_pretty(&cassette.interactions).expect("cassette should be serialized");
+        if let Err(e) = tokio::fs::write(&self.path, content).await {
+            tracing::error!(error = ?e, path = ?self.path, "write cassette failed");
+        }
+    }
+
+    async fn replay(&self, request: RecordedRequest, res: &mut Response) {
+        let mut cassette = self.cassette.lock().await;
+        let Cassette { interactions, used } = &mut *cassette;
+        let matched = interactions
+            .iter()
+            .enumerate()
+            .filter(|(_, interaction)| interaction.request == request)
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        let Some(index) = matched.iter().find(|index| !used[**index]).or(matched.last()).copied() else {
+            tracing::warn!(method = request.method, path = request.path, "no recorded response matches the request");
+            res.render(StatusError::not_found().brief("No recorded response matches the request."));
+            return;
+        };
+        used[index] = true;
+
+        let response = &interactions[index].response;
+        res.status_code(StatusCode::from_u16(response.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR));
+        for (name, value) in &response.headers {
+            match (HeaderName::from_bytes(name.as_bytes()), HeaderValue::from_str(value)) {
+                (Ok(name), Ok(value)) => {
+                    res.headers_mut().append(name, value);
+                }
+                _ => tracing::warn!(name, "invalid recorded header"),
+            }
+        }
+        res.body(ResBody::Once(response.body.to_bytes()));
+    }
+}
+
+#[async_trait]
+impl Handler for Vcr {
+    async fn handle(&self, req: &mut Request, depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl) {
+        let body = match req.payload().await {
+            Ok(body) => body.clone(),
+            Err(e) => {
+                e.write(req, depot, res).await;
+                ctrl.skip_rest();
+                return;
+            }
+        };
+        let request = RecordedRequest {
+            method: req.method().to_string(),
+            path: req.uri().path_and_query().map(|path| path.as_str()).unwrap_or("/").to_owned(),
+            body: RecordedBody::new(&body),
+        };
+        match self.mode {
+            Mode::Record => {
+                // The payload is cached, put the body back for the handlers which read it directly.
+                req.replace_body(ReqBody::Once(body));
+                self.record(request, req, depot, res, ctrl).await;
+            }
+            Mode::Replay => {
+                ctrl.skip_rest();
+                self.replay(request, res).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use salvo_core::http::Method;
+    use salvo_core::prelude::*;
+    use salvo_core::test::{RequestBuilder, ResponseExt};
+
+    use super::*;
+
+    #[handler]
+    async fn echo(req: &mut Request, res: &mut Response) {
+        let body = req.payload().await.unwrap().clone();
+        res.add_header("x-upstream", "real", true).unwrap();
+        res.status_code(StatusCode::CREATED);
+        res.body(ResBody::Once(body));
+    }
+    #[handler]
+    async fn hello() -> &'static str {
+        "hello"
+    }
+    #[handler]
+    async fn binary(res: &mut Response) {
+        res.body(vec![0, 159, 146, 150]);
+    }
+
+    async fn send(service: &Service, method: Method, path: &str, body: &str) -> Response {
+        RequestBuilder::new(format!("http://127.0.0.1:5801{path}"), method)
+            .text(body.to_owned())
+            .send(service)
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_vcr() {
+        let path = std::env::temp_dir().join(format!("salvo-vcr-{}.json", std::process::id()));
+        let routes = || {
+            Router::new()
+                .push(Router::with_path("echo").post(echo))
+                .push(Router::with_path("hello").get(hello))
+                .push(Router::with_path("binary").get(binary))
+        };
+
+        let service = Service::new(Router::new().hoop(record(&path)).push(routes()));
+        let mut res = send(&service, Method::POST, "/echo?id=1", "hello").await;
+        assert_eq!(res.status_code, Some(StatusCode::CREATED));
+        assert_eq!(res.take_string().await.unwrap(), "hello");
+        send(&service, Method::POST, "/echo?id=1", "world").await;
+        send(&service, Method::GET, "/binary", "").await;
+
+        let cassette: serde_json::Value = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        assert_eq!(cassette.as_array().unwrap().len(), 3);
+        assert_eq!(cassette[0]["request"]["path"], "/echo?id=1");
+        assert_eq!(cassette[0]["response"]["body"], "hello");
+        assert_eq!(cassette[2]["response"]["base64"], true);
+
+        // The handlers are not called in replay mode, even if the route exists.
+        let service = Service::new(Router::new().hoop(replay(&path)).push(routes()));
+        let mut res = send(&service, Method::POST, "/echo?id=1", "world").await;
+        assert_eq!(res.status_code, Some(StatusCode::CREATED));
+        assert_eq!(res.headers().get("x-upstream").unwrap(), "real");
+        assert_eq!(res.take_string().await.unwrap(), "world");
+        let mut res = send(&service, Method::POST, "/echo?id=1", "hello").await;
+        assert_eq!(res.take_string().await.unwrap(), "hello");
+        let mut res = send(&service, Method::GET, "/binary", "").await;
+        assert_eq!(res.take_bytes(None).await.unwrap().as_ref(), &[0, 159, 146, 150]);
+        let res = send(&service, Method::POST, "/echo?id=2", "hello").await;
+        assert_eq!(res.status_code, Some(StatusCode::NOT_FOUND));
+        let res = send(&service, Method::GET, "/hello", "").await;
+        assert_eq!(res.status_code, Some(StatusCode::NOT_FOUND));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
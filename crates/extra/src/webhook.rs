@@ -0,0 +1,215 @@
+//! Middleware for verifying webhook signatures.
+//!
+//! Webhook providers, like GitHub, sign the raw request body with HMAC-SHA256 and a shared secret, and send the hex
+//! encoded digest in a header. [`WebhookSignature`] computes the digest of the body and compares it with the header
+//! in constant time. The request is rejected with `401 Unauthorized` if the header is missing or the signature does
+//! not match, and with `413 Payload Too Large` if the body is larger than the limit.
+//!
+//! The body is read by [`Request::payload_with_max_size`] and cached, so the handler can still get it with
+//! [`Request::payload`] or parse it with [`Request::parse_json`] and the other parsing methods.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use salvo_core::prelude::*;
+//! use salvo_extra::webhook::WebhookSignature;
+//!
+//! #[handler]
+//! async fn github(req: &mut Request) -> Result<String, StatusError> {
+//!     let event = req
+//!         .parse_json::<serde_json::Value>()
+//!         .await
+//!         .map_err(|_| StatusError::bad_request())?;
+//!     Ok(format!("received {}", event["action"]))
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let verifier = WebhookSignature::new("It's a Secret to Everybody")
+//!         .header_name("x-hub-signature-256")
+//!         .prefix("sha256=");
+//!     let router = Router::with_path("webhooks/github").hoop(verifier).post(github);
+//!
+//!     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
+//!     Server::new(acceptor).serve(router).await;
+//! }
+//! ```
+use std::fmt::{self, Debug, Formatter};
+
+use hmac::{Hmac, Mac};
+use salvo_core::http::header::HeaderName;
+use salvo_core::http::{Request, Response, StatusError};
+use salvo_core::{async_trait, Depot, FlowCtrl, Handler, Writer};
+use sha2::Sha256;
+
+/// Middleware for verifying HMAC-SHA256 webhook signatures.
+///
+/// View [module level documentation](index.html) for more details.
+#[non_exhaustive]
+#[derive(Clone)]
+pub struct WebhookSignature {
+    secret: Vec<u8>,
+    /// The header which contains the signature, default is `x-signature`.
+    pub header_name: HeaderName,
+    /// The prefix of the signature in the header, like `sha256=` of GitHub, default is empty.
+    pub prefix: String,
+    /// The maximum size of the body, the secure max size of the request is used if it is `None`.
+    pub max_size: Option<usize>,
+}
+
+impl Debug for WebhookSignature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebhookSignature")
+            .field("secret", &"..")
+            .field("header_name", &self.header_name)
+            .field("prefix", &self.prefix)
+            .field("max_size", &self.max_size)
+            .finish()
+    }
+}
+
+impl WebhookSignature {
+    /// Create a new `WebhookSignature` with the shared secret.
+    #[inline]
+    pub fn new(secret: impl Into<Vec<u8>>) -> Self {
+        Self {
+            secret: secret.into(),
+            header_name: HeaderName::from_static("x-signature"),
+            prefix: String::new(),
+            max_size: None,
+        }
+    }
+
+    /// Set the header which contains the signature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `header_name` is not a valid header name.
+    #[inline]
+    pub fn header_name(mut self, header_name: impl AsRef<str>) -> Self {
+        self.header_name = HeaderName::try_from(header_name.as_ref()).expect("invalid header name");
+        self
+    }
+
+    /// Set the prefix of the signature in the header, like `sha256=`.
+    #[inline]
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Set the maximum size of the body.
+    #[inline]
+    pub fn max_size(mut self, max_size: usize) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    /// Sign the payload, returns the signature with the prefix, as the provider sends in the header.
+    pub fn sign(&self, payload: &[u8]) -> String {
+        let digest = self.mac().chain_update(payload).finalize().into_bytes();
+        format!("{}{}", self.prefix, hex::encode(digest))
+    }
+
+    /// Verify the signature in the header value in constant time.
+    pub fn verify(&self, payload: &[u8], signature: &str) -> bool {
+        let Some(signature) = signature.trim().strip_prefix(&*self.prefix) else {
+            return false;
+        };
+        let Ok(signature) = hex::decode(signature) else {
+            return false;
+        };
+        self.mac().chain_update(payload).verify_slice(&signature).is_ok()
+    }
+
+    fn mac(&self) -> Hmac<Sha256> {
+        Hmac::<Sha256>::new_from_slice(&self.secret).expect("HMAC can take key of any size")
+    }
+}
+
+#[async_trait]
+impl Handler for WebhookSignature {
+    async fn handle(&self, req: &mut Request, depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl) {
+        let Some(signature) = req.header::<String>(&self.header_name) else {
+            res.render(StatusError::unauthorized().brief("Missing webhook signature."));
+            ctrl.skip_rest();
+            return;
+        };
+        let max_size = self.max_size.unwrap_or_else(|| req.secure_max_size());
+        let payload = match req.payload_with_max_size(max_size).await {
+            Ok(payload) => payload,
+            Err(e) => {
+                e.write(req, depot, res).await;
+                ctrl.skip_rest();
+                return;
+            }
+        };
+        if !self.verify(payload, &signature) {
+            res.render(StatusError::unauthorized().brief("Invalid webhook signature."));
+            ctrl.skip_rest();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use salvo_core::prelude::*;
+    use salvo_core::test::{ResponseExt, TestClient};
+
+    use super::*;
+
+    #[handler]
+    async fn receive(req: &mut Request) -> String {
+        let event = req.parse_json::<serde_json::Value>().await.unwrap();
+        format!("received {}", event["action"])
+    }
+
+    #[test]
+    fn test_sign() {
+        // The example of GitHub documentation.
+        let verifier = WebhookSignature::new("It's a Secret to Everybody").prefix("sha256=");
+        let signature = "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17";
+        assert_eq!(verifier.sign(b"Hello, World!"), signature);
+        assert!(verifier.verify(b"Hello, World!", signature));
+        assert!(!verifier.verify(b"Hello, World?", signature));
+        assert!(!verifier.verify(b"Hello, World!", &signature[7..]));
+        assert!(!verifier.verify(b"Hello, World!", "sha256=not-hex"));
+    }
+
+    #[tokio::test]
+    async fn test_webhook_signature() {
+        let verifier = WebhookSignature::new("secret").header_name("x-hub-signature-256").prefix("sha256=");
+        let body = r#"{"action":"opened"}"#;
+        let signature = verifier.sign(body.as_bytes());
+        let router = Router::new().hoop(verifier.max_size(32)).post(receive);
+        let service = Service::new(router);
+
+        let mut res = TestClient::post("http://127.0.0.1:5801")
+            .add_header("x-hub-signature-256", &signature, true)
+            .raw_json(body)
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+        assert_eq!(res.take_string().await.unwrap(), r#"received "opened""#);
+
+        let res = TestClient::post("http://127.0.0.1:5801")
+            .add_header("x-hub-signature-256", &signature, true)
+            .raw_json(r#"{"action":"closed"}"#)
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::UNAUTHORIZED));
+
+        let res = TestClient::post("http://127.0.0.1:5801")
+            .raw_json(body)
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::UNAUTHORIZED));
+
+        let res = TestClient::post("http://127.0.0.1:5801")
+            .add_header("x-hub-signature-256", &signature, true)
+            .raw_json(r#"{"action":"opened","number":1,"more":true}"#)
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::PAYLOAD_TOO_LARGE));
+    }
+}
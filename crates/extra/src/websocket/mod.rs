@@ -76,23 +76,77 @@
 //! </html>
 //! "#;
 //!```
+//!
+//! Use [`Rooms`] to broadcast messages to a group of WebSockets.
+//!
+//! # Lifecycle
+//!
+//! The upgrade request goes through the hoops like any other request, so middlewares such as session or
+//! authentication run before the handler calling [`WebSocketUpgrade::upgrade`]. `upgrade` only validates the
+//! request and sets the `101 Switching Protocols` response, then the handler returns and the hoops finish their work
+//! after `call_next`, for example the session handler saves the session and sets the cookie in the `101` response.
+//! The connection is upgraded after the response is written, and the callback is called in a new task.
+//!
+//! So the [`Depot`](salvo_core::Depot) and the `Request` are not available in the callback, read what the socket
+//! needs, like the logged-in user from the session, before calling `upgrade` and move it into the callback. The
+//! changes to the session made in the callback are not saved.
+//!
+//! ```no_run
+//! use salvo_core::prelude::*;
+//! use salvo_extra::websocket::{Message, WebSocketUpgrade};
+//!
+//! #[handler]
+//! async fn auth(depot: &mut Depot) {
+//!     // Load the user from the session, token or anything else.
+//!     depot.insert("user", "chris".to_owned());
+//! }
+//!
+//! #[handler]
+//! async fn connect(req: &mut Request, depot: &mut Depot, res: &mut Response) -> Result<(), StatusError> {
+//!     let user = depot
+//!         .get::<String>("user")
+//!         .map_err(|_| StatusError::unauthorized())?
+//!         .clone();
+//!     WebSocketUpgrade::new()
+//!         .upgrade(req, res, |mut ws| async move {
+//!             let _ = ws.send(Message::text(format!("hello {user}"))).await;
+//!         })
+//!         .await
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let router = Router::with_path("ws").hoop(auth).goal(connect);
+//!
+//!     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
+//!     Server::new(acceptor).serve(router).await;
+//! }
+//! ```
+
+pub mod rooms;
+pub use rooms::Rooms;
 
 use std::fmt::{self, Debug, Formatter};
 use std::future::Future;
+use std::io::{Error as IoError, ErrorKind};
 use std::pin::Pin;
-use std::task::{ready, Context, Poll};
+use std::task::{Context, Poll};
+use std::time::Duration;
 
 use futures_util::sink::{Sink, SinkExt};
 use futures_util::stream::{Stream, StreamExt};
 use futures_util::{future, FutureExt, TryFutureExt};
 use hyper::upgrade::OnUpgrade;
-use salvo_core::http::header::{SEC_WEBSOCKET_VERSION, UPGRADE};
+use salvo_core::http::header::{
+    HeaderValue, SEC_WEBSOCKET_PROTOCOL, SEC_WEBSOCKET_VERSION, UPGRADE,
+};
 use salvo_core::http::headers::{
     Connection, HeaderMapExt, SecWebsocketAccept, SecWebsocketKey, Upgrade,
 };
 use salvo_core::http::{StatusCode, StatusError};
 use salvo_core::rt::tokio::TokioIo;
 use salvo_core::{Error, Request, Response};
+use tokio::time::{self, Instant, Interval, MissedTickBehavior, Sleep};
 use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
 use tokio_tungstenite::tungstenite::protocol::frame::{CloseFrame, Utf8Bytes};
 use tokio_tungstenite::tungstenite::protocol::{self, WebSocketConfig};
@@ -112,9 +166,13 @@ use tokio_tungstenite::WebSocketStream;
 /// - Header `connection: upgrade`
 /// - Header `upgrade: websocket`
 /// - Header `sec-websocket-accept` with the hash value of the received key.
+/// - Header `sec-websocket-protocol` with the negotiated subprotocol, if there is one.
 #[allow(missing_debug_implementations)]
 pub struct WebSocketUpgrade {
     config: Option<WebSocketConfig>,
+    protocols: Vec<String>,
+    ping_interval: Option<Duration>,
+    idle_timeout: Option<Duration>,
 }
 
 impl Default for WebSocketUpgrade {
@@ -128,7 +186,12 @@ impl WebSocketUpgrade {
     /// Create new `WebSocketUpgrade`.
     #[inline]
     pub fn new() -> Self {
-        WebSocketUpgrade { config: None }
+        WebSocketUpgrade {
+            config: None,
+            protocols: vec![],
+            ping_interval: None,
+            idle_timeout: None,
+        }
     }
 
     /// Create new `WebSocketUpgrade` with config.
@@ -136,9 +199,50 @@ impl WebSocketUpgrade {
     pub fn with_config(config: WebSocketConfig) -> Self {
         WebSocketUpgrade {
             config: Some(config),
+            ..Self::new()
         }
     }
 
+    /// Set the subprotocols supported by the server.
+    ///
+    /// The first protocol in the client's `sec-websocket-protocol` header which is also in this
+    /// list is selected and sent back in the response. If there is no match, the upgrade still
+    /// succeeds without a subprotocol. The selected protocol can be read from
+    /// [`WebSocket::protocol`].
+    #[inline]
+    pub fn protocols<I, P>(mut self, protocols: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<String>,
+    {
+        self.protocols = protocols.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Send a ping message to the client at this interval, so that idle connections are kept
+    /// alive through proxies. The default value is `None`, no ping is sent.
+    ///
+    /// **Note**: Pings are sent while the [`WebSocket`] is being polled for messages, for example
+    /// while awaiting [`WebSocket::recv`].
+    #[inline]
+    pub fn ping_interval(mut self, interval: Duration) -> Self {
+        self.ping_interval = Some(interval);
+        self
+    }
+
+    /// Close the connection if nothing is received from the client for this duration. The
+    /// default value is `None`, the connection is never closed for being idle.
+    ///
+    /// Any message from the client, including the pong for the pings sent by
+    /// [`ping_interval`](Self::ping_interval), resets the timer. When the timeout elapses the
+    /// [`WebSocket`] sends a close frame with `1001 Going Away` to the client, yields an error with
+    /// [`ErrorKind::TimedOut`], then ends. Nothing can be sent after it.
+    #[inline]
+    pub fn idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = Some(timeout);
+        self
+    }
+
     /// The target minimum size of the write buffer to reach before writing the data
     /// to the underlying stream.
     /// The default value is 128 KiB.
@@ -254,23 +358,48 @@ impl WebSocketUpgrade {
                 .brief("sec_websocket_key is not exist in request headers."));
         };
 
+        let protocol = self.negotiate_protocol(req);
+
         res.status_code(StatusCode::SWITCHING_PROTOCOLS);
 
         res.headers_mut().typed_insert(Connection::upgrade());
         res.headers_mut().typed_insert(Upgrade::websocket());
         res.headers_mut()
             .typed_insert(SecWebsocketAccept::from(sec_ws_key));
+        if let Some(protocol) = &protocol {
+            match HeaderValue::from_str(protocol) {
+                Ok(value) => {
+                    res.headers_mut().insert(SEC_WEBSOCKET_PROTOCOL, value);
+                }
+                Err(e) => {
+                    tracing::error!(error = ?e, "invalid websocket protocol");
+                    return Err(StatusError::internal_server_error()
+                        .brief("Invalid websocket protocol."));
+                }
+            }
+        }
 
         if let Some(on_upgrade) = req.extensions_mut().remove::<OnUpgrade>() {
             let config = self.config;
+            let ping_interval = self.ping_interval;
+            let idle_timeout = self.idle_timeout;
             tokio::spawn(async move {
-                let socket = on_upgrade
+                let mut socket = on_upgrade
                     .and_then(move |upgraded| {
                         tracing::debug!("websocket upgrade complete");
                         WebSocket::from_raw_socket(upgraded, protocol::Role::Server, config).map(Ok)
                     })
                     .await
                     .expect("connection upgrade failed");
+                socket.protocol = protocol;
+                if let Some(interval) = ping_interval {
+                    let mut interval = time::interval_at(Instant::now() + interval, interval);
+                    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+                    socket.ping_interval = Some(interval);
+                }
+                if let Some(timeout) = idle_timeout {
+                    socket.idle_timeout = Some((timeout, Box::pin(time::sleep(timeout))));
+                }
                 callback(socket).await;
             });
             Ok(())
@@ -280,6 +409,20 @@ impl WebSocketUpgrade {
                 .brief("Websocket couldn't be upgraded since no upgrade state was present."))
         }
     }
+
+    fn negotiate_protocol(&self, req: &Request) -> Option<String> {
+        if self.protocols.is_empty() {
+            return None;
+        }
+        req.headers()
+            .get_all(SEC_WEBSOCKET_PROTOCOL)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .map(str::trim)
+            .find(|p| self.protocols.iter().any(|s| s == p))
+            .map(ToOwned::to_owned)
+    }
 }
 
 /// A websocket `Stream` and `Sink`, provided to `ws` filters.
@@ -289,6 +432,24 @@ impl WebSocketUpgrade {
 /// `WebSocket`.
 pub struct WebSocket {
     inner: WebSocketStream<TokioIo<hyper::upgrade::Upgraded>>,
+    protocol: Option<String>,
+    ping_interval: Option<Interval>,
+    ping_pending: bool,
+    flush_pending: bool,
+    idle_timeout: Option<(Duration, Pin<Box<Sleep>>)>,
+    idle_state: IdleState,
+}
+
+/// The state of closing the connection for being idle.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum IdleState {
+    Active,
+    // The close frame is not sent yet.
+    Closing,
+    // The close frame is sent, and is being flushed.
+    Flushing,
+    // The `TimedOut` error is yielded.
+    TimedOut,
 }
 
 impl WebSocket {
@@ -299,10 +460,24 @@ impl WebSocket {
         config: Option<protocol::WebSocketConfig>,
     ) -> Self {
         WebSocketStream::from_raw_socket(TokioIo::new(upgraded), role, config)
-            .map(|inner| WebSocket { inner })
+            .map(|inner| WebSocket {
+                inner,
+                protocol: None,
+                ping_interval: None,
+                ping_pending: false,
+                flush_pending: false,
+                idle_timeout: None,
+                idle_state: IdleState::Active,
+            })
             .await
     }
 
+    /// The subprotocol negotiated with the client, see [`WebSocketUpgrade::protocols`].
+    #[inline]
+    pub fn protocol(&self) -> Option<&str> {
+        self.protocol.as_deref()
+    }
+
     /// Receive another message.
     ///
     /// Returns `None` if the stream has closed.
@@ -327,8 +502,35 @@ impl Stream for WebSocket {
 
     #[inline]
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
-        match ready!(Pin::new(&mut self.inner).poll_next(cx)) {
-            Some(Ok(item)) => Poll::Ready(Some(Ok(Message { inner: item }))),
+        match self.idle_state {
+            IdleState::Active => {}
+            IdleState::TimedOut => return Poll::Ready(None),
+            IdleState::Closing | IdleState::Flushing => return self.poll_idle_close(cx),
+        }
+        if let Err(e) = self.poll_keepalive(cx) {
+            tracing::debug!("websocket send ping error: {}", e);
+            return Poll::Ready(Some(Err(Error::other(e))));
+        }
+        let item = match Pin::new(&mut self.inner).poll_next(cx) {
+            Poll::Ready(item) => item,
+            Poll::Pending => {
+                if let Some((_, sleep)) = &mut self.idle_timeout {
+                    if sleep.as_mut().poll(cx).is_ready() {
+                        tracing::debug!("websocket idle timeout");
+                        self.idle_state = IdleState::Closing;
+                        return self.poll_idle_close(cx);
+                    }
+                }
+                return Poll::Pending;
+            }
+        };
+        match item {
+            Some(Ok(item)) => {
+                if let Some((timeout, sleep)) = &mut self.idle_timeout {
+                    sleep.as_mut().reset(Instant::now() + *timeout);
+                }
+                Poll::Ready(Some(Ok(Message { inner: item })))
+            }
             Some(Err(e)) => {
                 tracing::debug!("websocket poll error: {}", e);
                 Poll::Ready(Some(Err(Error::other(e))))
@@ -341,6 +543,65 @@ impl Stream for WebSocket {
     }
 }
 
+impl WebSocket {
+    // Send a close frame with `1001 Going Away` and flush it, then yield the `TimedOut` error. The errors of closing
+    // are only logged, since the connection is given up anyway.
+    fn poll_idle_close(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Message, Error>>> {
+        if self.idle_state == IdleState::Closing {
+            let Poll::Ready(result) = Pin::new(&mut self.inner).poll_ready(cx) else {
+                return Poll::Pending;
+            };
+            let frame = CloseFrame {
+                code: CloseCode::Away,
+                reason: Utf8Bytes::from_static("idle timeout"),
+            };
+            if let Err(e) =
+                result.and_then(|_| Pin::new(&mut self.inner).start_send(protocol::Message::Close(Some(frame))))
+            {
+                tracing::debug!("websocket send close error: {}", e);
+            }
+            self.idle_state = IdleState::Flushing;
+        }
+        let Poll::Ready(result) = Pin::new(&mut self.inner).poll_close(cx) else {
+            return Poll::Pending;
+        };
+        if let Err(e) = result {
+            tracing::debug!("websocket close error: {}", e);
+        }
+        self.idle_state = IdleState::TimedOut;
+        Poll::Ready(Some(Err(Error::other(IoError::new(
+            ErrorKind::TimedOut,
+            "websocket idle timeout",
+        )))))
+    }
+
+    fn poll_keepalive(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Result<(), tokio_tungstenite::tungstenite::Error> {
+        if let Some(interval) = &mut self.ping_interval {
+            while interval.poll_tick(cx).is_ready() {
+                self.ping_pending = true;
+            }
+        }
+        if self.ping_pending {
+            if let Poll::Ready(result) = Pin::new(&mut self.inner).poll_ready(cx) {
+                result?;
+                Pin::new(&mut self.inner).start_send(protocol::Message::Ping(Bytes::new()))?;
+                self.ping_pending = false;
+                self.flush_pending = true;
+            }
+        }
+        if self.flush_pending {
+            if let Poll::Ready(result) = Pin::new(&mut self.inner).poll_flush(cx) {
+                result?;
+                self.flush_pending = false;
+            }
+        }
+        Ok(())
+    }
+}
+
 impl Sink<Message> for WebSocket {
     type Error = Error;
 
@@ -376,7 +637,9 @@ impl Sink<Message> for WebSocket {
 impl Debug for WebSocket {
     #[inline]
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        f.debug_struct("WebSocket").finish()
+        f.debug_struct("WebSocket")
+            .field("protocol", &self.protocol)
+            .finish()
     }
 }
 
@@ -525,6 +788,7 @@ mod tests {
     use salvo_core::http::header::*;
     use salvo_core::prelude::*;
     use salvo_core::rt::tokio::TokioIo;
+    use salvo_session::{MemoryStore, Session, SessionDepotExt, SessionHandler};
 
     use super::*;
 
@@ -586,4 +850,183 @@ mod tests {
 
         assert_eq!(res.status(), StatusCode::SWITCHING_PROTOCOLS);
     }
+
+    async fn connect_client(
+        router: Router,
+        protocols: Option<&str>,
+    ) -> (
+        hyper::Response<hyper::body::Incoming>,
+        WebSocketStream<TokioIo<hyper::upgrade::Upgraded>>,
+    ) {
+        let acceptor = TcpListener::new("127.0.0.1:0").bind().await;
+        let addr = acceptor.holdings()[0]
+            .local_addr
+            .clone()
+            .into_std()
+            .unwrap();
+        tokio::spawn(async move {
+            Server::new(acceptor).serve(router).await;
+        });
+
+        let stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        let (mut sender, conn) = hyper::client::conn::http1::handshake(TokioIo::new(stream))
+            .await
+            .unwrap();
+        tokio::task::spawn(async move {
+            if let Err(err) = conn.with_upgrades().await {
+                println!("Connection failed: {:?}", err);
+            }
+        });
+
+        let mut req = hyper::Request::builder()
+            .uri(format!("http://{}", addr))
+            .header(UPGRADE, "websocket")
+            .header(CONNECTION, "Upgrade")
+            .header(SEC_WEBSOCKET_KEY, "6D69KGBOr4Re+Nj6zx9aQA==")
+            .header(SEC_WEBSOCKET_VERSION, "13");
+        if let Some(protocols) = protocols {
+            req = req.header(SEC_WEBSOCKET_PROTOCOL, protocols);
+        }
+        let req = req
+            .body(http_body_util::Empty::<hyper::body::Bytes>::new())
+            .unwrap();
+        let mut res = sender.send_request(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::SWITCHING_PROTOCOLS);
+        let upgraded = hyper::upgrade::on(&mut res).await.unwrap();
+        let ws =
+            WebSocketStream::from_raw_socket(TokioIo::new(upgraded), protocol::Role::Client, None)
+                .await;
+        (res, ws)
+    }
+
+    #[handler]
+    async fn connect_protocol(req: &mut Request, res: &mut Response) -> Result<(), StatusError> {
+        WebSocketUpgrade::new()
+            .protocols(["chat", "superchat"])
+            .upgrade(req, res, |mut ws| async move {
+                let protocol = ws.protocol().unwrap_or("none").to_owned();
+                let _ = ws.send(Message::text(protocol)).await;
+            })
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_websocket_protocol() {
+        let router = Router::new().goal(connect_protocol);
+        let (res, mut ws) = connect_client(router, Some("mqtt, superchat, chat")).await;
+        assert_eq!(
+            res.headers().get(SEC_WEBSOCKET_PROTOCOL).unwrap(),
+            "superchat"
+        );
+        let msg = ws.next().await.unwrap().unwrap();
+        assert_eq!(msg, protocol::Message::text("superchat"));
+
+        let router = Router::new().goal(connect_protocol);
+        let (res, mut ws) = connect_client(router, Some("mqtt")).await;
+        assert!(res.headers().get(SEC_WEBSOCKET_PROTOCOL).is_none());
+        let msg = ws.next().await.unwrap().unwrap();
+        assert_eq!(msg, protocol::Message::text("none"));
+    }
+
+    #[handler]
+    async fn login(depot: &mut Depot) {
+        let mut session = Session::new();
+        session.insert("username", "chris").unwrap();
+        depot.set_session(session);
+    }
+
+    #[handler]
+    async fn connect_user(
+        req: &mut Request,
+        depot: &mut Depot,
+        res: &mut Response,
+    ) -> Result<(), StatusError> {
+        let username = depot
+            .session()
+            .and_then(|session| session.get::<String>("username"))
+            .ok_or_else(StatusError::unauthorized)?;
+        WebSocketUpgrade::new()
+            .upgrade(req, res, |mut ws| async move {
+                let _ = ws.send(Message::text(format!("hello {username}"))).await;
+            })
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_websocket_session() {
+        let session_handler = SessionHandler::builder(
+            MemoryStore::new(),
+            b"secretabsecretabsecretabsecretabsecretabsecretabsecretabsecretab",
+        )
+        .build()
+        .unwrap();
+        let router = Router::new()
+            .hoop(session_handler)
+            .hoop(login)
+            .goal(connect_user);
+        let (res, mut ws) = connect_client(router, None).await;
+        // The session handler saves the session after the handler returns, so the cookie is in the `101` response.
+        assert!(res.headers().contains_key(SET_COOKIE));
+        let msg = ws.next().await.unwrap().unwrap();
+        assert_eq!(msg, protocol::Message::text("hello chris"));
+    }
+
+    #[handler]
+    async fn connect_keepalive(req: &mut Request, res: &mut Response) -> Result<(), StatusError> {
+        WebSocketUpgrade::new()
+            .ping_interval(Duration::from_millis(50))
+            .upgrade(req, res, |mut ws| async move {
+                while let Some(Ok(msg)) = ws.recv().await {
+                    if ws.send(msg).await.is_err() {
+                        return;
+                    }
+                }
+            })
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_websocket_ping_interval() {
+        let router = Router::new().goal(connect_keepalive);
+        let (_, mut ws) = connect_client(router, None).await;
+        let msg = ws.next().await.unwrap().unwrap();
+        assert!(msg.is_ping());
+    }
+
+    #[handler]
+    async fn connect_idle(req: &mut Request, res: &mut Response) -> Result<(), StatusError> {
+        WebSocketUpgrade::new()
+            .idle_timeout(Duration::from_millis(100))
+            .upgrade(req, res, |mut ws| async move {
+                while let Some(msg) = ws.recv().await {
+                    match msg {
+                        Ok(msg) => {
+                            if ws.send(msg).await.is_err() {
+                                return;
+                            }
+                        }
+                        Err(_) => {
+                            assert!(ws.send(Message::text("timeout")).await.is_err());
+                            return;
+                        }
+                    }
+                }
+            })
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_websocket_idle_timeout() {
+        let router = Router::new().goal(connect_idle);
+        let (_, mut ws) = connect_client(router, None).await;
+        ws.send(protocol::Message::text("hello")).await.unwrap();
+        let msg = ws.next().await.unwrap().unwrap();
+        assert_eq!(msg, protocol::Message::text("hello"));
+        let msg = ws.next().await.unwrap().unwrap();
+        let protocol::Message::Close(Some(frame)) = msg else {
+            panic!("expected a close frame, got {msg:?}");
+        };
+        assert_eq!(frame.code, CloseCode::Away);
+        assert!(ws.next().await.is_none());
+    }
 }
@@ -0,0 +1,370 @@
+//! Rooms for broadcasting messages to a group of WebSockets.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use futures_util::{SinkExt, StreamExt};
+//! use salvo_core::prelude::*;
+//! use salvo_extra::affix_state;
+//! use salvo_extra::websocket::rooms::Rooms;
+//! use salvo_extra::websocket::WebSocketUpgrade;
+//!
+//! #[handler]
+//! async fn connect(req: &mut Request, depot: &mut Depot, res: &mut Response) -> Result<(), StatusError> {
+//!     let rooms = depot.obtain::<Rooms>().cloned().map_err(|_| StatusError::internal_server_error())?;
+//!     let room = req.param::<String>("room").unwrap_or_default();
+//!     WebSocketUpgrade::new()
+//!         .upgrade(req, res, move |ws| async move {
+//!             let (mut ws_tx, mut ws_rx) = ws.split();
+//!             let mut subscriber = rooms.join(room);
+//!             loop {
+//!                 tokio::select! {
+//!                     Ok(msg) = subscriber.recv() => {
+//!                         if ws_tx.send(msg).await.is_err() {
+//!                             break;
+//!                         }
+//!                     }
+//!                     msg = ws_rx.next() => match msg {
+//!                         Some(Ok(msg)) => {
+//!                             subscriber.broadcast(msg);
+//!                         }
+//!                         _ => break,
+//!                     },
+//!                 }
+//!             }
+//!             // The subscriber leaves the room when it is dropped.
+//!         })
+//!         .await
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let router = Router::new()
+//!         .hoop(affix_state::inject(Rooms::new()))
+//!         .push(Router::with_path("ws/{room}").goal(connect));
+//!     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
+//!     Server::new(acceptor).serve(router).await;
+//! }
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::error::Error as StdError;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use parking_lot::Mutex;
+use tokio::sync::Notify;
+
+use super::Message;
+
+/// How messages are handled when the buffer of a [`Subscriber`] is full.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Backpressure {
+    /// Keep at most this count of messages, new messages are dropped when the buffer is full.
+    Drop(usize),
+    /// Keep at most this count of messages, the oldest messages are discarded when the buffer is
+    /// full, and the next [`Subscriber::recv`] returns [`RecvError::Lagged`].
+    Lag(usize),
+}
+impl Backpressure {
+    fn capacity(&self) -> usize {
+        match self {
+            Self::Drop(capacity) | Self::Lag(capacity) => (*capacity).max(1),
+        }
+    }
+}
+impl Default for Backpressure {
+    #[inline]
+    fn default() -> Self {
+        Self::Lag(1024)
+    }
+}
+
+/// Error returned by [`Subscriber::recv`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RecvError {
+    /// The room is removed, no more messages will be received.
+    Closed,
+    /// The subscriber lagged behind and this count of messages are discarded.
+    Lagged(u64),
+}
+impl Display for RecvError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Closed => f.write_str("room closed"),
+            Self::Lagged(count) => write!(f, "subscriber lagged behind by {count} messages"),
+        }
+    }
+}
+impl StdError for RecvError {}
+
+struct Queue {
+    state: Mutex<QueueState>,
+    notify: Notify,
+}
+struct QueueState {
+    messages: VecDeque<Message>,
+    backpressure: Backpressure,
+    lagged: u64,
+    closed: bool,
+}
+impl Queue {
+    fn new(backpressure: Backpressure) -> Self {
+        Self {
+            state: Mutex::new(QueueState {
+                messages: VecDeque::new(),
+                backpressure,
+                lagged: 0,
+                closed: false,
+            }),
+            notify: Notify::new(),
+        }
+    }
+    /// Returns `false` if the message is dropped.
+    fn push(&self, msg: Message) -> bool {
+        let mut state = self.state.lock();
+        if state.closed {
+            return false;
+        }
+        if state.messages.len() >= state.backpressure.capacity() {
+            match state.backpressure {
+                Backpressure::Drop(_) => return false,
+                Backpressure::Lag(_) => {
+                    state.messages.pop_front();
+                    state.lagged += 1;
+                }
+            }
+        }
+        state.messages.push_back(msg);
+        drop(state);
+        self.notify.notify_one();
+        true
+    }
+    fn close(&self) {
+        self.state.lock().closed = true;
+        self.notify.notify_one();
+    }
+}
+
+#[derive(Default)]
+struct RoomsInner {
+    rooms: Mutex<HashMap<String, HashMap<u64, Arc<Queue>>>>,
+    next_id: AtomicU64,
+}
+
+/// A group of named rooms, each room has several [`Subscriber`]s.
+///
+/// `Rooms` is cheap to clone, all the clones share the same rooms, so it can be injected to
+/// `Depot` with [`affix_state`](crate::affix_state) and obtained in handlers.
+#[derive(Clone, Default)]
+pub struct Rooms {
+    inner: Arc<RoomsInner>,
+}
+impl Rooms {
+    /// Create a new `Rooms`.
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Join a room with the default [`Backpressure`], the room is created if it does not exist.
+    #[inline]
+    pub fn join(&self, room: impl Into<String>) -> Subscriber {
+        self.join_with(room, Backpressure::default())
+    }
+
+    /// Join a room with the given [`Backpressure`], the room is created if it does not exist.
+    ///
+    /// The subscriber leaves the room when it is dropped, and the room is removed when it has
+    /// no subscribers.
+    pub fn join_with(&self, room: impl Into<String>, backpressure: Backpressure) -> Subscriber {
+        let room = room.into();
+        let id = self.inner.next_id.fetch_add(1, Ordering::Relaxed);
+        let queue = Arc::new(Queue::new(backpressure));
+        self.inner
+            .rooms
+            .lock()
+            .entry(room.clone())
+            .or_default()
+            .insert(id, queue.clone());
+        Subscriber {
+            rooms: self.clone(),
+            room,
+            id,
+            queue,
+        }
+    }
+
+    /// Broadcast a message to all the subscribers of the room.
+    ///
+    /// Returns the count of subscribers which accepted the message.
+    #[inline]
+    pub fn broadcast(&self, room: &str, msg: Message) -> usize {
+        self.broadcast_except(room, None, msg)
+    }
+
+    fn broadcast_except(&self, room: &str, except: Option<u64>, msg: Message) -> usize {
+        let rooms = self.inner.rooms.lock();
+        let Some(subscribers) = rooms.get(room) else {
+            return 0;
+        };
+        subscribers
+            .iter()
+            .filter(|(id, _)| Some(**id) != except)
+            .filter(|(_, queue)| queue.push(msg.clone()))
+            .count()
+    }
+
+    /// Returns the count of subscribers in the room.
+    #[inline]
+    pub fn member_count(&self, room: &str) -> usize {
+        self.inner.rooms.lock().get(room).map_or(0, HashMap::len)
+    }
+
+    /// Returns the names of all the rooms.
+    #[inline]
+    pub fn room_names(&self) -> Vec<String> {
+        self.inner.rooms.lock().keys().cloned().collect()
+    }
+
+    /// Remove a room, all its subscribers will receive [`RecvError::Closed`] after the
+    /// buffered messages.
+    ///
+    /// Returns `true` if the room exists.
+    pub fn remove(&self, room: &str) -> bool {
+        if let Some(subscribers) = self.inner.rooms.lock().remove(room) {
+            for queue in subscribers.values() {
+                queue.close();
+            }
+            true
+        } else {
+            false
+        }
+    }
+
+    fn leave(&self, room: &str, id: u64) {
+        let mut rooms = self.inner.rooms.lock();
+        if let Some(subscribers) = rooms.get_mut(room) {
+            subscribers.remove(&id);
+            if subscribers.is_empty() {
+                rooms.remove(room);
+            }
+        }
+    }
+}
+impl Debug for Rooms {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Rooms")
+            .field("rooms", &self.room_names())
+            .finish()
+    }
+}
+
+/// A member of a room, it receives the messages broadcast to the room.
+///
+/// It leaves the room when dropped.
+pub struct Subscriber {
+    rooms: Rooms,
+    room: String,
+    id: u64,
+    queue: Arc<Queue>,
+}
+impl Subscriber {
+    /// The name of the room.
+    #[inline]
+    pub fn room(&self) -> &str {
+        &self.room
+    }
+
+    /// Receive the next message broadcast to the room.
+    pub async fn recv(&mut self) -> Result<Message, RecvError> {
+        loop {
+            {
+                let mut state = self.queue.state.lock();
+                if state.lagged > 0 {
+                    let lagged = state.lagged;
+                    state.lagged = 0;
+                    return Err(RecvError::Lagged(lagged));
+                }
+                if let Some(msg) = state.messages.pop_front() {
+                    return Ok(msg);
+                }
+                if state.closed {
+                    return Err(RecvError::Closed);
+                }
+            }
+            self.queue.notify.notified().await;
+        }
+    }
+
+    /// Broadcast a message to all the other subscribers of the room.
+    ///
+    /// Returns the count of subscribers which accepted the message.
+    #[inline]
+    pub fn broadcast(&self, msg: Message) -> usize {
+        self.rooms.broadcast_except(&self.room, Some(self.id), msg)
+    }
+}
+impl Drop for Subscriber {
+    fn drop(&mut self) {
+        self.rooms.leave(&self.room, self.id);
+    }
+}
+impl Debug for Subscriber {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Subscriber")
+            .field("room", &self.room)
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_rooms_broadcast() {
+        let rooms = Rooms::new();
+        let mut alice = rooms.join("todos");
+        let mut bob = rooms.join("todos");
+        let _carol = rooms.join("other");
+        assert_eq!(rooms.member_count("todos"), 2);
+
+        assert_eq!(rooms.broadcast("todos", Message::text("hello")), 2);
+        assert_eq!(alice.recv().await, Ok(Message::text("hello")));
+        assert_eq!(bob.recv().await, Ok(Message::text("hello")));
+
+        assert_eq!(alice.broadcast(Message::text("from alice")), 1);
+        assert_eq!(bob.recv().await, Ok(Message::text("from alice")));
+
+        drop(bob);
+        assert_eq!(rooms.member_count("todos"), 1);
+        drop(alice);
+        assert_eq!(rooms.member_count("todos"), 0);
+        assert_eq!(rooms.room_names(), vec!["other".to_owned()]);
+    }
+
+    #[tokio::test]
+    async fn test_rooms_backpressure() {
+        let rooms = Rooms::new();
+        let mut dropper = rooms.join_with("todos", Backpressure::Drop(2));
+        let mut lagger = rooms.join_with("todos", Backpressure::Lag(2));
+        for i in 0..4 {
+            rooms.broadcast("todos", Message::text(i.to_string()));
+        }
+
+        assert_eq!(dropper.recv().await, Ok(Message::text("0")));
+        assert_eq!(dropper.recv().await, Ok(Message::text("1")));
+
+        assert_eq!(lagger.recv().await, Err(RecvError::Lagged(2)));
+        assert_eq!(lagger.recv().await, Ok(Message::text("2")));
+        assert_eq!(lagger.recv().await, Ok(Message::text("3")));
+
+        assert!(rooms.remove("todos"));
+        assert_eq!(dropper.recv().await, Err(RecvError::Closed));
+    }
+}
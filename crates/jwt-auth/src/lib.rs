@@ -333,12 +333,12 @@ where
             match self.decoder.decode::<C>(&token, depot).await {
                 Ok(data) => {
                     depot.insert(JWT_AUTH_DATA_KEY, data);
-                    depot.insert(JWT_AUTH_STATE_KEY, JwtAuthState::Authorized);
-                    depot.insert(JWT_AUTH_TOKEN_KEY, token);
+                    depot.insert_cloneable(JWT_AUTH_STATE_KEY, JwtAuthState::Authorized);
+                    depot.insert_cloneable(JWT_AUTH_TOKEN_KEY, token);
                 }
                 Err(e) => {
                     tracing::info!(error = ?e, "jwt auth error");
-                    depot.insert(JWT_AUTH_STATE_KEY, JwtAuthState::Forbidden);
+                    depot.insert_cloneable(JWT_AUTH_STATE_KEY, JwtAuthState::Forbidden);
                     depot.insert(JWT_AUTH_ERROR_KEY, e);
                     if !self.force_passed {
                         res.render(StatusError::forbidden());
@@ -347,7 +347,7 @@ where
                 }
             }
         } else {
-            depot.insert(JWT_AUTH_STATE_KEY, JwtAuthState::Unauthorized);
+            depot.insert_cloneable(JWT_AUTH_STATE_KEY, JwtAuthState::Unauthorized);
             if !self.force_passed {
                 res.render(StatusError::unauthorized());
                 ctrl.skip_rest();
@@ -10,7 +10,7 @@ use syn::{
 };
 
 use crate::{
-    attribute, omit_type_path_lifetimes, salvo_crate,
+    attribute, omit_type_lifetimes, omit_type_path_lifetimes, salvo_crate,
     serde_util::{self, RenameRule, SerdeValue},
 };
 
@@ -21,6 +21,7 @@ struct FieldInfo {
     aliases: Vec<String>,
     rename: Option<String>,
     serde_rename: Option<String>,
+    serde_default: bool,
     flatten: bool,
 }
 impl TryFrom<&Field> for FieldInfo {
@@ -52,13 +53,17 @@ impl TryFrom<&Field> for FieldInfo {
         sources.dedup();
         aliases.dedup();
 
-        let (serde_rename, serde_flatten) = if let Some(SerdeValue {
-            rename, flatten, ..
-        }) = serde_util::parse_value(&field.attrs)
+        let (serde_rename, serde_default, serde_flatten) = if let Some(SerdeValue {
+            rename,
+            is_default,
+            flatten,
+            ..
+        }) =
+            serde_util::parse_value(&field.attrs)
         {
-            (rename, flatten)
+            (rename, is_default, flatten)
         } else {
-            (None, false)
+            (None, false, false)
         };
         let flatten = flatten.unwrap_or(serde_flatten);
         if flatten {
@@ -83,6 +88,7 @@ impl TryFrom<&Field> for FieldInfo {
             aliases,
             rename,
             serde_rename,
+            serde_default,
             flatten,
         })
     }
@@ -145,6 +151,10 @@ impl Parse for SourceInfo {
         for field in fields {
             if field.path.is_ident("from") {
                 source.from = parse_path_or_lit_str(&field.value)?.to_lowercase();
+                // `path` is an alias of `param`.
+                if source.from == "path" {
+                    source.from = "param".to_owned();
+                }
             } else if field.path.is_ident("parse") {
                 source.parser = parse_path_or_lit_str(&field.value)?.to_lowercase();
             } else {
@@ -154,13 +164,13 @@ impl Parse for SourceInfo {
         if source.parser.is_empty() {
             source.parser = "smart".to_string();
         }
-        if !["param", "query", "header", "body"].contains(&source.from.as_str()) {
+        if !["param", "query", "header", "cookie", "body"].contains(&source.from.as_str()) {
             return Err(Error::new(
                 input.span(),
                 format!("source from is invalid: {}", source.from),
             ));
         }
-        if !["multimap", "json", "smart"].contains(&source.parser.as_str()) {
+        if !["multimap", "json", "smart", "nested"].contains(&source.parser.as_str()) {
             return Err(Error::new(
                 input.span(),
                 format!("source parser is invalid: {}", source.parser),
@@ -178,6 +188,7 @@ struct ExtractibleArgs {
     default_sources: Vec<SourceInfo>,
     rename_all: Option<RenameRule>,
     serde_rename_all: Option<RenameRule>,
+    serde_default: bool,
 }
 
 impl ExtractibleArgs {
@@ -227,7 +238,8 @@ impl ExtractibleArgs {
             }
         }
         let serde_container = serde_util::parse_container(&attrs);
-        let serde_rename_all = serde_container.and_then(|c| c.rename_all);
+        let serde_rename_all = serde_container.as_ref().and_then(|c| c.rename_all);
+        let serde_default = serde_container.is_some_and(|c| c.is_default);
         Ok(Self {
             ident,
             generics,
@@ -235,6 +247,7 @@ impl ExtractibleArgs {
             default_sources,
             rename_all,
             serde_rename_all,
+            serde_default,
         })
     }
 }
@@ -318,6 +331,7 @@ pub(crate) fn generate(args: DeriveInput) -> Result<TokenStream, Error> {
             .ok_or_else(|| Error::new_spanned(name, "All fields must be named."))?
             .to_string();
         let mut nested_metadata = None;
+        let mut checker = None;
         let mut sources = Vec::with_capacity(field.sources.len());
         if field.flatten {
             if let Type::Path(ty) = &field.ty {
@@ -336,6 +350,14 @@ pub(crate) fn generate(args: DeriveInput) -> Result<TokenStream, Error> {
                     field = field.add_source(#source);
                 });
             }
+            // Checker is used to collect errors of all fields, it is not generated for generic types.
+            if args.generics.type_params().next().is_none() {
+                let ty = omit_type_lifetimes(&field.ty);
+                let has_default = field.serde_default || args.serde_default;
+                checker = Some(quote! {
+                    field = field.checker(|req, metadata, field| #salvo::serde::check_field::<#ty>(req, metadata, field, #has_default));
+                });
+            }
         }
         if nested_metadata.is_some() && field.sources.len() > 1 {
             return Err(Error::new_spanned(
@@ -362,6 +384,7 @@ pub(crate) fn generate(args: DeriveInput) -> Result<TokenStream, Error> {
             let mut field = #salvo::extract::metadata::Field::new(#field_ident);
             #nested_metadata
             #(#sources)*
+            #checker
             #(#aliases)*
             #rename
             #serde_rename
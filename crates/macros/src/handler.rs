@@ -2,6 +2,7 @@ use proc_macro2::{Span, TokenStream};
 use quote::{ToTokens, quote};
 use syn::{Ident, ImplItem, Item, Pat, ReturnType, Signature, Type};
 
+use crate::serde_util::{extract_args_ts, obtain_arg_ts};
 use crate::shared::*;
 
 pub(crate) fn generate(input: Item) -> syn::Result<TokenStream> {
@@ -78,8 +79,9 @@ pub(crate) fn generate(input: Item) -> syn::Result<TokenStream> {
 
 fn handle_fn(salvo: &Ident, sig: &Signature) -> syn::Result<TokenStream> {
     let name = &sig.ident;
-    let mut extract_ts = Vec::with_capacity(sig.inputs.len());
+    let mut extract_args = Vec::with_capacity(sig.inputs.len());
     let mut call_args: Vec<Ident> = Vec::with_capacity(sig.inputs.len());
+    let mut obtain_ts = Vec::new();
     for input in &sig.inputs {
         match parse_input_type(input) {
             InputType::Request(_pat) => {
@@ -112,20 +114,19 @@ fn handle_fn(salvo: &Ident, sig: &Signature) -> syn::Result<TokenStream> {
                     let id = Ident::new(&idv, Span::call_site());
                     let idv = idv.trim_start_matches('_');
 
-                    extract_ts.push(quote!{
-                        let #id: #ty = match <#ty as #salvo::Extractible>::extract_with_arg(__macro_gen_req, #idv).await {
-                            Ok(data) => data,
-                            Err(e) => {
-                                e.write(__macro_gen_req, __macro_gen_depot, __macro_gen_res).await;
-                                // If status code is not set or is not error, set it to 400.
-                                let status_code = __macro_gen_res.status_code.unwrap_or_default();
-                                if !status_code.is_client_error() && !status_code.is_server_error() {
-                                    __macro_gen_res.status_code(#salvo::http::StatusCode::BAD_REQUEST);
-                                }
-                                return;
-                            }
-                        };
-                    });
+                    extract_args.push((id, ty, idv.to_owned()));
+                } else {
+                    return Err(syn::Error::new_spanned(pat, "invalid param definition"));
+                }
+            }
+            InputType::Obtain(pat) => {
+                if let (Pat::Ident(ident), Type::Path(ty)) = (&*pat.pat, &*pat.ty) {
+                    call_args.push(ident.ident.clone());
+                    obtain_ts.push(obtain_arg_ts(
+                        salvo,
+                        &pat.pat,
+                        &omit_type_path_lifetimes(ty),
+                    ));
                 } else {
                     return Err(syn::Error::new_spanned(pat, "invalid param definition"));
                 }
@@ -135,6 +136,10 @@ fn handle_fn(salvo: &Ident, sig: &Signature) -> syn::Result<TokenStream> {
             }
         }
     }
+    let extract_ts: Vec<_> = obtain_ts
+        .into_iter()
+        .chain(extract_args_ts(salvo, &extract_args))
+        .collect();
 
     match sig.output {
         ReturnType::Default => {
@@ -192,8 +192,10 @@ mod tests {
                                 salvo::extract::metadata::SourceFrom::Query,
                                 salvo::extract::metadata::SourceParser::Smart
                             ));
+                            field = field.checker(|req, metadata, field| salvo::serde::check_field::<i64>(req, metadata, field, false));
                             metadata = metadata.add_field(field);
                             let mut field = salvo::extract::metadata::Field::new("username");
+                            field = field.checker(|req, metadata, field| salvo::serde::check_field::<String>(req, metadata, field, false));
                             metadata = metadata.add_field(field);
                             metadata
                         })
@@ -248,14 +250,19 @@ mod tests {
                                 salvo::extract::metadata::SourceParser::Smart
                             ));
                             let mut field = salvo::extract::metadata::Field::new("id");
+                            field = field.checker(|req, metadata, field| salvo::serde::check_field::<i64>(req, metadata, field, false));
                             metadata = metadata.add_field(field);
                             let mut field = salvo::extract::metadata::Field::new("username");
+                            field = field.checker(|req, metadata, field| salvo::serde::check_field::<String>(req, metadata, field, false));
                             metadata = metadata.add_field(field);
                             let mut field = salvo::extract::metadata::Field::new("first_name");
+                            field = field.checker(|req, metadata, field| salvo::serde::check_field::<&'_ str>(req, metadata, field, false));
                             metadata = metadata.add_field(field);
                             let mut field = salvo::extract::metadata::Field::new("last_name");
+                            field = field.checker(|req, metadata, field| salvo::serde::check_field::<String>(req, metadata, field, false));
                             metadata = metadata.add_field(field);
                             let mut field = salvo::extract::metadata::Field::new("lovers");
+                            field = field.checker(|req, metadata, field| salvo::serde::check_field::<Vec<String> >(req, metadata, field, false));
                             metadata = metadata.add_field(field);
                             metadata
                         })
@@ -13,6 +13,7 @@ pub(crate) enum InputType<'a> {
     Unknown,
     Receiver(&'a Receiver),
     NoReference(&'a PatType),
+    Obtain(&'a PatType),
 }
 
 pub(crate) fn salvo_crate() -> syn::Ident {
@@ -31,6 +32,18 @@ pub(crate) fn salvo_crate() -> syn::Ident {
     }
 }
 
+// The `Obtain` arguments are extracted from depot instead of request.
+fn is_obtain(ty: &Type) -> bool {
+    if let Type::Path(ty) = ty {
+        ty.path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Obtain")
+    } else {
+        false
+    }
+}
+
 pub(crate) fn parse_input_type(input: &FnArg) -> InputType {
     if let FnArg::Typed(p) = input {
         if let Type::Reference(ty) = &*p.ty {
@@ -59,6 +72,8 @@ pub(crate) fn parse_input_type(input: &FnArg) -> InputType {
             } else {
                 InputType::Unknown
             }
+        } else if is_obtain(&p.ty) {
+            InputType::Obtain(p)
         } else {
             InputType::NoReference(p)
         }
@@ -70,6 +85,13 @@ pub(crate) fn parse_input_type(input: &FnArg) -> InputType {
     }
 }
 
+pub(crate) fn omit_type_lifetimes(ty: &Type) -> Type {
+    let reg = Regex::new(r"'\w+").expect("invalid regex");
+    let ty = ty.into_token_stream().to_string();
+    let ty = reg.replace_all(&ty, "'_");
+    syn::parse_str(ty.as_ref()).expect("invalid type")
+}
+
 pub(crate) fn omit_type_path_lifetimes(ty_path: &TypePath) -> TypePath {
     let reg = Regex::new(r"'\w+").expect("invalid regex");
     let ty_path = ty_path.into_token_stream().to_string();
@@ -425,17 +425,21 @@ impl ComponentSchema {
                         let default = pop_feature!(features => Feature::Default(_))
                             .map(|feature| feature.try_to_token_stream())
                             .transpose()?;
+                        let schema = quote_spanned! {type_path.span()=>
+                            {
+                                let schema = <#type_path as #oapi::oapi::ToSchema>::to_schema(components);
+                                components.resolve_schema(schema)
+                            }
+                        };
                         let schema = if default.is_some() || nullable {
-                            quote_spanned! {type_path.span()=>
+                            quote! {
                                 #oapi::oapi::schema::AllOf::new()
                                     #nullable_item
-                                    .item(<#type_path as #oapi::oapi::ToSchema>::to_schema(components))
+                                    .item(#schema)
                                 #default
                             }
                         } else {
-                            quote_spanned! {type_path.span() =>
-                                <#type_path as #oapi::oapi::ToSchema>::to_schema(components)
-                            }
+                            schema
                         };
                         schema.to_tokens(tokens);
                     } else {
@@ -4,6 +4,7 @@ use syn::{Expr, LitStr};
 use syn::{parenthesized, parse::Parse};
 
 use crate::operation::request_body::RequestBodyAttr;
+use crate::operation::{CallbackAttr, ExternalDocsAttr};
 use crate::{
     Array, Parameter, Response, Token, parse_utils, security_requirement::SecurityRequirementsAttr,
 };
@@ -13,8 +14,10 @@ pub(crate) struct EndpointAttr<'p> {
     pub(crate) request_body: Option<RequestBodyAttr<'p>>,
     pub(crate) responses: Vec<Response<'p>>,
     pub(crate) status_codes: Vec<Expr>,
+    pub(crate) callbacks: Vec<CallbackAttr>,
     pub(crate) operation_id: Option<Expr>,
     pub(crate) tags: Option<Vec<String>>,
+    pub(crate) external_docs: Option<ExternalDocsAttr>,
     pub(crate) parameters: Vec<Parameter<'p>>,
     pub(crate) security: Option<Array<'p, SecurityRequirementsAttr>>,
 
@@ -26,7 +29,7 @@ pub(crate) struct EndpointAttr<'p> {
 
 impl Parse for EndpointAttr<'_> {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        const EXPECTED_ATTRIBUTE_MESSAGE: &str = "unexpected identifier, expected any of: operation_id, path, get, post, put, delete, options, head, patch, trace, connect, request_body, responses, params, tag, security, context_path, description, summary";
+        const EXPECTED_ATTRIBUTE_MESSAGE: &str = "unexpected identifier, expected any of: operation_id, path, get, post, put, delete, options, head, patch, trace, connect, request_body, responses, callbacks, params, tags, external_docs, security, context_path, description, summary, deprecated";
         let mut attr = EndpointAttr::default();
 
         while !input.is_empty() {
@@ -51,6 +54,13 @@ impl Parse for EndpointAttr<'_> {
                         Punctuated::<Response, Token![,]>::parse_terminated(&responses)
                             .map(|punctuated| punctuated.into_iter().collect::<Vec<Response>>())?;
                 }
+                "callbacks" => {
+                    let callbacks;
+                    parenthesized!(callbacks in input);
+                    attr.callbacks =
+                        Punctuated::<CallbackAttr, Token![,]>::parse_terminated(&callbacks)
+                            .map(|punctuated| punctuated.into_iter().collect::<Vec<_>>())?;
+                }
                 "status_codes" => {
                     let status_codes;
                     parenthesized!(status_codes in input);
@@ -79,6 +89,9 @@ impl Parse for EndpointAttr<'_> {
                         )?,
                     );
                 }
+                "external_docs" => {
+                    attr.external_docs = Some(input.parse::<ExternalDocsAttr>()?);
+                }
                 "security" => {
                     let security;
                     parenthesized!(security in input);
@@ -88,6 +101,7 @@ impl Parse for EndpointAttr<'_> {
                     attr.description = Some(parse_utils::parse_next_lit_str_or_expr(input)?)
                 }
                 "summary" => attr.summary = Some(parse_utils::parse_next_lit_str_or_expr(input)?),
+                "deprecated" => attr.deprecated = Some(parse_utils::parse_bool_or_true(input)?),
                 _ => {
                     return Err(syn::Error::new(ident.span(), EXPECTED_ATTRIBUTE_MESSAGE));
                 }
@@ -1,8 +1,10 @@
 use proc_macro2::{Span, TokenStream};
 use quote::{ToTokens, quote};
+use syn::ext::IdentExt;
 use syn::{Expr, Ident, ImplItem, Item, Pat, ReturnType, Signature, Type};
 
 use crate::doc_comment::CommentAttributes;
+use crate::serde_util::{extract_args_ts, obtain_arg_ts};
 use crate::{Array, DiagResult, InputType, Operation, omit_type_path_lifetimes, parse_input_type};
 
 mod attr;
@@ -23,6 +25,7 @@ fn metadata(
         &format!("__macro_gen_oapi_endpoint_creator_{}", name),
         Span::call_site(),
     );
+    let operation_id = name.unraw().to_string();
     let opt = Operation::new(&attr);
     modifiers.append(opt.modifiers()?.as_mut());
     let status_codes = Array::from_iter(attr.status_codes.iter().map(|expr| match expr {
@@ -56,7 +59,7 @@ fn metadata(
             let mut operation = #oapi::oapi::Operation::new();
             #modifiers
             if operation.operation_id.is_none() {
-                operation.operation_id = Some(#oapi::oapi::naming::assign_name::<#name>(#oapi::oapi::naming::NameRule::Auto));
+                operation.operation_id = Some(#operation_id.into());
             }
             if !status_codes.is_empty() {
                 let responses = std::ops::DerefMut::deref_mut(&mut operation.responses);
@@ -110,11 +113,9 @@ pub(crate) fn generate(mut attr: EndpointAttr, input: Item) -> syn::Result<Token
             };
 
             attr.doc_comments = Some(CommentAttributes::from_attributes(attrs).0);
-            attr.deprecated = if attrs.iter().any(|attr| attr.path().is_ident("deprecated")) {
-                Some(true)
-            } else {
-                None
-            };
+            if attrs.iter().any(|attr| attr.path().is_ident("deprecated")) {
+                attr.deprecated = Some(true);
+            }
 
             let (hfn, modifiers) = handle_fn(&salvo, &oapi, sig)?;
             let meta = metadata(&salvo, &oapi, attr, name, modifiers)?;
@@ -131,11 +132,9 @@ pub(crate) fn generate(mut attr: EndpointAttr, input: Item) -> syn::Result<Token
             let attrs = &item_impl.attrs;
 
             attr.doc_comments = Some(CommentAttributes::from_attributes(attrs).0);
-            attr.deprecated = if attrs.iter().any(|attr| attr.path().is_ident("deprecated")) {
-                Some(true)
-            } else {
-                None
-            };
+            if attrs.iter().any(|attr| attr.path().is_ident("deprecated")) {
+                attr.deprecated = Some(true);
+            }
 
             let mut hmtd = None;
             for item in &item_impl.items {
@@ -179,8 +178,9 @@ fn handle_fn(
     sig: &Signature,
 ) -> syn::Result<(TokenStream, Vec<TokenStream>)> {
     let name = &sig.ident;
-    let mut extract_ts = Vec::with_capacity(sig.inputs.len());
+    let mut extract_args = Vec::with_capacity(sig.inputs.len());
     let mut call_args: Vec<Ident> = Vec::with_capacity(sig.inputs.len());
+    let mut obtain_ts = Vec::new();
     let mut modifiers = Vec::new();
     for input in &sig.inputs {
         match parse_input_type(input) {
@@ -214,22 +214,7 @@ fn handle_fn(
                         .unwrap_or(idv);
                     let id = Ident::new(&idv, Span::call_site());
                     let idv = idv.trim_start_matches('_');
-                    extract_ts.push(quote!{
-                        let #id: #ty = match <#ty as #salvo::Extractible>::extract_with_arg(__macro_gen_req, #idv).await {
-                            Ok(data) => {
-                                data
-                            },
-                            Err(e) => {
-                                e.write(__macro_gen_req, __macro_gen_depot, __macro_gen_res).await;
-                                // If status code is not set or is not error, set it to 400.
-                                let status_code = __macro_gen_res.status_code.unwrap_or_default();
-                                if !status_code.is_client_error() && !status_code.is_server_error() {
-                                    __macro_gen_res.status_code(#salvo::http::StatusCode::BAD_REQUEST);
-                                }
-                                return;
-                            }
-                        };
-                    });
+                    extract_args.push((id, ty.clone(), idv.to_owned()));
                     modifiers.push(quote! {
                          <#ty as #oapi::oapi::EndpointArgRegister>::register(components, operation, #idv);
                     });
@@ -237,11 +222,27 @@ fn handle_fn(
                     return Err(syn::Error::new_spanned(pat, "invalid param definition"));
                 }
             }
+            InputType::Obtain(pat) => {
+                if let (Pat::Ident(ident), Type::Path(ty)) = (&*pat.pat, &*pat.ty) {
+                    call_args.push(ident.ident.clone());
+                    obtain_ts.push(obtain_arg_ts(
+                        salvo,
+                        &pat.pat,
+                        &omit_type_path_lifetimes(ty),
+                    ));
+                } else {
+                    return Err(syn::Error::new_spanned(pat, "invalid param definition"));
+                }
+            }
             InputType::Receiver(_) => {
                 call_args.push(Ident::new("self", Span::call_site()));
             }
         }
     }
+    let extract_ts: Vec<_> = obtain_ts
+        .into_iter()
+        .chain(extract_args_ts(salvo, &extract_args))
+        .collect();
 
     let hfn = match &sig.output {
         ReturnType::Default => {
@@ -107,6 +107,19 @@ pub fn to_responses(input: TokenStream) -> TokenStream {
     }
 }
 
+/// Generate responses with status codes like [`ToResponses`][to_responses], and write them to the response at runtime,
+/// [Read more][more].
+///
+/// [to_responses]: ../salvo_oapi/derive.ToResponses.html
+/// [more]: ../salvo_oapi/derive.EndpointOutput.html
+#[proc_macro_derive(EndpointOutput, attributes(salvo))]
+pub fn endpoint_output(input: TokenStream) -> TokenStream {
+    match response::endpoint_output(syn::parse_macro_input!(input)) {
+        Ok(stream) => stream.into(),
+        Err(e) => e.emit_as_item_tokens().into(),
+    }
+}
+
 #[doc(hidden)]
 #[proc_macro]
 pub fn schema(input: TokenStream) -> TokenStream {
@@ -206,7 +219,7 @@ mod tests {
                     let status_codes: &[salvo::http::StatusCode] = &[];
                     let mut operation = salvo::oapi::Operation::new();
                     if operation.operation_id.is_none() {
-                        operation.operation_id = Some(salvo::oapi::naming::assign_name::<hello>(salvo::oapi::naming::NameRule::Auto));
+                        operation.operation_id = Some("hello".into());
                     }
                     if !status_codes.is_empty() {
                         let responses = std::ops::DerefMut::deref_mut(&mut operation.responses);
@@ -565,7 +578,10 @@ mod tests {
                                 salvo::oapi::parameter::Parameter::new("kind")
                                     .description("Kind of pet")
                                     .required(salvo::oapi::Required::True)
-                                    .schema(<PetKind as salvo::oapi::ToSchema>::to_schema(components)),
+                                    .schema({
+                                        let schema = <PetKind as salvo::oapi::ToSchema>::to_schema(components);
+                                        components.resolve_schema(schema)
+                                    }),
                             ]
                             .to_vec()
                         )
@@ -0,0 +1,127 @@
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{ToTokens, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::{Error, LitStr, Path, Token, parenthesized};
+
+use crate::parse_utils;
+
+const METHODS: [(&str, &str); 9] = [
+    ("get", "Get"),
+    ("post", "Post"),
+    ("put", "Put"),
+    ("delete", "Delete"),
+    ("options", "Options"),
+    ("head", "Head"),
+    ("patch", "Patch"),
+    ("trace", "Trace"),
+    ("connect", "Connect"),
+];
+
+// (name = "...", expression = "...", method = post, endpoint = handler)
+#[derive(Debug)]
+pub(crate) struct CallbackAttr {
+    pub(crate) name: LitStr,
+    pub(crate) expression: LitStr,
+    pub(crate) method: Ident,
+    pub(crate) endpoint: Path,
+}
+
+impl Parse for CallbackAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        const EXPECTED_ATTRIBUTE_MESSAGE: &str =
+            "expected one of: name, expression, method, endpoint";
+        let content;
+        let paren = parenthesized!(content in input);
+
+        let mut name = None;
+        let mut expression = None;
+        let mut method = None;
+        let mut endpoint = None;
+        while !content.is_empty() {
+            let ident = content.parse::<Ident>()?;
+            let attr_name = &*ident.to_string();
+            match attr_name {
+                "name" => {
+                    name = Some(parse_utils::parse_next(&content, || {
+                        content.parse::<LitStr>()
+                    })?)
+                }
+                "expression" => {
+                    expression = Some(parse_utils::parse_next(&content, || {
+                        content.parse::<LitStr>()
+                    })?)
+                }
+                "method" => {
+                    let ident = parse_utils::parse_next(&content, || content.parse::<Ident>())?;
+                    let Some((_, variant)) = METHODS.iter().find(|(method, _)| ident == method)
+                    else {
+                        return Err(Error::new(
+                            ident.span(),
+                            "unexpected method, expected one of: get, post, put, delete, options, head, patch, \
+                             trace, connect",
+                        ));
+                    };
+                    method = Some(Ident::new(variant, ident.span()));
+                }
+                "endpoint" => {
+                    endpoint = Some(parse_utils::parse_next(&content, || {
+                        content.parse::<Path>()
+                    })?)
+                }
+                _ => {
+                    return Err(Error::new(
+                        ident.span(),
+                        format!("unexpected attribute: {attr_name}, {EXPECTED_ATTRIBUTE_MESSAGE}"),
+                    ));
+                }
+            }
+
+            if !content.is_empty() {
+                content.parse::<Token![,]>()?;
+            }
+        }
+
+        let (Some(name), Some(expression), Some(endpoint)) = (name, expression, endpoint) else {
+            return Err(Error::new(
+                paren.span.join(),
+                "missing `name`, `expression` or `endpoint` for callback, e.g. `(name = \"todoCreated\", \
+                 expression = \"{$request.body#/callbackUrl}\", endpoint = notify_todo_created)`",
+            ));
+        };
+        Ok(Self {
+            name,
+            expression,
+            method: method.unwrap_or_else(|| Ident::new("Post", Span::call_site())),
+            endpoint,
+        })
+    }
+}
+
+impl ToTokens for CallbackAttr {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let oapi = crate::oapi_crate();
+        let Self {
+            name,
+            expression,
+            method,
+            endpoint,
+        } = self;
+
+        tokens.extend(quote! {
+            if let Some(creator) = #oapi::oapi::EndpointRegistry::find(&::std::any::TypeId::of::<#endpoint>()) {
+                let #oapi::oapi::Endpoint {
+                    operation: callback_operation,
+                    components: mut callback_components,
+                    ..
+                } = (creator)();
+                components.append(&mut callback_components);
+                let item = #oapi::oapi::PathItem::new(#oapi::oapi::PathItemType::#method, callback_operation);
+                operation
+                    .callbacks
+                    .entry(#name.to_owned())
+                    .or_default()
+                    .insert(#expression, item);
+            }
+        })
+    }
+}
@@ -0,0 +1,75 @@
+use proc_macro2::{Ident, TokenStream};
+use quote::{ToTokens, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::{Error, LitStr, Token, parenthesized};
+
+use crate::parse_utils;
+
+// (url = "...", description = "...")
+#[derive(Debug)]
+pub(crate) struct ExternalDocsAttr {
+    pub(crate) url: String,
+    pub(crate) description: Option<String>,
+}
+
+impl Parse for ExternalDocsAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        let paren = parenthesized!(content in input);
+
+        let mut url = None;
+        let mut description = None;
+        while !content.is_empty() {
+            let ident = content.parse::<Ident>()?;
+            let attr_name = &*ident.to_string();
+            match attr_name {
+                "url" => {
+                    url = Some(
+                        parse_utils::parse_next(&content, || content.parse::<LitStr>())?.value(),
+                    )
+                }
+                "description" => {
+                    description = Some(
+                        parse_utils::parse_next(&content, || content.parse::<LitStr>())?.value(),
+                    )
+                }
+                _ => {
+                    return Err(Error::new(
+                        ident.span(),
+                        format!(
+                            "unexpected attribute: {attr_name}, expected one of: url, description"
+                        ),
+                    ));
+                }
+            }
+
+            if !content.is_empty() {
+                content.parse::<Token![,]>()?;
+            }
+        }
+
+        let Some(url) = url else {
+            return Err(Error::new(
+                paren.span.join(),
+                "missing `url` for `external_docs`, e.g. `external_docs(url = \"https://...\")`",
+            ));
+        };
+        Ok(Self { url, description })
+    }
+}
+
+impl ToTokens for ExternalDocsAttr {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let oapi = crate::oapi_crate();
+        let url = &self.url;
+        let description = self
+            .description
+            .as_ref()
+            .map(|description| quote!(.description(#description)));
+
+        tokens.extend(quote! {
+            #oapi::oapi::ExternalDocs::new(#url)
+                #description
+        })
+    }
+}
@@ -12,7 +12,11 @@ use crate::security_requirement::SecurityRequirementsAttr;
 use crate::type_tree::{GenericType, TypeTree};
 use crate::{Array, DiagResult, TryToTokens};
 
+pub(crate) mod callback;
+pub(crate) use self::callback::CallbackAttr;
 pub(crate) mod example;
+pub(crate) mod external_docs;
+pub(crate) use self::external_docs::ExternalDocsAttr;
 pub(crate) mod request_body;
 pub(crate) use self::request_body::RequestBodyAttr;
 use crate::parameter::Parameter;
@@ -23,9 +27,11 @@ pub(crate) struct Operation<'a> {
     deprecated: &'a Option<bool>,
     operation_id: Option<&'a Expr>,
     tags: &'a Option<Vec<String>>,
+    external_docs: Option<&'a ExternalDocsAttr>,
     parameters: &'a Vec<Parameter<'a>>,
     request_body: Option<&'a RequestBodyAttr<'a>>,
     responses: &'a Vec<Response<'a>>,
+    callbacks: &'a Vec<CallbackAttr>,
     security: Option<&'a Array<'a, SecurityRequirementsAttr>>,
     summary: Option<Summary<'a>>,
     description: Option<Description<'a>>,
@@ -74,9 +80,11 @@ impl<'a> Operation<'a> {
             deprecated: &attr.deprecated,
             operation_id: attr.operation_id.as_ref(),
             tags: &attr.tags,
+            external_docs: attr.external_docs.as_ref(),
             parameters: attr.parameters.as_ref(),
             request_body: attr.request_body.as_ref(),
             responses: attr.responses.as_ref(),
+            callbacks: attr.callbacks.as_ref(),
             security: attr.security.as_ref(),
             summary,
             description,
@@ -106,6 +114,9 @@ impl<'a> Operation<'a> {
         // modifiers.push(quote!{
         //     .responses(#responses)
         // });
+        for callback in self.callbacks {
+            modifiers.push(callback.to_token_stream());
+        }
         if let Some(security_requirements) = self.security {
             modifiers.push(quote! {
                 operation.securities.append(&mut #security_requirements.into_iter().collect());
@@ -130,6 +141,12 @@ impl<'a> Operation<'a> {
             })
         }
 
+        if let Some(external_docs) = self.external_docs {
+            modifiers.push(quote! {
+                operation.external_docs = Some(#external_docs);
+            })
+        }
+
         if let Some(summary) = &self.summary {
             if !summary.is_empty() {
                 modifiers.push(quote! {
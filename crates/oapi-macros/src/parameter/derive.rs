@@ -23,6 +23,7 @@ use crate::feature::{
     Feature, FeaturesExt, Merge, TryToTokensExt, impl_into_inner, impl_merge, parse_features,
     pop_feature,
 };
+use crate::operation::PathTypeTree;
 use crate::parameter::ParameterIn;
 use crate::serde_util::{self, RenameRule, SerdeContainer, SerdeValue};
 use crate::type_tree::TypeTree;
@@ -42,7 +43,8 @@ impl Parse for ToParametersFeatures {
             input as DefaultStyle,
             DefaultParameterIn,
             ToParametersNames,
-            RenameAll
+            RenameAll,
+            attributes::Default
         )))
     }
 }
@@ -119,6 +121,12 @@ impl TryToTokens for ToParameters {
         let default_parameter_in =
             pop_feature!(parameters_features => Feature::DefaultParameterIn(_));
         let rename_all = pop_feature!(parameters_features => Feature::RenameAll(_));
+        let default_struct = (pop_feature!(parameters_features => Feature::Default(_)).is_some()
+            || serde_container
+                .as_ref()
+                .map(|container| container.is_default)
+                .unwrap_or(false))
+        .then_some(ident);
         let default_source_from =
             if let Some(Feature::DefaultParameterIn(DefaultParameterIn(default_parameter_in))) =
                 default_parameter_in
@@ -157,6 +165,7 @@ impl TryToTokens for ToParameters {
                         }),
                         default_style: &default_style,
                         default_parameter_in: &default_parameter_in,
+                        default_struct,
                         name: names.as_ref().map(|names| names.get(index).ok_or_else(||  Diagnostic::spanned(
                             ident.span(),
                             DiagLevel::Error,
@@ -352,6 +361,9 @@ pub(crate) struct FieldParameterContainerAttributes<'a> {
     default_parameter_in: &'a Option<Feature>,
     /// Custom rename all if serde attribute is not present.
     rename_all: Option<&'a RenameAll>,
+    /// The struct whose [`Default`](std::default::Default) implementation provides the default values of the
+    /// fields, set by the `default` container attribute or serde's `default`.
+    default_struct: Option<&'a Ident>,
 }
 
 struct FieldFeatures(Vec<Feature>);
@@ -540,7 +552,20 @@ impl TryToTokens for Parameter<'_> {
             name = &name[2..];
         }
 
-        let (schema_features, mut param_features) = self.resolve_field_features()?;
+        let (mut schema_features, mut param_features) = self.resolve_field_features()?;
+        if let (Some(struct_ident), Some(field_ident)) =
+            (self.container_attributes.default_struct, &field.ident)
+        {
+            if !schema_features
+                .iter()
+                .any(|feature| matches!(feature, Feature::Default(_)))
+            {
+                schema_features.push(Feature::Default(attributes::Default::new_default_trait(
+                    struct_ident.clone(),
+                    field_ident.clone().into(),
+                )));
+            }
+        }
 
         let rename = param_features
             .pop_rename_feature()
@@ -567,16 +592,25 @@ impl TryToTokens for Parameter<'_> {
 
         tokens.extend(quote! { #oapi::oapi::parameter::Parameter::new(#name)});
 
+        let mut is_query = false;
         if let Some(parameter_in) = param_features.pop_parameter_in_feature() {
+            is_query = parameter_in.0 == ParameterIn::Query;
             tokens.extend(quote! { .parameter_in(#parameter_in) });
         } else if let Some(parameter_in) = &self.container_attributes.default_parameter_in {
+            is_query = matches!(
+                parameter_in,
+                Feature::DefaultParameterIn(DefaultParameterIn(ParameterIn::Query))
+            );
             tokens.extend(parameter_in.try_to_token_stream()?);
         }
 
+        let mut has_style = true;
         if let Some(style) = param_features.pop_style_feature() {
             tokens.extend(quote! { .style(#style) });
         } else if let Some(style) = &self.container_attributes.default_style {
             tokens.extend(style.try_to_token_stream());
+        } else {
+            has_style = false;
         }
 
         if let Some(deprecated) = crate::get_deprecated(&field.attrs) {
@@ -602,6 +636,14 @@ impl TryToTokens for Parameter<'_> {
                 .transpose()?
                 .unwrap_or(type_tree);
 
+            // Arrays in query are repeated by default, like `?tags=a&tags=b`, the `explode` feature below
+            // overrides it, like `explode = false` for `?tags=a,b`.
+            if is_query && !has_style && component.is_array() {
+                tokens.extend(quote! {
+                    .style(#oapi::oapi::parameter::ParameterStyle::Form).explode(true)
+                });
+            }
+
             let required: Option<attributes::Required> =
                 pop_feature!(param_features => Feature::Required(_)).into_inner();
             let component_required = !component.is_option()
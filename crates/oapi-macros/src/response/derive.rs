@@ -2,7 +2,7 @@ use std::borrow::Cow;
 use std::{iter, mem};
 
 use proc_macro2::{Ident, Span, TokenStream};
-use quote::quote;
+use quote::{ToTokens, quote};
 use syn::parse::ParseStream;
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
@@ -11,8 +11,9 @@ use syn::{
 };
 
 use crate::doc_comment::CommentAttributes;
-use crate::operation::{InlineType, PathType};
+use crate::operation::{InlineType, PathType, PathTypeTree};
 use crate::schema::{EnumSchema, NamedStructSchema};
+use crate::type_tree::TypeTree;
 use crate::{Array, DiagLevel, DiagResult, Diagnostic, TryToTokens, attribute, parse_utils};
 
 use super::{
@@ -195,6 +196,143 @@ impl TryToTokens for ToResponses<'_> {
     }
 }
 
+/// `ToResponses` with a `Scribe` implementation, which writes the status code and the body of each response.
+pub(crate) struct EndpointOutput<'a> {
+    pub(crate) attributes: &'a [Attribute],
+    pub(crate) data: &'a Data,
+    pub(crate) generics: &'a Generics,
+    pub(crate) ident: &'a Ident,
+}
+
+impl EndpointOutput<'_> {
+    fn status_code(&self, attributes: &[Attribute]) -> DiagResult<u16> {
+        let derive_value = DeriveToResponsesValue::from_attributes(attributes)?
+            .expect("`EndpointOutput` must have `#[salvo(response(...))]` attribute");
+        let status_code = derive_value
+            .status_code
+            .to_token_stream()
+            .to_string()
+            .trim_matches('"')
+            .parse::<u16>()
+            .ok()
+            .filter(|code| (100..1000).contains(code));
+        status_code.ok_or_else(|| {
+            Diagnostic::spanned(
+                self.ident.span(),
+                DiagLevel::Error,
+                "`EndpointOutput` requires an exact `status_code`, status code ranges and `default` are not supported",
+            )
+        })
+    }
+
+    fn render_arm(
+        &self,
+        path: TokenStream,
+        attributes: &[Attribute],
+        fields: &Fields,
+        is_variant: bool,
+    ) -> DiagResult<TokenStream> {
+        let salvo = crate::salvo_crate();
+        let status_code = self.status_code(attributes)?;
+        let set_status_code = quote! {
+            res.status_code(#salvo::http::StatusCode::from_u16(#status_code).expect("invalid status code"));
+        };
+        let arm = match fields {
+            Fields::Unit => quote! {
+                #path => {
+                    #set_status_code
+                }
+            },
+            Fields::Unnamed(fields) => {
+                let field = fields
+                    .unnamed
+                    .iter()
+                    .next()
+                    .expect("Unnamed struct must have 1 field");
+                let render = match TypeTree::from_type(&field.ty)?.get_default_content_type() {
+                    "text/plain" => {
+                        quote!(res.render(#salvo::writing::Text::Plain(body.to_string()));)
+                    }
+                    "application/octet-stream" => quote!(res.body(body);),
+                    _ => quote!(res.render(#salvo::writing::Json(body));),
+                };
+                quote! {
+                    #path(body) => {
+                        #set_status_code
+                        #render
+                    }
+                }
+            }
+            Fields::Named(_) if is_variant => {
+                return Err(Diagnostic::spanned(
+                    path.span(),
+                    DiagLevel::Error,
+                    "`EndpointOutput` does not support named field enum variants, wrap the fields in a struct and use an unnamed variant",
+                ));
+            }
+            Fields::Named(_) => quote! {
+                body @ #path { .. } => {
+                    #set_status_code
+                    res.render(#salvo::writing::Json(body));
+                }
+            },
+        };
+        Ok(arm)
+    }
+}
+
+impl TryToTokens for EndpointOutput<'_> {
+    fn try_to_tokens(&self, tokens: &mut TokenStream) -> DiagResult<()> {
+        ToResponses {
+            attributes: self.attributes,
+            data: self.data,
+            generics: self.generics,
+            ident: self.ident,
+        }
+        .try_to_tokens(tokens)?;
+
+        let salvo = crate::salvo_crate();
+        let arms = match &self.data {
+            Data::Struct(struct_value) => {
+                vec![self.render_arm(quote!(Self), self.attributes, &struct_value.fields, false)?]
+            }
+            Data::Enum(enum_value) => enum_value
+                .variants
+                .iter()
+                .map(|variant| {
+                    let variant_ident = &variant.ident;
+                    self.render_arm(
+                        quote!(Self::#variant_ident),
+                        &variant.attrs,
+                        &variant.fields,
+                        true,
+                    )
+                })
+                .collect::<DiagResult<Vec<_>>>()?,
+            Data::Union(_) => {
+                return Err(Diagnostic::spanned(
+                    self.ident.span(),
+                    DiagLevel::Error,
+                    "`EndpointOutput` does not support `Union` type",
+                ));
+            }
+        };
+
+        let ident = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        tokens.extend(quote! {
+            impl #impl_generics #salvo::Scribe for #ident #ty_generics #where_clause {
+                fn render(self, res: &mut #salvo::Response) {
+                    match self {
+                        #(#arms)*
+                    }
+                }
+            }
+        });
+        Ok(())
+    }
+}
+
 trait Response {
     fn to_type(ident: &Ident) -> Type {
         let path = Path::from(ident.clone());
@@ -20,7 +20,7 @@ use crate::operation::{
 use crate::type_tree::TypeTree;
 use crate::{AnyValue, Array, DiagResult, Diagnostic, TryToTokens, attribute, parse_utils};
 
-use self::derive::{ToResponse, ToResponses};
+use self::derive::{EndpointOutput, ToResponse, ToResponses};
 use self::link::LinkTuple;
 
 pub(crate) fn to_response(input: DeriveInput) -> DiagResult<TokenStream> {
@@ -51,6 +51,23 @@ pub(crate) fn to_responses(input: DeriveInput) -> DiagResult<TokenStream> {
     .try_to_token_stream()
 }
 
+pub(crate) fn endpoint_output(input: DeriveInput) -> DiagResult<TokenStream> {
+    let DeriveInput {
+        attrs,
+        ident,
+        data,
+        generics,
+        ..
+    } = input;
+    EndpointOutput {
+        attributes: &attrs,
+        ident: &ident,
+        generics: &generics,
+        data: &data,
+    }
+    .try_to_token_stream()
+}
+
 #[derive(Debug)]
 pub(crate) enum Response<'r> {
     /// A type that implements `salvo_oapi::ToResponses`.
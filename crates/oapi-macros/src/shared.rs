@@ -25,6 +25,7 @@ pub(crate) enum InputType<'a> {
     Unknown,
     Receiver(&'a Receiver),
     NoReference(&'a PatType),
+    Obtain(&'a PatType),
 }
 
 // https://github.com/bkchr/proc-macro-crate/issues/14
@@ -54,6 +55,18 @@ pub(crate) fn salvo_crate() -> syn::Ident {
     }
 }
 
+// The `Obtain` arguments are extracted from depot instead of request.
+fn is_obtain(ty: &Type) -> bool {
+    if let Type::Path(ty) = ty {
+        ty.path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Obtain")
+    } else {
+        false
+    }
+}
+
 pub(crate) fn parse_input_type(input: &FnArg) -> InputType {
     if let FnArg::Typed(p) = input {
         if let Type::Reference(ty) = &*p.ty {
@@ -82,6 +95,8 @@ pub(crate) fn parse_input_type(input: &FnArg) -> InputType {
             } else {
                 InputType::Unknown
             }
+        } else if is_obtain(&p.ty) {
+            InputType::Obtain(p)
         } else {
             InputType::NoReference(p)
         }
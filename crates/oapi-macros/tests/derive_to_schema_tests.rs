@@ -250,3 +250,160 @@ fn test_derive_to_schema_enum() {
         })
     );
 }
+
+#[test]
+fn test_derive_to_schema_nested() {
+    #[derive(Serialize, Deserialize, ToSchema, Debug)]
+    struct Metadata {
+        created_by: String,
+    }
+    #[derive(Serialize, Deserialize, ToSchema, Debug)]
+    struct Todo {
+        id: u64,
+        metadata: Metadata,
+        previous: Option<Metadata>,
+        history: Vec<Metadata>,
+        #[salvo(schema(inline))]
+        inlined: Metadata,
+    }
+
+    #[endpoint]
+    async fn create(body: JsonBody<Todo>) -> String {
+        format!("{:?}", body)
+    }
+
+    salvo::oapi::naming::set_namer(
+        salvo::oapi::naming::FlexNamer::new()
+            .short_mode(true)
+            .generic_delimiter('_', '_'),
+    );
+
+    let router = Router::new().push(Router::with_path("todos").post(create));
+
+    let doc = OpenApi::new("test api", "0.0.1").merge_router(&router);
+    let value = serde_json::to_value(&doc).unwrap();
+    let schemas = value.pointer("/components/schemas").unwrap();
+    assert_json_eq!(
+        schemas,
+        json!({
+            "Metadata": {
+                "type": "object",
+                "required": ["created_by"],
+                "properties": {
+                    "created_by": {
+                        "type": "string"
+                    }
+                }
+            },
+            "Todo": {
+                "type": "object",
+                "required": ["id", "metadata", "history", "inlined"],
+                "properties": {
+                    "id": {
+                        "type": "integer",
+                        "format": "uint64",
+                        "minimum": 0.0
+                    },
+                    "metadata": {
+                        "$ref": "#/components/schemas/Metadata"
+                    },
+                    "previous": {
+                        "allOf": [
+                            {
+                                "type": "null"
+                            },
+                            {
+                                "$ref": "#/components/schemas/Metadata"
+                            }
+                        ]
+                    },
+                    "history": {
+                        "type": "array",
+                        "items": {
+                            "$ref": "#/components/schemas/Metadata"
+                        }
+                    },
+                    "inlined": {
+                        "type": "object",
+                        "required": ["created_by"],
+                        "properties": {
+                            "created_by": {
+                                "type": "string"
+                            }
+                        }
+                    }
+                }
+            }
+        })
+    );
+}
+
+#[test]
+fn test_derive_to_schema_inline_type() {
+    #[derive(Serialize, Deserialize, ToSchema, Debug)]
+    struct Pet {
+        name: String,
+    }
+    #[derive(Serialize, Deserialize, ToSchema, Debug)]
+    #[salvo(schema(inline))]
+    struct PetEnvelope {
+        code: u16,
+        data: Pet,
+    }
+
+    #[endpoint]
+    async fn show() -> Json<PetEnvelope> {
+        Json(PetEnvelope {
+            code: 0,
+            data: Pet {
+                name: "Kitty".into(),
+            },
+        })
+    }
+
+    salvo::oapi::naming::set_namer(
+        salvo::oapi::naming::FlexNamer::new()
+            .short_mode(true)
+            .generic_delimiter('_', '_'),
+    );
+
+    let router = Router::new().push(Router::with_path("pet").get(show));
+
+    let doc = OpenApi::new("test api", "0.0.1").merge_router(&router);
+    let value = serde_json::to_value(&doc).unwrap();
+    let schemas = value.pointer("/components/schemas").unwrap();
+    assert_json_eq!(
+        schemas,
+        json!({
+            "Pet": {
+                "type": "object",
+                "required": ["name"],
+                "properties": {
+                    "name": {
+                        "type": "string"
+                    }
+                }
+            }
+        })
+    );
+    let schema = value
+        .pointer("/paths/~1pet/get/responses/200/content/application~1json/schema")
+        .unwrap();
+    assert_json_eq!(
+        schema,
+        json!({
+            "type": "object",
+            "required": ["code", "data"],
+            "properties": {
+                "code": {
+                    "type": "integer",
+                    "format": "uint16",
+                    "minimum": 0.0
+                },
+                "data": {
+                    "$ref": "#/components/schemas/Pet"
+                }
+            }
+        })
+    );
+}
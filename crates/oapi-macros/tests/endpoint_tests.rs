@@ -25,7 +25,7 @@ fn test_endpoint_hello() {
             "paths":{
                 "/hello":{
                     "get":{
-                        "operationId":"endpoint_tests.test_endpoint_hello.hello",
+                        "operationId":"hello",
                         "parameters":[{
                                 "name":"name",
                                 "in":"query",
@@ -44,3 +44,404 @@ fn test_endpoint_hello() {
         })
     );
 }
+
+#[test]
+fn test_endpoint_obtain() {
+    use std::sync::Arc;
+
+    use salvo::extract::Obtain;
+
+    struct AppState {
+        greeting: &'static str,
+    }
+
+    #[endpoint]
+    async fn hello(state: Obtain<Arc<AppState>>, name: QueryParam<String, false>) -> String {
+        format!(
+            "{}, {}!",
+            state.greeting,
+            name.as_deref().unwrap_or("World")
+        )
+    }
+
+    let router = Router::with_path("hello").get(hello);
+
+    let doc = OpenApi::new("test api", "0.0.1").merge_router(&router);
+    let doc = serde_json::to_value(&doc).unwrap();
+    let parameters = doc["paths"]["/hello"]["get"]["parameters"]
+        .as_array()
+        .unwrap();
+    assert_eq!(parameters.len(), 1);
+    assert_eq!(parameters[0]["name"], "name");
+}
+
+#[test]
+fn test_endpoint_query_array_style() {
+    use serde::Deserialize;
+
+    #[derive(Deserialize, ToParameters, Debug)]
+    #[salvo(parameters(default_parameter_in = Query))]
+    #[allow(dead_code)]
+    struct TodoFilter {
+        tags: Vec<String>,
+        owners: Option<Vec<u64>>,
+        #[salvo(parameter(explode = false))]
+        fields: Vec<String>,
+        #[salvo(parameter(style = SpaceDelimited, explode = false))]
+        sort: Vec<String>,
+        completed: Option<bool>,
+    }
+
+    #[endpoint]
+    async fn list_todos(filter: TodoFilter) -> String {
+        format!("{filter:?}")
+    }
+
+    let router = Router::with_path("todos").get(list_todos);
+
+    let doc = OpenApi::new("test api", "0.0.1").merge_router(&router);
+    let doc = serde_json::to_value(&doc).unwrap();
+    let parameters = doc["paths"]["/todos"]["get"]["parameters"]
+        .as_array()
+        .unwrap();
+    let parameter = |name: &str| {
+        parameters
+            .iter()
+            .find(|parameter| parameter["name"] == name)
+            .unwrap()
+    };
+    assert_eq!(parameter("tags")["in"], "query");
+    assert_eq!(parameter("tags")["style"], "form");
+    assert_eq!(parameter("tags")["explode"], true);
+    assert_eq!(parameter("tags")["schema"]["type"], "array");
+    assert_eq!(parameter("owners")["style"], "form");
+    assert_eq!(parameter("owners")["explode"], true);
+    assert_eq!(parameter("fields")["style"], "form");
+    assert_eq!(parameter("fields")["explode"], false);
+    assert_eq!(parameter("fields")["schema"]["type"], "array");
+    assert_eq!(parameter("sort")["style"], "spaceDelimited");
+    assert_eq!(parameter("sort")["explode"], false);
+    assert!(parameter("completed").get("style").is_none());
+    assert!(parameter("completed").get("explode").is_none());
+}
+
+#[test]
+fn test_endpoint_parameters_default() {
+    use serde::Deserialize;
+
+    #[derive(Deserialize, ToParameters, Debug)]
+    #[salvo(parameters(default_parameter_in = Query, default))]
+    #[serde(default)]
+    struct ListOptions {
+        offset: usize,
+        limit: usize,
+        #[salvo(parameter(default = "created_at"))]
+        sort: String,
+    }
+    impl Default for ListOptions {
+        fn default() -> Self {
+            Self {
+                offset: 0,
+                limit: 100,
+                sort: "id".into(),
+            }
+        }
+    }
+
+    #[derive(Deserialize, ToParameters, Debug)]
+    #[salvo(parameters(default_parameter_in = Query))]
+    #[allow(dead_code)]
+    struct SearchOptions {
+        #[salvo(parameter(default = 10))]
+        limit: Option<usize>,
+        text: Option<String>,
+    }
+
+    #[endpoint]
+    async fn list_todos(options: ListOptions) -> String {
+        format!("{options:?}")
+    }
+    #[endpoint]
+    async fn search_todos(options: SearchOptions) -> String {
+        format!("{options:?}")
+    }
+
+    let router = Router::with_path("todos")
+        .get(list_todos)
+        .push(Router::with_path("search").get(search_todos));
+
+    let doc = OpenApi::new("test api", "0.0.1").merge_router(&router);
+    let doc = serde_json::to_value(&doc).unwrap();
+    let parameter = |path: &str, name: &str| {
+        doc["paths"][path]["get"]["parameters"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|parameter| parameter["name"] == name)
+            .unwrap()
+            .clone()
+    };
+    assert_eq!(parameter("/todos", "offset")["schema"]["default"], 0);
+    assert_eq!(parameter("/todos", "offset")["required"], false);
+    assert_eq!(parameter("/todos", "limit")["schema"]["default"], 100);
+    assert_eq!(
+        parameter("/todos", "sort")["schema"]["default"],
+        "created_at"
+    );
+    assert_eq!(parameter("/todos/search", "limit")["schema"]["default"], 10);
+    assert!(
+        parameter("/todos/search", "text")["schema"]
+            .get("default")
+            .is_none()
+    );
+}
+
+#[test]
+fn test_endpoint_operation_id() {
+    #[endpoint]
+    async fn list_todos() -> &'static str {
+        "list"
+    }
+    #[endpoint(operation_id = "todos.create")]
+    async fn create_todo() -> &'static str {
+        "create"
+    }
+
+    let router = Router::with_path("todos").get(list_todos).post(create_todo);
+
+    let doc = OpenApi::new("test api", "0.0.1").merge_router(&router);
+    let doc = serde_json::to_value(&doc).unwrap();
+    assert_eq!(doc["paths"]["/todos"]["get"]["operationId"], "list_todos");
+    assert_eq!(
+        doc["paths"]["/todos"]["post"]["operationId"],
+        "todos.create"
+    );
+}
+
+#[test]
+fn test_endpoint_tags_and_external_docs() {
+    #[endpoint(
+        tags("todo"),
+        external_docs(url = "https://example.com/todos", description = "More about todos")
+    )]
+    async fn list_todos() -> &'static str {
+        "list"
+    }
+
+    let router = Router::with_path("todos").get(list_todos);
+
+    let doc = OpenApi::new("test api", "0.0.1")
+        .tags(["todo"])
+        .merge_router(&router);
+    let doc = serde_json::to_value(&doc).unwrap();
+    assert_eq!(doc["tags"], json!([{"name": "todo"}]));
+    let operation = &doc["paths"]["/todos"]["get"];
+    assert_eq!(operation["tags"], json!(["todo"]));
+    assert_eq!(
+        operation["externalDocs"],
+        json!({"url": "https://example.com/todos", "description": "More about todos"})
+    );
+}
+
+#[test]
+fn test_endpoint_deprecated() {
+    #[endpoint(deprecated)]
+    async fn update_todo() -> &'static str {
+        "update"
+    }
+    #[endpoint]
+    async fn patch_todo() -> &'static str {
+        "patch"
+    }
+
+    let router = Router::with_path("todos/{id}")
+        .put(update_todo)
+        .patch(patch_todo);
+
+    let doc = OpenApi::new("test api", "0.0.1").merge_router(&router);
+    let doc = serde_json::to_value(&doc).unwrap();
+    let path = &doc["paths"]["/todos/{id}"];
+    assert_eq!(path["put"]["deprecated"], json!(true));
+    assert!(path["patch"].get("deprecated").is_none());
+}
+
+#[test]
+fn test_endpoint_output() {
+    use salvo::http::ResBody;
+    use salvo::oapi::ToSchema;
+    use serde::Serialize;
+
+    #[derive(Serialize, ToSchema, Debug)]
+    struct ErrorBody {
+        message: String,
+    }
+
+    #[derive(EndpointOutput, Debug)]
+    enum TodoError {
+        /// Todo not found.
+        #[salvo(response(status_code = 404))]
+        NotFound(ErrorBody),
+        /// Todo already exists.
+        #[salvo(response(status_code = StatusCode::CONFLICT))]
+        Conflict(String),
+        /// Unauthorized to change the todo.
+        #[salvo(response(status_code = 401))]
+        Unauthorized,
+    }
+
+    #[endpoint]
+    async fn update_todo() -> Result<StatusCode, TodoError> {
+        Err(TodoError::Unauthorized)
+    }
+
+    let router = Router::with_path("todos/{id}").put(update_todo);
+    let doc = OpenApi::new("test api", "0.0.1").merge_router(&router);
+    let doc = serde_json::to_value(&doc).unwrap();
+    let responses = &doc["paths"]["/todos/{id}"]["put"]["responses"];
+    assert_eq!(responses["404"]["description"], json!("Todo not found."));
+    assert_eq!(
+        responses["404"]["content"]["application/json"]["schema"],
+        json!({"$ref": "#/components/schemas/endpoint_tests.test_endpoint_output.ErrorBody"})
+    );
+    assert_eq!(
+        responses["409"]["content"]["text/plain"]["schema"],
+        json!({"type": "string"})
+    );
+    assert_eq!(
+        responses["401"],
+        json!({"description": "Unauthorized to change the todo."})
+    );
+
+    fn render(error: TodoError) -> (Option<StatusCode>, Option<String>, String) {
+        let mut res = Response::new();
+        res.render(error);
+        let content_type = res.content_type().map(|mime| mime.to_string());
+        let body = match res.body {
+            ResBody::Once(bytes) => String::from_utf8(bytes.to_vec()).unwrap(),
+            ResBody::None => String::new(),
+            _ => panic!("unexpected body"),
+        };
+        (res.status_code, content_type, body)
+    }
+    assert_eq!(
+        render(TodoError::NotFound(ErrorBody {
+            message: "not found".into()
+        })),
+        (
+            Some(StatusCode::NOT_FOUND),
+            Some("application/json; charset=utf-8".into()),
+            r#"{"message":"not found"}"#.into()
+        )
+    );
+    assert_eq!(
+        render(TodoError::Conflict("id = 1".into())),
+        (
+            Some(StatusCode::CONFLICT),
+            Some("text/plain; charset=utf-8".into()),
+            "id = 1".into()
+        )
+    );
+    assert_eq!(
+        render(TodoError::Unauthorized),
+        (Some(StatusCode::UNAUTHORIZED), None, String::new())
+    );
+}
+
+#[test]
+fn test_endpoint_callbacks_and_webhooks() {
+    #[derive(serde::Deserialize, ToSchema)]
+    #[allow(dead_code)]
+    struct TodoEvent {
+        id: u64,
+    }
+
+    /// Todo is done.
+    #[endpoint(status_codes(204))]
+    async fn todo_done(_event: JsonBody<TodoEvent>) -> StatusCode {
+        StatusCode::NO_CONTENT
+    }
+
+    #[endpoint(callbacks(
+        (name = "todoDone", expression = "{$request.body#/callback_url}", endpoint = todo_done),
+        (name = "todoDone", expression = "{$request.body#/callback_url}", method = delete, endpoint = todo_done)
+    ))]
+    async fn create_todo() {}
+
+    let router = Router::with_path("todos").post(create_todo);
+    let doc = OpenApi::new("test api", "0.0.1")
+        .merge_router(&router)
+        .add_webhook_endpoint::<todo_done>("todoDone", salvo::oapi::PathItemType::Post);
+    let operation = json!({
+        "summary": "Todo is done.",
+        "operationId": "todo_done",
+        "requestBody": {
+            "content": {
+                "application/json": {
+                    "schema": {"$ref": "#/components/schemas/endpoint_tests.test_endpoint_callbacks_and_webhooks.TodoEvent"}
+                }
+            },
+            "description": "Extract json format data from request."
+        },
+        "responses": {
+            "204": {"description": "No Content"}
+        }
+    });
+    let value = serde_json::to_value(&doc).unwrap();
+    assert_json_eq!(
+        value["paths"]["/todos"]["post"]["callbacks"],
+        json!({
+            "todoDone": {
+                "{$request.body#/callback_url}": {
+                    "post": operation,
+                    "delete": operation
+                }
+            }
+        })
+    );
+    assert_json_eq!(value["webhooks"], json!({"todoDone": {"post": operation}}));
+    assert!(
+        value["components"]["schemas"]
+            .get("endpoint_tests.test_endpoint_callbacks_and_webhooks.TodoEvent")
+            .is_some()
+    );
+}
+
+mod v1 {
+    use salvo::prelude::*;
+
+    #[endpoint]
+    pub(crate) async fn list_todos() -> &'static str {
+        "v1"
+    }
+}
+mod v2 {
+    use salvo::prelude::*;
+
+    #[endpoint]
+    pub(crate) async fn list_todos() -> &'static str {
+        "v2"
+    }
+}
+
+#[test]
+fn test_endpoint_operation_id_collision() {
+    let router = Router::new()
+        .push(Router::with_path("v1/todos").get(v1::list_todos))
+        .push(Router::with_path("v2/todos").get(v2::list_todos))
+        .push(Router::with_path("v3/todos").get(v1::list_todos));
+
+    let doc = OpenApi::new("test api", "0.0.1").merge_router(&router);
+    let doc = serde_json::to_value(&doc).unwrap();
+    assert_eq!(
+        doc["paths"]["/v1/todos"]["get"]["operationId"],
+        "list_todos"
+    );
+    assert_eq!(
+        doc["paths"]["/v2/todos"]["get"]["operationId"],
+        "endpoint_tests.v2.list_todos"
+    );
+    assert_eq!(
+        doc["paths"]["/v3/todos"]["get"]["operationId"],
+        "list_todos"
+    );
+}
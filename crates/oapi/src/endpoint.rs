@@ -3,7 +3,9 @@ use std::any::TypeId;
 use salvo_core::http::StatusCode;
 use salvo_core::{prelude::StatusError, writing};
 
-use crate::{Components, Operation, Response, ToResponse, ToResponses, ToSchema};
+use crate::{
+    Components, Header, Operation, RefOr, Response, Responses, ToResponse, ToResponses, ToSchema,
+};
 
 /// Represents an endpoint.
 ///
@@ -47,6 +49,41 @@ where
             .insert("200", Self::to_response(components));
     }
 }
+impl EndpointOutRegister for writing::Created {
+    #[inline]
+    fn register(components: &mut Components, operation: &mut Operation) {
+        operation.responses.insert(
+            "201",
+            created_response(Response::new("Created"), components),
+        );
+    }
+}
+impl<T> EndpointOutRegister for writing::Created<T>
+where
+    T: EndpointOutRegister,
+{
+    // The `200` response of the body is registered as the `201` response, with the `Location` header.
+    fn register(components: &mut Components, operation: &mut Operation) {
+        let mut body = Operation::new();
+        T::register(components, &mut body);
+        let mut responses = Responses::new();
+        let mut response = Response::new("Created");
+        for (code, body_response) in body.responses {
+            match body_response {
+                RefOr::Type(body_response) if code == "200" => response = body_response,
+                _ => responses.insert(code, body_response),
+            }
+        }
+        responses.insert("201", created_response(response, components));
+        operation.responses.append(&mut responses);
+    }
+}
+fn created_response(response: Response, components: &mut Components) -> Response {
+    response.add_header(
+        "location",
+        Header::new(String::to_schema(components)).description("URL of the created resource"),
+    )
+}
 impl<T, E> EndpointOutRegister for Result<T, E>
 where
     T: EndpointOutRegister + Send,
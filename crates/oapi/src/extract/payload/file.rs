@@ -9,11 +9,38 @@ use salvo_core::{Request, async_trait};
 
 use crate::endpoint::EndpointArgRegister;
 use crate::{
-    Array, BasicType, Components, Content, KnownFormat, Object, Operation, RequestBody, Schema,
-    SchemaFormat,
+    Array, BasicType, Components, Content, KnownFormat, Object, Operation, RefOr, RequestBody,
+    Schema, SchemaFormat, ToSchema,
 };
 
 /// Represents the upload file.
+///
+/// When used as an argument of `#[endpoint]`, it is documented as a file field of the
+/// `multipart/form-data` request body, so the file can be chosen and uploaded from the
+/// generated UI. Several file arguments are documented as several fields of the same request body.
+///
+/// It also implements [`ToSchema`] as `type: string, format: binary`, so it can be used as a field
+/// of the request body type when files are uploaded with other fields:
+///
+/// ```
+/// use salvo_core::prelude::*;
+/// use salvo_oapi::extract::FormFile;
+/// use salvo_oapi::{ToSchema, endpoint};
+///
+/// #[derive(ToSchema)]
+/// #[allow(dead_code)]
+/// struct UploadForm {
+///     title: String,
+///     file: FormFile,
+/// }
+///
+/// #[endpoint(request_body(content = UploadForm, content_type = "multipart/form-data"))]
+/// async fn upload(req: &mut Request) -> String {
+///     let title = req.form::<String>("title").await.unwrap_or_default();
+///     let size = req.file("file").await.map(|file| file.size()).unwrap_or_default();
+///     format!("{title}: {size} bytes")
+/// }
+/// ```
 #[derive(Clone, Debug)]
 pub struct FormFile {
     name: Option<String>,
@@ -93,27 +120,40 @@ impl<'ex> Extractible<'ex> for FormFile {
     }
 }
 
+fn binary_schema() -> Schema {
+    Schema::from(
+        Object::with_type(BasicType::String).format(SchemaFormat::KnownFormat(KnownFormat::Binary)),
+    )
+}
+
+/// Add a file field to the `multipart/form-data` request body of the operation, the fields
+/// added before are kept.
+fn register_file_field(operation: &mut Operation, arg: &str, field: Schema, description: &str) {
+    let request_body = operation
+        .request_body
+        .get_or_insert_with(|| RequestBody::new().description(description));
+    let content = request_body
+        .contents
+        .entry("multipart/form-data".into())
+        .or_insert_with(|| Content::new(Object::new()));
+    if let RefOr::Type(Schema::Object(object)) = &mut content.schema {
+        object.properties.insert(arg.into(), field.into());
+        object.required.insert(arg.into());
+    } else {
+        content.schema = Object::new().property(arg, field).required(arg).into();
+    }
+}
+
+impl ToSchema for FormFile {
+    fn to_schema(_components: &mut Components) -> RefOr<Schema> {
+        binary_schema().into()
+    }
+}
+
 #[async_trait]
 impl EndpointArgRegister for FormFile {
     fn register(_components: &mut Components, operation: &mut Operation, arg: &str) {
-        let schema = Schema::from(
-            Object::new().property(
-                arg,
-                Object::with_type(BasicType::String)
-                    .format(SchemaFormat::KnownFormat(KnownFormat::Binary)),
-            ),
-        );
-
-        if let Some(request_body) = &mut operation.request_body {
-            request_body
-                .contents
-                .insert("multipart/form-data".into(), Content::new(schema));
-        } else {
-            let request_body = RequestBody::new()
-                .description("Upload a file.")
-                .add_content("multipart/form-data", Content::new(schema));
-            operation.request_body = Some(request_body);
-        }
+        register_file_field(operation, arg, binary_schema(), "Upload a file.");
     }
 }
 
@@ -167,27 +207,105 @@ impl<'ex> Extractible<'ex> for FormFiles {
     }
 }
 
+impl ToSchema for FormFiles {
+    fn to_schema(_components: &mut Components) -> RefOr<Schema> {
+        Schema::from(Array::new().items(binary_schema())).into()
+    }
+}
+
 #[async_trait]
 impl EndpointArgRegister for FormFiles {
     fn register(_components: &mut Components, operation: &mut Operation, arg: &str) {
-        let schema = Schema::from(
-            Object::new().property(
-                arg,
-                Array::new().items(Schema::from(
-                    Object::with_type(BasicType::String)
-                        .format(SchemaFormat::KnownFormat(KnownFormat::Binary)),
-                )),
-            ),
+        register_file_field(
+            operation,
+            arg,
+            Schema::from(Array::new().items(binary_schema())),
+            "Upload files.",
         );
-        if let Some(request_body) = &mut operation.request_body {
-            request_body
-                .contents
-                .insert("multipart/form-data".into(), Content::new(schema));
-        } else {
-            let request_body = RequestBody::new()
-                .description("Upload files.")
-                .add_content("multipart/form-data", Content::new(schema));
-            operation.request_body = Some(request_body);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_json_diff::assert_json_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_form_file_register() {
+        let mut components = Components::new();
+        let mut operation = Operation::new();
+        FormFile::register(&mut components, &mut operation, "avatar");
+        FormFiles::register(&mut components, &mut operation, "photos");
+        assert_json_eq!(
+            operation,
+            json!({
+                "requestBody": {
+                    "content": {
+                        "multipart/form-data": {
+                            "schema": {
+                                "type": "object",
+                                "required": ["avatar", "photos"],
+                                "properties": {
+                                    "avatar": {
+                                        "type": "string",
+                                        "format": "binary"
+                                    },
+                                    "photos": {
+                                        "type": "array",
+                                        "items": {
+                                            "type": "string",
+                                            "format": "binary"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    },
+                    "description": "Upload a file."
+                },
+                "responses": {}
+            })
+        );
+    }
+
+    #[test]
+    fn test_form_file_to_schema() {
+        #[derive(ToSchema)]
+        #[allow(dead_code)]
+        struct UploadForm {
+            title: String,
+            file: FormFile,
+            attachments: FormFiles,
         }
+        let mut components = Components::new();
+        let schema = UploadForm::to_schema(&mut components);
+        assert_json_eq!(
+            components
+                .schemas
+                .get(&crate::naming::assign_name::<UploadForm>(Default::default())),
+            json!({
+                "type": "object",
+                "required": ["title", "file", "attachments"],
+                "properties": {
+                    "title": {
+                        "type": "string"
+                    },
+                    "file": {
+                        "type": "string",
+                        "format": "binary"
+                    },
+                    "attachments": {
+                        "type": "array",
+                        "items": {
+                            "type": "string",
+                            "format": "binary"
+                        }
+                    }
+                }
+            })
+        );
+        assert!(matches!(schema, RefOr::Ref(_)));
     }
 }
@@ -176,6 +176,32 @@ mod tests {
         assert_eq!("value", result.unwrap().0["key"]);
     }
 
+    #[tokio::test]
+    async fn test_json_body_extract_media_types() {
+        for ctype in [
+            "application/json; charset=utf-8",
+            "application/vnd.todo+json",
+        ] {
+            let mut req = TestClient::post("http://127.0.0.1:5800/")
+                .raw_json(r#"{"key":"value"}"#)
+                .add_header("content-type", ctype, true)
+                .build();
+            let result = JsonBody::<BTreeMap<&str, &str>>::extract(&mut req).await;
+            assert_eq!("value", result.unwrap().0["key"]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_json_body_extract_raw_value() {
+        let payload = r#"{ "z": 1, "a": 12345678901234567890123 }"#;
+        let mut req = TestClient::post("http://127.0.0.1:5800/")
+            .raw_json(format!(r#"{{"payload":{payload}}}"#))
+            .build();
+        let result =
+            JsonBody::<BTreeMap<&str, Box<serde_json::value::RawValue>>>::extract(&mut req).await;
+        assert_eq!(payload, result.unwrap().0["payload"].get());
+    }
+
     #[test]
     fn test_json_body_register() {
         let mut components = Components::new();
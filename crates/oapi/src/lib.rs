@@ -35,6 +35,8 @@ cfg_feature! {
     pub mod redoc;
 }
 
+#[doc = include_str!("../docs/derive_endpoint_output.md")]
+pub use salvo_oapi_macros::EndpointOutput;
 #[doc = include_str!("../docs/derive_to_parameters.md")]
 pub use salvo_oapi_macros::ToParameters;
 #[doc = include_str!("../docs/derive_to_response.md")]
@@ -209,7 +211,7 @@ impl_to_schema_primitive!(chrono::NaiveDate, chrono::Duration, chrono::NaiveDate
 #[cfg(feature = "chrono")]
 impl<T: chrono::TimeZone> ToSchema for chrono::DateTime<T> {
     fn to_schema(_components: &mut Components) -> RefOr<schema::Schema> {
-        schema!(#[inline] DateTime<T>).into()
+        schema!(DateTime<T>).into()
     }
 }
 #[cfg(feature = "compact_str")]
@@ -232,60 +234,48 @@ impl_to_schema_primitive!(
 #[cfg(feature = "smallvec")]
 impl<T: ToSchema + smallvec::Array> ToSchema for smallvec::SmallVec<T> {
     fn to_schema(components: &mut Components) -> RefOr<schema::Schema> {
-        schema!(#[inline] smallvec::SmallVec<T>).into()
+        schema!(smallvec::SmallVec<T>).into()
     }
 }
 #[cfg(feature = "indexmap")]
 impl<K: ToSchema, V: ToSchema> ToSchema for indexmap::IndexMap<K, V> {
     fn to_schema(_components: &mut Components) -> RefOr<schema::Schema> {
-        schema!(#[inline] indexmap::IndexMap<K, V>).into()
+        schema!(indexmap::IndexMap<K, V>).into()
     }
 }
 
 impl<T: ToSchema> ToSchema for Vec<T> {
     fn to_schema(components: &mut Components) -> RefOr<schema::Schema> {
-        schema!(#[inline] Vec<T>).into()
+        schema!(Vec<T>).into()
     }
 }
 
 impl<T: ToSchema> ToSchema for LinkedList<T> {
     fn to_schema(components: &mut Components) -> RefOr<schema::Schema> {
-        schema!(#[inline] LinkedList<T>).into()
+        schema!(LinkedList<T>).into()
     }
 }
 
 impl<T: ToSchema> ToSchema for [T] {
     fn to_schema(components: &mut Components) -> RefOr<schema::Schema> {
-        schema!(
-            #[inline]
-            [T]
-        )
-        .into()
+        schema!([T]).into()
     }
 }
 impl<T: ToSchema, const N: usize> ToSchema for [T; N] {
     fn to_schema(components: &mut Components) -> RefOr<schema::Schema> {
-        schema!(
-            #[inline]
-            [T; N]
-        )
-        .into()
+        schema!([T; N]).into()
     }
 }
 
 impl<T: ToSchema> ToSchema for &[T] {
     fn to_schema(components: &mut Components) -> RefOr<schema::Schema> {
-        schema!(
-            #[inline]
-            &[T]
-        )
-        .into()
+        schema!(&[T]).into()
     }
 }
 
 impl<T: ToSchema> ToSchema for Option<T> {
     fn to_schema(components: &mut Components) -> RefOr<schema::Schema> {
-        schema!(#[inline] Option<T>).into()
+        schema!(Option<T>).into()
     }
 }
 
@@ -297,13 +287,13 @@ impl<T> ToSchema for PhantomData<T> {
 
 impl<K: ToSchema, V: ToSchema> ToSchema for BTreeMap<K, V> {
     fn to_schema(_components: &mut Components) -> RefOr<schema::Schema> {
-        schema!(#[inline]BTreeMap<K, V>).into()
+        schema!(BTreeMap<K, V>).into()
     }
 }
 
 impl<K: ToSchema, V: ToSchema> ToSchema for HashMap<K, V> {
     fn to_schema(_components: &mut Components) -> RefOr<schema::Schema> {
-        schema!(#[inline]HashMap<K, V>).into()
+        schema!(HashMap<K, V>).into()
     }
 }
 
@@ -362,7 +352,7 @@ impl ToSchema for serde_json::Value {
 }
 impl ToSchema for serde_json::Map<String, serde_json::Value> {
     fn to_schema(_components: &mut Components) -> RefOr<schema::Schema> {
-        schema!(#[inline]HashMap<K, V>).into()
+        schema!(HashMap<K, V>).into()
     }
 }
 
@@ -0,0 +1,78 @@
+//! Implements [OpenAPI Callback Object][callback] types.
+//!
+//! [callback]: https://spec.openapis.org/oas/latest.html#callback-object
+use std::ops::{Deref, DerefMut};
+
+use serde::{Deserialize, Serialize};
+
+use super::{PathItem, Paths};
+
+/// Implements [OpenAPI Callback Object][callback].
+///
+/// A callback describes the requests the API sends to the client out of band, like the notifications sent to an URL
+/// given by the client. The keys are the [runtime expressions][expression] of the URLs, like
+/// `{$request.body#/callbackUrl}`, and the values are the [`PathItem`]s of the requests.
+///
+/// [callback]: https://spec.openapis.org/oas/latest.html#callback-object
+/// [expression]: https://spec.openapis.org/oas/latest.html#runtime-expressions
+#[derive(Serialize, Deserialize, Default, Clone, PartialEq, Debug)]
+pub struct Callback(Paths);
+impl Deref for Callback {
+    type Target = Paths;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl DerefMut for Callback {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl Callback {
+    /// Construct a new empty [`Callback`]. This is effectively same as calling [`Callback::default`].
+    pub fn new() -> Self {
+        Default::default()
+    }
+    /// Inserts a runtime expression and [`PathItem`] pair into the instance and returns `self`.
+    pub fn path<K: Into<String>, V: Into<PathItem>>(mut self, expression: K, item: V) -> Self {
+        self.0.insert(expression, item);
+        self
+    }
+    /// Moves all elements from `other` into `self`, leaving `other` empty.
+    ///
+    /// The operations of the same expression are merged.
+    pub fn append(&mut self, other: &mut Callback) {
+        self.0.append(&mut other.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_json_diff::assert_json_eq;
+    use serde_json::json;
+
+    use super::*;
+    use crate::{Operation, PathItemType};
+
+    #[test]
+    fn test_build_callback() {
+        let mut callback = Callback::new().path(
+            "{$request.body#/callbackUrl}",
+            PathItem::new(PathItemType::Post, Operation::new().summary("created")),
+        );
+        callback.append(&mut Callback::new().path(
+            "{$request.body#/callbackUrl}",
+            PathItem::new(PathItemType::Delete, Operation::new().summary("deleted")),
+        ));
+        assert_json_eq!(
+            callback,
+            json!({
+                "{$request.body#/callbackUrl}": {
+                    "post": {"summary": "created", "responses": {}},
+                    "delete": {"summary": "deleted", "responses": {}}
+                }
+            })
+        );
+    }
+}
@@ -126,6 +126,24 @@ impl Components {
         self
     }
 
+    /// Resolve the reference to a schema registered in [`Components`], it is used to inline the schemas of fields.
+    ///
+    /// If `schema` is not a reference to a registered schema, it is returned as it is. The schema which is still
+    /// being registered, this happens to recursive types, is not resolved.
+    pub fn resolve_schema(&self, schema: RefOr<Schema>) -> RefOr<Schema> {
+        let RefOr::Ref(reference) = &schema else {
+            return schema;
+        };
+        match reference
+            .ref_location
+            .strip_prefix("#/components/schemas/")
+            .and_then(|name| self.schemas.get(name))
+        {
+            Some(resolved @ RefOr::Type(_)) => resolved.clone(),
+            _ => schema,
+        }
+    }
+
     /// Add a new response and returns `self`.
     pub fn response<S: Into<String>, R: Into<RefOr<Response>>>(
         mut self,
@@ -1,5 +1,6 @@
 //! Rust implementation of Openapi Spec V3.1.
 
+mod callback;
 mod components;
 mod content;
 mod encoding;
@@ -19,16 +20,26 @@ pub mod server;
 mod tag;
 mod xml;
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Formatter;
 use std::sync::LazyLock;
+use std::time::Duration;
 
+use etag::EntityTag;
 use regex::Regex;
+use salvo_core::http::StatusCode;
+#[cfg(feature = "gzip")]
+use salvo_core::http::StatusError;
+#[cfg(feature = "gzip")]
+use salvo_core::http::header::{ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_TYPE, VARY};
+use salvo_core::http::header::{CACHE_CONTROL, HeaderValue};
+use salvo_core::http::headers::{ETag, HeaderMapExt, IfNoneMatch};
 use salvo_core::{Depot, FlowCtrl, Handler, Router, async_trait, writing};
 use serde::de::{Error, Expected, Visitor};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 pub use self::{
+    callback::Callback,
     components::Components,
     content::Content,
     example::Example,
@@ -101,6 +112,13 @@ pub struct OpenApi {
     /// See more details at <https://spec.openapis.org/oas/latest.html#paths-object>.
     pub paths: Paths,
 
+    /// The incoming requests the API may send to the clients out of band, like the event notifications registered
+    /// by the clients. The keys are the names of the webhooks.
+    ///
+    /// See more details at <https://spec.openapis.org/oas/latest.html#oasWebhooks>.
+    #[serde(skip_serializing_if = "Paths::is_empty", default)]
+    pub webhooks: Paths,
+
     /// Holds various reusable schemas for the OpenAPI document.
     ///
     /// Few of these elements are security schemas and object schemas.
@@ -139,6 +157,41 @@ pub struct OpenApi {
     /// Optional extensions "x-something".
     #[serde(skip_serializing_if = "PropMap::is_empty", flatten)]
     pub extensions: PropMap<String, serde_json::Value>,
+
+    /// The max age of the `Cache-Control` header when the document is served, see [`OpenApi::cache_max_age`].
+    #[serde(skip)]
+    pub(crate) cache_max_age: Option<Duration>,
+
+    /// The gzipped documents which are served, keyed by their etags.
+    #[cfg(feature = "gzip")]
+    #[serde(skip)]
+    pub(crate) gzip_cache: GzipCache,
+
+    /// The handlers of the operation ids, which are used to find the operation ids used by several handlers.
+    #[serde(skip)]
+    pub(crate) operation_handlers: OperationHandlers,
+}
+
+/// The documents are the same if only their caches are different.
+#[cfg(feature = "gzip")]
+#[derive(Default, Clone, Debug)]
+pub(crate) struct GzipCache(
+    std::sync::Arc<parking_lot::Mutex<std::collections::HashMap<String, bytes::Bytes>>>,
+);
+#[cfg(feature = "gzip")]
+impl PartialEq for GzipCache {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+/// The documents are the same if only the handlers of their operation ids are different.
+#[derive(Default, Clone, Debug)]
+pub(crate) struct OperationHandlers(BTreeMap<String, &'static str>);
+impl PartialEq for OperationHandlers {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
 }
 
 impl OpenApi {
@@ -195,7 +248,7 @@ impl OpenApi {
 
     /// Merge `other` [`OpenApi`] consuming it and resuming it's content.
     ///
-    /// Merge function will take all `self` nonexistent _`servers`, `paths`, `schemas`, `responses`,
+    /// Merge function will take all `self` nonexistent _`servers`, `paths`, `webhooks`, `schemas`, `responses`,
     /// `security_schemes`, `security_requirements` and `tags`_ from _`other`_ [`OpenApi`].
     ///
     /// This function performs a shallow comparison for `paths`, `schemas`, `responses` and
@@ -209,6 +262,7 @@ impl OpenApi {
     pub fn merge(mut self, mut other: OpenApi) -> Self {
         self.servers.append(&mut other.servers);
         self.paths.append(&mut other.paths);
+        self.webhooks.append(&mut other.webhooks);
         self.components.append(&mut other.components);
         self.security.append(&mut other.security);
         self.tags.append(&mut other.tags);
@@ -250,6 +304,60 @@ impl OpenApi {
         self
     }
 
+    /// Add a webhook by name and returns `Self`, the [`PathItem`] describes the requests the API sends.
+    ///
+    /// The operations of the same name are merged.
+    pub fn add_webhook<N, I>(mut self, name: N, item: I) -> Self
+    where
+        N: Into<String>,
+        I: Into<PathItem>,
+    {
+        self.webhooks.insert(name.into(), item.into());
+        self
+    }
+    /// Add a webhook by name with the operation of the handler `H` which is defined by `#[endpoint]`, its
+    /// components are added too.
+    ///
+    /// The handler describes the request the API sends, it does not need to be added to a router:
+    ///
+    /// ```
+    /// # use salvo_core::prelude::*;
+    /// # use salvo_oapi::{OpenApi, PathItemType, ToSchema};
+    /// # use serde::Deserialize;
+    /// #[derive(Deserialize, ToSchema)]
+    /// struct TodoEvent {
+    ///     id: u64,
+    /// }
+    ///
+    /// /// Sent when a todo is created.
+    /// #[salvo_oapi::endpoint]
+    /// async fn todo_created(_event: salvo_oapi::extract::JsonBody<TodoEvent>) {}
+    ///
+    /// let doc = OpenApi::new("todos", "1.0.0").add_webhook_endpoint::<todo_created>("todoCreated", PathItemType::Post);
+    /// assert!(doc.webhooks.contains_key("todoCreated"));
+    /// ```
+    pub fn add_webhook_endpoint<H: 'static>(
+        mut self,
+        name: impl Into<String>,
+        item_type: PathItemType,
+    ) -> Self {
+        if let Some(creator) = crate::EndpointRegistry::find(&std::any::TypeId::of::<H>()) {
+            let Endpoint {
+                operation,
+                mut components,
+            } = (creator)();
+            self.components.append(&mut components);
+            self.webhooks
+                .insert(name.into(), PathItem::new(item_type, operation));
+        } else {
+            tracing::warn!(
+                handler = std::any::type_name::<H>(),
+                "webhook handler is not defined by `#[endpoint]`"
+            );
+        }
+        self
+    }
+
     /// Add [`Components`] to configure reusable schemas.
     pub fn components(mut self, components: impl Into<Components>) -> Self {
         self.components = components.into();
@@ -404,6 +512,25 @@ impl OpenApi {
         self
     }
 
+    /// Set the max age of the `Cache-Control` header when the document is served by its [`Handler`], the default
+    /// is `no-cache`.
+    ///
+    /// The clients use the cached document without revalidating it until the max age elapses, so a long max age
+    /// suits the documents which are only changed by deployments, and the clients may see an old document for
+    /// that long after a deployment. Pass `None` to send `no-cache` again.
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use salvo_oapi::OpenApi;
+    /// let router = OpenApi::new("pet api", "0.1.0")
+    ///     .cache_max_age(Duration::from_secs(3600))
+    ///     .into_router("/api-doc/openapi.json");
+    /// ```
+    pub fn cache_max_age(mut self, max_age: impl Into<Option<Duration>>) -> Self {
+        self.cache_max_age = max_age.into();
+        self
+    }
+
     /// Add openapi extension (`x-something`) for [`OpenApi`].
     pub fn add_extension<K: Into<String>>(mut self, key: K, value: serde_json::Value) -> Self {
         self.extensions.insert(key.into(), value);
@@ -416,10 +543,35 @@ impl OpenApi {
     }
 
     /// Consusmes the [`OpenApi`] and informations from a [`Router`].
+    ///
+    /// It can be chained to merge several routers, see also [`OpenApi::merge_routers`].
     pub fn merge_router(self, router: &Router) -> Self {
         self.merge_router_with_base(router, "/")
     }
 
+    /// Consusmes the [`OpenApi`] and informations from several [`Router`]s.
+    ///
+    /// This is useful when routes are split across modules, each module builds its own
+    /// [`Router`], and there is no need to build a single router only for the documentation.
+    /// Components shared by the routers are only added once. If several routers contain the same
+    /// operation of the same path, the first one is kept.
+    ///
+    /// ```
+    /// # use salvo_core::prelude::*;
+    /// # use salvo_oapi::OpenApi;
+    /// let users = Router::with_path("users");
+    /// let todos = Router::with_path("todos");
+    /// let doc = OpenApi::new("my application", "0.1.0").merge_routers([&users, &todos]);
+    /// ```
+    pub fn merge_routers<'a, I>(self, routers: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Router>,
+    {
+        routers
+            .into_iter()
+            .fold(self, |doc, router| doc.merge_router(router))
+    }
+
     /// Consusmes the [`OpenApi`] and informations from a [`Router`] with base path.
     pub fn merge_router_with_base(mut self, router: &Router, base: impl AsRef<str>) -> Self {
         let mut node = NormNode::new(router, Default::default());
@@ -465,6 +617,35 @@ impl OpenApi {
                 operation
                     .securities
                     .extend(node.metadata.securities.iter().cloned());
+                if operation.request_body.is_some() {
+                    if let Some(size) = node.metadata.max_body_size {
+                        operation
+                            .extensions
+                            .insert("x-max-body-size".to_owned(), size.into());
+                        if !operation.responses.contains_key("413") {
+                            operation.responses.insert(
+                                "413",
+                                Response::new(format!(
+                                    "Payload Too Large, the request body is larger than {size} bytes."
+                                )),
+                            );
+                        }
+                    }
+                    if let Some(timeout) = node.metadata.body_read_timeout {
+                        let millis = u64::try_from(timeout.as_millis()).unwrap_or(u64::MAX);
+                        operation
+                            .extensions
+                            .insert("x-body-read-timeout-ms".to_owned(), millis.into());
+                        if !operation.responses.contains_key("408") {
+                            operation.responses.insert(
+                                "408",
+                                Response::new(format!(
+                                    "Request Timeout, the request body is not received in {millis} milliseconds."
+                                )),
+                            );
+                        }
+                    }
+                }
                 let methods = if let Some(method) = &node.method {
                     vec![*method]
                 } else {
@@ -501,6 +682,9 @@ impl OpenApi {
                 if !meta_not_exist_parameters.is_empty() {
                     tracing::warn!(parameters = ?meta_not_exist_parameters, path, handler_name = node.handler_type_name, "parameters information not provided");
                 }
+                if let Some(handler_name) = node.handler_type_name {
+                    self.dedup_operation_id(&mut operation, handler_name);
+                }
                 let path_item = self.paths.entry(path.clone()).or_default();
                 for method in methods {
                     if path_item.operations.contains_key(&method) {
@@ -520,8 +704,54 @@ impl OpenApi {
             self.merge_norm_node(child, &path);
         }
     }
+
+    // The default operation id is the function name of the handler, so the handlers with the same name in different
+    // modules get the same id. The later ones fall back to the module qualified id to keep the ids unique.
+    fn dedup_operation_id(&mut self, operation: &mut Operation, handler_name: &'static str) {
+        let Some(operation_id) = operation.operation_id.clone() else {
+            return;
+        };
+        match self.operation_handlers.0.get(&operation_id) {
+            None => {
+                self.operation_handlers.0.insert(operation_id, handler_name);
+            }
+            Some(exist_name) if *exist_name != handler_name => {
+                if handler_name.rsplit("::").next() == Some(&*operation_id) {
+                    let qualified_id = handler_name.replace("::", ".");
+                    tracing::warn!(
+                        operation_id,
+                        qualified_id,
+                        "operation id is already used by `{exist_name}`, the module qualified id is used"
+                    );
+                    operation.operation_id = Some(qualified_id.clone());
+                    self.operation_handlers.0.insert(qualified_id, handler_name);
+                } else {
+                    tracing::warn!(
+                        operation_id,
+                        handler_name,
+                        "operation id is already used by `{exist_name}`"
+                    );
+                }
+            }
+            Some(_) => {}
+        }
+    }
 }
 
+/// Writes the document as json, `?pretty` writes it pretty-printed.
+///
+/// The response has an `ETag` computed from the written json, and `Cache-Control: no-cache`, so the clients like
+/// Swagger UI, which fetch the document repeatedly, revalidate it with `If-None-Match` and get `304 Not Modified`
+/// until the document is changed. By default max age is not set, so a new document is fetched right after it is
+/// deployed, use [`OpenApi::cache_max_age`] to send `Cache-Control: public, max-age=...` instead.
+///
+/// With the `gzip` feature, the document is gzipped if the request accepts it by `Accept-Encoding`, so large
+/// documents are served compressed without a [`Compression`] middleware for all the routes. The gzipped document
+/// has its own `ETag`, with a `-gzip` suffix, and `Vary: Accept-Encoding`. The document is compressed once, and the
+/// gzipped bytes are reused until the document is changed. A `Compression` middleware skips the response, since it
+/// has a `Content-Encoding` already.
+///
+/// [`Compression`]: https://docs.rs/salvo-compression
 #[async_trait]
 impl Handler for OpenApi {
     async fn handle(
@@ -541,7 +771,99 @@ impl Handler for OpenApi {
         } else {
             self.to_json().unwrap_or_default()
         };
-        res.render(writing::Text::Json(&content));
+        #[cfg(feature = "gzip")]
+        let gzip = accepts_gzip(req);
+        #[cfg(not(feature = "gzip"))]
+        let gzip = false;
+        let tag = content_tag(&content, gzip);
+        let etag: ETag = tag.parse().expect("content etag should be valid");
+        let not_modified = req
+            .headers()
+            .typed_get::<IfNoneMatch>()
+            .is_some_and(|if_none_match| !if_none_match.precondition_passes(&etag));
+        res.headers_mut().typed_insert(etag);
+        let cache_control = match self.cache_max_age {
+            Some(max_age) => {
+                HeaderValue::from_str(&format!("public, max-age={}", max_age.as_secs()))
+                    .expect("cache control should be valid")
+            }
+            None => HeaderValue::from_static("no-cache"),
+        };
+        res.headers_mut().insert(CACHE_CONTROL, cache_control);
+        #[cfg(feature = "gzip")]
+        res.headers_mut()
+            .insert(VARY, HeaderValue::from_static("accept-encoding"));
+        if not_modified {
+            res.status_code(StatusCode::NOT_MODIFIED);
+        } else if gzip {
+            #[cfg(feature = "gzip")]
+            render_gzip(res, &content, &tag, &self.gzip_cache);
+        } else {
+            res.render(writing::Text::Json(&content));
+        }
+    }
+}
+
+#[cfg(feature = "gzip")]
+fn accepts_gzip(req: &salvo_core::Request) -> bool {
+    req.headers()
+        .get(ACCEPT_ENCODING)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|header| {
+            // `*` only applies to the encodings which are not listed, like `gzip;q=0, *`.
+            let encodings = salvo_core::http::parse_accept_encoding(header);
+            match encodings.iter().find(|(algo, _)| algo == "gzip") {
+                Some((_, q)) => *q > 0,
+                None => encodings.iter().any(|(algo, q)| algo == "*" && *q > 0),
+            }
+        })
+}
+
+#[cfg(feature = "gzip")]
+fn render_gzip(res: &mut salvo_core::Response, content: &str, tag: &str, cache: &GzipCache) {
+    use std::io::Write;
+
+    let cached = cache.0.lock().get(tag).cloned();
+    let data = match cached {
+        Some(data) => Ok(data),
+        None => {
+            let mut encoder =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder
+                .write_all(content.as_bytes())
+                .and_then(|_| encoder.finish())
+                .map(|data| {
+                    let data = bytes::Bytes::from(data);
+                    cache.0.lock().insert(tag.to_owned(), data.clone());
+                    data
+                })
+        }
+    };
+    match data {
+        Ok(data) => {
+            res.headers_mut().insert(
+                CONTENT_TYPE,
+                HeaderValue::from_static("application/json; charset=utf-8"),
+            );
+            res.headers_mut()
+                .insert(CONTENT_ENCODING, HeaderValue::from_static("gzip"));
+            res.body(data);
+        }
+        Err(e) => {
+            tracing::error!(error = ?e, "gzip openapi document failed");
+            res.render(StatusError::internal_server_error().cause(e));
+        }
+    }
+}
+
+// The content is only known after it is serialized, so it is hashed instead of using a version. The gzipped content
+// is another representation, so it has another etag.
+fn content_tag(content: &str, gzip: bool) -> String {
+    let etag = EntityTag::from_data(content.as_bytes());
+    if gzip {
+        format!("\"{}-gzip\"", etag.tag())
+    } else {
+        etag.to_string()
     }
 }
 /// Represents available [OpenAPI versions][version].
@@ -1039,7 +1361,7 @@ mod tests {
                           "get": {
                              "summary": "Get pet by id",
                              "description": "Get pet from database by pet database id",
-                             "operationId": "salvo_oapi.openapi.tests.test_simple_document_with_security.get_pet_by_id",
+                             "operationId": "get_pet_by_id",
                              "parameters": [
                                 {
                                    "name": "pet_id",
@@ -1153,6 +1475,32 @@ mod tests {
             .into_router("/openapi/doc");
     }
 
+    #[test]
+    fn test_openapi_webhooks() {
+        let doc = OpenApi::new("pet api", "0.1.0")
+            .add_webhook(
+                "petAdopted",
+                PathItem::new(PathItemType::Post, Operation::new().summary("adopted")),
+            )
+            .merge(OpenApi::new("other", "0.1.0").add_webhook(
+                "petAdopted",
+                PathItem::new(PathItemType::Delete, Operation::new()),
+            ));
+        let value = serde_json::to_value(&doc).unwrap();
+        assert_eq!(
+            value["webhooks"],
+            json!({
+                "petAdopted": {
+                    "post": {"summary": "adopted", "responses": {}},
+                    "delete": {"responses": {}}
+                }
+            })
+        );
+
+        let value = serde_json::to_value(OpenApi::new("pet api", "0.1.0")).unwrap();
+        assert!(value.get("webhooks").is_none());
+    }
+
     #[test]
     fn test_openapi_to_pretty_json() -> Result<(), serde_json::Error> {
         let raw_json = r#"{
@@ -1271,6 +1619,167 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_openapi_handle_etag() {
+        let doc = OpenApi::new("pet api", "0.1.0");
+        let mut req = Request::new();
+        let mut depot = Depot::new();
+        let mut res = salvo_core::Response::new();
+        let mut ctrl = FlowCtrl::default();
+        doc.handle(&mut req, &mut depot, &mut res, &mut ctrl).await;
+        let etag = res.headers()[salvo_core::http::header::ETAG].clone();
+        assert_eq!(res.headers()[CACHE_CONTROL], "no-cache");
+
+        let mut req = Request::new();
+        req.headers_mut()
+            .insert(salvo_core::http::header::IF_NONE_MATCH, etag.clone());
+        let mut res = salvo_core::Response::new();
+        doc.handle(&mut req, &mut depot, &mut res, &mut ctrl).await;
+        assert_eq!(res.status_code, Some(StatusCode::NOT_MODIFIED));
+        assert!(res.body.is_none());
+        assert_eq!(res.headers()[salvo_core::http::header::ETAG], etag);
+
+        let doc = OpenApi::new("pet api", "0.2.0");
+        let mut res = salvo_core::Response::new();
+        doc.handle(&mut req, &mut depot, &mut res, &mut ctrl).await;
+        assert_eq!(res.status_code, None);
+        assert_ne!(res.headers()[salvo_core::http::header::ETAG], etag);
+
+        let doc = doc.cache_max_age(std::time::Duration::from_secs(3600));
+        let mut res = salvo_core::Response::new();
+        doc.handle(&mut req, &mut depot, &mut res, &mut ctrl).await;
+        assert_eq!(res.headers()[CACHE_CONTROL], "public, max-age=3600");
+        assert_eq!(
+            doc.to_json().unwrap(),
+            OpenApi::new("pet api", "0.2.0").to_json().unwrap()
+        );
+    }
+
+    #[cfg(feature = "gzip")]
+    #[tokio::test]
+    async fn test_openapi_handle_gzip() {
+        use std::io::Read;
+
+        use salvo_core::http::header::{ACCEPT_ENCODING, CONTENT_ENCODING, ETAG, IF_NONE_MATCH};
+
+        let doc = OpenApi::new("pet api", "0.1.0");
+        let mut req = Request::new();
+        req.headers_mut().insert(
+            ACCEPT_ENCODING,
+            HeaderValue::from_static("br;q=1.0, gzip;q=0.8"),
+        );
+        let mut depot = Depot::new();
+        let mut res = salvo_core::Response::new();
+        let mut ctrl = FlowCtrl::default();
+        doc.handle(&mut req, &mut depot, &mut res, &mut ctrl).await;
+        assert_eq!(res.headers()[CONTENT_ENCODING], "gzip");
+        assert_eq!(
+            res.headers()[salvo_core::http::header::VARY],
+            "accept-encoding"
+        );
+        assert_eq!(
+            res.content_type().unwrap().to_string(),
+            "application/json; charset=utf-8"
+        );
+        let etag = res.headers()[ETAG].clone();
+        assert!(etag.to_str().unwrap().ends_with("-gzip\""));
+
+        let bytes = match res.body.take() {
+            ResBody::Once(bytes) => bytes,
+            _ => Bytes::new(),
+        };
+        let mut json = String::new();
+        flate2::read::GzDecoder::new(&bytes[..])
+            .read_to_string(&mut json)
+            .unwrap();
+        assert_eq!(json, doc.to_json().unwrap());
+
+        req.headers_mut().insert(IF_NONE_MATCH, etag.clone());
+        let mut res = salvo_core::Response::new();
+        doc.handle(&mut req, &mut depot, &mut res, &mut ctrl).await;
+        assert_eq!(res.status_code, Some(StatusCode::NOT_MODIFIED));
+
+        let mut req = Request::new();
+        req.headers_mut()
+            .insert(ACCEPT_ENCODING, HeaderValue::from_static("gzip;q=0"));
+        req.headers_mut().insert(IF_NONE_MATCH, etag.clone());
+        let mut res = salvo_core::Response::new();
+        doc.handle(&mut req, &mut depot, &mut res, &mut ctrl).await;
+        assert_eq!(res.status_code, None);
+        assert!(res.headers().get(CONTENT_ENCODING).is_none());
+        assert_ne!(res.headers()[ETAG], etag);
+        assert_eq!(doc.gzip_cache.0.lock().len(), 1);
+
+        for (header, gzip) in [("gzip;q=0, *", false), ("*", true), ("br, *;q=0", false)] {
+            let mut req = Request::new();
+            req.headers_mut()
+                .insert(ACCEPT_ENCODING, HeaderValue::from_static(header));
+            assert_eq!(accepts_gzip(&req), gzip, "{header}");
+        }
+    }
+
+    #[test]
+    fn test_openapi_response_headers() {
+        #[salvo_oapi::endpoint(
+            responses(
+                (status_code = 201, description = "Todo created",
+                    headers(("location" = String, description = "URL of created todo"))
+                ),
+            ),
+            status_codes(201, 409)
+        )]
+        async fn create_todo(res: &mut salvo_core::Response) -> Result<StatusCode, StatusError> {
+            res.add_header("location", "/todos/1", true)
+                .map_err(|_| StatusError::internal_server_error())?;
+            Ok(StatusCode::CREATED)
+        }
+
+        let router = Router::with_path("todos").post(create_todo);
+        let doc = OpenApi::new("todo api", "0.1.0").merge_router(&router);
+        let responses =
+            &serde_json::to_value(&doc).unwrap()["paths"]["/todos"]["post"]["responses"];
+        assert_eq!(
+            responses["201"],
+            json!({
+                "description": "Todo created",
+                "headers": {
+                    "location": {
+                        "schema": {
+                            "type": "string"
+                        },
+                        "description": "URL of created todo"
+                    }
+                }
+            })
+        );
+        assert!(responses["409"].is_object());
+    }
+
+    #[test]
+    fn test_openapi_created_response() {
+        use salvo_core::writing::Created;
+
+        #[salvo_oapi::endpoint]
+        async fn create_todo() -> Result<Created<Json<String>>, StatusError> {
+            Ok(Created::at("/todos/1").body(Json("write docs".into())))
+        }
+
+        let router = Router::with_path("todos").post(create_todo);
+        let doc = OpenApi::new("todo api", "0.1.0").merge_router(&router);
+        let responses =
+            &serde_json::to_value(&doc).unwrap()["paths"]["/todos"]["post"]["responses"];
+        assert!(responses.get("200").is_none());
+        assert_eq!(
+            responses["201"]["headers"]["location"]["schema"],
+            json!({"type": "string"})
+        );
+        assert_eq!(
+            responses["201"]["content"]["application/json"]["schema"],
+            json!({"type": "string"})
+        );
+        assert!(responses["500"].is_object());
+    }
+
     #[test]
     fn test_openapi_schema_work_with_generics() {
         #[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
@@ -1468,4 +1977,150 @@ mod tests {
             Value::from_str(&doc.to_json().unwrap()).unwrap()
         );
     }
+
+    #[test]
+    fn test_openapi_merge_routers() {
+        #[derive(Serialize, Deserialize, Debug, ToSchema)]
+        struct Todo {
+            id: u64,
+        }
+
+        #[salvo_oapi::endpoint]
+        async fn list_todos() -> Json<Vec<Todo>> {
+            Json(vec![])
+        }
+        #[salvo_oapi::endpoint]
+        async fn show_todo() -> Json<Todo> {
+            Json(Todo { id: 1 })
+        }
+        #[salvo_oapi::endpoint]
+        async fn list_users() -> &'static str {
+            "users"
+        }
+
+        let todos = Router::with_path("todos")
+            .get(list_todos)
+            .push(Router::with_path("{id}").get(show_todo));
+        let users = Router::with_path("users").get(list_users);
+        let admin = Router::with_path("admin/todos").get(list_todos);
+
+        let doc = OpenApi::new("my application", "0.1.0").merge_routers([&todos, &users, &admin]);
+        let chained = OpenApi::new("my application", "0.1.0")
+            .merge_router(&todos)
+            .merge_router(&users)
+            .merge_router(&admin);
+        assert_eq!(doc, chained);
+
+        // With `preserve-path-order` the paths keep the order of merging, so sort them.
+        let mut paths = doc.paths.keys().collect::<Vec<_>>();
+        paths.sort();
+        assert_eq!(paths, ["/admin/todos", "/todos", "/todos/{id}", "/users"]);
+        assert_eq!(
+            doc.components.schemas.keys().collect::<Vec<_>>(),
+            ["salvo_oapi.openapi.tests.test_openapi_merge_routers.Todo"]
+        );
+    }
+
+    #[test]
+    fn test_openapi_body_limits() {
+        use std::time::Duration;
+
+        use crate::RouterExt;
+
+        #[derive(Serialize, Deserialize, Debug, ToSchema)]
+        struct Todo {
+            id: u64,
+        }
+
+        #[salvo_oapi::endpoint]
+        async fn list_todos() -> Json<Vec<Todo>> {
+            Json(vec![])
+        }
+        #[salvo_oapi::endpoint]
+        async fn create_todo(todo: JsonBody<Todo>) -> Json<Todo> {
+            Json(todo.into_inner())
+        }
+        #[salvo_oapi::endpoint(responses((status_code = 413, description = "Todo is too large.")))]
+        async fn update_todo(todo: JsonBody<Todo>) -> Json<Todo> {
+            Json(todo.into_inner())
+        }
+
+        let router = Router::with_path("todos")
+            .oapi_max_body_size(1024 * 16)
+            .oapi_body_read_timeout(Duration::from_secs(10))
+            .get(list_todos)
+            .post(create_todo)
+            .push(
+                Router::with_path("{id}")
+                    .oapi_max_body_size(1024 * 64)
+                    .put(update_todo),
+            );
+        let doc = OpenApi::new("my application", "0.1.0").merge_router(&router);
+
+        let list = &doc.paths["/todos"].operations[&PathItemType::Get];
+        assert!(list.extensions.is_empty());
+        assert!(!list.responses.contains_key("413"));
+
+        let value =
+            serde_json::to_value(&doc.paths["/todos"].operations[&PathItemType::Post]).unwrap();
+        assert_eq!(value["x-max-body-size"], 1024 * 16);
+        assert_eq!(value["x-body-read-timeout-ms"], 10_000);
+        assert_eq!(
+            value["responses"]["413"]["description"],
+            "Payload Too Large, the request body is larger than 16384 bytes."
+        );
+        assert_eq!(
+            value["responses"]["408"]["description"],
+            "Request Timeout, the request body is not received in 10000 milliseconds."
+        );
+
+        let value =
+            serde_json::to_value(&doc.paths["/todos/{id}"].operations[&PathItemType::Put]).unwrap();
+        assert_eq!(value["x-max-body-size"], 1024 * 64);
+        assert_eq!(
+            value["responses"]["413"]["description"],
+            "Todo is too large."
+        );
+    }
+
+    #[tokio::test]
+    async fn test_router_max_body_size() {
+        use std::time::Duration;
+
+        use salvo_core::test::TestClient;
+
+        use crate::RouterExt;
+
+        #[derive(Serialize, Deserialize, Debug, ToSchema)]
+        struct Todo {
+            id: u64,
+        }
+
+        #[salvo_oapi::endpoint]
+        async fn create_todo(todo: JsonBody<Todo>) -> Json<Todo> {
+            Json(todo.into_inner())
+        }
+
+        let router = Router::with_path("todos")
+            .max_body_size(16)
+            .body_read_timeout(Duration::from_secs(10))
+            .post(create_todo);
+        let doc = OpenApi::new("my application", "0.1.0").merge_router(&router);
+        let value =
+            serde_json::to_value(&doc.paths["/todos"].operations[&PathItemType::Post]).unwrap();
+        assert_eq!(value["x-max-body-size"], 16);
+        assert_eq!(value["x-body-read-timeout-ms"], 10_000);
+
+        let service = Service::new(router);
+        let res = TestClient::post("http://127.0.0.1:5800/todos")
+            .json(&json!({"id": 1}))
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+        let res = TestClient::post("http://127.0.0.1:5800/todos")
+            .json(&json!({"id": 1, "title": "a too large todo"}))
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::PAYLOAD_TOO_LARGE));
+    }
 }
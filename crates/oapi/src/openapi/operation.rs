@@ -6,7 +6,7 @@ use std::ops::{Deref, DerefMut};
 use serde::{Deserialize, Serialize};
 
 use super::{
-    Deprecated, ExternalDocs, RefOr, SecurityRequirement, Server,
+    Callback, Deprecated, ExternalDocs, RefOr, SecurityRequirement, Server,
     request_body::RequestBody,
     response::{Response, Responses},
 };
@@ -143,9 +143,9 @@ pub struct Operation {
     /// List of possible responses returned by the [`Operation`].
     pub responses: Responses,
 
-    /// Callback information.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub callbacks: Option<String>,
+    /// Map of the out of band [`Callback`]s related to this [`Operation`], the keys are the names of the callbacks.
+    #[serde(skip_serializing_if = "PropMap::is_empty", default)]
+    pub callbacks: PropMap<String, Callback>,
 
     /// Define whether the operation is deprecated or not and thus should be avoided consuming.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -247,6 +247,17 @@ impl Operation {
         self
     }
 
+    /// Append a [`Callback`] by name to the [`Operation`] callbacks and returns `Self`.
+    ///
+    /// The callbacks of the same name are merged.
+    pub fn add_callback<S: Into<String>>(mut self, name: S, mut callback: Callback) -> Self {
+        self.callbacks
+            .entry(name.into())
+            .or_default()
+            .append(&mut callback);
+        self
+    }
+
     /// Add or change deprecated status of the [`Operation`].
     pub fn deprecated<D: Into<Deprecated>>(mut self, deprecated: D) -> Self {
         self.deprecated = Some(deprecated.into());
@@ -310,7 +321,7 @@ mod tests {
         assert!(operation.parameters.is_empty());
         assert!(operation.request_body.is_none());
         assert!(operation.responses.is_empty());
-        assert!(operation.callbacks.is_none());
+        assert!(operation.callbacks.is_empty());
         assert!(operation.deprecated.is_none());
         assert!(operation.securities.is_empty());
         assert!(operation.servers.is_empty());
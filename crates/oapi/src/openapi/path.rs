@@ -30,6 +30,10 @@ impl Paths {
     pub fn new() -> Self {
         Default::default()
     }
+    /// Returns `true` if instance contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
     /// Inserts a key-value pair into the instance and returns `self`.
     pub fn path<K: Into<String>, V: Into<PathItem>>(mut self, key: K, value: V) -> Self {
         self.insert(key, value);
@@ -1,9 +1,11 @@
 use std::any::TypeId;
 use std::collections::{BTreeSet, HashMap};
 use std::sync::{LazyLock, RwLock};
+use std::time::Duration;
 
 use regex::Regex;
 use salvo_core::Router;
+use salvo_core::http::request::{BodyReadTimeout, SecureMaxSize};
 
 use crate::{SecurityRequirement, path::PathItemType};
 
@@ -33,6 +35,13 @@ impl NormNode {
             node.metadata
                 .securities
                 .extend(metadata.securities.iter().cloned());
+            // The size and the timeout closest to the handler win.
+            if metadata.max_body_size.is_some() {
+                node.metadata.max_body_size = metadata.max_body_size;
+            }
+            if metadata.body_read_timeout.is_some() {
+                node.metadata.body_read_timeout = metadata.body_read_timeout;
+            }
         }
 
         let regex = Regex::new(r#"<([^/:>]+)(:[^>]*)?>"#).expect("invalid regex");
@@ -106,6 +115,44 @@ pub trait RouterExt {
     where
         I: IntoIterator<Item = V>,
         V: Into<String>;
+
+    /// Document the maximum size of the request body in bytes, like the size set by `size_limiter::max_size`.
+    ///
+    /// The endpoints with a request body in the router and it's descents get the `x-max-body-size` extension and a
+    /// `413 Payload Too Large` response, if the response is not documented by the endpoint. If it is set in a router
+    /// and it's ancestors, the one closest to the endpoints takes effect.
+    ///
+    /// The size limiters are not detected from the hoops, use [`RouterExt::max_body_size`] to add the limiter and
+    /// document it at once, or call this with the same size when adding another limiter:
+    ///
+    /// ```
+    /// # use salvo_core::prelude::*;
+    /// # use salvo_oapi::RouterExt;
+    /// // The same size as `size_limiter::max_size(1024 * 16)` added to the router.
+    /// let router = Router::with_path("todos").oapi_max_body_size(1024 * 16);
+    /// ```
+    fn oapi_max_body_size(self, size: u64) -> Self;
+
+    /// Limit the size of the request body in bytes with [`SecureMaxSize`], and document it like
+    /// [`RouterExt::oapi_max_body_size`].
+    ///
+    /// ```
+    /// # use salvo_core::prelude::*;
+    /// # use salvo_oapi::RouterExt;
+    /// let router = Router::with_path("todos").max_body_size(1024 * 16);
+    /// ```
+    fn max_body_size(self, size: usize) -> Self;
+
+    /// Document the timeout of reading the request body, like the timeout set by `BodyReadTimeout`.
+    ///
+    /// The endpoints with a request body in the router and it's descents get the `x-body-read-timeout-ms`
+    /// extension and a `408 Request Timeout` response, if the response is not documented by the endpoint. If it is
+    /// set in a router and it's ancestors, the one closest to the endpoints takes effect.
+    fn oapi_body_read_timeout(self, timeout: Duration) -> Self;
+
+    /// Limit the time of reading the request body with [`BodyReadTimeout`], and document it like
+    /// [`RouterExt::oapi_body_read_timeout`].
+    fn body_read_timeout(self, timeout: Duration) -> Self;
 }
 
 impl RouterExt for Router {
@@ -148,6 +195,30 @@ impl RouterExt for Router {
         metadata.tags.extend(iter.into_iter().map(Into::into));
         self
     }
+    fn oapi_max_body_size(self, size: u64) -> Self {
+        let mut guard = METADATA_REGISTRY
+            .write()
+            .expect("failed to lock METADATA_REGISTRY for write");
+        let metadata = guard.entry(self.id).or_default();
+        metadata.max_body_size = Some(size);
+        self
+    }
+    fn max_body_size(self, size: usize) -> Self {
+        self.hoop(SecureMaxSize::new(size))
+            .oapi_max_body_size(size as u64)
+    }
+    fn oapi_body_read_timeout(self, timeout: Duration) -> Self {
+        let mut guard = METADATA_REGISTRY
+            .write()
+            .expect("failed to lock METADATA_REGISTRY for write");
+        let metadata = guard.entry(self.id).or_default();
+        metadata.body_read_timeout = Some(timeout);
+        self
+    }
+    fn body_read_timeout(self, timeout: Duration) -> Self {
+        self.hoop(BodyReadTimeout::new(timeout))
+            .oapi_body_read_timeout(timeout)
+    }
 }
 
 #[non_exhaustive]
@@ -155,4 +226,6 @@ impl RouterExt for Router {
 pub(crate) struct Metadata {
     pub(crate) tags: BTreeSet<String>,
     pub(crate) securities: Vec<SecurityRequirement>,
+    pub(crate) max_body_size: Option<u64>,
+    pub(crate) body_read_timeout: Option<Duration>,
 }
@@ -156,6 +156,10 @@ pub struct Config<'a> {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) persist_authorization: Option<bool>,
 
+    /// Syntax highlighting of the request and response examples.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) syntax_highlight: Option<SyntaxHighlight>,
+
     /// [`oauth::Config`] the Swagger UI is using for auth flow.
     #[serde(skip)]
     pub(crate) oauth: Option<oauth::Config>,
@@ -179,7 +183,7 @@ impl<'a> Config<'a> {
         }
     }
 
-    fn new_config_with_multiple_urls(urls: Vec<Url<'a>>) -> Self {
+    pub(crate) fn new_config_with_multiple_urls(urls: Vec<Url<'a>>) -> Self {
         let primary_name = urls.iter().find(|url| url.primary).map(|url| url.name.to_string());
 
         Self {
@@ -440,7 +444,7 @@ impl<'a> Config<'a> {
     /// ```rust
     /// # use salvo_oapi::swagger_ui::Config;
     /// let config = Config::new(["/api-docs/openapi.json"])
-    ///     .doc_expansion(r#"["list"*, "full", "none"]"#);
+    ///     .doc_expansion("none");
     /// ```
     pub fn doc_expansion<S: Into<String>>(mut self, doc_expansion: S) -> Self {
         self.doc_expansion = Some(doc_expansion.into());
@@ -694,6 +698,61 @@ impl<'a> Config<'a> {
 
         self
     }
+
+    /// Set `syntax_highlight` to enable or disable syntax highlighting of the request and response
+    /// examples.
+    ///
+    /// Syntax highlighting is enabled by default, disabling it can improve the performance of
+    /// large payloads.
+    ///
+    /// # Examples
+    ///
+    /// Disable the syntax highlighting.
+    /// ```rust
+    /// # use salvo_oapi::swagger_ui::Config;
+    /// let config = Config::new(["/api-docs/openapi.json"])
+    ///     .syntax_highlight(false);
+    /// ```
+    pub fn syntax_highlight(mut self, activated: bool) -> Self {
+        self.syntax_highlight.get_or_insert_with(Default::default).activated = activated;
+
+        self
+    }
+
+    /// Set `syntax_highlight_theme` to change the theme of syntax highlighting.
+    ///
+    /// Available themes are `agate`, `arta`, `monokai`, `nord`, `obsidian`, `tomorrow-night`
+    /// and `idea`. Default theme is `agate`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use salvo_oapi::swagger_ui::Config;
+    /// let config = Config::new(["/api-docs/openapi.json"])
+    ///     .syntax_highlight_theme("monokai");
+    /// ```
+    pub fn syntax_highlight_theme<S: Into<String>>(mut self, theme: S) -> Self {
+        self.syntax_highlight.get_or_insert_with(Default::default).theme = Some(theme.into());
+
+        self
+    }
+}
+
+/// Swagger UI `syntaxHighlight` options.
+#[derive(Serialize, Clone, Debug)]
+pub(crate) struct SyntaxHighlight {
+    pub(crate) activated: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) theme: Option<String>,
+}
+
+impl Default for SyntaxHighlight {
+    fn default() -> Self {
+        Self {
+            activated: true,
+            theme: None,
+        }
+    }
 }
 
 impl Default for Config<'_> {
@@ -724,6 +783,7 @@ impl Default for Config<'_> {
             validator_url: Default::default(),
             with_credentials: Default::default(),
             persist_authorization: Default::default(),
+            syntax_highlight: Default::default(),
             oauth: Default::default(),
             layout: SWAGGER_STANDALONE_LAYOUT,
         }
@@ -75,6 +75,25 @@ const INDEX_TMPL: &str = r#"
 "#;
 
 /// Implements [`Handler`] for serving Swagger UI.
+///
+/// The page can be customized with the builder methods, they are mapped to the
+/// [Swagger UI configuration](https://github.com/swagger-api/swagger-ui/blob/master/docs/usage/configuration.md)
+/// options, the other options can be set with [`Config`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use salvo_oapi::swagger_ui::{SwaggerUi, oauth};
+/// let swagger = SwaggerUi::new("/api-doc/openapi.json")
+///     .title("Todos API")
+///     .deep_linking(true)
+///     .doc_expansion("list")
+///     .default_models_expand_depth(-1)
+///     .try_it_out_enabled(true)
+///     .persist_authorization(true)
+///     .syntax_highlight_theme("monokai")
+///     .oauth(oauth::Config::new().client_id("todos-client").app_name("Todos"));
+/// ```
 #[derive(Clone, Debug)]
 pub struct SwaggerUi {
     config: Config<'static>,
@@ -106,6 +125,45 @@ impl SwaggerUi {
         }
     }
 
+    /// Create a new [`SwaggerUi`] serving several api docs, they can be switched with the spec
+    /// dropdown in the top bar.
+    ///
+    /// Each item is converted into a [`Url`], so it can be a `(name, url)` tuple. The first doc is
+    /// shown by default, use [`Url::with_primary`] to choose another one.
+    ///
+    /// # Examples
+    ///
+    /// Serve the docs of a versioned api.
+    /// ```rust
+    /// # use salvo_core::prelude::*;
+    /// # use salvo_oapi::OpenApi;
+    /// # use salvo_oapi::swagger_ui::SwaggerUi;
+    /// let v1 = Router::with_path("api/v1");
+    /// let v2 = Router::with_path("api/v2");
+    /// let doc_v1 = OpenApi::new("todos api", "1.0.0").merge_router(&v1);
+    /// let doc_v2 = OpenApi::new("todos api", "2.0.0").merge_router(&v2);
+    ///
+    /// let router = Router::new()
+    ///     .push(v1)
+    ///     .push(v2)
+    ///     .push(doc_v1.into_router("/api-doc/v1/openapi.json"))
+    ///     .push(doc_v2.into_router("/api-doc/v2/openapi.json"))
+    ///     .push(
+    ///         SwaggerUi::new_multi([
+    ///             ("v1", "/api-doc/v1/openapi.json"),
+    ///             ("v2", "/api-doc/v2/openapi.json"),
+    ///         ])
+    ///         .into_router("swagger-ui"),
+    ///     );
+    /// ```
+    pub fn new_multi<I, U>(urls: I) -> Self
+    where
+        I: IntoIterator<Item = U>,
+        U: Into<Url<'static>>,
+    {
+        Self::new(Config::new_config_with_multiple_urls(urls.into_iter().map(Into::into).collect()))
+    }
+
     /// Set title of the html page. The default title is "Swagger UI".
     pub fn title(mut self, title: impl Into<Cow<'static, str>>) -> Self {
         self.title = title.into();
@@ -193,6 +251,62 @@ impl SwaggerUi {
         self
     }
 
+    /// Set whether deep linking of tags and operations is enabled. Deep linking is enabled by default.
+    ///
+    /// See [`Config::deep_linking`] for more details.
+    pub fn deep_linking(mut self, deep_linking: bool) -> Self {
+        self.config = self.config.deep_linking(deep_linking);
+        self
+    }
+
+    /// Set the default expansion depth of the models section, `-1` hides the models completely.
+    ///
+    /// See [`Config::default_models_expand_depth`] for more details.
+    pub fn default_models_expand_depth(mut self, depth: isize) -> Self {
+        self.config = self.config.default_models_expand_depth(depth);
+        self
+    }
+
+    /// Set the default expansion depth of the model in the example section.
+    ///
+    /// See [`Config::default_model_expand_depth`] for more details.
+    pub fn default_model_expand_depth(mut self, depth: isize) -> Self {
+        self.config = self.config.default_model_expand_depth(depth);
+        self
+    }
+
+    /// Set the default expansion of operations and tags, it is one of `list`, `full` and `none`.
+    ///
+    /// See [`Config::doc_expansion`] for more details.
+    pub fn doc_expansion(mut self, doc_expansion: impl Into<String>) -> Self {
+        self.config = self.config.doc_expansion(doc_expansion);
+        self
+    }
+
+    /// Set whether the _**'Try it out'**_ section is enabled by default.
+    ///
+    /// See [`Config::try_it_out_enabled`] for more details.
+    pub fn try_it_out_enabled(mut self, try_it_out_enabled: bool) -> Self {
+        self.config = self.config.try_it_out_enabled(try_it_out_enabled);
+        self
+    }
+
+    /// Set whether the authorizations are kept after browser refresh and close.
+    ///
+    /// See [`Config::persist_authorization`] for more details.
+    pub fn persist_authorization(mut self, persist_authorization: bool) -> Self {
+        self.config = self.config.persist_authorization(persist_authorization);
+        self
+    }
+
+    /// Set the theme of syntax highlighting, for example `monokai`.
+    ///
+    /// See [`Config::syntax_highlight_theme`] for more details.
+    pub fn syntax_highlight_theme(mut self, theme: impl Into<String>) -> Self {
+        self.config = self.config.syntax_highlight_theme(theme);
+        self
+    }
+
     /// Consusmes the [`SwaggerUi`] and returns [`Router`] with the [`SwaggerUi`] as handler.
     pub fn into_router(self, path: impl Into<String>) -> Router {
         Router::with_path(format!("{}/{{**}}", path.into())).goal(self)
@@ -357,6 +471,20 @@ impl From<Cow<'static, str>> for Url<'_> {
     }
 }
 
+impl<'a, N, U> From<(N, U)> for Url<'a>
+where
+    N: Into<Cow<'a, str>>,
+    U: Into<Cow<'a, str>>,
+{
+    fn from((name, url): (N, U)) -> Self {
+        Self {
+            name: name.into(),
+            url: url.into(),
+            ..Default::default()
+        }
+    }
+}
+
 /// Represents servable file of Swagger UI. This is used together with [`serve`] function
 /// to serve Swagger UI files via web server.
 #[non_exhaustive]
@@ -419,3 +547,55 @@ pub fn serve<'a>(
 
     Ok(file)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_swagger_ui_new_multi() {
+        let swagger = SwaggerUi::new_multi([("v1", "/api-doc/v1/openapi.json"), ("v2", "/api-doc/v2/openapi.json")]);
+        let config = serde_json::to_value(&swagger.config).unwrap();
+        assert_eq!(
+            config["urls"],
+            serde_json::json!([
+                {"name": "v1", "url": "/api-doc/v1/openapi.json"},
+                {"name": "v2", "url": "/api-doc/v2/openapi.json"},
+            ])
+        );
+        assert!(config.get("url").is_none());
+
+        let swagger = SwaggerUi::new_multi([
+            Url::new("v1", "/api-doc/v1/openapi.json"),
+            Url::with_primary("v2", "/api-doc/v2/openapi.json", true),
+        ]);
+        let config = serde_json::to_value(&swagger.config).unwrap();
+        assert_eq!(config["urls.primaryName"], "v2");
+    }
+
+    #[test]
+    fn test_swagger_ui_config() {
+        let swagger = SwaggerUi::new("/api-doc/openapi.json")
+            .title("Todos API")
+            .deep_linking(false)
+            .doc_expansion("none")
+            .default_models_expand_depth(-1)
+            .default_model_expand_depth(2)
+            .try_it_out_enabled(true)
+            .persist_authorization(true)
+            .syntax_highlight_theme("monokai")
+            .oauth(oauth::Config::new().client_id("todos-client"));
+
+        let file = serve("", &swagger.title, "", "", &swagger.config).unwrap().unwrap();
+        let index = String::from_utf8(file.bytes.to_vec()).unwrap();
+        assert!(index.contains("<title>Todos API</title>"));
+        assert!(index.contains(r#""deepLinking":false"#));
+        assert!(index.contains(r#""docExpansion":"none""#));
+        assert!(index.contains(r#""defaultModelsExpandDepth":-1"#));
+        assert!(index.contains(r#""defaultModelExpandDepth":2"#));
+        assert!(index.contains(r#""tryItOutEnabled":true"#));
+        assert!(index.contains(r#""persistAuthorization":true"#));
+        assert!(index.contains(r#""syntaxHighlight":{"activated":true,"theme":"monokai"}"#));
+        assert!(index.contains(r#"window.ui.initOAuth({"clientId":"todos-client"});"#));
+    }
+}
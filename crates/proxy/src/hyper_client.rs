@@ -94,6 +94,7 @@ impl Client for HyperClient {
 // Unit tests for Proxy
 #[cfg(test)]
 mod tests {
+    use salvo_core::conn::Acceptor;
     use salvo_core::prelude::*;
     use salvo_core::test::*;
 
@@ -123,6 +124,81 @@ mod tests {
         assert!(content.contains("Install Rust"));
     }
 
+    fn http_client() -> HyperClient {
+        let http = HttpsConnectorBuilder::new()
+            .with_native_roots()
+            .unwrap()
+            .https_or_http()
+            .enable_http1()
+            .build();
+        HyperClient::new(HyperUtilClient::builder(TokioExecutor::new()).build(http))
+    }
+
+    async fn serve_upstream() -> String {
+        #[handler]
+        async fn chunked(res: &mut Response) {
+            let chunks = ["hello", " ", "world"].map(Ok::<_, std::io::Error>);
+            res.stream(futures_util::stream::iter(chunks));
+        }
+        #[handler]
+        async fn media(req: &mut Request, res: &mut Response) {
+            if req.header::<String>("range").as_deref() == Some("bytes=0-3") {
+                res.status_code(StatusCode::PARTIAL_CONTENT);
+                res.add_header("content-range", "bytes 0-3/10", true).unwrap();
+                res.add_header("content-length", 4, true).unwrap();
+                res.body("0123");
+            } else {
+                res.add_header("content-length", 10, true).unwrap();
+                res.body("0123456789");
+            }
+            res.add_header("accept-ranges", "bytes", true).unwrap();
+        }
+        let router = Router::new()
+            .push(Router::with_path("chunked").get(chunked))
+            .push(Router::with_path("media").get(media));
+        let acceptor = TcpListener::new("127.0.0.1:0").bind().await;
+        let addr = acceptor.holdings()[0].local_addr.clone().into_std().unwrap();
+        tokio::spawn(Server::new(acceptor).serve(router));
+        format!("http://{addr}")
+    }
+
+    #[tokio::test]
+    async fn test_hyper_client_streaming() {
+        let upstream = serve_upstream().await;
+        let router = Router::with_path("{**rest}").goal(Proxy::new(upstream, http_client()));
+        let service = Service::new(router);
+
+        let mut res = TestClient::get("http://127.0.0.1:5801/chunked").send(&service).await;
+        assert!(res.body.is_hyper());
+        assert!(res.headers().get("transfer-encoding").is_none());
+        assert_eq!(res.take_string().await.unwrap(), "hello world");
+
+        let mut res = TestClient::get("http://127.0.0.1:5801/media").send(&service).await;
+        assert_eq!(res.headers()["content-length"], "10");
+        assert_eq!(res.headers()["accept-ranges"], "bytes");
+        assert_eq!(res.take_string().await.unwrap(), "0123456789");
+
+        let mut res = TestClient::get("http://127.0.0.1:5801/media")
+            .add_header("range", "bytes=0-3", true)
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::PARTIAL_CONTENT));
+        assert_eq!(res.headers()["content-range"], "bytes 0-3/10");
+        assert_eq!(res.headers()["content-length"], "4");
+        assert_eq!(res.take_string().await.unwrap(), "0123");
+    }
+
+    #[tokio::test]
+    async fn test_hyper_client_bad_gateway() {
+        let acceptor = TcpListener::new("127.0.0.1:0").bind().await;
+        let addr = acceptor.holdings()[0].local_addr.clone().into_std().unwrap();
+        drop(acceptor);
+        let router = Router::with_path("{**rest}").goal(Proxy::new(format!("http://{addr}"), http_client()));
+
+        let res = TestClient::get("http://127.0.0.1:5801/unreachable").send(router).await;
+        assert_eq!(res.status_code, Some(StatusCode::BAD_GATEWAY));
+    }
+
     #[test]
     fn test_others() {
         let mut handler = Proxy::new(["https://www.bing.com"], HyperClient::default());
@@ -30,6 +30,15 @@
 //!     Server::new(acceptor).serve(router).await;
 //! }
 //! ```
+//!
+//! # Streaming
+//!
+//! The request and response bodies are streamed between the client and the upstream without buffering, so the proxy
+//! can be used for large files and media streaming. The `range` header of requests, and the `content-length`,
+//! `content-range` and `accept-ranges` headers of upstream responses are forwarded as they are. Hop-by-hop headers
+//! of upstream responses, like `transfer-encoding` and `connection`, are not forwarded.
+//!
+//! If the upstream can not be connected or does not respond, the proxy responds with `502 Bad Gateway`.
 #![doc(html_favicon_url = "https://salvo.rs/favicon-32x32.png")]
 #![doc(html_logo_url = "https://salvo.rs/images/logo.svg")]
 #![cfg_attr(docsrs, feature(doc_cfg))]
@@ -40,7 +49,9 @@ use std::future::Future;
 
 use hyper::upgrade::OnUpgrade;
 use percent_encoding::{CONTROLS, utf8_percent_encode};
-use salvo_core::http::header::{CONNECTION, HOST, HeaderMap, HeaderName, HeaderValue, UPGRADE};
+use salvo_core::http::header::{
+    CONNECTION, HOST, HeaderMap, HeaderName, HeaderValue, TE, TRANSFER_ENCODING, UPGRADE,
+};
 use salvo_core::http::uri::Uri;
 use salvo_core::http::{ReqBody, ResBody, StatusCode};
 use salvo_core::{BoxedError, Depot, Error, FlowCtrl, Handler, Request, Response, async_trait};
@@ -320,16 +331,21 @@ where
                             body,
                         ) = response.into_parts();
                         res.status_code(status);
+                        let hop_by_hop = hop_by_hop_headers(&headers, status);
                         for name in headers.keys() {
+                            if hop_by_hop.contains(name) {
+                                continue;
+                            }
                             for value in headers.get_all(name) {
                                 res.headers.append(name, value.to_owned());
                             }
                         }
+                        // The body is streamed to the client as it is received from the upstream.
                         res.body(body);
                     }
                     Err(e) => {
                         tracing::error!( error = ?e, uri = ?req.uri(), "get response data failed: {}", e);
-                        res.status_code(StatusCode::INTERNAL_SERVER_ERROR);
+                        res.status_code(StatusCode::BAD_GATEWAY);
                     }
                 }
             }
@@ -339,6 +355,33 @@ where
         }
     }
 }
+/// Get the hop-by-hop headers of the upstream response, they are only meaningful for the connection between proxy
+/// and upstream, so they are not forwarded to the client. Hyper will set the `transfer-encoding` for the client
+/// connection by itself, while `content-length`, `content-range` and `accept-ranges` are forwarded as they are.
+fn hop_by_hop_headers(headers: &HeaderMap, status: StatusCode) -> Vec<HeaderName> {
+    let mut names = vec![
+        HeaderName::from_static("keep-alive"),
+        HeaderName::from_static("proxy-connection"),
+        TE,
+        TRANSFER_ENCODING,
+    ];
+    // `connection` and `upgrade` are needed by the client to switch protocols.
+    if status != StatusCode::SWITCHING_PROTOCOLS {
+        names.push(CONNECTION);
+        names.push(UPGRADE);
+        for value in headers.get_all(CONNECTION) {
+            names.extend(
+                value
+                    .to_str()
+                    .unwrap_or_default()
+                    .split(',')
+                    .filter_map(|name| HeaderName::from_bytes(name.trim().as_bytes()).ok()),
+            );
+        }
+    }
+    names
+}
+
 #[inline]
 #[allow(dead_code)]
 fn get_upgrade_type(headers: &HeaderMap) -> Option<&str> {
@@ -377,6 +420,24 @@ mod tests {
         assert_eq!(encoded_path, "/test/path");
     }
 
+    #[test]
+    fn test_hop_by_hop_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            CONNECTION,
+            HeaderValue::from_static("keep-alive, x-private"),
+        );
+        let names = hop_by_hop_headers(&headers, StatusCode::OK);
+        assert!(names.contains(&TRANSFER_ENCODING));
+        assert!(names.contains(&CONNECTION));
+        assert!(names.contains(&HeaderName::from_static("x-private")));
+        assert!(!names.contains(&HeaderName::from_static("content-length")));
+
+        let names = hop_by_hop_headers(&headers, StatusCode::SWITCHING_PROTOCOLS);
+        assert!(!names.contains(&CONNECTION));
+        assert!(!names.contains(&UPGRADE));
+    }
+
     #[test]
     fn test_get_upgrade_type() {
         let mut headers = HeaderMap::new();
@@ -16,6 +16,7 @@
 //! | `test` | Utilities for testing application | ✔️ |
 //! | `acme` | Automatically obtain certificates through ACME | ❌ |
 //! | `rustls` | TLS built on [`rustls`](https://crates.io/crates/rustls) | ❌ |
+//! | `client-cert` | Verified client certificates of the `rustls` connections | ❌ |
 //! | `openssl` | TLS built on [`openssl-tls`](https://crates.io/crates/openssl) | ❌ |
 //! | `native-tls` | TLS built on [`native-tls`](https://crates.io/crates/native-tls) | ❌ |
 //! | `unix` | Listener based on unix socket | ❌ |
@@ -25,16 +26,33 @@
 //! | `affix-state` | Middleware for adding prefix and suffix to the request path | ❌ |
 //! | `craft` | Generate handlers or endpoints with shared data | ❌ |
 //! | `basic-auth` | Middleware for basic authentication | ❌ |
+//! | `batch` | Handler for batch requests | ❌ |
+//! | `body-tee` | Middleware for teeing response bodies to another writer | ❌ |
+//! | `body-transform` | Middleware for transforming response bodies | ❌ |
 //! | `caching-headers` | Middleware for setting caching headers | ❌ |
 //! | `catch-panic` | Middleware for catching panics | ❌ |
 //! | `concurrency-limiter` | Middleware for limiting concurrency | ❌ |
+//! | `cursor` | Opaque cursors for keyset pagination | ❌ |
+//! | `deadline` | Middleware for setting a request deadline | ❌ |
+//! | `deprecation` | Middleware for advertising the deprecation of endpoints | ❌ |
 //! | `force-https` | Middleware for forcing HTTPS | ❌ |
+//! | `geo-ip` | Middleware for attaching geo metadata of the client IP | ❌ |
+//! | `health` | Handlers for health check endpoints | ❌ |
+//! | `idempotency` | Middleware for replaying the responses of retried requests by idempotency keys | ❌ |
+//! | `locale` | Middleware for detecting the preferred locale | ❌ |
 //! | `logging` | Middleware for logging requests and responses | ❌ |
 //! | `request-id` | Middleware for setting a request ID | ❌ |
+//! | `rewrite` | Middleware for rewriting request paths | ❌ |
+//! | `server-stats` | Middleware and handler for a summary of server statistics | ❌ |
+//! | `server-timing` | Middleware for writing the `Server-Timing` header | ❌ |
 //! | `size-limiter` | Middleware for limiting request size | ❌ |
 //! | `sse` | Server-Sent Events (SSE) middleware | ❌ |
 //! | `timeout` | Middleware for setting a timeout | ❌ |
 //! | `trailing-slash` | Middleware for handling trailing slashes | ❌ |
+//! | `transaction` | Middleware for running requests in database transactions | ❌ |
+//! | `trusted-proxy` | Middleware for requests forwarded by trusted proxies | ❌ |
+//! | `vcr` | Middleware for recording and replaying responses | ❌ |
+//! | `webhook` | Middleware for verifying webhook signatures | ❌ |
 //! | `websocket` | WebSocket implementation | ❌ |
 #![doc(html_favicon_url = "https://salvo.rs/favicon-32x32.png")]
 #![doc(html_logo_url = "https://salvo.rs/images/logo.svg")]
@@ -58,6 +76,21 @@ cfg_feature! {
     // #[doc(no_inline)]
     pub use salvo_extra::basic_auth;
 }
+cfg_feature! {
+    #![feature ="batch"]
+    // #[doc(no_inline)]
+    pub use salvo_extra::batch;
+}
+cfg_feature! {
+    #![feature ="body-tee"]
+    // #[doc(no_inline)]
+    pub use salvo_extra::body_tee;
+}
+cfg_feature! {
+    #![feature ="body-transform"]
+    // #[doc(no_inline)]
+    pub use salvo_extra::body_transform;
+}
 cfg_feature! {
     #![feature ="caching-headers"]
     // #[doc(no_inline)]
@@ -68,11 +101,41 @@ cfg_feature! {
     // #[doc(no_inline)]
     pub use salvo_extra::catch_panic;
 }
+cfg_feature! {
+    #![feature ="cursor"]
+    // #[doc(no_inline)]
+    pub use salvo_extra::cursor;
+}
+cfg_feature! {
+    #![feature ="deprecation"]
+    // #[doc(no_inline)]
+    pub use salvo_extra::deprecation;
+}
 cfg_feature! {
     #![feature ="force-https"]
     // #[doc(no_inline)]
     pub use salvo_extra::force_https;
 }
+cfg_feature! {
+    #![feature ="geo-ip"]
+    // #[doc(no_inline)]
+    pub use salvo_extra::geo_ip;
+}
+cfg_feature! {
+    #![feature ="health"]
+    // #[doc(no_inline)]
+    pub use salvo_extra::health;
+}
+cfg_feature! {
+    #![feature ="idempotency"]
+    // #[doc(no_inline)]
+    pub use salvo_extra::idempotency;
+}
+cfg_feature! {
+    #![feature ="locale"]
+    // #[doc(no_inline)]
+    pub use salvo_extra::locale;
+}
 cfg_feature! {
     #![feature ="logging"]
     // #[doc(no_inline)]
@@ -103,6 +166,31 @@ cfg_feature! {
     // #[doc(no_inline)]
     pub use salvo_extra::timeout;
 }
+cfg_feature! {
+    #![feature ="transaction"]
+    // #[doc(no_inline)]
+    pub use salvo_extra::transaction;
+}
+cfg_feature! {
+    #![feature ="trusted-proxy"]
+    // #[doc(no_inline)]
+    pub use salvo_extra::trusted_proxy;
+}
+cfg_feature! {
+    #![feature ="vcr"]
+    // #[doc(no_inline)]
+    pub use salvo_extra::vcr;
+}
+cfg_feature! {
+    #![feature ="deadline"]
+    // #[doc(no_inline)]
+    pub use salvo_extra::deadline;
+}
+cfg_feature! {
+    #![feature ="webhook"]
+    // #[doc(no_inline)]
+    pub use salvo_extra::webhook;
+}
 cfg_feature! {
     #![feature ="websocket"]
     // #[doc(no_inline)]
@@ -113,6 +201,21 @@ cfg_feature! {
     // #[doc(no_inline)]
     pub use salvo_extra::request_id;
 }
+cfg_feature! {
+    #![feature ="rewrite"]
+    // #[doc(no_inline)]
+    pub use salvo_extra::rewrite;
+}
+cfg_feature! {
+    #![feature ="server-stats"]
+    // #[doc(no_inline)]
+    pub use salvo_extra::server_stats;
+}
+cfg_feature! {
+    #![feature ="server-timing"]
+    // #[doc(no_inline)]
+    pub use salvo_extra::server_timing;
+}
 cfg_feature! {
     #![feature ="cache"]
     #[doc(no_inline)]
@@ -251,6 +354,10 @@ pub mod prelude {
         #![feature ="timeout"]
         pub use salvo_extra::timeout::Timeout;
     }
+    cfg_feature! {
+        #![feature ="deadline"]
+        pub use salvo_extra::deadline::{Deadline, DeadlineDepotExt};
+    }
     cfg_feature! {
         #![feature ="tower-compat"]
         pub use salvo_extra::tower_compat::{TowerServiceCompat, TowerLayerCompat};
@@ -269,7 +376,7 @@ pub mod prelude {
     }
     cfg_feature! {
         #![feature ="oapi"]
-        pub use crate::oapi::{endpoint, RouterExt, EndpointArgRegister, EndpointOutRegister, OpenApi, ToParameter, ToParameters, ToSchema, ToResponse, ToResponses};
+        pub use crate::oapi::{endpoint, RouterExt, EndpointArgRegister, EndpointOutput, EndpointOutRegister, OpenApi, ToParameter, ToParameters, ToSchema, ToResponse, ToResponses};
         pub use crate::oapi::swagger_ui::SwaggerUi;
         pub use crate::oapi::rapidoc::RapiDoc;
         pub use crate::oapi::redoc::ReDoc;
@@ -0,0 +1,80 @@
+//! Generates the statements of handlers which extract their arguments, shared by `#[handler]` and `#[endpoint]`.
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use syn::{Pat, TypePath};
+
+/// Generate the statement getting an argument injected into the depot by `FromDepot`.
+pub fn obtain_arg_ts(salvo: &Ident, pat: &Pat, ty: &TypePath) -> TokenStream {
+    quote! {
+        let #pat: #ty = match <#ty>::from_depot(__macro_gen_depot) {
+            Ok(data) => data,
+            Err(e) => {
+                #salvo::Writer::write(e, __macro_gen_req, __macro_gen_depot, __macro_gen_res).await;
+                return;
+            }
+        };
+    }
+}
+
+/// Generate the statements extracting the arguments `(ident, type, arg name)` by `Extractible`.
+///
+/// If there are several arguments, the parse errors of all of them are collected and written as one error.
+pub fn extract_args_ts(salvo: &Ident, args: &[(Ident, TypePath, String)]) -> Vec<TokenStream> {
+    let finish_with_error = quote! {
+        // If status code is not set or is not error, set it to 400.
+        let status_code = __macro_gen_res.status_code.unwrap_or_default();
+        if !status_code.is_client_error() && !status_code.is_server_error() {
+            __macro_gen_res.status_code(#salvo::http::StatusCode::BAD_REQUEST);
+        }
+        return;
+    };
+    if args.len() <= 1 {
+        return args.iter().map(|(id, ty, idv)| {
+            quote!{
+                let #id: #ty = match <#ty as #salvo::Extractible>::extract_with_arg(__macro_gen_req, #idv).await {
+                    Ok(data) => data,
+                    Err(e) => {
+                        e.write(__macro_gen_req, __macro_gen_depot, __macro_gen_res).await;
+                        #finish_with_error
+                    }
+                };
+            }
+        }).collect();
+    }
+
+    // Collect errors of all extractors, and write them as one error.
+    let mut extract_ts = Vec::with_capacity(args.len() + 2);
+    extract_ts.push(quote! {
+        let mut __macro_gen_errors = Vec::new();
+    });
+    for (id, ty, idv) in args {
+        extract_ts.push(quote!{
+            let #id: Option<#ty> = match <#ty as #salvo::Extractible>::extract_with_arg(__macro_gen_req, #idv).await {
+                Ok(data) => Some(data),
+                Err(e) => match #salvo::serde::downcast_parse_error(e) {
+                    Ok(e) => {
+                        __macro_gen_errors.push((#idv, e));
+                        None
+                    }
+                    Err(e) => {
+                        e.write(__macro_gen_req, __macro_gen_depot, __macro_gen_res).await;
+                        #finish_with_error
+                    }
+                }
+            };
+        });
+    }
+    let unwraps = args.iter().map(|(id, ty, _)| {
+        quote! {
+            let #id: #ty = #id.expect("extracted data should exist");
+        }
+    });
+    extract_ts.push(quote! {
+        if let Some(e) = #salvo::serde::merge_parse_errors(__macro_gen_errors) {
+            #salvo::Writer::write(e, __macro_gen_req, __macro_gen_depot, __macro_gen_res).await;
+            #finish_with_error
+        }
+        #(#unwraps)*
+    });
+    extract_ts
+}
@@ -10,6 +10,8 @@ use syn::{Attribute, Error, buffer::Cursor};
 
 pub(crate) mod case;
 pub use case::RenameRule;
+mod extract;
+pub use extract::{extract_args_ts, obtain_arg_ts};
 
 #[inline]
 fn parse_next_lit_str(next: Cursor) -> Option<(String, Span)> {
@@ -1,16 +1,16 @@
 //! serve static dir
 
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::ffi::OsStr;
 use std::fmt::{self, Display, Formatter, Write};
 use std::fs::Metadata;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 
 use salvo_core::fs::NamedFile;
 use salvo_core::handler::Handler;
-use salvo_core::http::header::ACCEPT_ENCODING;
+use salvo_core::http::header::{ACCEPT_ENCODING, CACHE_CONTROL, VARY};
 use salvo_core::http::{self, HeaderValue, Request, Response, StatusCode, StatusError};
 use salvo_core::writing::Text;
 use salvo_core::{Depot, FlowCtrl, IntoVecString, async_trait};
@@ -123,6 +123,27 @@ where
 }
 
 /// Handler that serves a directory.
+///
+/// The served files have `etag` and `last-modified` headers, so the conditional requests with `if-none-match` and
+/// `if-modified-since` get `304 Not Modified` if the file is not changed, and range requests are supported.
+///
+/// If a precompressed sibling of the file exists, like `app.js.br` or `app.js.gz` for `app.js`, and the client
+/// accepts its encoding, the sibling is served with the `content-encoding` header instead, the preference of the
+/// client in `accept-encoding` is respected. The files which have precompressed siblings are served with
+/// `vary: accept-encoding`. View [`StaticDir::compressed_variation`] to change the extensions.
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use salvo_core::prelude::*;
+/// use salvo_serve_static::StaticDir;
+///
+/// let router = Router::with_path("{*path}").get(
+///     StaticDir::new(["static"])
+///         .defaults("index.html")
+///         .max_age(Duration::from_secs(3600)),
+/// );
+/// ```
 #[non_exhaustive]
 pub struct StaticDir {
     /// Static roots.
@@ -149,6 +170,8 @@ pub struct StaticDir {
     pub defaults: Vec<String>,
     /// Fallback file name. This is used when the requested file is not found.
     pub fallback: Option<String>,
+    /// The `cache-control` header of the served files. Default is `None`, the header is not set.
+    pub cache_control: Option<HeaderValue>,
 }
 impl StaticDir {
     /// Create new `StaticDir`.
@@ -169,6 +192,7 @@ impl StaticDir {
             compressed_variations,
             defaults: vec![],
             fallback: None,
+            cache_control: None,
         }
     }
 
@@ -224,6 +248,22 @@ impl StaticDir {
         self
     }
 
+    /// Sets the `cache-control` header of the served files, like `public, max-age=3600`.
+    #[inline]
+    pub fn cache_control(mut self, cache_control: HeaderValue) -> Self {
+        self.cache_control = Some(cache_control);
+        self
+    }
+
+    /// Sets the `cache-control` header of the served files to `public, max-age=<seconds>`.
+    #[inline]
+    pub fn max_age(self, max_age: Duration) -> Self {
+        let value = format!("public, max-age={}", max_age.as_secs());
+        self.cache_control(
+            HeaderValue::from_str(&value).expect("max-age should be a valid header value"),
+        )
+    }
+
     /// During the file chunk read, the maximum read size at one time will affect the
     /// access experience and the demand for server memory.
     ///
@@ -392,6 +432,7 @@ impl Handler for StaticDir {
                 .map(|ext| self.is_compressed_ext(ext))
                 .unwrap_or(false);
             let mut content_encoding = None;
+            let mut varied = false;
             let named_path = if !is_compressed_ext {
                 if !self.compressed_variations.is_empty() {
                     let mut new_abs_path = None;
@@ -400,29 +441,36 @@ impl Handler for StaticDir {
                         .get(ACCEPT_ENCODING)
                         .and_then(|v| v.to_str().ok())
                         .unwrap_or_default();
+                    // The algorithms are sorted by the preference of the client, `q=0` means not acceptable.
                     let accept_algos = http::parse_accept_encoding(header)
                         .into_iter()
-                        .filter_map(|(algo, _level)| {
-                            if let Ok(algo) = algo.parse::<CompressionAlgo>() {
-                                Some(algo)
-                            } else {
-                                None
-                            }
-                        })
-                        .collect::<HashSet<_>>();
-                    for (algo, exts) in &self.compressed_variations {
-                        if accept_algos.contains(algo) {
-                            for zip_ext in exts {
-                                let mut path = abs_path.clone();
-                                path.as_mut_os_string().push(&*format!(".{}", zip_ext));
-                                if path.is_file() {
-                                    new_abs_path = Some(path);
-                                    content_encoding = Some(algo.to_string());
-                                    break;
-                                }
+                        .filter(|(_, q)| *q > 0)
+                        .filter_map(|(algo, _)| algo.parse::<CompressionAlgo>().ok());
+                    'algos: for algo in accept_algos {
+                        let Some(exts) = self.compressed_variations.get(&algo) else {
+                            continue;
+                        };
+                        for zip_ext in exts {
+                            let mut path = abs_path.clone();
+                            path.as_mut_os_string().push(&*format!(".{}", zip_ext));
+                            if path.is_file() {
+                                new_abs_path = Some(path);
+                                content_encoding = Some(algo.to_string());
+                                break 'algos;
                             }
                         }
                     }
+                    // The response differs by the accepted encodings of the client if a precompressed file exists.
+                    varied = content_encoding.is_some()
+                        || self
+                            .compressed_variations
+                            .values()
+                            .flatten()
+                            .any(|zip_ext| {
+                                let mut path = abs_path.clone();
+                                path.as_mut_os_string().push(&*format!(".{}", zip_ext));
+                                path.is_file()
+                            });
                     new_abs_path.unwrap_or(abs_path)
                 } else {
                     abs_path
@@ -445,6 +493,14 @@ impl Handler for StaticDir {
                 builder
             };
             if let Ok(named_file) = builder.build().await {
+                if varied {
+                    res.headers_mut()
+                        .append(VARY, HeaderValue::from_static("accept-encoding"));
+                }
+                if let Some(cache_control) = &self.cache_control {
+                    res.headers_mut()
+                        .insert(CACHE_CONTROL, cache_control.clone());
+                }
                 let headers = req.headers();
                 named_file.send(headers, res).await;
             } else {
@@ -197,6 +197,75 @@ mod tests {
         assert!(content == "copy3");
     }
 
+    #[tokio::test]
+    async fn test_serve_static_dir_caching() {
+        use std::time::Duration;
+
+        use salvo_core::http::header::{
+            ACCEPT_ENCODING, CACHE_CONTROL, CONTENT_ENCODING, ETAG, IF_MODIFIED_SINCE,
+            IF_NONE_MATCH, LAST_MODIFIED, VARY,
+        };
+
+        let router = Router::with_path("{*path}")
+            .get(StaticDir::new(vec!["test/static"]).max_age(Duration::from_secs(60)));
+        let service = Service::new(router);
+
+        let mut response = TestClient::get("http://127.0.0.1:5801/test2.txt")
+            .send(&service)
+            .await;
+        assert_eq!(response.status_code.unwrap(), StatusCode::OK);
+        assert_eq!(response.headers()[CACHE_CONTROL], "public, max-age=60");
+        assert_eq!(response.headers()[VARY], "accept-encoding");
+        assert!(response.headers().get(CONTENT_ENCODING).is_none());
+        assert_eq!(response.take_string().await.unwrap(), "copy2");
+        let etag = response.headers()[ETAG].clone();
+        let last_modified = response.headers()[LAST_MODIFIED].clone();
+
+        let mut response = TestClient::get("http://127.0.0.1:5801/test2.txt")
+            .add_header(ACCEPT_ENCODING, "br, gzip;q=0.8", true)
+            .send(&service)
+            .await;
+        assert_eq!(response.headers()[CONTENT_ENCODING], "gzip");
+        assert_eq!(
+            response.take_bytes(None).await.unwrap(),
+            std::fs::read("test/static/test2.txt.gz").unwrap()
+        );
+
+        let mut response = TestClient::get("http://127.0.0.1:5801/test2.txt")
+            .add_header(ACCEPT_ENCODING, "gzip;q=0", true)
+            .send(&service)
+            .await;
+        assert!(response.headers().get(CONTENT_ENCODING).is_none());
+        assert_eq!(response.take_string().await.unwrap(), "copy2");
+
+        let response = TestClient::get("http://127.0.0.1:5801/test2.txt")
+            .add_header(IF_NONE_MATCH, etag, true)
+            .send(&service)
+            .await;
+        assert_eq!(response.status_code.unwrap(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()[CACHE_CONTROL], "public, max-age=60");
+
+        let response = TestClient::get("http://127.0.0.1:5801/test2.txt")
+            .add_header(IF_MODIFIED_SINCE, last_modified, true)
+            .send(&service)
+            .await;
+        assert_eq!(response.status_code.unwrap(), StatusCode::NOT_MODIFIED);
+
+        // No precompressed file, so the response does not vary.
+        let response = TestClient::get("http://127.0.0.1:5801/test1.txt")
+            .add_header(ACCEPT_ENCODING, "gzip", true)
+            .send(&service)
+            .await;
+        assert_eq!(response.status_code.unwrap(), StatusCode::OK);
+        assert!(response.headers().get(VARY).is_none());
+        let response = TestClient::get("http://127.0.0.1:5801/missing.txt")
+            .add_header(ACCEPT_ENCODING, "gzip", true)
+            .send(&service)
+            .await;
+        assert_eq!(response.status_code.unwrap(), StatusCode::NOT_FOUND);
+        assert!(response.headers().get(VARY).is_none());
+    }
+
     #[tokio::test]
     async fn test_serve_static_file() {
         let router = Router::new()
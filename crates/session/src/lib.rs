@@ -63,6 +63,31 @@ will not be used as valid sessions, For most session stores, it is the
 salvo application's responsibility to call cleanup on the session
 store if it requires it.
 
+## WebSocket
+
+The session is loaded before the handler which upgrades the connection to WebSocket, and it is saved
+when the `101 Switching Protocols` response is returned, before the socket is served. So read the
+session in the handler and move the data into the upgrade callback, the changes made to the session
+in the callback are not saved:
+
+```no_run
+use salvo_core::prelude::*;
+use salvo_extra::websocket::{Message, WebSocketUpgrade};
+use salvo_session::SessionDepotExt;
+
+#[handler]
+async fn connect(req: &mut Request, depot: &mut Depot, res: &mut Response) -> Result<(), StatusError> {
+    let Some(username) = depot.session().and_then(|s| s.get::<String>("username")) else {
+        return Err(StatusError::unauthorized());
+    };
+    WebSocketUpgrade::new()
+        .upgrade(req, res, |mut ws| async move {
+            let _ = ws.send(Message::text(format!("hello {username}"))).await;
+        })
+        .await
+}
+```
+
 Read more: <https://salvo.rs>
 */
 #![doc(html_favicon_url = "https://salvo.rs/favicon-32x32.png")]
@@ -78,7 +103,6 @@ use async_session::base64;
 use async_session::hmac::{Hmac, Mac, NewMac};
 use async_session::sha2::Sha256;
 use cookie::{Cookie, Key, SameSite};
-use salvo_core::http::uri::Scheme;
 use salvo_core::{Depot, Error, FlowCtrl, Handler, Request, Response, async_trait};
 
 /// Key for store data in depot.
@@ -341,7 +365,7 @@ where
             match self.store.store_session(session).await {
                 Ok(cookie_value) => {
                     if let Some(cookie_value) = cookie_value {
-                        let secure_cookie = req.uri().scheme() == Some(&Scheme::HTTPS);
+                        let secure_cookie = req.is_secure();
                         let cookie = self.build_cookie(secure_cookie, cookie_value);
                         res.add_cookie(cookie);
                     }
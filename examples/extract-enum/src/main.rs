@@ -0,0 +1,83 @@
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use salvo::extract::Parsed;
+use salvo::http::ParseError;
+use salvo::macros::Extractible;
+use salvo::prelude::*;
+use serde::Deserialize;
+
+/// Parsed with `FromStr`, so it is wrapped in `Parsed` when extracted.
+#[derive(Clone, Copy, Debug)]
+enum SortOrder {
+    Asc,
+    Desc,
+}
+impl FromStr for SortOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "asc" => Ok(Self::Asc),
+            "desc" => Ok(Self::Desc),
+            _ => Err(format!(
+                "unknown sort order `{s}`, expected `asc` or `desc`"
+            )),
+        }
+    }
+}
+impl Display for SortOrder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Asc => f.write_str("asc"),
+            Self::Desc => f.write_str("desc"),
+        }
+    }
+}
+
+/// Derives `Deserialize`, so it can be extracted directly.
+#[derive(Deserialize, Clone, Copy, Debug)]
+#[serde(rename_all = "lowercase")]
+enum Status {
+    Active,
+    Completed,
+}
+
+#[derive(Deserialize, Extractible, Debug)]
+#[salvo(extract(default_source(from = "query")))]
+struct ListTodos {
+    #[serde(default = "default_order")]
+    order: Parsed<SortOrder>,
+    status: Option<Status>,
+}
+fn default_order() -> Parsed<SortOrder> {
+    Parsed(SortOrder::Asc)
+}
+
+// Invalid `order` or `status` is responded with `422 Unprocessable Entity` and the field errors,
+// extractible types report all failed fields at once instead of the first bad value.
+#[handler]
+async fn list_todos(todos: ListTodos) -> String {
+    format!("order: {}, status: {:?}", todos.order, todos.status)
+}
+
+// Invalid `order` is responded with `400 Bad Request`.
+#[handler]
+async fn sort_users(req: &mut Request) -> Result<String, ParseError> {
+    let order = req.try_query::<Parsed<SortOrder>>("order")?;
+    Ok(format!("users sorted in {order} order"))
+}
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::fmt().init();
+
+    let router = Router::new()
+        .push(Router::with_path("todos").get(list_todos))
+        .push(Router::with_path("users").get(sort_users));
+
+    println!("Example url: http://0.0.0.0:5800/todos?order=desc&status=completed");
+    println!("Example url: http://0.0.0.0:5800/users?order=asc");
+    let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
+    Server::new(acceptor).serve(router).await;
+}
@@ -1,7 +1,8 @@
 use std::sync::LazyLock;
 
-use salvo::oapi::{ToSchema, extract::*};
+use salvo::oapi::{EndpointOutput, RouterExt, ToSchema, extract::*};
 use salvo::prelude::*;
+use salvo::writing::Created;
 use serde::{Deserialize, Serialize};
 use tokio::sync::Mutex;
 
@@ -21,6 +22,16 @@ pub struct Todo {
     pub completed: bool,
 }
 
+#[derive(EndpointOutput, Debug)]
+pub enum TodoError {
+    /// Todo already exists.
+    #[salvo(response(status_code = 409))]
+    Conflict(String),
+    /// Todo not found.
+    #[salvo(response(status_code = 404))]
+    NotFound,
+}
+
 #[tokio::main]
 async fn main() {
     tracing_subscriber::fmt().init();
@@ -28,6 +39,8 @@ async fn main() {
     let router = Router::new().get(index).push(
         Router::with_path("api").push(
             Router::with_path("todos")
+                // The limit is documented too, so the users trying the endpoints know it.
+                .max_body_size(1024 * 16)
                 .get(list_todos)
                 .post(create_todo)
                 .push(
@@ -95,7 +108,7 @@ pub async fn list_todos(
 
 /// Create new todo.
 #[endpoint(tags("todos"), status_codes(201, 409))]
-pub async fn create_todo(req: JsonBody<Todo>) -> Result<StatusCode, StatusError> {
+pub async fn create_todo(req: JsonBody<Todo>) -> Result<Created, TodoError> {
     tracing::debug!(todo = ?req, "create todo");
 
     let mut vec = STORE.lock().await;
@@ -103,12 +116,16 @@ pub async fn create_todo(req: JsonBody<Todo>) -> Result<StatusCode, StatusError>
     for todo in vec.iter() {
         if todo.id == req.id {
             tracing::debug!(id = ?req.id, "todo already exists");
-            return Err(StatusError::bad_request().brief("todo already exists"));
+            return Err(TodoError::Conflict(format!(
+                "todo {} already exists",
+                req.id
+            )));
         }
     }
 
+    let location = format!("/api/todos/{}", req.id);
     vec.push(req.into_inner());
-    Ok(StatusCode::CREATED)
+    Ok(Created::at(location))
 }
 
 /// Update existing todo.
@@ -116,7 +133,7 @@ pub async fn create_todo(req: JsonBody<Todo>) -> Result<StatusCode, StatusError>
 pub async fn update_todo(
     id: PathParam<u64>,
     updated: JsonBody<Todo>,
-) -> Result<StatusCode, StatusError> {
+) -> Result<StatusCode, TodoError> {
     tracing::debug!(todo = ?updated, id = ?id, "update todo");
     let mut vec = STORE.lock().await;
 
@@ -128,12 +145,12 @@ pub async fn update_todo(
     }
 
     tracing::debug!(?id, "todo is not found");
-    Err(StatusError::not_found())
+    Err(TodoError::NotFound)
 }
 
 /// Delete todo.
 #[endpoint(tags("todos"), status_codes(200, 401, 404))]
-pub async fn delete_todo(id: PathParam<u64>) -> Result<StatusCode, StatusError> {
+pub async fn delete_todo(id: PathParam<u64>) -> Result<StatusCode, TodoError> {
     tracing::debug!(?id, "delete todo");
 
     let mut vec = STORE.lock().await;
@@ -146,7 +163,7 @@ pub async fn delete_todo(id: PathParam<u64>) -> Result<StatusCode, StatusError>
         Ok(StatusCode::NO_CONTENT)
     } else {
         tracing::debug!(?id, "todo is not found");
-        Err(StatusError::not_found())
+        Err(TodoError::NotFound)
     }
 }
 
@@ -21,12 +21,43 @@ async fn upload(file: FormFile, res: &mut Response) {
     res.render(Text::Plain(info));
 }
 
+#[derive(ToSchema)]
+#[allow(dead_code)]
+struct UploadForm {
+    /// The title of the file.
+    title: String,
+    /// The file to upload.
+    file: FormFile,
+}
+
+/// Upload a file with a title.
+#[endpoint(request_body(content = UploadForm, content_type = "multipart/form-data"))]
+async fn upload_with_title(req: &mut Request, res: &mut Response) {
+    let title = req.form::<String>("title").await.unwrap_or_default();
+    let Some(file) = req.file("file").await else {
+        res.status_code(StatusCode::BAD_REQUEST);
+        res.render(Text::Plain("file not found in request"));
+        return;
+    };
+    let dest = format!("temp/{}", file.name().unwrap_or("file"));
+    let info = if let Err(e) = std::fs::copy(file.path(), Path::new(&dest)) {
+        res.status_code(StatusCode::INTERNAL_SERVER_ERROR);
+        format!("file not found in request: {e}")
+    } else {
+        format!("File `{title}` uploaded to {dest}")
+    };
+    res.render(Text::Plain(info));
+}
+
 #[tokio::main]
 async fn main() {
     tracing_subscriber::fmt().init();
 
     create_dir_all("temp").unwrap();
-    let router = Router::new().get(index).post(upload);
+    let router = Router::new()
+        .get(index)
+        .post(upload)
+        .push(Router::with_path("titled").post(upload_with_title));
 
     let doc = OpenApi::new("test api", "0.0.1").merge_router(&router);
 
@@ -1,7 +1,13 @@
 use std::sync::LazyLock;
+use std::time::Duration;
 
+use salvo::catcher::{Catcher, DefaultGoal};
+use salvo::extract::{MergePatch, PatchError};
+use salvo::http::ParseError;
+use salvo::http::request::BodyReadTimeout;
 use salvo::prelude::*;
-use salvo::size_limiter;
+use salvo::writing::{Paginated, Pagination};
+use salvo::{health, size_limiter};
 
 use self::models::*;
 
@@ -15,7 +21,12 @@ async fn main() {
 
 pub(crate) async fn start_server() {
     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
-    Server::new(acceptor).serve(route()).await;
+    Server::new(acceptor).serve(service()).await;
+}
+
+// The details of `AppError` are written for the clients, none of them leaks the internals.
+fn service() -> Service {
+    Service::new(route()).catcher(Catcher::new(DefaultGoal::new().show_detail(true)))
 }
 
 #[handler]
@@ -32,75 +43,115 @@ fn index(res: &mut Response) {
 fn route() -> Router {
     Router::new()
         .push(Router::new().get(index))
-        .push(Router::new().path("todos").push(todo_route()))
-}
-
-fn todo_route() -> Router {
-    Router::new()
-        .hoop(size_limiter::max_size(1024 * 16))
-        .get(list_todos)
-        .post(create_todo)
-        .push(
-            Router::with_path("{id}")
-                .put(update_todo)
-                .delete(delete_todo),
-        )
+        .push(Router::with_path("healthz").get(health::liveness()))
+        .push(Router::with_path("readyz").get(health::readiness(|| async {
+            // The store is ready once it can be locked.
+            drop(STORE.lock().await);
+            true
+        })))
+        .scope("todos", |todos| {
+            todos
+                .hoop(size_limiter::max_size(1024 * 16))
+                .hoop(BodyReadTimeout::new(Duration::from_secs(10)))
+                .get(list_todos)
+                .post(create_todo)
+                .scope("{id}", |todo| {
+                    todo.put(update_todo).patch(patch_todo).delete(delete_todo)
+                })
+        })
 }
 
 #[handler]
-pub async fn list_todos(req: &mut Request, res: &mut Response) {
-    let opts = req.parse_body::<ListOptions>().await.unwrap_or_default();
+pub async fn list_todos(req: &mut Request) -> Paginated<Vec<Todo>> {
+    let opts = req.parse_queries::<ListOptions>().unwrap_or_default();
     let todos = STORE.lock().await;
-    let todos: Vec<Todo> = todos
+    let page: Vec<Todo> = todos
         .clone()
         .into_iter()
         .skip(opts.offset.unwrap_or(0))
         .take(opts.limit.unwrap_or(usize::MAX))
         .collect();
-    res.render(Json(todos));
+    Paginated::new(page, todos.len(), Pagination::new(opts.offset, opts.limit)).total_count(true)
+}
+
+// All the handlers return `AppError`, so the errors are propagated by `?` and rendered by the catcher. A client which
+// stalls in the middle of the body gets `408 Request Timeout` from the `ParseError`.
+#[derive(Debug, thiserror::Error)]
+pub enum AppError {
+    #[error("invalid todo id")]
+    InvalidId,
+    #[error("todo not found")]
+    NotFound,
+    #[error("todo already exists")]
+    AlreadyExists,
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+    #[error(transparent)]
+    Patch(#[from] PatchError),
+}
+impl Scribe for AppError {
+    fn render(self, res: &mut Response) {
+        let error = match self {
+            Self::InvalidId | Self::AlreadyExists => {
+                StatusError::bad_request().detail(self.to_string())
+            }
+            Self::NotFound => StatusError::not_found().detail(self.to_string()),
+            Self::Parse(e) => e.into(),
+            Self::Patch(e) => e.into(),
+        };
+        res.render(error);
+    }
 }
 
 #[handler]
-pub async fn create_todo(req: &mut Request, res: &mut Response) {
-    let new_todo = req.parse_body::<Todo>().await.unwrap();
+pub async fn create_todo(req: &mut Request, res: &mut Response) -> Result<(), AppError> {
+    let new_todo = req.parse_body::<Todo>().await?;
     tracing::debug!(todo = ?new_todo, "create todo");
 
     let mut vec = STORE.lock().await;
 
-    for todo in vec.iter() {
-        if todo.id == new_todo.id {
-            tracing::debug!(id = ?new_todo.id, "todo already exists");
-            res.status_code(StatusCode::BAD_REQUEST);
-            return;
-        }
+    if vec.iter().any(|todo| todo.id == new_todo.id) {
+        tracing::debug!(id = ?new_todo.id, "todo already exists");
+        return Err(AppError::AlreadyExists);
     }
 
+    res.status(StatusCode::CREATED).render(Json(&new_todo));
     vec.push(new_todo);
-    res.status_code(StatusCode::CREATED);
+    Ok(())
 }
 
 #[handler]
-pub async fn update_todo(req: &mut Request, res: &mut Response) {
-    let id = req.param::<u64>("id").unwrap();
-    let updated_todo = req.parse_body::<Todo>().await.unwrap();
+pub async fn update_todo(req: &mut Request) -> Result<StatusCode, AppError> {
+    let id = req.param::<u64>("id").ok_or(AppError::InvalidId)?;
+    let updated_todo = req.parse_body::<Todo>().await?;
     tracing::debug!(todo = ?updated_todo, id = ?id, "update todo");
     let mut vec = STORE.lock().await;
 
-    for todo in vec.iter_mut() {
-        if todo.id == id {
-            *todo = updated_todo;
-            res.status_code(StatusCode::OK);
-            return;
-        }
-    }
+    let Some(todo) = vec.iter_mut().find(|todo| todo.id == id) else {
+        tracing::debug!(?id, "todo is not found");
+        return Err(AppError::NotFound);
+    };
+    *todo = updated_todo;
+    Ok(StatusCode::OK)
+}
+
+#[handler]
+pub async fn patch_todo(req: &mut Request, patch: MergePatch) -> Result<Json<Todo>, AppError> {
+    let id = req.param::<u64>("id").ok_or(AppError::InvalidId)?;
+    tracing::debug!(?patch, id = ?id, "patch todo");
+    let mut vec = STORE.lock().await;
 
-    tracing::debug!(?id, "todo is not found");
-    res.status_code(StatusCode::NOT_FOUND);
+    let Some(todo) = vec.iter_mut().find(|todo| todo.id == id) else {
+        tracing::debug!(?id, "todo is not found");
+        return Err(AppError::NotFound);
+    };
+    patch.apply(todo)?;
+    Ok(Json(todo.clone()))
 }
 
 #[handler]
-pub async fn delete_todo(req: &mut Request, res: &mut Response) {
-    let id = req.param::<u64>("id").unwrap();
+pub async fn delete_todo(req: &mut Request) -> Result<StatusCode, AppError> {
+    let id = req.param::<u64>("id").ok_or(AppError::InvalidId)?;
     tracing::debug!(?id, "delete todo");
 
     let mut vec = STORE.lock().await;
@@ -108,12 +159,11 @@ pub async fn delete_todo(req: &mut Request, res: &mut Response) {
     let len = vec.len();
     vec.retain(|todo| todo.id != id);
 
-    let deleted = vec.len() != len;
-    if deleted {
-        res.status_code(StatusCode::NO_CONTENT);
+    if vec.len() != len {
+        Ok(StatusCode::NO_CONTENT)
     } else {
         tracing::debug!(?id, "todo is not found");
-        res.status_code(StatusCode::NOT_FOUND);
+        Err(AppError::NotFound)
     }
 }
 
@@ -166,6 +216,12 @@ mod tests {
             .await;
 
         assert_eq!(res.status_code.unwrap(), StatusCode::BAD_REQUEST);
+
+        let res = TestClient::put("http://0.0.0.0:5800/todos/2")
+            .json(&test_todo())
+            .send(super::route())
+            .await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::NOT_FOUND);
     }
 
     fn test_todo() -> Todo {